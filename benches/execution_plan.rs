@@ -0,0 +1,106 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Per-request overhead of recompiling the DAG versus reusing a shared `ExecutionPlan`.
+//!
+//! Run with `cargo bench --bench execution_plan`.
+
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use the_dagwood::config::{Config, ProcessorMap};
+use the_dagwood::engine::execution_plan::graph_from_config;
+use the_dagwood::engine::{ExecutionPlan, ExecutorFactory};
+use the_dagwood::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+
+const LAYERS: usize = 6;
+const WIDTH: usize = 8;
+
+/// Build a layered pipeline of local processors where every processor depends on the
+/// whole previous layer, alternating Transform and Analyze processors within a layer.
+fn layered_config(strategy: &str) -> Config {
+    let mut yaml = format!(
+        "strategy: {}\nfailure_strategy: fail_fast\nexecutor_options:\n  max_concurrency: 4\nprocessors:\n",
+        strategy
+    );
+
+    for layer in 0..LAYERS {
+        for index in 0..WIDTH {
+            let processor = if index % 2 == 0 {
+                "change_text_case_upper"
+            } else {
+                "token_counter"
+            };
+            let depends_on = if layer == 0 {
+                String::new()
+            } else {
+                (0..WIDTH)
+                    .map(|dep| format!("p{}_{}", layer - 1, dep))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            yaml.push_str(&format!(
+                "  - id: p{}_{}\n    type: local\n    processor: {}\n    depends_on: [{}]\n",
+                layer, index, processor, depends_on
+            ));
+        }
+    }
+
+    serde_yaml::from_str(&yaml).expect("benchmark config should deserialize")
+}
+
+fn bench_execution_plan(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().expect("tokio runtime");
+    let mut group = c.benchmark_group("execution_plan");
+
+    for strategy in ["work_queue", "level", "reactive"] {
+        let config = layered_config(strategy);
+
+        let processors = ProcessorMap::from_config(&config).expect("processors");
+        let (graph, entrypoints) = graph_from_config(&config);
+        let executor = ExecutorFactory::from_config(&config);
+        let plan = Arc::new(
+            ExecutionPlan::compile(processors.clone(), graph.clone(), entrypoints.clone())
+                .expect("plan"),
+        );
+
+        group.bench_function(BenchmarkId::new("recompile_per_request", strategy), |b| {
+            b.to_async(&runtime).iter(|| async {
+                executor
+                    .execute_with_strategy(
+                        processors.clone(),
+                        graph.clone(),
+                        entrypoints.clone(),
+                        ProcessorRequest {
+                            payload: b"hello dagwood".to_vec(),
+                        },
+                        PipelineMetadata::new(),
+                        config.failure_strategy,
+                    )
+                    .await
+                    .expect("execution")
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("shared_plan", strategy), |b| {
+            b.to_async(&runtime).iter(|| async {
+                executor
+                    .execute_plan(
+                        plan.clone(),
+                        ProcessorRequest {
+                            payload: b"hello dagwood".to_vec(),
+                        },
+                        PipelineMetadata::new(),
+                        config.failure_strategy,
+                    )
+                    .await
+                    .expect("execution")
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_execution_plan);
+criterion_main!(benches);
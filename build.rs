@@ -4,10 +4,13 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Build protobuf definitions
     let proto_root = "proto";
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
     tonic_build::configure()
         .build_server(true)
         .build_client(true)
         .out_dir("src/proto") // generated Rust goes here
+        // Encoded descriptors for the gRPC reflection service
+        .file_descriptor_set_path(out_dir.join("processor_descriptor.bin"))
         .compile(&[format!("{proto_root}/processor.proto")], &[proto_root])?;
 
     println!("cargo:rerun-if-changed={proto_root}/processor.proto");
@@ -34,6 +34,13 @@
 //! - **Intent**: Transform processor
 //! - **Testing**: Validates executor error handling for protocol violations
 //!
+//! ## SlowProcessor
+//! A processor that echoes its input after a delay:
+//! - **Use Case**: Testing cancellation and draining of in-flight executions
+//! - **Behavior**: Sleeps for its delay, then returns `NextPayload(input)`
+//! - **Intent**: Transform processor
+//! - **Testing**: Records whether its run started, finished, and stopped
+//!
 //! # Examples
 //!
 //! ## Testing DAG Structure
@@ -76,6 +83,9 @@
 //! # }
 //! ```
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
 use crate::traits::{processor::ProcessorIntent, Processor};
 
 /// A test-only stub processor for testing DAG structure and dependency resolution.
@@ -237,3 +247,78 @@ impl Processor for NoOutcomeProcessor {
         ProcessorIntent::Transform
     }
 }
+
+/// A test-only processor that echoes its input after a delay, recording how far it got.
+///
+/// **Available only in test builds** - not available in production.
+///
+/// ## Behavior
+/// - **Execution**: Sleeps for `delay`
+/// - **Output**: The input payload
+/// - **Progress**: `started` is set when a run begins, `finished` once it slept the whole
+///   delay, and `stopped` when the run ends, whether it finished or its task was aborted
+/// - **Intent**: Transform processor
+///
+/// ## Use Cases
+/// - Testing that cancelled executions stop their running processors
+/// - Testing that shutdown drains in-flight executions
+pub struct SlowProcessor {
+    pub delay: Duration,
+    pub started: AtomicBool,
+    pub finished: AtomicBool,
+    pub stopped: AtomicBool,
+}
+
+impl SlowProcessor {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            started: AtomicBool::new(false),
+            finished: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+        }
+    }
+
+    /// Wait until a run of this processor has started.
+    pub async fn wait_started(&self) {
+        while !self.started.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    }
+}
+
+/// Sets its flag when dropped together with the processor run holding it
+struct StopFlag<'a>(&'a AtomicBool);
+
+impl Drop for StopFlag<'_> {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for SlowProcessor {
+    async fn process(
+        &self,
+        req: crate::proto::processor_v1::ProcessorRequest,
+    ) -> crate::proto::processor_v1::ProcessorResponse {
+        let _stopped = StopFlag(&self.stopped);
+        self.started.store(true, Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.finished.store(true, Ordering::SeqCst);
+        crate::proto::processor_v1::ProcessorResponse {
+            outcome: Some(
+                crate::proto::processor_v1::processor_response::Outcome::NextPayload(req.payload),
+            ),
+            metadata: None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "slow"
+    }
+
+    fn declared_intent(&self) -> ProcessorIntent {
+        ProcessorIntent::Transform
+    }
+}
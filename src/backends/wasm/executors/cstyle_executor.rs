@@ -50,6 +50,7 @@ use std::time::Instant;
 use super::super::processing_node::{
     ExecutionMetadata, ProcessingNodeError, ProcessingNodeExecutor,
 };
+use super::record_fuel_consumed;
 use wasmtime::*;
 
 /// Executor for C-Style WASM modules with manual memory management.
@@ -93,6 +94,10 @@ impl CStyleNodeExecutor {
 
 impl ProcessingNodeExecutor for CStyleNodeExecutor {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, ProcessingNodeError> {
+        self.execute_metered(input).0
+    }
+
+    fn execute_metered(&self, input: &[u8]) -> (Result<Vec<u8>, ProcessingNodeError>, u64) {
         let start_msg = ExecutionStarted {
             module_path: "<cstyle-module>",
             executor_type: "CStyleNodeExecutor",
@@ -105,17 +110,14 @@ impl ProcessingNodeExecutor for CStyleNodeExecutor {
 
         let start_time = Instant::now();
 
-        let mut store = Store::new(&self.engine, ());
-
-        store
-            .set_fuel(self.fuel_level)
-            .map_err(|e| ProcessingNodeError::RuntimeError(e.to_string()))?;
-
-        let instance = Instance::new(&mut store, &self.module, &[])
-            .map_err(|e| ProcessingNodeError::RuntimeError(e.to_string()))?;
+        let (mut store, instance) = match self.instantiate() {
+            Ok(instantiated) => instantiated,
+            Err(error) => return (Err(error), 0),
+        };
 
         let result = self.execute_c_style_process(&mut store, &instance, input);
         let duration = start_time.elapsed();
+        let fuel_consumed = record_fuel_consumed(&store, self.artifact_type(), self.fuel_level);
 
         match &result {
             Ok(output) => {
@@ -138,7 +140,7 @@ impl ProcessingNodeExecutor for CStyleNodeExecutor {
             }
         }
 
-        result
+        (result, fuel_consumed)
     }
 
     fn artifact_type(&self) -> &'static str {
@@ -161,9 +163,36 @@ impl ProcessingNodeExecutor for CStyleNodeExecutor {
             capabilities: self.capabilities(),
         }
     }
+
+    fn compiled_size(&self) -> usize {
+        let image = self.module.image_range();
+        image.end as usize - image.start as usize
+    }
 }
 
 impl CStyleNodeExecutor {
+    /// Create a store with the configured fuel and instantiate the module in it
+    fn instantiate(&self) -> Result<(Store<()>, Instance), ProcessingNodeError> {
+        let instantiate_msg = InstantiationStarted {
+            executor_type: "CStyleNodeExecutor",
+            fuel_level: self.fuel_level,
+        };
+        let span = instantiate_msg.span("wasm_instantiate");
+        let _guard = span.enter();
+        instantiate_msg.log();
+
+        let mut store = Store::new(&self.engine, ());
+
+        store
+            .set_fuel(self.fuel_level)
+            .map_err(|e| ProcessingNodeError::RuntimeError(e.to_string()))?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])
+            .map_err(|e| ProcessingNodeError::RuntimeError(e.to_string()))?;
+
+        Ok((store, instance))
+    }
+
     /// Execute the C-Style process function with manual memory management.
     ///
     /// This internal method handles the complete memory management lifecycle:
@@ -105,3 +105,18 @@ mod wit_executor;
 
 pub use cstyle_executor::CStyleNodeExecutor;
 pub use wit_executor::WitNodeExecutor;
+
+/// Record the fuel a finished execution consumed from its store's fuel budget, returning it.
+///
+/// Stores running out of fuel report zero remaining fuel, so traps from fuel exhaustion are
+/// counted as consuming the full budget.
+fn record_fuel_consumed<T>(
+    store: &wasmtime::Store<T>,
+    artifact_type: &str,
+    fuel_level: u64,
+) -> u64 {
+    let remaining = store.get_fuel().unwrap_or(fuel_level);
+    let consumed = fuel_level.saturating_sub(remaining);
+    crate::observability::metrics::metrics().wasm_fuel_consumed(artifact_type, consumed);
+    consumed
+}
@@ -50,6 +50,7 @@ use super::super::{
         ComponentExecutionError, ExecutionMetadata, ProcessingNodeError, ProcessingNodeExecutor,
     },
 };
+use super::record_fuel_consumed;
 use crate::observability::messages::{wasm::*, StructuredLog};
 use std::sync::Arc;
 use std::time::Instant;
@@ -94,6 +95,50 @@ impl WitNodeExecutor {
             fuel_level,
         })
     }
+
+    /// Create a store with the configured fuel and a WASI context, and instantiate the
+    /// component in it
+    fn instantiate(&self) -> Result<(Store<Ctx>, DagwoodComponent), ProcessingNodeError> {
+        let instantiate_msg = InstantiationStarted {
+            executor_type: "WitNodeExecutor",
+            fuel_level: self.fuel_level,
+        };
+        let span = instantiate_msg.span("wasm_instantiate");
+        let _guard = span.enter();
+        instantiate_msg.log();
+
+        let wasi_ctx = WasiCtxBuilder::new()
+            .inherit_stdio()
+            .args(&["dagwood-component"])
+            .build();
+
+        let store_data = Ctx {
+            wasi: wasi_ctx,
+            table: wasmtime::component::ResourceTable::new(),
+        };
+        let mut store = Store::new(&self.engine, store_data);
+
+        store
+            .set_fuel(self.fuel_level)
+            .map_err(|e| ProcessingNodeError::RuntimeError(e.to_string()))?;
+
+        let mut linker = Linker::<Ctx>::new(&self.engine);
+
+        wasmtime_wasi::p2::add_to_linker_sync(&mut linker).map_err(|e| {
+            ProcessingNodeError::ComponentError(ComponentExecutionError::InstantiationFailed(
+                format!("Failed to add WASI to linker: {}", e),
+            ))
+        })?;
+
+        let bindings = DagwoodComponent::instantiate(&mut store, &self.component, &linker)
+            .map_err(|e| {
+                ProcessingNodeError::ComponentError(ComponentExecutionError::InstantiationFailed(
+                    format!("Failed to instantiate component: {}", e),
+                ))
+            })?;
+
+        Ok((store, bindings))
+    }
 }
 
 /// WASI context wrapper for Component Model execution.
@@ -116,6 +161,10 @@ impl WasiView for Ctx {
 
 impl ProcessingNodeExecutor for WitNodeExecutor {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, ProcessingNodeError> {
+        self.execute_metered(input).0
+    }
+
+    fn execute_metered(&self, input: &[u8]) -> (Result<Vec<u8>, ProcessingNodeError>, u64) {
         let start_msg = ExecutionStarted {
             module_path: "<wit-component>",
             executor_type: "WitNodeExecutor",
@@ -128,50 +177,32 @@ impl ProcessingNodeExecutor for WitNodeExecutor {
 
         let start_time = Instant::now();
 
-        let wasi_ctx = WasiCtxBuilder::new()
-            .inherit_stdio()
-            .args(&["dagwood-component"])
-            .build();
-
-        let store_data = Ctx {
-            wasi: wasi_ctx,
-            table: wasmtime::component::ResourceTable::new(),
+        let (mut store, bindings) = match self.instantiate() {
+            Ok(instantiated) => instantiated,
+            Err(error) => return (Err(error), 0),
         };
-        let mut store = Store::new(&self.engine, store_data);
-
-        store
-            .set_fuel(self.fuel_level)
-            .map_err(|e| ProcessingNodeError::RuntimeError(e.to_string()))?;
-
-        let mut linker = Linker::<Ctx>::new(&self.engine);
-
-        wasmtime_wasi::p2::add_to_linker_sync(&mut linker).map_err(|e| {
-            ProcessingNodeError::ComponentError(ComponentExecutionError::InstantiationFailed(
-                format!("Failed to add WASI to linker: {}", e),
-            ))
-        })?;
-
-        let bindings = DagwoodComponent::instantiate(&mut store, &self.component, &linker)
-            .map_err(|e| {
-                ProcessingNodeError::ComponentError(ComponentExecutionError::InstantiationFailed(
-                    format!("Failed to instantiate component: {}", e),
-                ))
-            })?;
 
         let result = bindings
             .dagwood_component_processing_node()
-            .call_process(&mut store, input)
+            .call_process(&mut store, input);
+        let fuel_consumed = record_fuel_consumed(&store, self.artifact_type(), self.fuel_level);
+
+        let output = result
             .map_err(|e| {
                 ProcessingNodeError::ComponentError(ComponentExecutionError::FunctionCallFailed(
                     format!("Component instantiation/call failed: {}", e),
                 ))
-            })?;
-
-        let output = result.map_err(|processing_error| {
-            ProcessingNodeError::ComponentError(ComponentExecutionError::FunctionCallFailed(
-                format!("Component process() returned error: {:?}", processing_error),
-            ))
-        });
+            })
+            .and_then(|result| {
+                result.map_err(|processing_error| {
+                    ProcessingNodeError::ComponentError(
+                        ComponentExecutionError::FunctionCallFailed(format!(
+                            "Component process() returned error: {:?}",
+                            processing_error
+                        )),
+                    )
+                })
+            });
 
         let duration = start_time.elapsed();
 
@@ -196,7 +227,7 @@ impl ProcessingNodeExecutor for WitNodeExecutor {
             }
         }
 
-        output
+        (output, fuel_consumed)
     }
 
     fn artifact_type(&self) -> &'static str {
@@ -215,4 +246,9 @@ impl ProcessingNodeExecutor for WitNodeExecutor {
             capabilities: self.capabilities(),
         }
     }
+
+    fn compiled_size(&self) -> usize {
+        let image = self.component.image_range();
+        image.end as usize - image.start as usize
+    }
 }
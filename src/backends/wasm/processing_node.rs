@@ -94,9 +94,22 @@ use std::fmt;
 /// - Simpler error handling and stack traces
 pub trait ProcessingNodeExecutor: Send + Sync {
     fn execute(&self, input: &[u8]) -> Result<Vec<u8>, ProcessingNodeError>;
+
+    /// Execute like [`execute`](Self::execute), also returning the fuel the execution
+    /// consumed, whether it succeeded or not; executors that do not meter fuel report zero
+    fn execute_metered(&self, input: &[u8]) -> (Result<Vec<u8>, ProcessingNodeError>, u64) {
+        (self.execute(input), 0)
+    }
+
     fn artifact_type(&self) -> &'static str;
     fn capabilities(&self) -> Vec<String>;
     fn execution_metadata(&self) -> ExecutionMetadata;
+
+    /// Bytes of compiled machine code held by the executor, counted against the server's
+    /// memory budget (ADR 28)
+    fn compiled_size(&self) -> usize {
+        0
+    }
 }
 
 /// Metadata about a WASM processor execution environment.
@@ -25,7 +25,8 @@
 //! ## Key Features
 //! - **Automatic Strategy Selection**: Detects Component Model vs Classic WASM
 //! - **Intent Support**: Configurable Transform vs Analyze processor intent
-//! - **Metadata Collection**: Comprehensive execution metadata
+//! - **Metadata Collection**: Comprehensive execution metadata, including the fuel each
+//!   execution consumed (`fuel_consumed`), which the server charges against WASM fuel quotas
 //! - **Error Handling**: Converts WASM errors to processor responses
 //!
 //! # Processor Intent
@@ -105,11 +106,15 @@
 //! ```
 
 use crate::backends::wasm::detector::detect_component_type;
-use crate::backends::wasm::error::WasmResult;
+use crate::backends::wasm::error::{WasmError, WasmResult};
 use crate::backends::wasm::factory::create_executor;
 use crate::backends::wasm::loader::load_wasm_bytes;
 use crate::backends::wasm::processing_node::ProcessingNodeExecutor;
 use crate::config::consts::DEFAULT_FUEL_LEVEL;
+use crate::observability::messages::wasm::{ModuleEvicted, ModuleReloaded};
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::{metrics, DIRECTION_INPUT, DIRECTION_OUTPUT};
+use crate::observability::trace_context::TraceContext;
 use crate::proto::processor_v1::{
     processor_response::Outcome, ErrorDetail, PipelineMetadata, ProcessorMetadata,
     ProcessorRequest, ProcessorResponse,
@@ -117,7 +122,10 @@ use crate::proto::processor_v1::{
 use crate::traits::processor::{Processor, ProcessorIntent};
 use async_trait::async_trait;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+
+/// Processor metadata key holding the fuel an execution consumed
+pub const FUEL_CONSUMED_KEY: &str = "fuel_consumed";
 
 /// High-level WASM processor with automatic strategy selection.
 ///
@@ -128,9 +136,14 @@ use std::sync::Arc;
 /// # Fields
 /// - **processor_id**: Unique identifier for this processor instance
 /// - **module_path**: Path to the WASM module file
-/// - **executor**: Strategy-specific executor (WIT or C-Style)
+/// - **executor**: Strategy-specific executor (WIT or C-Style), absent while evicted
 /// - **intent**: Processor intent (Transform or Analyze)
 ///
+/// # Memory
+/// The compiled module can be released with [`Processor::release_memory`] when the server
+/// runs over its memory budget (ADR 28); the next execution compiles it again on the
+/// blocking thread pool.
+///
 /// # Thread Safety
 /// - Uses `Arc<dyn ProcessingNodeExecutor>` for shared executor access
 /// - Safe for concurrent use across async tasks
@@ -139,8 +152,12 @@ pub struct WasmProcessor {
     processor_id: String,
     /// Path to the WASM module file
     module_path: String,
-    /// The appropriate executor for this WASM artifact type
-    executor: Arc<dyn ProcessingNodeExecutor>,
+    /// Fuel limit the module is compiled with
+    fuel_level: u64,
+    /// The appropriate executor for this WASM artifact type; `None` while evicted
+    executor: RwLock<Option<Arc<dyn ProcessingNodeExecutor>>>,
+    /// Serializes recompilations so concurrent executions of an evicted module compile it once
+    reload_lock: tokio::sync::Mutex<()>,
     /// Processor intent (Transform or Analyze)
     intent: ProcessorIntent,
 }
@@ -174,15 +191,14 @@ impl WasmProcessor {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn new(processor_id: String, module_path: String) -> WasmResult<Self> {
-        let bytes = load_wasm_bytes(&module_path)?;
-        let component_type = detect_component_type(&bytes)
-            .map_err(|e| crate::backends::wasm::WasmError::ValidationError(e.to_string()))?;
-        let executor = create_executor(&bytes, component_type, DEFAULT_FUEL_LEVEL)?.into();
+        let executor = Self::load_executor(&module_path, DEFAULT_FUEL_LEVEL)?;
 
         Ok(Self {
             processor_id,
             module_path,
-            executor,
+            fuel_level: DEFAULT_FUEL_LEVEL,
+            executor: RwLock::new(Some(executor)),
+            reload_lock: tokio::sync::Mutex::new(()),
             intent: ProcessorIntent::Transform,
         })
     }
@@ -241,26 +257,7 @@ impl WasmProcessor {
             )
         })?;
 
-        let intent = if let Some(intent_value) = config.options.get("intent") {
-            if let Some(intent_str) = intent_value.as_str() {
-                match intent_str.to_lowercase().as_str() {
-                    "transform" => ProcessorIntent::Transform,
-                    "analyze" => ProcessorIntent::Analyze,
-                    invalid => {
-                        return Err(crate::backends::wasm::WasmError::ValidationError(format!(
-                            "Invalid intent '{}'. Must be 'transform' or 'analyze'.",
-                            invalid
-                        )))
-                    }
-                }
-            } else {
-                return Err(crate::backends::wasm::WasmError::ValidationError(
-                    "Intent option must be a string".to_string(),
-                ));
-            }
-        } else {
-            ProcessorIntent::Transform
-        };
+        let intent = Self::intent_from_config(config)?;
 
         // Extract and validate fuel_level from options
         let fuel_level = if let Some(fuel_value) = config.options.get("fuel_level") {
@@ -287,56 +284,153 @@ impl WasmProcessor {
             global_fuel_config.get_default()
         };
 
-        let bytes = load_wasm_bytes(module_path)?;
-        let component_type = detect_component_type(&bytes)
-            .map_err(|e| crate::backends::wasm::WasmError::ValidationError(e.to_string()))?;
-        let executor = create_executor(&bytes, component_type, fuel_level)?.into();
+        let executor = Self::load_executor(module_path, fuel_level)?;
 
         Ok(Self {
             processor_id: config.id.clone(),
             module_path: module_path.clone(),
-            executor,
+            fuel_level,
+            executor: RwLock::new(Some(executor)),
+            reload_lock: tokio::sync::Mutex::new(()),
             intent,
         })
     }
 
+    /// Read the declared intent from the `intent` option of a processor configuration.
+    ///
+    /// Defaults to `Transform` when the option is absent. The module itself is not loaded,
+    /// so this can describe a pipeline without instantiating its WASM processors.
+    pub fn intent_from_config(
+        config: &crate::config::ProcessorConfig,
+    ) -> WasmResult<ProcessorIntent> {
+        let Some(intent_value) = config.options.get("intent") else {
+            return Ok(ProcessorIntent::Transform);
+        };
+
+        match intent_value.as_str().map(str::to_lowercase).as_deref() {
+            Some("transform") => Ok(ProcessorIntent::Transform),
+            Some("analyze") => Ok(ProcessorIntent::Analyze),
+            Some(invalid) => Err(crate::backends::wasm::WasmError::ValidationError(format!(
+                "Invalid intent '{}'. Must be 'transform' or 'analyze'.",
+                invalid
+            ))),
+            None => Err(crate::backends::wasm::WasmError::ValidationError(
+                "Intent option must be a string".to_string(),
+            )),
+        }
+    }
+
+    /// Load a WASM module and create the executor matching its component type.
+    ///
+    /// The time spent loading, detecting and compiling the module is recorded in the
+    /// `dagwood_wasm_module_load_seconds` metric.
+    fn load_executor(
+        module_path: &str,
+        fuel_level: u64,
+    ) -> WasmResult<Arc<dyn ProcessingNodeExecutor>> {
+        let start = std::time::Instant::now();
+
+        let bytes = load_wasm_bytes(module_path)?;
+        let component_type = detect_component_type(&bytes)
+            .map_err(|e| crate::backends::wasm::WasmError::ValidationError(e.to_string()))?;
+        let executor = create_executor(&bytes, component_type, fuel_level)?.into();
+
+        metrics().wasm_module_loaded(module_path, start.elapsed());
+        Ok(executor)
+    }
+
+    /// The executor, compiling the module again if it was evicted.
+    ///
+    /// The compilation runs on the blocking thread pool without holding the executor lock,
+    /// so memory accounting and evictions never wait for it; the compiled executor is then
+    /// swapped in.
+    async fn executor(&self) -> WasmResult<Arc<dyn ProcessingNodeExecutor>> {
+        if let Some(executor) = self.loaded_executor()? {
+            return Ok(executor);
+        }
+
+        let _reload = self.reload_lock.lock().await;
+        if let Some(executor) = self.loaded_executor()? {
+            return Ok(executor);
+        }
+        let start = std::time::Instant::now();
+        let module_path = self.module_path.clone();
+        let fuel_level = self.fuel_level;
+        let executor =
+            tokio::task::spawn_blocking(move || Self::load_executor(&module_path, fuel_level))
+                .await
+                .map_err(|join_error| {
+                    WasmError::ModuleError(format!("compilation task failed: {}", join_error))
+                })??;
+        *self.executor.write().map_err(|_| lock_poisoned())? = Some(executor.clone());
+
+        metrics().wasm_module_reloaded(&self.module_path);
+        ModuleReloaded {
+            module_path: &self.module_path,
+            duration: start.elapsed(),
+        }
+        .log();
+        Ok(executor)
+    }
+
+    /// The executor, unless the module is evicted
+    fn loaded_executor(&self) -> WasmResult<Option<Arc<dyn ProcessingNodeExecutor>>> {
+        Ok(self.executor.read().map_err(|_| lock_poisoned())?.clone())
+    }
+
+    /// Whether the compiled module is loaded, rather than evicted.
+    pub fn is_loaded(&self) -> bool {
+        self.executor
+            .read()
+            .expect("WASM executor lock poisoned")
+            .is_some()
+    }
+
     /// Execute WASM module synchronously.
     ///
     /// Internal method that calls the executor and handles error conversion.
     /// This bridges the synchronous WASM execution with the async processor interface.
     ///
     /// # Arguments
+    /// * `executor` - Executor of the compiled module
     /// * `input` - Input data bytes
     ///
     /// # Returns
-    /// * `Ok(Vec<u8>)` - Processed output data
-    /// * `Err(Box<dyn Error>)` - If execution fails
+    /// The processed output data, or the error if execution failed, and the fuel consumed
+    /// either way
     fn execute_wasm(
         &self,
+        executor: &dyn ProcessingNodeExecutor,
         input: &[u8],
-    ) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> (
+        Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>,
+        u64,
+    ) {
         use crate::observability::messages::wasm::{ExecutionStarted, ExecutionCompleted, ExecutionFailed};
         use std::time::Instant;
         
         let start = Instant::now();
+        metrics().wasm_payload(&self.module_path, DIRECTION_INPUT, input.len());
         
         tracing::info!(
             "{}",
             ExecutionStarted {
                 module_path: &self.module_path,
-                executor_type: self.executor.artifact_type(),
+                executor_type: executor.artifact_type(),
                 input_size: input.len(),
             }
         );
 
-        match self.executor.execute(input) {
+        let (result, fuel_consumed) = executor.execute_metered(input);
+        let result = match result {
             Ok(output) => {
                 let duration = start.elapsed();
+                metrics().wasm_payload(&self.module_path, DIRECTION_OUTPUT, output.len());
                 tracing::info!(
                     "{}",
                     ExecutionCompleted {
                         module_path: &self.module_path,
-                        executor_type: self.executor.artifact_type(),
+                        executor_type: executor.artifact_type(),
                         input_size: input.len(),
                         output_size: output.len(),
                         duration,
@@ -349,12 +443,22 @@ impl WasmProcessor {
                     "{}",
                     ExecutionFailed {
                         module_path: &self.module_path,
-                        executor_type: self.executor.artifact_type(),
+                        executor_type: executor.artifact_type(),
                         error: &error,
                     }
                 );
-                Err(Box::new(error))
+                Err(Box::new(error) as Box<dyn std::error::Error + Send + Sync>)
             }
+        };
+        (result, fuel_consumed)
+    }
+
+    /// Pipeline metadata holding `metadata` under the processor's ID
+    fn pipeline_metadata(&self, metadata: HashMap<String, String>) -> PipelineMetadata {
+        let mut pipeline_metadata_map = HashMap::new();
+        pipeline_metadata_map.insert(self.processor_id.clone(), ProcessorMetadata { metadata });
+        PipelineMetadata {
+            metadata: pipeline_metadata_map,
         }
     }
 }
@@ -369,58 +473,103 @@ impl Processor for WasmProcessor {
         self.intent
     }
 
-    async fn process(&self, request: ProcessorRequest) -> ProcessorResponse {
-        let input = request.payload;
+    fn memory_footprint(&self) -> usize {
+        self.executor
+            .read()
+            .expect("WASM executor lock poisoned")
+            .as_ref()
+            .map_or(0, |executor| executor.compiled_size())
+    }
 
-        match self.execute_wasm(&input) {
-            Ok(output) => {
-                let mut processor_metadata_map = HashMap::new();
-                processor_metadata_map
-                    .insert("processor_id".to_string(), self.processor_id.clone());
-                processor_metadata_map.insert("module_path".to_string(), self.module_path.clone());
-                processor_metadata_map.insert(
-                    "artifact_type".to_string(),
-                    self.executor.artifact_type().to_string(),
-                );
-                processor_metadata_map.insert(
-                    "capabilities".to_string(),
-                    format!("{:?}", self.executor.capabilities()),
-                );
-                processor_metadata_map.insert("input_length".to_string(), input.len().to_string());
-                processor_metadata_map
-                    .insert("output_length".to_string(), output.len().to_string());
+    fn release_memory(&self) -> usize {
+        let released = self
+            .executor
+            .write()
+            .expect("WASM executor lock poisoned")
+            .take();
+        let Some(executor) = released else {
+            return 0;
+        };
 
-                let processor_metadata = ProcessorMetadata {
-                    metadata: processor_metadata_map,
-                };
+        // Executions still running keep their own reference until they finish
+        let compiled_bytes = executor.compiled_size();
+        metrics().wasm_module_evicted(&self.module_path);
+        ModuleEvicted {
+            module_path: &self.module_path,
+            compiled_bytes,
+        }
+        .log();
+        compiled_bytes
+    }
 
-                let mut pipeline_metadata_map = HashMap::new();
-                pipeline_metadata_map.insert(self.processor_id.clone(), processor_metadata);
+    async fn process(&self, request: ProcessorRequest) -> ProcessorResponse {
+        let input = request.payload;
 
-                let pipeline_metadata = PipelineMetadata {
-                    metadata: pipeline_metadata_map,
-                };
+        let executor = match self.executor().await {
+            Ok(executor) => executor,
+            Err(error) => return execution_failed(&error, None),
+        };
+        let (result, fuel_consumed) = self.execute_wasm(executor.as_ref(), &input);
+
+        let mut processor_metadata_map = HashMap::new();
+        processor_metadata_map.insert("processor_id".to_string(), self.processor_id.clone());
+        processor_metadata_map.insert("module_path".to_string(), self.module_path.clone());
+        processor_metadata_map.insert(
+            "artifact_type".to_string(),
+            executor.artifact_type().to_string(),
+        );
+        processor_metadata_map.insert(
+            "capabilities".to_string(),
+            format!("{:?}", executor.capabilities()),
+        );
+        processor_metadata_map.insert("input_length".to_string(), input.len().to_string());
+        // Failed executions report their fuel too, so quotas charge runs that trap or run
+        // out of fuel
+        processor_metadata_map.insert(FUEL_CONSUMED_KEY.to_string(), fuel_consumed.to_string());
 
-                ProcessorResponse {
-                    outcome: Some(Outcome::NextPayload(output)),
-                    metadata: Some(pipeline_metadata),
-                }
-            }
+        let output = match result {
+            Ok(output) => output,
             Err(error) => {
-                let error_detail = ErrorDetail {
-                    code: 500,
-                    message: format!("WASM execution failed: {}", error),
-                };
-
-                ProcessorResponse {
-                    outcome: Some(Outcome::Error(error_detail)),
-                    metadata: None,
-                }
+                let metadata = self.pipeline_metadata(processor_metadata_map);
+                return execution_failed(&error, Some(metadata));
             }
+        };
+        processor_metadata_map.insert("output_length".to_string(), output.len().to_string());
+
+        // WASM modules only receive the payload, so the trace context of this
+        // invocation travels as a metadata entry instead
+        if let Some(trace_context) = TraceContext::current() {
+            trace_context.inject_metadata(&mut processor_metadata_map);
+        }
+
+        ProcessorResponse {
+            outcome: Some(Outcome::NextPayload(output)),
+            metadata: Some(self.pipeline_metadata(processor_metadata_map)),
         }
     }
 }
 
+/// Error for a poisoned executor lock, left behind by a thread that panicked while holding it
+fn lock_poisoned() -> WasmError {
+    WasmError::ProcessorError("WASM executor lock poisoned".to_string())
+}
+
+/// Response of a failed execution, carrying the processor's metadata if it got to run
+fn execution_failed(
+    error: &dyn std::fmt::Display,
+    metadata: Option<PipelineMetadata>,
+) -> ProcessorResponse {
+    let error_detail = ErrorDetail {
+        code: 500,
+        message: format!("WASM execution failed: {}", error),
+    };
+
+    ProcessorResponse {
+        outcome: Some(Outcome::Error(error_detail)),
+        metadata,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -640,4 +789,105 @@ mod tests {
             );
         }
     }
+
+    /// C-style module echoing its input
+    fn echo_module() -> tempfile::NamedTempFile {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (global $next (mut i32) (i32.const 1024))
+              (func (export "allocate") (param $size i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $size)))
+                (local.get $ptr))
+              (func (export "deallocate") (param i32 i32))
+              (func (export "process") (param $ptr i32) (param $len i32) (param $out i32) (result i32)
+                (i32.store (local.get $out) (local.get $len))
+                (local.get $ptr)))
+            "#,
+        )
+        .unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &wasm).unwrap();
+        file
+    }
+
+    #[tokio::test]
+    async fn test_release_memory_reloads_module_on_next_execution() {
+        let module = echo_module();
+        let processor =
+            WasmProcessor::new("echo".to_string(), module.path().display().to_string()).unwrap();
+        let footprint = processor.memory_footprint();
+        assert!(footprint > 0);
+
+        assert_eq!(processor.release_memory(), footprint);
+        assert!(!processor.is_loaded());
+        assert_eq!(processor.memory_footprint(), 0);
+        assert_eq!(processor.release_memory(), 0);
+
+        let response = processor
+            .process(ProcessorRequest {
+                payload: b"hello".to_vec(),
+            })
+            .await;
+        assert_eq!(
+            response.outcome,
+            Some(Outcome::NextPayload(b"hello".to_vec()))
+        );
+        assert!(processor.is_loaded());
+        assert_eq!(processor.memory_footprint(), footprint);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_executions_compile_evicted_module_once() {
+        let module = echo_module();
+        let module_path = module.path().display().to_string();
+        let processor =
+            Arc::new(WasmProcessor::new("echo".to_string(), module_path.clone()).unwrap());
+        processor.release_memory();
+
+        let executions: Vec<_> = (0..4)
+            .map(|_| {
+                let processor = processor.clone();
+                tokio::spawn(async move {
+                    processor
+                        .process(ProcessorRequest {
+                            payload: b"hello".to_vec(),
+                        })
+                        .await
+                })
+            })
+            .collect();
+        for execution in executions {
+            let response = execution.await.unwrap();
+            assert_eq!(
+                response.outcome,
+                Some(Outcome::NextPayload(b"hello".to_vec()))
+            );
+        }
+
+        let reloads = format!(
+            "dagwood_wasm_module_reloads_total{{module=\"{}\"}} 1\n",
+            module_path
+        );
+        assert!(metrics().encode().contains(&reloads));
+    }
+
+    #[tokio::test]
+    async fn test_fuel_consumed_reported_in_metadata() {
+        let module = echo_module();
+        let processor =
+            WasmProcessor::new("echo".to_string(), module.path().display().to_string()).unwrap();
+
+        let response = processor
+            .process(ProcessorRequest {
+                payload: b"hello".to_vec(),
+            })
+            .await;
+        let metadata = &response.metadata.unwrap().metadata["echo"].metadata;
+        let fuel_consumed: u64 = metadata[FUEL_CONSUMED_KEY].parse().unwrap();
+        assert!(fuel_consumed > 0 && fuel_consumed < DEFAULT_FUEL_LEVEL);
+    }
 }
@@ -0,0 +1,201 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood bench` - execute a pipeline repeatedly and report timing statistics.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Args, ValueEnum};
+use the_dagwood::config::{RuntimeBuilder, Strategy};
+use the_dagwood::engine::ExecutorFactory;
+use the_dagwood::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+
+use super::{load_config, CliError, InputArgs};
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Pipeline configuration file
+    #[arg(short, long, value_name = "CONFIG")]
+    pub config: PathBuf,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Number of measured executions per strategy
+    #[arg(short = 'n', long, default_value_t = 100)]
+    pub iterations: usize,
+
+    /// Number of unmeasured executions before measuring
+    #[arg(long, default_value_t = 5)]
+    pub warmup: usize,
+
+    /// Executor strategy to measure
+    #[arg(short, long, value_enum, default_value_t = BenchStrategy::Configured)]
+    pub strategy: BenchStrategy,
+}
+
+/// Strategies `dagwood bench` can measure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BenchStrategy {
+    /// The strategy from the configuration
+    Configured,
+    WorkQueue,
+    Level,
+    Reactive,
+    /// Every strategy, one after the other, on the same compiled plan
+    All,
+}
+
+/// Timing statistics of the measured executions
+#[derive(Debug, PartialEq)]
+struct BenchStats {
+    min: Duration,
+    mean: Duration,
+    p50: Duration,
+    p95: Duration,
+    max: Duration,
+}
+
+impl BenchStats {
+    /// Compute statistics from the measured durations; `None` if nothing was measured
+    fn from_durations(mut durations: Vec<Duration>) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+        durations.sort();
+
+        let percentile = |p: usize| durations[(durations.len() - 1) * p / 100];
+        let total: Duration = durations.iter().sum();
+        Some(Self {
+            min: durations[0],
+            mean: total / durations.len() as u32,
+            p50: percentile(50),
+            p95: percentile(95),
+            max: durations[durations.len() - 1],
+        })
+    }
+}
+
+pub async fn bench(args: BenchArgs) -> Result<(), CliError> {
+    let input = args.input.read()?;
+    let mut config = load_config(&args.config)?;
+
+    // Processors and the plan are built once and shared by every strategy and iteration
+    let (plan, configured_executor, failure_strategy) =
+        RuntimeBuilder::compile(&config).map_err(|e| CliError::InvalidConfig {
+            path: args.config.display().to_string(),
+            message: e,
+        })?;
+
+    let strategies = match args.strategy {
+        BenchStrategy::Configured => vec![None],
+        BenchStrategy::WorkQueue => vec![Some(Strategy::WorkQueue)],
+        BenchStrategy::Level => vec![Some(Strategy::Level)],
+        BenchStrategy::Reactive => vec![Some(Strategy::Reactive)],
+        BenchStrategy::All => vec![
+            Some(Strategy::WorkQueue),
+            Some(Strategy::Level),
+            Some(Strategy::Reactive),
+        ],
+    };
+
+    println!(
+        "⏱️  {}: {} iterations ({} warmup), {} byte input",
+        args.config.display(),
+        args.iterations,
+        args.warmup,
+        input.len()
+    );
+    println!(
+        "\n{:<14} {:>12} {:>12} {:>12} {:>12} {:>12} {:>10} {:>8}",
+        "strategy", "min", "mean", "p50", "p95", "max", "runs/s", "failed"
+    );
+
+    let mut total_failed = 0;
+    let mut configured_executor = Some(configured_executor);
+    for strategy in strategies {
+        let executor = match strategy {
+            Some(strategy) => {
+                config.strategy = strategy;
+                ExecutorFactory::from_config(&config)
+            }
+            None => configured_executor
+                .take()
+                .expect("configured executor is measured once"),
+        };
+
+        let mut durations = Vec::with_capacity(args.iterations);
+        let mut failed = 0;
+        let mut strategy_name = "";
+        for iteration in 0..args.warmup + args.iterations {
+            let result = executor
+                .execute_plan(
+                    plan.clone(),
+                    ProcessorRequest {
+                        payload: input.clone(),
+                    },
+                    PipelineMetadata::new(),
+                    failure_strategy,
+                )
+                .await;
+
+            if iteration < args.warmup {
+                continue;
+            }
+            match result {
+                Ok(report) => {
+                    strategy_name = report.strategy;
+                    if !report.is_success() {
+                        failed += 1;
+                    }
+                    durations.push(report.duration);
+                }
+                Err(_) => failed += 1,
+            }
+        }
+
+        total_failed += failed;
+        match BenchStats::from_durations(durations) {
+            Some(stats) => println!(
+                "{:<14} {:>12} {:>12} {:>12} {:>12} {:>12} {:>10.1} {:>8}",
+                strategy_name,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.p50),
+                format!("{:.2?}", stats.p95),
+                format!("{:.2?}", stats.max),
+                1.0 / stats.mean.as_secs_f64().max(f64::EPSILON),
+                failed
+            ),
+            None => println!("{:<14} no successful executions, {} failed", "-", failed),
+        }
+    }
+
+    if total_failed > 0 {
+        return Err(CliError::ExecutionFailed {
+            path: args.config.display().to_string(),
+            message: format!("{} benchmark executions failed", total_failed),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_stats() {
+        let durations = (1..=100).map(Duration::from_millis).collect();
+
+        let stats = BenchStats::from_durations(durations).unwrap();
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(100));
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p95, Duration::from_millis(95));
+        assert_eq!(stats.mean, Duration::from_micros(50_500));
+        assert_eq!(BenchStats::from_durations(Vec::new()), None);
+    }
+}
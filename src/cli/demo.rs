@@ -0,0 +1,167 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood demo` - guided interactive walkthrough of the example pipelines.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use super::run::{run_config, OutputFormat};
+
+/// Demo configuration with description and learning objectives
+struct DemoConfig {
+    file: &'static str,
+    title: &'static str,
+    description: &'static str,
+    learning_objectives: Vec<&'static str>,
+}
+
+/// Wait for user to press Enter with a custom prompt
+fn wait_for_keypress(prompt: &str) {
+    print!("{}", prompt);
+    io::stdout().flush().unwrap();
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).unwrap();
+}
+
+/// Run the guided interactive demo with progressive complexity
+pub async fn run_guided_demo() {
+    let demo_configs = vec![
+        DemoConfig {
+            file: "docs/walkthrough/configs/01-hello-world.yaml",
+            title: "Hello World: Single Processor",
+            description: "The simplest possible DAG with one processor and no dependencies",
+            learning_objectives: vec![
+                "Basic Rust ownership patterns in processor execution",
+                "Simple async/await usage with tokio",
+                "ProcessorRequest and ProcessorResponse structures",
+                "Entry point detection in DAG execution",
+            ],
+        },
+        DemoConfig {
+            file: "docs/walkthrough/configs/02-text-pipeline.yaml",
+            title: "Text Pipeline: Linear Chain",
+            description: "A sequence of processors showing data flow and chaining",
+            learning_objectives: vec![
+                "Data flow chaining between processors",
+                "Dependency resolution and topological ordering",
+                "Rust Result<T, E> error handling patterns",
+                "Arc and Mutex for shared state management",
+            ],
+        },
+        DemoConfig {
+            file: "docs/walkthrough/configs/03-diamond-analysis.yaml",
+            title: "Diamond Analysis: Parallel Execution",
+            description: "Classic diamond dependency pattern with parallel analysis processors",
+            learning_objectives: vec![
+                "Parallel execution with tokio async tasks",
+                "Canonical payload architecture (Transform vs Analyze)",
+                "Metadata collection and merging strategies",
+                "Race condition prevention in concurrent execution",
+            ],
+        },
+        DemoConfig {
+            file: "docs/walkthrough/configs/04-wasm-integration.yaml",
+            title: "WASM Integration: Sandboxed Processing",
+            description: "WASM processor integration with security sandboxing",
+            learning_objectives: vec![
+                "WASM module loading and execution with wasmtime",
+                "Memory management across WASM boundary",
+                "Security sandboxing and isolation patterns",
+                "Multi-backend processor architecture",
+            ],
+        },
+        DemoConfig {
+            file: "docs/walkthrough/configs/05-complex-workflow.yaml",
+            title: "Complex Workflow: Multi-Backend Pipeline",
+            description: "Advanced DAG with multiple backends and execution strategies",
+            learning_objectives: vec![
+                "Level-by-Level vs Work Queue execution strategies",
+                "Mixed local and WASM processor coordination",
+                "Advanced error handling with failure strategies",
+                "Production-ready workflow orchestration patterns",
+            ],
+        },
+    ];
+
+    println!("🦀 The DAGwood Project - Interactive Demo");
+    println!("═══════════════════════════════════════════");
+    println!();
+    println!("Welcome to an interactive demonstration of The DAGwood Project!");
+    println!("This demo showcases our four primary learning objectives:");
+    println!();
+    println!("🦀 1. Learn Rust - Ownership, async/await, traits, and error handling");
+    println!("🔄 2. Learn DAG Execution Strategies - Work Queue, Level-by-Level, Reactive");
+    println!("🧩 3. Learn WASM Components - Security sandboxing and multi-language support");
+    println!("🤖 4. Use AI Tools - How AI assistance accelerated development");
+    println!();
+    println!("We'll progress through 5 examples of increasing complexity:");
+    for (i, config) in demo_configs.iter().enumerate() {
+        println!("  {}. {}", i + 1, config.title);
+    }
+    println!();
+
+    wait_for_keypress("Press Enter to begin the demo... ");
+
+    let input_text = "hello world";
+
+    for (i, demo_config) in demo_configs.iter().enumerate() {
+        println!("\n{}", "═".repeat(80));
+        println!("Demo {}: {}", i + 1, demo_config.title);
+        println!("{}", "═".repeat(80));
+        println!();
+        println!("📖 Description:");
+        println!("   {}", demo_config.description);
+        println!();
+        println!("🎯 Learning Objectives:");
+        for objective in &demo_config.learning_objectives {
+            println!("   • {}", objective);
+        }
+        println!();
+
+        wait_for_keypress(&format!(
+            "Press Enter to run Demo {} ({})... ",
+            i + 1,
+            demo_config.title
+        ));
+
+        match run_config(
+            Path::new(demo_config.file),
+            input_text.as_bytes(),
+            OutputFormat::Text,
+        )
+        .await
+        {
+            Ok(_) => {
+                println!("\n✅ Demo {} completed successfully!", i + 1);
+            }
+            Err(e) => {
+                println!("\n❌ Demo {} failed: {}", i + 1, e);
+                println!("   This might be expected if WASM modules aren't built yet.");
+            }
+        }
+
+        if i < demo_configs.len() - 1 {
+            println!();
+            wait_for_keypress("Press Enter to continue to the next demo... ");
+        }
+    }
+
+    println!("\n{}", "═".repeat(80));
+    println!("🎉 Demo Complete - Thank You!");
+    println!("{}", "═".repeat(80));
+    println!();
+    println!("You've seen The DAGwood Project demonstrate:");
+    println!("• 🦀 Rust ownership, async/await, and error handling patterns");
+    println!("• 🔄 Multiple DAG execution strategies (Work Queue, Level-by-Level)");
+    println!("• 🧩 WASM component integration with security sandboxing");
+    println!("• 🤖 AI-assisted development accelerating complex implementations");
+    println!();
+    println!("Next Steps:");
+    println!("• 📚 Explore the full mdBook presentation: cd docs/walkthrough && mdbook serve");
+    println!("• 🔍 Examine the source code and ADRs for architectural decisions");
+    println!("• 🚀 Try building your own processors and DAG configurations");
+    println!("• 🤝 Contribute to the project or adapt it for your use cases");
+    println!();
+    println!("Thank you for exploring The DAGwood Project!");
+}
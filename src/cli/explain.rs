@@ -0,0 +1,142 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood explain` - describe how each strategy would execute a pipeline, without running it.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use the_dagwood::config::Config;
+use the_dagwood::engine::explain::{PlannedStep, StrategyPlan};
+use the_dagwood::engine::PlanExplanation;
+
+use super::graph::describe_backend;
+use super::{load_config, CliError};
+
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    /// Pipeline configuration file
+    #[arg(value_name = "CONFIG")]
+    pub config: PathBuf,
+}
+
+/// Print the executor settings, the processors, and the dry-run plan of every strategy.
+///
+/// WASM modules are not loaded and remote backends are not contacted.
+pub fn explain(args: ExplainArgs) -> Result<(), CliError> {
+    let config = load_config(&args.config)?;
+    let explanation =
+        PlanExplanation::from_config(&config).map_err(|e| CliError::InvalidConfig {
+            path: args.config.display().to_string(),
+            message: e,
+        })?;
+
+    println!("🔍 Execution plan: {}", args.config.display());
+    println!("🔧 Configured Strategy: {:?}", config.strategy);
+    match config.executor_options.max_concurrency {
+        Some(max_concurrency) => println!("⚙️  Max Concurrency: {}", max_concurrency),
+        None => println!("⚙️  Max Concurrency: available CPU cores"),
+    }
+    println!("🛡️  Failure Strategy: {:?}", config.failure_strategy);
+    println!("🚪 Entry Points: {}", explanation.entry_points.join(", "));
+
+    println!("\n📋 Processors (topological rank order):");
+    for processor in &explanation.processors {
+        let backend = backend_of(&config, &processor.id);
+        let placement = format!(
+            "{:?}, rank {}, level {}",
+            processor.intent, processor.task.topological_rank, processor.level
+        );
+        if processor.dependencies.is_empty() {
+            println!("   • {} [{}] {}", processor.id, backend, placement);
+        } else {
+            println!(
+                "   • {} [{}] {}, after {}",
+                processor.id,
+                backend,
+                placement,
+                processor.dependencies.join(", ")
+            );
+        }
+    }
+
+    println!("\n🧵 Work Queue (priority order when run one at a time):");
+    for (i, step) in explanation.work_queue.steps.iter().enumerate() {
+        println!("   {}. {}", i + 1, describe_step(step));
+    }
+    print_final_payload(&explanation.work_queue);
+
+    println!("\n📶 Level-by-Level:");
+    for (level, processor_ids) in explanation.levels.iter().enumerate() {
+        println!("   Level {} ({} concurrent):", level, processor_ids.len());
+        for step in explanation
+            .level_by_level
+            .steps
+            .iter()
+            .filter(|step| step.level == level)
+        {
+            println!("     • {}", describe_step(step));
+        }
+    }
+    print_final_payload(&explanation.level_by_level);
+
+    println!("\n⚡ Reactive (notification network):");
+    for step in &explanation.reactive.steps {
+        let Some(processor) = explanation.processor(&step.processor_id) else {
+            continue;
+        };
+        let waits_for = if processor.dependencies.is_empty() {
+            "triggered by the executor".to_string()
+        } else {
+            format!("waits for {}", processor.dependencies.join(", "))
+        };
+        let notifies = if processor.dependents.is_empty() {
+            "notifies nobody".to_string()
+        } else {
+            format!("notifies {}", processor.dependents.join(", "))
+        };
+        println!(
+            "   • {}: {}, {}; {}",
+            step.processor_id,
+            waits_for,
+            notifies,
+            describe_input(step)
+        );
+    }
+    print_final_payload(&explanation.reactive);
+
+    println!("\n🎯 Outputs:");
+    if config.outputs.is_empty() {
+        println!("   Final canonical payload");
+    }
+    for output in &config.outputs {
+        println!("   {} ← {}", output.name(), output.processor);
+    }
+
+    Ok(())
+}
+
+fn backend_of(config: &Config, processor_id: &str) -> String {
+    config
+        .processors
+        .iter()
+        .find(|processor| processor.id == processor_id)
+        .map(describe_backend)
+        .unwrap_or_default()
+}
+
+fn describe_step(step: &PlannedStep) -> String {
+    format!("{}: {}", step.processor_id, describe_input(step))
+}
+
+fn describe_input(step: &PlannedStep) -> String {
+    if step.updates_canonical {
+        format!("receives {}, sets the canonical payload", step.input)
+    } else {
+        format!("receives {}", step.input)
+    }
+}
+
+fn print_final_payload(plan: &StrategyPlan) {
+    println!("   ⇒ Final canonical payload: {}", plan.final_payload);
+}
@@ -0,0 +1,160 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood graph` - print the dependency graph of a pipeline.
+
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use the_dagwood::config::{BackendType, Config, ProcessorConfig, RuntimeBuilder};
+use the_dagwood::engine::execution_plan::{compute_topological_levels, graph_from_config};
+use the_dagwood::engine::GraphExport;
+use the_dagwood::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+
+use super::{load_config, CliError};
+
+#[derive(Debug, Args)]
+pub struct GraphArgs {
+    /// Pipeline configuration file
+    #[arg(value_name = "CONFIG")]
+    pub config: PathBuf,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = GraphFormat::Text)]
+    pub format: GraphFormat,
+
+    /// Execute the pipeline with this input and overlay processor statuses and timings
+    #[arg(long, value_name = "INPUT")]
+    pub execute: Option<String>,
+}
+
+/// Formats `dagwood graph` can print
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Processors level by level
+    Text,
+    /// Graphviz DOT, e.g. for `dot -Tsvg`
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+/// Print the dependency graph of a pipeline in the requested format
+pub async fn graph(args: GraphArgs) -> Result<(), CliError> {
+    let config = load_config(&args.config)?;
+    if args.format == GraphFormat::Text && args.execute.is_none() {
+        return print_levels(&args, &config);
+    }
+
+    let invalid = |message: String| CliError::InvalidConfig {
+        path: args.config.display().to_string(),
+        message,
+    };
+    let mut export = GraphExport::from_config(&config).map_err(|e| invalid(e.to_string()))?;
+
+    match RuntimeBuilder::compile(&config) {
+        Ok((plan, executor, failure_strategy)) => {
+            export = export.with_plan(&plan);
+            if let Some(input) = &args.execute {
+                let report = executor
+                    .execute_plan(
+                        plan,
+                        ProcessorRequest {
+                            payload: input.as_bytes().to_vec(),
+                        },
+                        PipelineMetadata::new(),
+                        failure_strategy,
+                    )
+                    .await
+                    .map_err(|e| CliError::ExecutionFailed {
+                        path: args.config.display().to_string(),
+                        message: e.to_string(),
+                    })?;
+                export = export.with_report(&report);
+            }
+        }
+        // Drawing the graph does not need the processors, so only an execution requires them
+        Err(e) if args.execute.is_some() => return Err(invalid(e)),
+        Err(e) => eprintln!("⚠️  Processor intents omitted: {}", e),
+    }
+
+    match args.format {
+        GraphFormat::Dot => print!("{}", export.to_dot()),
+        GraphFormat::Mermaid => print!("{}", export.to_mermaid()),
+        GraphFormat::Text => {
+            for node in export.nodes() {
+                match node.outcome {
+                    Some((status, duration)) => println!(
+                        "  • {} (level {}): {} in {:.2?}",
+                        node.id, node.level, status, duration
+                    ),
+                    None => println!("  • {} (level {}): not run", node.id, node.level),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the processors level by level, each with its backend and dependents
+fn print_levels(args: &GraphArgs, config: &Config) -> Result<(), CliError> {
+    let (graph, entrypoints) = graph_from_config(config);
+    let levels =
+        compute_topological_levels(&graph, &entrypoints).map_err(|e| CliError::InvalidConfig {
+            path: args.config.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+    println!(
+        "📈 {} ({:?} strategy, {} processors)",
+        args.config.display(),
+        config.strategy,
+        config.processors.len()
+    );
+
+    for (level, processor_ids) in levels.iter().enumerate() {
+        println!("\nLevel {}:", level);
+        for processor_id in processor_ids {
+            let backend = config
+                .processors
+                .iter()
+                .find(|processor| &processor.id == processor_id)
+                .map(describe_backend)
+                .unwrap_or_default();
+            let mut dependents = graph
+                .get_dependents(processor_id)
+                .cloned()
+                .unwrap_or_default();
+            dependents.sort();
+
+            if dependents.is_empty() {
+                println!("  • {} [{}]", processor_id, backend);
+            } else {
+                println!(
+                    "  • {} [{}] → {}",
+                    processor_id,
+                    backend,
+                    dependents.join(", ")
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Backend type and implementation (processor name, module or endpoint) of a processor
+pub(super) fn describe_backend(processor: &ProcessorConfig) -> String {
+    let implementation = match processor.backend {
+        BackendType::Local => processor.processor.as_deref(),
+        BackendType::Wasm => processor.module.as_deref(),
+        BackendType::Grpc | BackendType::Http => processor.endpoint.as_deref(),
+        BackendType::Loadable => None,
+    };
+
+    match implementation {
+        Some(implementation) => format!("{:?}: {}", processor.backend, implementation),
+        None => format!("{:?}", processor.backend),
+    }
+}
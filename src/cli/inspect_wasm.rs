@@ -0,0 +1,191 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood inspect-wasm` - show the type, imports and exports of a WASM module.
+
+use std::path::PathBuf;
+
+use clap::Args;
+use the_dagwood::backends::wasm::{detect_component_type, load_wasm_bytes, ComponentType};
+use wasmparser::{Parser, Payload, TypeRef};
+
+use super::CliError;
+
+/// Exports a C-style module must provide to be executed by `CStyleNodeExecutor`
+const CSTYLE_REQUIRED_EXPORTS: [&str; 4] = ["memory", "process", "allocate", "deallocate"];
+
+#[derive(Debug, Args)]
+pub struct InspectWasmArgs {
+    /// WASM module or component file
+    #[arg(value_name = "MODULE")]
+    pub module: PathBuf,
+}
+
+/// Top-level imports and exports of a module or component
+#[derive(Debug, Default, PartialEq)]
+struct ModuleInterface {
+    /// `(module or interface, name, kind)`; the name is empty for component imports
+    imports: Vec<(String, String, String)>,
+    /// `(name, kind)`
+    exports: Vec<(String, String)>,
+}
+
+pub fn inspect_wasm(args: InspectWasmArgs) -> Result<(), CliError> {
+    let module_path = args.module.display().to_string();
+    let invalid = |message: String| CliError::InvalidWasm {
+        path: module_path.clone(),
+        message,
+    };
+
+    let bytes = load_wasm_bytes(&args.module).map_err(|e| invalid(e.to_string()))?;
+    let component_type = detect_component_type(&bytes).map_err(|e| invalid(e.to_string()))?;
+    let interface = read_interface(&bytes).map_err(|e| invalid(e.to_string()))?;
+
+    println!("🧩 {}", module_path);
+    println!("   Size: {} bytes", bytes.len());
+    println!(
+        "   Type: {}",
+        match component_type {
+            ComponentType::Wit => "WIT component (Component Model), executed by WitNodeExecutor",
+            ComponentType::CStyle => "C-style core module, executed by CStyleNodeExecutor",
+        }
+    );
+
+    if component_type.is_cstyle() {
+        let missing: Vec<&str> = CSTYLE_REQUIRED_EXPORTS
+            .into_iter()
+            .filter(|required| !interface.exports.iter().any(|(name, _)| name == required))
+            .collect();
+        if missing.is_empty() {
+            println!("   ABI: ✅ exports {}", CSTYLE_REQUIRED_EXPORTS.join(", "));
+        } else {
+            println!("   ABI: ❌ missing exports {}", missing.join(", "));
+        }
+    }
+
+    println!("\n📥 Imports ({}):", interface.imports.len());
+    for (module, name, kind) in &interface.imports {
+        if name.is_empty() {
+            println!("   • {} ({})", module, kind);
+        } else {
+            println!("   • {}::{} ({})", module, name, kind);
+        }
+    }
+
+    println!("\n📤 Exports ({}):", interface.exports.len());
+    for (name, kind) in &interface.exports {
+        println!("   • {} ({})", name, kind);
+    }
+
+    Ok(())
+}
+
+/// Collect the imports and exports of the outermost module or component.
+///
+/// `Parser::parse_all` also yields the payloads of modules nested inside a component; those
+/// are skipped by tracking the nesting depth through their `Version` and `End` payloads.
+fn read_interface(bytes: &[u8]) -> wasmparser::Result<ModuleInterface> {
+    let mut interface = ModuleInterface::default();
+    let mut depth = 0usize;
+
+    for payload in Parser::new(0).parse_all(bytes) {
+        match payload? {
+            Payload::Version { .. } => depth += 1,
+            Payload::End(_) => depth = depth.saturating_sub(1),
+            Payload::ImportSection(reader) if depth == 1 => {
+                for import in reader {
+                    let import = import?;
+                    let kind = match import.ty {
+                        TypeRef::Func(_) => "func",
+                        TypeRef::Table(_) => "table",
+                        TypeRef::Memory(_) => "memory",
+                        TypeRef::Global(_) => "global",
+                        TypeRef::Tag(_) => "tag",
+                    };
+                    interface.imports.push((
+                        import.module.to_string(),
+                        import.name.to_string(),
+                        kind.to_string(),
+                    ));
+                }
+            }
+            Payload::ExportSection(reader) if depth == 1 => {
+                for export in reader {
+                    let export = export?;
+                    interface.exports.push((
+                        export.name.to_string(),
+                        format!("{:?}", export.kind).to_lowercase(),
+                    ));
+                }
+            }
+            Payload::ComponentImportSection(reader) if depth == 1 => {
+                for import in reader {
+                    let import = import?;
+                    interface.imports.push((
+                        import.name.0.to_string(),
+                        String::new(),
+                        component_kind(&import.ty).to_string(),
+                    ));
+                }
+            }
+            Payload::ComponentExportSection(reader) if depth == 1 => {
+                for export in reader {
+                    let export = export?;
+                    interface.exports.push((
+                        export.name.0.to_string(),
+                        format!("{:?}", export.kind).to_lowercase(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(interface)
+}
+
+fn component_kind(ty: &wasmparser::ComponentTypeRef) -> &'static str {
+    match ty {
+        wasmparser::ComponentTypeRef::Module(_) => "module",
+        wasmparser::ComponentTypeRef::Func(_) => "func",
+        wasmparser::ComponentTypeRef::Value(_) => "value",
+        wasmparser::ComponentTypeRef::Type(_) => "type",
+        wasmparser::ComponentTypeRef::Instance(_) => "instance",
+        wasmparser::ComponentTypeRef::Component(_) => "component",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_interface_of_cstyle_module() {
+        let bytes = wat::parse_str(
+            r#"(module
+                (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+                (memory (export "memory") 1)
+                (func (export "process") (param i32 i32 i32) (result i32) i32.const 0)
+            )"#,
+        )
+        .unwrap();
+
+        let interface = read_interface(&bytes).unwrap();
+
+        assert_eq!(
+            interface.imports,
+            vec![(
+                "wasi_snapshot_preview1".to_string(),
+                "fd_write".to_string(),
+                "func".to_string()
+            )]
+        );
+        assert_eq!(
+            interface.exports,
+            vec![
+                ("memory".to_string(), "memory".to_string()),
+                ("process".to_string(), "func".to_string())
+            ]
+        );
+    }
+}
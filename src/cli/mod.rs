@@ -0,0 +1,268 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Command line interface of the `dagwood` binary.
+//!
+//! Following ADR 25 (single binary with subcommands), every use of the binary is a subcommand:
+//!
+//! * `run` - Execute one or more pipelines once with an input and exit
+//! * `validate` - Check configurations without executing them
+//! * `graph` - Print a pipeline's dependency graph
+//! * `explain` - Describe how a pipeline would be executed
+//! * `inspect-wasm` - Show the type, imports and exports of a WASM module
+//! * `bench` - Execute a pipeline repeatedly and report timing statistics
+//! * `serve` - Host pipelines behind HTTP and other protocol receivers until SIGTERM/SIGINT
+//! * `demo` - The guided, interactive walkthrough
+//!
+//! Each subcommand returns a [`CliError`] on failure, which determines the process exit code
+//! (see [`exit_code`]).
+
+mod bench;
+mod demo;
+mod explain;
+mod graph;
+mod inspect_wasm;
+mod run;
+mod serve;
+mod validate;
+
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use the_dagwood::config::{load_and_validate_config, Config};
+
+/// Process exit codes used by every subcommand
+pub mod exit_code {
+    /// Command completed and, for executions, every processor succeeded
+    pub const SUCCESS: u8 = 0;
+    /// A pipeline ran but failed according to its failure strategy
+    pub const EXECUTION_FAILED: u8 = 1;
+    /// Invalid command line arguments (reported by clap)
+    pub const USAGE: u8 = 2;
+    /// A configuration could not be loaded, validated or compiled
+    pub const INVALID_CONFIG: u8 = 3;
+    /// Input or output files could not be read or written
+    pub const IO_ERROR: u8 = 4;
+    /// A WASM module could not be read or parsed
+    pub const INVALID_WASM: u8 = 5;
+    /// The server could not start or stop its protocol receivers
+    pub const SERVER_FAILED: u8 = 6;
+}
+
+#[derive(Debug, Parser)]
+#[command(name = "dagwood", version)]
+#[command(about = "DAG-based workflow orchestration engine")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Log line format on stderr
+    #[arg(long, value_enum, global = true, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
+
+    /// Write a Chrome trace (chrome://tracing, Perfetto) of the run
+    #[arg(long, global = true, value_name = "FILE")]
+    pub trace: Option<PathBuf>,
+
+    /// Write Prometheus metrics (text exposition format) after the run
+    #[arg(long, global = true, value_name = "FILE")]
+    pub metrics: Option<PathBuf>,
+
+    /// Export execution traces to an OTLP/gRPC collector
+    #[cfg(feature = "otel")]
+    #[arg(long, global = true, value_name = "URL")]
+    pub otlp: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Execute pipelines once with the given input and exit
+    Run(run::RunArgs),
+    /// Validate configurations without executing them
+    Validate(validate::ValidateArgs),
+    /// Print the dependency graph of a pipeline
+    Graph(graph::GraphArgs),
+    /// Describe how a pipeline would be executed, without running it
+    Explain(explain::ExplainArgs),
+    /// Show the type, imports and exports of a WASM module
+    InspectWasm(inspect_wasm::InspectWasmArgs),
+    /// Execute a pipeline repeatedly and report timing statistics
+    Bench(bench::BenchArgs),
+    /// Host pipelines behind the configured protocol receivers until SIGTERM/SIGINT
+    Serve(serve::ServeArgs),
+    /// Run the guided interactive demo
+    Demo,
+}
+
+/// Log line format selected with `--log-format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines
+    Text,
+    /// One JSON object per line with the structured message fields and enclosing spans
+    Json,
+}
+
+/// Pipeline input, from exactly one source
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct InputArgs {
+    /// Input text
+    #[arg(value_name = "INPUT")]
+    pub input: Option<String>,
+
+    /// Read the input from a file; binary content is passed through unchanged
+    #[arg(long, value_name = "PATH")]
+    pub input_file: Option<PathBuf>,
+
+    /// Read the input from stdin; binary content is passed through unchanged
+    #[arg(long)]
+    pub stdin: bool,
+}
+
+impl InputArgs {
+    /// Read the input payload from the selected source
+    pub fn read(&self) -> Result<Vec<u8>, CliError> {
+        if let Some(text) = &self.input {
+            return Ok(text.as_bytes().to_vec());
+        }
+
+        if let Some(path) = &self.input_file {
+            return std::fs::read(path).map_err(|e| CliError::Io {
+                context: format!("Failed to read input file {}", path.display()),
+                message: e.to_string(),
+            });
+        }
+
+        let mut payload = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut payload)
+            .map_err(|e| CliError::Io {
+                context: "Failed to read input from stdin".to_string(),
+                message: e.to_string(),
+            })?;
+        Ok(payload)
+    }
+}
+
+/// Error returned by a subcommand, mapped to the process exit code
+#[derive(Debug)]
+pub enum CliError {
+    /// A configuration could not be loaded, validated or compiled
+    InvalidConfig { path: String, message: String },
+    /// A pipeline ran but failed
+    ExecutionFailed { path: String, message: String },
+    /// An input or output file could not be read or written
+    Io { context: String, message: String },
+    /// A WASM module could not be read or parsed
+    InvalidWasm { path: String, message: String },
+    /// The server could not start or stop its protocol receivers
+    ServerFailed { message: String },
+    /// Some of several pipelines failed; each failure has already been reported
+    PipelinesFailed {
+        failed: usize,
+        total: usize,
+        exit_code: u8,
+    },
+}
+
+impl CliError {
+    /// Exit code reported for this error
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::InvalidConfig { .. } => exit_code::INVALID_CONFIG,
+            CliError::ExecutionFailed { .. } => exit_code::EXECUTION_FAILED,
+            CliError::Io { .. } => exit_code::IO_ERROR,
+            CliError::InvalidWasm { .. } => exit_code::INVALID_WASM,
+            CliError::ServerFailed { .. } => exit_code::SERVER_FAILED,
+            CliError::PipelinesFailed { exit_code, .. } => *exit_code,
+        }
+    }
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            CliError::InvalidConfig { path, message } => {
+                write!(f, "Invalid configuration {}: {}", path, message)
+            }
+            CliError::ExecutionFailed { path, message } => {
+                write!(f, "Execution of {} failed: {}", path, message)
+            }
+            CliError::Io { context, message } => write!(f, "{}: {}", context, message),
+            CliError::InvalidWasm { path, message } => {
+                write!(f, "Invalid WASM module {}: {}", path, message)
+            }
+            CliError::ServerFailed { message } => write!(f, "Server failed: {}", message),
+            CliError::PipelinesFailed { failed, total, .. } => {
+                write!(f, "{} of {} pipelines failed", failed, total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Load and validate a pipeline configuration
+pub fn load_config(path: &Path) -> Result<Config, CliError> {
+    load_and_validate_config(path).map_err(|e| CliError::InvalidConfig {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// Execute the selected subcommand
+pub async fn execute(command: Command) -> Result<(), CliError> {
+    match command {
+        Command::Run(args) => run::run(args).await,
+        Command::Validate(args) => validate::validate(args),
+        Command::Graph(args) => graph::graph(args).await,
+        Command::Explain(args) => explain::explain(args),
+        Command::InspectWasm(args) => inspect_wasm::inspect_wasm(args),
+        Command::Bench(args) => bench::bench(args).await,
+        Command::Serve(args) => serve::serve(args).await,
+        Command::Demo => {
+            demo::run_guided_demo().await;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_run_requires_exactly_one_input_source() {
+        assert!(Cli::try_parse_from(["dagwood", "run", "-c", "a.yaml", "hello"]).is_ok());
+        assert!(Cli::try_parse_from(["dagwood", "run", "-c", "a.yaml", "--stdin"]).is_ok());
+        assert!(Cli::try_parse_from(["dagwood", "run", "-c", "a.yaml"]).is_err());
+        assert!(
+            Cli::try_parse_from(["dagwood", "run", "-c", "a.yaml", "hello", "--stdin"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        let invalid = CliError::InvalidConfig {
+            path: "a.yaml".to_string(),
+            message: "cycle".to_string(),
+        };
+        assert_eq!(invalid.exit_code(), exit_code::INVALID_CONFIG);
+        assert_eq!(invalid.to_string(), "Invalid configuration a.yaml: cycle");
+
+        let failed = CliError::ExecutionFailed {
+            path: "a.yaml".to_string(),
+            message: "processor failed".to_string(),
+        };
+        assert_eq!(failed.exit_code(), exit_code::EXECUTION_FAILED);
+    }
+}
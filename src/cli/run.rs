@@ -0,0 +1,373 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood run` - execute pipelines once with an input.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use clap::{Args, ValueEnum};
+use the_dagwood::config::RuntimeBuilder;
+use the_dagwood::engine::{ExecutionReport, ProcessorStatus};
+use the_dagwood::proto::processor_v1::processor_response::Outcome;
+use the_dagwood::proto::processor_v1::{PipelineMetadata, ProcessorMetadata, ProcessorRequest};
+
+use super::{load_config, CliError, InputArgs};
+
+const UNKNOWN_KEY: &str = "unknown";
+
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Pipeline configuration file; repeat to run the same input through several pipelines
+    #[arg(short, long = "config", value_name = "CONFIG", required = true)]
+    pub configs: Vec<PathBuf>,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Result format on stdout
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// Only print machine-readable results (same as `--output json`)
+    #[arg(short, long, conflicts_with = "output")]
+    pub quiet: bool,
+}
+
+/// Result format of `dagwood run`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable execution summary
+    Text,
+    /// One JSON object per pipeline with the report and outputs
+    Json,
+    /// The final payload bytes, unmodified
+    Raw,
+}
+
+pub async fn run(args: RunArgs) -> Result<(), CliError> {
+    let output = if args.quiet {
+        OutputFormat::Json
+    } else {
+        args.output
+    };
+    let input = args.input.read()?;
+
+    if let [config_file] = args.configs.as_slice() {
+        run_config(config_file, &input, output).await?;
+    } else {
+        // Every pipeline runs even if an earlier one fails; the first failure sets the exit code
+        let mut failures = Vec::new();
+        for (i, config_file) in args.configs.iter().enumerate() {
+            if i > 0 && output == OutputFormat::Text {
+                println!("\n{}", "─".repeat(80));
+            }
+
+            if let Err(e) = run_config(config_file, &input, output).await {
+                eprintln!("❌ {}", e);
+                failures.push(e);
+            }
+        }
+
+        if let Some(first) = failures.first() {
+            return Err(CliError::PipelinesFailed {
+                failed: failures.len(),
+                total: args.configs.len(),
+                exit_code: first.exit_code(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute a single pipeline configuration and print its result in the given format
+pub async fn run_config(
+    config_file: &Path,
+    input: &[u8],
+    output: OutputFormat,
+) -> Result<(), CliError> {
+    let start_time = Instant::now();
+    let config_path = config_file.display().to_string();
+    let input_text = String::from_utf8_lossy(input);
+
+    // Load configuration
+    let config = load_config(config_file)?;
+
+    // Build runtime components and compile the execution plan from configuration
+    let (plan, executor, failure_strategy) =
+        RuntimeBuilder::compile(&config).map_err(|e| CliError::InvalidConfig {
+            path: config_path.clone(),
+            message: format!("Failed to build runtime: {}", e),
+        })?;
+
+    let request_metadata = HashMap::from([{
+        (
+            "initial_context".to_string(),
+            ProcessorMetadata {
+                metadata: HashMap::from([
+                    ("config_file".to_string(), config_path.clone()),
+                    (
+                        "hostname".to_string(),
+                        std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string()),
+                    ),
+                    ("input_text".to_string(), input_text.to_string()),
+                ]),
+            },
+        )
+    }]);
+
+    // Prepare input and pipeline metadata
+    let pipeline_metadata = PipelineMetadata {
+        metadata: request_metadata,
+    };
+    let request = ProcessorRequest {
+        payload: input.to_vec(),
+    };
+
+    if output == OutputFormat::Text {
+        println!("📋 Configuration: {}", config_path);
+        println!("🔧 Strategy: {:?}", config.strategy);
+        println!(
+            "⚙️  Max Concurrency: {}",
+            config
+                .executor_options
+                .max_concurrency
+                .unwrap_or_else(default_concurrency)
+        );
+        println!("🛡️  Failure Strategy: {:?}", config.failure_strategy);
+    }
+
+    // Execute the DAG
+    let report = executor
+        .execute_plan(plan, request, pipeline_metadata, failure_strategy)
+        .await
+        .map_err(|e| CliError::ExecutionFailed {
+            path: config_path.clone(),
+            message: e.to_string(),
+        })?;
+
+    match output {
+        OutputFormat::Text => {
+            print_report(&report, &input_text);
+            println!(
+                "\n⏱️  Total Time (including config load): {:?}",
+                start_time.elapsed()
+            );
+        }
+        OutputFormat::Json => println!("{}", report_json(&config_path, &report)),
+        OutputFormat::Raw => {
+            let mut stdout = std::io::stdout();
+            stdout
+                .write_all(&report.final_output)
+                .and_then(|()| stdout.flush())
+                .map_err(|e| CliError::Io {
+                    context: "Failed to write output".to_string(),
+                    message: e.to_string(),
+                })?;
+        }
+    }
+
+    // Surface processor failures according to the failure strategy
+    report
+        .into_result()
+        .map_err(|e| CliError::ExecutionFailed {
+            path: config_path,
+            message: e.to_string(),
+        })?;
+    Ok(())
+}
+
+/// Get the default concurrency level based on system capabilities
+///
+/// Returns the number of available CPU cores, falling back to 4 if detection fails.
+/// This provides a sensible default for concurrent processor execution.
+fn default_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
+
+/// Print the human-readable execution summary
+fn print_report(report: &ExecutionReport, input_text: &str) {
+    // Display results
+    println!("\n📊 Execution Results:");
+    println!("🆔 Run ID: {}", report.run_id);
+    println!("⏱️  Execution Time: {:?}", report.duration);
+    println!(
+        "🔢 Processors Succeeded: {}/{}",
+        report
+            .processors_with_status(ProcessorStatus::Succeeded)
+            .len(),
+        report.processors.len()
+    );
+
+    // Show processor chain in the order processors started
+    println!("\n🔄 Processor Chain:");
+    for (i, (processor_id, processor_report)) in
+        report.processors_in_order().into_iter().enumerate()
+    {
+        let output = match processor_report
+            .response
+            .as_ref()
+            .and_then(|response| response.outcome.as_ref())
+        {
+            Some(Outcome::NextPayload(payload)) => {
+                format!("\"{}\"", String::from_utf8_lossy(payload))
+            }
+            _ => "[No output]".to_string(),
+        };
+
+        println!(
+            "  {}. {} [{}] → {}",
+            i + 1,
+            processor_id,
+            processor_report.status,
+            output
+        );
+        println!(
+            "     ⏱️  {:?} ({} attempt{})",
+            processor_report.duration,
+            processor_report.attempts,
+            if processor_report.attempts == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+
+        if let Some(error) = &processor_report.error {
+            println!("     ❌ {}", error);
+        }
+
+        // Show metadata if present
+        if let Some(pipeline_metadata) = processor_report
+            .response
+            .as_ref()
+            .and_then(|response| response.metadata.as_ref())
+        {
+            println!(
+                "     📝 Metadata: {} entries",
+                pipeline_metadata.metadata.len()
+            );
+            for (key, metadata) in pipeline_metadata.metadata.iter().take(3) {
+                // Show the first 3 metadata entries
+                if !metadata.metadata.is_empty() {
+                    let sample_key = metadata
+                        .metadata
+                        .keys()
+                        .next()
+                        .map(|k| k.as_str())
+                        .unwrap_or(UNKNOWN_KEY);
+                    println!(
+                        "        • {}: {} keys (e.g., {})",
+                        key,
+                        metadata.metadata.len(),
+                        sample_key
+                    );
+                }
+            }
+            if pipeline_metadata.metadata.len() > 3 {
+                println!(
+                    "        • ... and {} more",
+                    pipeline_metadata.metadata.len() - 3
+                );
+            }
+        }
+    }
+
+    // Final transformation summary
+    println!("\n🎯 Final Transformation:");
+    println!("   Input:  \"{}\"", input_text);
+    if report.outputs.is_empty() {
+        // No designated outputs - fall back to the canonical payload
+        println!(
+            "   Output: \"{}\"",
+            String::from_utf8_lossy(&report.final_output)
+        );
+    } else {
+        for output in &report.outputs {
+            match &output.payload {
+                Some(payload) => println!(
+                    "   Output '{}' ({}): \"{}\"",
+                    output.name,
+                    output.processor_id,
+                    String::from_utf8_lossy(payload)
+                ),
+                None => println!(
+                    "   Output '{}' ({}): [{}]",
+                    output.name, output.processor_id, output.status
+                ),
+            }
+            let mut keys: Vec<&String> = output.metadata.keys().collect();
+            keys.sort();
+            for key in keys {
+                println!("      • {}: {}", key, output.metadata[key]);
+            }
+        }
+    }
+
+    // Show accumulated pipeline metadata
+    if report.pipeline_metadata.metadata.is_empty() {
+        println!("   No metadata");
+    } else {
+        println!("   Pipeline Metadata:");
+        for (processor_name, metadata) in report.pipeline_metadata.metadata.iter() {
+            println!("   {}:", processor_name);
+            for (key, value) in metadata.metadata.iter() {
+                println!("      • {}: {}", key, value);
+            }
+        }
+    }
+
+    if !report.errors.is_empty() {
+        println!("\n❌ Errors:");
+        for error in &report.errors {
+            println!("   • {}", error);
+        }
+    }
+}
+
+/// Machine-readable summary of an execution, printed as a single line
+fn report_json(config_file: &str, report: &ExecutionReport) -> serde_json::Value {
+    let processors: Vec<serde_json::Value> = report
+        .processors_in_order()
+        .into_iter()
+        .map(|(processor_id, processor_report)| {
+            serde_json::json!({
+                "id": processor_id,
+                "status": processor_report.status.to_string(),
+                "attempts": processor_report.attempts,
+                "duration_ms": processor_report.duration.as_secs_f64() * 1000.0,
+                "error": processor_report.error.as_ref().map(|error| error.to_string()),
+            })
+        })
+        .collect();
+    let outputs: Vec<serde_json::Value> = report
+        .outputs
+        .iter()
+        .map(|output| {
+            serde_json::json!({
+                "name": output.name,
+                "processor_id": output.processor_id,
+                "status": output.status.to_string(),
+                "payload": output.payload.as_ref().map(|payload| String::from_utf8_lossy(payload)),
+                "metadata": output.metadata,
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "config": config_file,
+        "run_id": report.run_id,
+        "strategy": report.strategy,
+        "success": report.is_success(),
+        "duration_ms": report.duration.as_secs_f64() * 1000.0,
+        "final_output": String::from_utf8_lossy(&report.final_output),
+        "outputs": outputs,
+        "processors": processors,
+        "errors": report.errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+    })
+}
@@ -0,0 +1,145 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood serve` - host pipelines behind the configured protocol receivers.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use the_dagwood::config::{load_and_validate_server_config, ServerConfig};
+use the_dagwood::errors::{ProtocolError, ServerError};
+use the_dagwood::observability::messages::server::ShutdownSignalReceived;
+use the_dagwood::observability::messages::StructuredLog;
+use the_dagwood::server::{DagwoodServer, ShutdownSignal};
+
+use super::CliError;
+
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Server configuration file with `protocols` and `pipelines`; single-pipeline files are
+    /// served as the `default` pipeline
+    #[arg(short, long, value_name = "CONFIG")]
+    pub config: PathBuf,
+
+    /// Reload the pipelines whenever the configuration file changes; reloads are also
+    /// available at `POST /admin/reload` on HTTP receivers
+    #[arg(long)]
+    pub watch: bool,
+}
+
+/// Serve until SIGTERM or SIGINT, then shut down gracefully: stop accepting requests, drain
+/// in-flight runs for up to `shutdown.drain_timeout_seconds` and cancel the rest
+pub async fn serve(args: ServeArgs) -> Result<(), CliError> {
+    let signal = ShutdownSignal::listen().map_err(|e| CliError::ServerFailed {
+        message: format!("Failed to listen for shutdown signals: {}", e),
+    })?;
+    serve_until(&args.config, args.watch, async move {
+        let signal = signal.recv().await;
+        ShutdownSignalReceived { signal }.log();
+        eprintln!("🛑 Received {}, shutting down...", signal);
+    })
+    .await
+}
+
+/// Serve the configuration until `signal` completes
+async fn serve_until(
+    config_file: &Path,
+    watch: bool,
+    signal: impl Future<Output = ()>,
+) -> Result<(), CliError> {
+    let config_path = config_file.display().to_string();
+    let config: ServerConfig =
+        load_and_validate_server_config(config_file).map_err(|e| CliError::InvalidConfig {
+            path: config_path.clone(),
+            message: e.to_string(),
+        })?;
+
+    let mut server = DagwoodServer::from_config(config)
+        .map_err(|e| server_error(&config_path, e))?
+        .with_hot_reload(config_file);
+    if watch {
+        server = server.watch_config();
+    }
+    server
+        .start()
+        .await
+        .map_err(|e| server_error(&config_path, e))?;
+
+    println!("🚀 DAGwood server started ({})", config_path);
+    if watch {
+        println!("   👀 Reloading pipelines when the configuration changes");
+    }
+    for (name, state) in server.registry().states() {
+        println!("   📋 Pipeline '{}' [{}]", name, state);
+    }
+    for receiver in server.receivers() {
+        println!(
+            "   📡 {} '{}' on {}",
+            receiver.protocol_name(),
+            receiver.name(),
+            receiver.endpoint().unwrap_or_default()
+        );
+    }
+
+    signal.await;
+    server
+        .shutdown()
+        .await
+        .map_err(|e| server_error(&config_path, e))?;
+    println!("👋 DAGwood server stopped");
+    Ok(())
+}
+
+/// Configuration mistakes are reported as invalid configuration, everything else as a
+/// server failure
+fn server_error(config_path: &str, error: ServerError) -> CliError {
+    match error {
+        ServerError::Pipeline(_)
+        | ServerError::Protocol(ProtocolError::InvalidOptions { .. })
+        | ServerError::Auth(_) => CliError::InvalidConfig {
+            path: config_path.to_string(),
+            message: error.to_string(),
+        },
+        ServerError::Protocol(_) | ServerError::ConfigWatch { .. } | ServerError::Job(_) => {
+            CliError::ServerFailed {
+                message: error.to_string(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn test_serve_until_signal() {
+        let mut config = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            config,
+            r#"
+protocols:
+  - type: http
+    options: {{port: 0}}
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#
+        )
+        .unwrap();
+
+        serve_until(config.path(), false, async {}).await.unwrap();
+        serve_until(config.path(), true, async {}).await.unwrap();
+
+        let error = serve_until(Path::new("missing.yaml"), false, async {})
+            .await
+            .unwrap_err();
+        assert!(matches!(error, CliError::InvalidConfig { .. }));
+    }
+}
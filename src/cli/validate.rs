@@ -0,0 +1,68 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! `dagwood validate` - check configurations without executing them.
+
+use std::path::{Path, PathBuf};
+
+use clap::Args;
+use the_dagwood::config::{Config, RuntimeBuilder};
+
+use super::{load_config, CliError};
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    /// Pipeline configuration files to validate
+    #[arg(value_name = "CONFIG", required = true)]
+    pub configs: Vec<PathBuf>,
+
+    /// Only check syntax and the dependency graph; do not build processors or load WASM modules
+    #[arg(long)]
+    pub syntax_only: bool,
+}
+
+/// Validate every configuration, reporting each result; fails if any configuration is invalid
+pub fn validate(args: ValidateArgs) -> Result<(), CliError> {
+    let mut failures = Vec::new();
+
+    for config_file in &args.configs {
+        match validate_config(config_file, args.syntax_only) {
+            Ok(config) => println!(
+                "✅ {}: valid ({} processors, {:?} strategy)",
+                config_file.display(),
+                config.processors.len(),
+                config.strategy
+            ),
+            Err(e) => {
+                println!("❌ {}", e);
+                failures.push(e);
+            }
+        }
+    }
+
+    match failures.first() {
+        Some(first) => Err(CliError::PipelinesFailed {
+            failed: failures.len(),
+            total: args.configs.len(),
+            exit_code: first.exit_code(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Load, validate and (unless `syntax_only`) build the runtime of a single configuration.
+///
+/// Building the runtime checks that every processor can be created, e.g. that local
+/// processors exist and WASM modules load.
+fn validate_config(config_file: &Path, syntax_only: bool) -> Result<Config, CliError> {
+    let config = load_config(config_file)?;
+
+    if !syntax_only {
+        RuntimeBuilder::compile(&config).map_err(|e| CliError::InvalidConfig {
+            path: config_file.display().to_string(),
+            message: e,
+        })?;
+    }
+
+    Ok(config)
+}
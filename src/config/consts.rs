@@ -7,3 +7,32 @@ pub const DEFAULT_FUEL_LEVEL: u64 = 100_000_000;
 pub const MIN_FUEL_LEVEL: u64 = 1_000_000;
 /// Maximum allowed fuel level (500 million instructions) - security limit
 pub const MAX_FUEL_LEVEL: u64 = 500_000_000;
+
+/// Name given to a legacy single-pipeline configuration when hosted as a named pipeline
+pub const DEFAULT_PIPELINE_NAME: &str = "default";
+/// Default number of initialization retries before a pipeline is permanently failed
+pub const DEFAULT_INIT_MAX_RETRIES: u32 = 3;
+/// Default delay before the first initialization retry (1 second)
+pub const DEFAULT_INIT_RETRY_DELAY_MS: u64 = 1_000;
+/// Upper bound for exponential initialization and webhook retry delays (1 minute)
+pub const MAX_INIT_RETRY_DELAY_MS: u64 = 60_000;
+/// Default time in-flight executions of a replaced pipeline version may finish (30 seconds)
+pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+/// Default time in-flight executions may finish when the server shuts down (30 seconds)
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SECS: u64 = 30;
+/// Default interval between memory management checks (30 seconds)
+pub const DEFAULT_EVICTION_CHECK_INTERVAL_SECS: u64 = 30;
+/// Default time finished asynchronous jobs are kept (1 hour)
+pub const DEFAULT_JOB_RETENTION_SECS: u64 = 3_600;
+/// Default number of asynchronous jobs executed at once; further jobs stay queued
+pub const DEFAULT_MAX_CONCURRENT_JOBS: usize = 16;
+/// Default interval between removals of expired jobs (1 minute)
+pub const DEFAULT_JOB_CLEANUP_INTERVAL_SECS: u64 = 60;
+/// Default time a webhook delivery attempt may take (5 seconds)
+pub const DEFAULT_WEBHOOK_TIMEOUT_MS: u64 = 5_000;
+/// Default number of webhook delivery retries after the first failed attempt
+pub const DEFAULT_WEBHOOK_MAX_RETRIES: u32 = 3;
+/// Default delay before the first webhook delivery retry (1 second)
+pub const DEFAULT_WEBHOOK_RETRY_DELAY_MS: u64 = 1_000;
+/// Default length of the window WASM fuel quotas are measured over (1 minute)
+pub const DEFAULT_QUOTA_FUEL_WINDOW_SECS: u64 = 60;
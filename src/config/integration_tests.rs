@@ -3,7 +3,10 @@
 
 #[cfg(test)]
 mod integration_tests {
-    use crate::config::{load_and_validate_config, RuntimeBuilder, Strategy};
+    use crate::config::{
+        load_and_validate_config, load_and_validate_pipelines_config,
+        load_and_validate_server_config, ProtocolType, RuntimeBuilder, Strategy,
+    };
     use crate::errors::FailureStrategy;
 
     /// Test that YAML configurations can be loaded and parsed correctly
@@ -100,4 +103,55 @@ mod integration_tests {
         // Just check that we got an executor back - the Box is guaranteed to be non-null
         assert!(true); // Executor creation succeeded if we got here
     }
+
+    /// Test loading a multi-pipeline configuration file
+    #[test]
+    fn test_multi_pipeline_yaml_loading() {
+        let config = load_and_validate_pipelines_config("configs/multi-pipeline.yaml").unwrap();
+
+        assert_eq!(
+            config.names(),
+            vec![
+                "text_processing_workqueue",
+                "text_processing_level",
+                "text_analysis"
+            ]
+        );
+
+        let level = config.pipeline("text_processing_level").unwrap();
+        assert_eq!(level.config.strategy, Strategy::Level);
+        assert_eq!(
+            level.config.failure_strategy,
+            FailureStrategy::ContinueOnError
+        );
+
+        let analysis = config.pipeline("text_analysis").unwrap();
+        assert_eq!(analysis.config.strategy, Strategy::Reactive);
+        assert_eq!(analysis.config.outputs.len(), 2);
+    }
+
+    /// Test loading the protocols of a server configuration file
+    #[test]
+    fn test_server_yaml_loading() {
+        let config = load_and_validate_server_config("configs/multi-pipeline.yaml").unwrap();
+
+        assert_eq!(config.protocols.len(), 3);
+        assert_eq!(config.protocols[0].protocol, ProtocolType::Http);
+        assert_eq!(config.protocols[0].name(), "public");
+        assert_eq!(config.protocols[1].protocol, ProtocolType::Grpc);
+        assert_eq!(config.protocols[1].name(), "internal");
+        assert_eq!(config.protocols[2].protocol, ProtocolType::UnixSocket);
+        assert!(config.jobs.enabled);
+        assert_eq!(config.pipelines.pipelines.len(), 3);
+    }
+
+    /// Test that legacy single-pipeline files load as the `default` pipeline
+    #[test]
+    fn test_legacy_yaml_loads_as_default_pipeline() {
+        let config =
+            load_and_validate_pipelines_config("configs/simple-text-pipeline.yaml").unwrap();
+
+        assert_eq!(config.names(), vec!["default"]);
+        assert_eq!(config.pipelines[0].config.processors.len(), 3);
+    }
 }
@@ -1,12 +1,20 @@
 // Copyright (c) 2025 Steve Wagner (ciroque@live.com)
 // SPDX-License-Identifier: MIT
 
-use crate::config::consts::{DEFAULT_FUEL_LEVEL, MAX_FUEL_LEVEL, MIN_FUEL_LEVEL};
+use crate::config::consts::{
+    DEFAULT_DRAIN_TIMEOUT_SECS, DEFAULT_EVICTION_CHECK_INTERVAL_SECS, DEFAULT_FUEL_LEVEL,
+    DEFAULT_INIT_MAX_RETRIES, DEFAULT_INIT_RETRY_DELAY_MS, DEFAULT_JOB_CLEANUP_INTERVAL_SECS,
+    DEFAULT_JOB_RETENTION_SECS, DEFAULT_MAX_CONCURRENT_JOBS, DEFAULT_PIPELINE_NAME,
+    DEFAULT_QUOTA_FUEL_WINDOW_SECS, DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SECS,
+    DEFAULT_WEBHOOK_MAX_RETRIES, DEFAULT_WEBHOOK_RETRY_DELAY_MS, DEFAULT_WEBHOOK_TIMEOUT_MS,
+    MAX_FUEL_LEVEL, MAX_INIT_RETRY_DELAY_MS, MIN_FUEL_LEVEL,
+};
 use crate::errors::FailureStrategy;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Main configuration structure for the DAG execution engine.
 ///
@@ -20,6 +28,7 @@ use std::path::Path;
 /// * `executor_options` - Executor-specific configuration options (optional)
 /// * `wasm` - WASM-specific configuration options (optional)
 /// * `processors` - Vector of processor configurations that define the DAG nodes
+/// * `outputs` - Processors whose payloads form the pipeline result (optional)
 ///
 /// # Example
 /// ```yaml
@@ -36,8 +45,10 @@ use std::path::Path;
 ///   - id: "processor1"
 ///     type: local
 ///     processor: "my_processor"
+/// outputs:
+///   - processor: "processor1"
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct Config {
     pub strategy: Strategy,
     #[serde(default)]
@@ -47,6 +58,919 @@ pub struct Config {
     #[serde(default)]
     pub wasm: WasmConfig,
     pub processors: Vec<ProcessorConfig>,
+    #[serde(default)]
+    pub outputs: Vec<OutputConfig>,
+}
+
+/// Configuration for a named pipeline hosted alongside other pipelines.
+///
+/// A named pipeline carries the same settings as a single-pipeline [`Config`] (strategy,
+/// failure strategy, executor options, processors, ...), so every pipeline hosted in one
+/// process can use its own execution strategy and failure handling.
+///
+/// # Fields
+/// * `name` - Unique name used to route requests to this pipeline
+/// * `startup` - When the pipeline is initialized (optional, defaults to `auto`)
+/// * `initialization` - Retry and timeout settings for initialization (optional)
+/// * `hot_reload` - Drain settings used when the pipeline is reloaded or removed (optional)
+/// * `webhook` - Where to notify the completion of every execution (optional)
+/// * `config` - The pipeline definition, flattened into the same mapping as `name`
+///
+/// # Example
+/// ```yaml
+/// name: text_processing
+/// startup: on-demand
+/// initialization:
+///   max_retries: 3
+///   retry_backoff: exponential
+/// hot_reload:
+///   drain_timeout_seconds: 60
+/// webhook:
+///   url: "http://127.0.0.1:9000/dagwood"
+/// quota:
+///   max_concurrent: 4
+/// strategy: level
+/// failure_strategy: continue_on_error
+/// processors:
+///   - id: "uppercase"
+///     type: local
+///     processor: "change_text_case_upper"
+/// ```
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct PipelineConfig {
+    pub name: String,
+    #[serde(default)]
+    pub startup: StartupMode,
+    #[serde(default)]
+    pub initialization: InitializationConfig,
+    #[serde(default)]
+    pub hot_reload: HotReloadConfig,
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    #[serde(default)]
+    pub quota: Option<QuotaConfig>,
+    #[serde(flatten)]
+    pub config: Config,
+}
+
+/// When a hosted pipeline is initialized.
+///
+/// Initialization instantiates the processors (including compiling WASM modules) and
+/// compiles the execution plan, which can be slow.
+///
+/// # Variants
+/// * `Auto` - Initialize when the host starts; startup fails if initialization fails
+/// * `OnDemand` - Initialize on the first request; later requests queue behind it
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StartupMode {
+    #[default]
+    Auto,
+    OnDemand,
+}
+
+impl StartupMode {
+    /// Name of the startup mode as written in configuration files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StartupMode::Auto => "auto",
+            StartupMode::OnDemand => "on-demand",
+        }
+    }
+}
+
+/// Delay growth between retries of pipeline initializations and webhook deliveries.
+///
+/// # Variants
+/// * `Exponential` - The delay doubles after every failed attempt (1s, 2s, 4s, ...)
+/// * `Fixed` - The same delay between every attempt
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryBackoff {
+    #[default]
+    Exponential,
+    Fixed,
+}
+
+impl RetryBackoff {
+    /// Delay before retrying after the given failed attempt (1-based), starting from `base_ms`.
+    ///
+    /// Exponential delays are capped at one minute, or `base_ms` if larger.
+    pub fn delay(&self, base_ms: u64, failed_attempt: u32) -> Duration {
+        let delay = match self {
+            RetryBackoff::Fixed => base_ms,
+            RetryBackoff::Exponential => {
+                let factor = 1u64
+                    .checked_shl(failed_attempt.saturating_sub(1))
+                    .unwrap_or(u64::MAX);
+                base_ms
+                    .saturating_mul(factor)
+                    .min(MAX_INIT_RETRY_DELAY_MS.max(base_ms))
+            }
+        };
+        Duration::from_millis(delay)
+    }
+}
+
+/// Retry and timeout settings for pipeline initialization.
+///
+/// A failed initialization is retried `max_retries` times with the configured backoff before
+/// the pipeline is marked permanently failed. All values are optional and use sensible defaults.
+///
+/// # Fields
+/// * `max_retries` - Retries after the first failed attempt (defaults to 3)
+/// * `retry_backoff` - Delay growth between retries (defaults to exponential)
+/// * `retry_delay_ms` - Delay before the first retry in milliseconds (defaults to 1000)
+/// * `timeout_seconds` - How long requests wait for initialization (optional, unlimited)
+///
+/// # Example
+/// ```yaml
+/// initialization:
+///   max_retries: 5
+///   retry_backoff: fixed
+///   retry_delay_ms: 5000
+///   timeout_seconds: 30
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+pub struct InitializationConfig {
+    pub max_retries: Option<u32>,
+    #[serde(default)]
+    pub retry_backoff: RetryBackoff,
+    pub retry_delay_ms: Option<u64>,
+    pub timeout_seconds: Option<u64>,
+}
+
+impl InitializationConfig {
+    /// Get the number of retries, using the built-in default if not configured.
+    pub fn get_max_retries(&self) -> u32 {
+        self.max_retries.unwrap_or(DEFAULT_INIT_MAX_RETRIES)
+    }
+
+    /// Get the maximum number of initialization attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.get_max_retries().saturating_add(1)
+    }
+
+    /// Get the time requests wait for initialization, if limited.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_seconds.map(Duration::from_secs)
+    }
+
+    /// Delay before retrying after the given failed attempt (1-based).
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use the_dagwood::config::InitializationConfig;
+    ///
+    /// let config = InitializationConfig::default();
+    /// assert_eq!(config.retry_delay(1), Duration::from_secs(1));
+    /// assert_eq!(config.retry_delay(3), Duration::from_secs(4));
+    /// ```
+    pub fn retry_delay(&self, failed_attempt: u32) -> Duration {
+        self.retry_backoff.delay(
+            self.retry_delay_ms.unwrap_or(DEFAULT_INIT_RETRY_DELAY_MS),
+            failed_attempt,
+        )
+    }
+}
+
+/// Drain settings applied when a running pipeline is reloaded or removed.
+///
+/// The previous version of a reloaded pipeline keeps running its in-flight executions while
+/// new requests go to the new version; executions still running after the drain timeout
+/// are cancelled.
+///
+/// # Fields
+/// * `drain_timeout_seconds` - How long in-flight executions may finish (defaults to 30)
+///
+/// # Example
+/// ```yaml
+/// hot_reload:
+///   drain_timeout_seconds: 60
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+pub struct HotReloadConfig {
+    pub drain_timeout_seconds: Option<u64>,
+}
+
+impl HotReloadConfig {
+    /// Get the drain timeout, using the built-in default if not configured.
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.drain_timeout_seconds
+                .unwrap_or(DEFAULT_DRAIN_TIMEOUT_SECS),
+        )
+    }
+}
+
+/// Webhook notified when an execution of the pipeline finishes.
+///
+/// Each notification is a JSON summary of the execution report, POSTed to `url` and, when
+/// `allow_request_callbacks` is set, to the callback URL given by the request. Failed
+/// deliveries (connection errors, timeouts, `429` and `5xx` responses) are retried with the
+/// configured backoff. With a `secret`, every notification carries an HMAC-SHA256 signature
+/// of its body in the `X-Dagwood-Signature` header.
+///
+/// # Fields
+/// * `url` - `http://` or `https://` URL notified of every execution (optional)
+/// * `secret` - Key signing the notifications (optional, unsigned without)
+/// * `allow_request_callbacks` - Whether requests may name their own callback URL
+///   (defaults to false)
+/// * `timeout_ms` - Time allowed for each delivery attempt (defaults to 5000)
+/// * `max_retries` - Retries after the first failed attempt (defaults to 3)
+/// * `retry_backoff` - Delay growth between retries (defaults to exponential)
+/// * `retry_delay_ms` - Delay before the first retry in milliseconds (defaults to 1000)
+///
+/// # Example
+/// ```yaml
+/// webhook:
+///   url: "http://notifications.internal:9000/dagwood"
+///   secret: "change-me"
+///   allow_request_callbacks: true
+///   max_retries: 5
+/// ```
+#[derive(Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub allow_request_callbacks: bool,
+    pub timeout_ms: Option<u64>,
+    pub max_retries: Option<u32>,
+    #[serde(default)]
+    pub retry_backoff: RetryBackoff,
+    pub retry_delay_ms: Option<u64>,
+}
+
+impl WebhookConfig {
+    /// Get the time allowed for each delivery attempt, using the built-in default if not
+    /// configured.
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms.unwrap_or(DEFAULT_WEBHOOK_TIMEOUT_MS))
+    }
+
+    /// Get the maximum number of delivery attempts, including the first one.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_retries
+            .unwrap_or(DEFAULT_WEBHOOK_MAX_RETRIES)
+            .saturating_add(1)
+    }
+
+    /// Delay before retrying after the given failed delivery attempt (1-based).
+    pub fn retry_delay(&self, failed_attempt: u32) -> Duration {
+        self.retry_backoff.delay(
+            self.retry_delay_ms
+                .unwrap_or(DEFAULT_WEBHOOK_RETRY_DELAY_MS),
+            failed_attempt,
+        )
+    }
+}
+
+impl std::fmt::Debug for WebhookConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The secret stays out of logs and error messages
+        f.debug_struct("WebhookConfig")
+            .field("url", &self.url)
+            .field("secret", &self.secret.as_ref().map(|_| "<redacted>"))
+            .field("allow_request_callbacks", &self.allow_request_callbacks)
+            .field("timeout_ms", &self.timeout_ms)
+            .field("max_retries", &self.max_retries)
+            .field("retry_backoff", &self.retry_backoff)
+            .field("retry_delay_ms", &self.retry_delay_ms)
+            .finish()
+    }
+}
+
+/// Limits on how much of the server a client or pipeline may use.
+///
+/// Set per pipeline, and per authenticated client as `quota` of its [`AuthClientConfig`];
+/// a request must stay within the quotas of both. Every limit is optional and unlimited when
+/// not set. Requests over a limit are rejected before anything executes, with a hint of
+/// when to retry where waiting helps (see [`crate::server::quotas`]).
+///
+/// # Fields
+/// * `max_concurrent` - Executions in flight at once, including queued jobs
+/// * `requests_per_second` - Sustained rate of accepted requests (token bucket refill rate)
+/// * `burst` - Requests accepted at once on top of an idle rate (defaults to
+///   `requests_per_second` rounded up)
+/// * `max_payload_bytes` - Largest request payload
+/// * `fuel_per_window` - WASM fuel the executions may consume per window
+/// * `fuel_window_seconds` - Length of the fuel window (defaults to 60)
+///
+/// # Example
+/// ```yaml
+/// quota:
+///   max_concurrent: 4
+///   requests_per_second: 10
+///   burst: 20
+///   max_payload_bytes: 1048576
+///   fuel_per_window: 50000000000
+///   fuel_window_seconds: 60
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaConfig {
+    pub max_concurrent: Option<usize>,
+    pub requests_per_second: Option<f64>,
+    pub burst: Option<u32>,
+    pub max_payload_bytes: Option<usize>,
+    pub fuel_per_window: Option<u64>,
+    pub fuel_window_seconds: Option<u64>,
+}
+
+impl QuotaConfig {
+    /// Get the token bucket capacity, if requests are rate limited.
+    pub fn burst(&self) -> Option<u32> {
+        let rate = self.requests_per_second?;
+        Some(self.burst.unwrap_or(rate.ceil() as u32).max(1))
+    }
+
+    /// Get the length of the fuel window, using the built-in default if not configured.
+    pub fn fuel_window(&self) -> Duration {
+        Duration::from_secs(
+            self.fuel_window_seconds
+                .unwrap_or(DEFAULT_QUOTA_FUEL_WINDOW_SECS),
+        )
+    }
+
+    /// Check that every configured limit can admit a request.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_concurrent == Some(0) {
+            return Err("max_concurrent must be at least 1".to_string());
+        }
+        match self.requests_per_second {
+            Some(rate) if !(rate.is_finite() && rate > 0.0) => {
+                return Err("requests_per_second must be positive".to_string())
+            }
+            None if self.burst.is_some() => {
+                return Err("burst requires requests_per_second".to_string())
+            }
+            _ => {}
+        }
+        if self.burst == Some(0) {
+            return Err("burst must be at least 1".to_string());
+        }
+        if self.fuel_window_seconds == Some(0) {
+            return Err("fuel_window_seconds must be at least 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Multi-pipeline configuration hosting several named DAGs in one process.
+///
+/// Legacy single-pipeline configurations are accepted wherever a `PipelinesConfig` is
+/// loaded and are wrapped as a single pipeline named `default`.
+///
+/// # Fields
+/// * `pipelines` - The named pipelines; names must be unique
+///
+/// # Example
+/// ```yaml
+/// pipelines:
+///   - name: text_processing_workqueue
+///     strategy: work_queue
+///     processors:
+///       - id: "uppercase"
+///         type: local
+///         processor: "change_text_case_upper"
+///   - name: text_processing_level
+///     strategy: level
+///     processors:
+///       - id: "uppercase"
+///         type: local
+///         processor: "change_text_case_upper"
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct PipelinesConfig {
+    pub pipelines: Vec<PipelineConfig>,
+}
+
+impl PipelinesConfig {
+    /// Parse a configuration in either the multi-pipeline or the legacy single-pipeline format.
+    ///
+    /// A document with a top-level `pipelines` key is parsed as a multi-pipeline configuration;
+    /// any other document is parsed as a legacy [`Config`] and wrapped as the `default` pipeline.
+    pub fn from_yaml(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_value(serde_yaml::from_str(content)?)
+    }
+
+    /// Parse an already loaded YAML document; see [`from_yaml`](Self::from_yaml).
+    pub fn from_value(document: serde_yaml::Value) -> Result<Self, Box<dyn std::error::Error>> {
+        let is_multi_pipeline = document
+            .as_mapping()
+            .is_some_and(|mapping| mapping.contains_key("pipelines"));
+        if !is_multi_pipeline {
+            let cfg: Config = serde_yaml::from_value(document)?;
+            return Ok(cfg.into());
+        }
+
+        if let Some(mapping) = document.as_mapping() {
+            if mapping.contains_key("processors") || mapping.contains_key("strategy") {
+                return Err(
+                    "Configuration mixes top-level 'pipelines' with single-pipeline \
+                    settings; move 'strategy' and 'processors' into a pipeline entry"
+                        .into(),
+                );
+            }
+        }
+
+        Ok(serde_yaml::from_value(document)?)
+    }
+
+    /// Look up a pipeline by name.
+    pub fn pipeline(&self, name: &str) -> Option<&PipelineConfig> {
+        self.pipelines.iter().find(|pipeline| pipeline.name == name)
+    }
+
+    /// Names of all pipelines, in configuration order.
+    pub fn names(&self) -> Vec<&str> {
+        self.pipelines
+            .iter()
+            .map(|pipeline| pipeline.name.as_str())
+            .collect()
+    }
+}
+
+impl From<Config> for PipelinesConfig {
+    /// Wrap a legacy single-pipeline configuration as the `default` pipeline.
+    fn from(config: Config) -> Self {
+        Self {
+            pipelines: vec![PipelineConfig {
+                name: DEFAULT_PIPELINE_NAME.to_string(),
+                startup: StartupMode::default(),
+                initialization: InitializationConfig::default(),
+                hot_reload: HotReloadConfig::default(),
+                webhook: None,
+                quota: None,
+                config,
+            }],
+        }
+    }
+}
+
+/// Configuration of a server: the protocol receivers and the pipelines they serve.
+///
+/// The pipelines use the same formats as [`PipelinesConfig`]; the server adds an optional
+/// top-level `protocols` list, `shutdown` settings and `memory_management` next to them.
+///
+/// # Example
+/// ```yaml
+/// protocols:
+///   - type: http
+///     name: public
+///     options:
+///       host: "0.0.0.0"
+///       port: 8080
+/// shutdown:
+///   drain_timeout_seconds: 20
+/// memory_management:
+///   enabled: true
+///   max_memory_mb: 512
+/// jobs:
+///   enabled: true
+/// auth:
+///   enabled: true
+///   clients:
+///     - name: operator
+///       api_key_env: DAGWOOD_API_KEY
+///       admin: true
+/// pipelines:
+///   - name: text_processing
+///     strategy: work_queue
+///     processors:
+///       - id: "uppercase"
+///         type: local
+///         processor: "change_text_case_upper"
+/// ```
+#[derive(Debug)]
+pub struct ServerConfig {
+    pub protocols: Vec<ProtocolConfig>,
+    pub shutdown: ShutdownConfig,
+    pub memory_management: MemoryManagementConfig,
+    pub jobs: JobsConfig,
+    pub auth: AuthConfig,
+    pub pipelines: PipelinesConfig,
+}
+
+impl ServerConfig {
+    /// Parse a server configuration.
+    ///
+    /// Everything except `protocols`, `shutdown`, `memory_management`, `jobs` and `auth` is
+    /// parsed as a [`PipelinesConfig`], so legacy single-pipeline files can be served as the
+    /// `default` pipeline.
+    pub fn from_yaml(content: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut document: serde_yaml::Value = serde_yaml::from_str(content)?;
+
+        let mut take = |key: &str| {
+            document
+                .as_mapping_mut()
+                .and_then(|mapping| mapping.remove(key))
+        };
+        let protocols = match take("protocols") {
+            Some(protocols) => serde_yaml::from_value(protocols)?,
+            None => Vec::new(),
+        };
+        let shutdown = match take("shutdown") {
+            Some(shutdown) => serde_yaml::from_value(shutdown)?,
+            None => ShutdownConfig::default(),
+        };
+        let memory_management = match take("memory_management") {
+            Some(memory_management) => serde_yaml::from_value(memory_management)?,
+            None => MemoryManagementConfig::default(),
+        };
+        let jobs = match take("jobs") {
+            Some(jobs) => serde_yaml::from_value(jobs)?,
+            None => JobsConfig::default(),
+        };
+        let auth = match take("auth") {
+            Some(auth) => serde_yaml::from_value(auth)?,
+            None => AuthConfig::default(),
+        };
+
+        Ok(Self {
+            protocols,
+            shutdown,
+            memory_management,
+            jobs,
+            auth,
+            pipelines: PipelinesConfig::from_value(document)?,
+        })
+    }
+}
+
+/// How the server shuts down (ADR 27).
+///
+/// On shutdown the server stops accepting requests and lets in-flight executions finish for
+/// up to the drain timeout; executions still running then are cancelled.
+///
+/// # Fields
+/// * `drain_timeout_seconds` - How long in-flight executions may finish (defaults to 30;
+///   `0` cancels them right away)
+///
+/// # Example
+/// ```yaml
+/// shutdown:
+///   drain_timeout_seconds: 20
+/// ```
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ShutdownConfig {
+    pub drain_timeout_seconds: Option<u64>,
+}
+
+impl ShutdownConfig {
+    /// Get the drain timeout, using the built-in default if not configured.
+    pub fn drain_timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.drain_timeout_seconds
+                .unwrap_or(DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SECS),
+        )
+    }
+}
+
+/// Memory management of the hosted pipelines (ADR 28).
+///
+/// When enabled, the server periodically evicts idle pipelines, least recently used first.
+/// An evicted pipeline releases its processors and is initialized again by its next request.
+/// A pipeline is evicted once it has been unused for the idle timeout, or while more pipelines
+/// are initialized than allowed or their estimated memory exceeds the budget. Pipelines with
+/// executions in flight are never evicted, nor are protected ones. When evicting pipelines
+/// cannot meet the budget, the compiled WASM modules of idle pipelines (protected ones
+/// included) are released instead and compiled again on their next execution.
+///
+/// The memory of a pipeline is estimated from the compiled code of its WASM modules, which
+/// dominates its footprint; native processors are not counted.
+///
+/// # Fields
+/// * `enabled` - Whether eviction runs (defaults to false: pipelines stay initialized)
+/// * `max_memory_mb` - Memory budget for initialized pipelines (optional)
+/// * `max_pipelines` - Maximum number of initialized pipelines (optional)
+/// * `idle_timeout_seconds` - Evict pipelines unused for this long (optional)
+/// * `check_interval_seconds` - How often the limits are checked (defaults to 30, at least 1)
+/// * `protect_startup_auto` - Never evict `startup: auto` pipelines (defaults to true)
+/// * `protected_pipelines` - Pipelines that are never evicted
+/// * `evict_wasm_modules` - Release compiled WASM modules when evicting pipelines does not
+///   meet the budget (defaults to true)
+///
+/// # Example
+/// ```yaml
+/// memory_management:
+///   enabled: true
+///   max_memory_mb: 512
+///   max_pipelines: 20
+///   idle_timeout_seconds: 3600
+///   protected_pipelines: [checkout]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MemoryManagementConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    pub max_memory_mb: Option<u64>,
+    pub max_pipelines: Option<usize>,
+    pub idle_timeout_seconds: Option<u64>,
+    pub check_interval_seconds: Option<u64>,
+    pub protect_startup_auto: Option<bool>,
+    #[serde(default)]
+    pub protected_pipelines: Vec<String>,
+    pub evict_wasm_modules: Option<bool>,
+}
+
+impl MemoryManagementConfig {
+    /// Get the memory budget in bytes, if one is configured.
+    pub fn budget_bytes(&self) -> Option<usize> {
+        self.max_memory_mb
+            .map(|megabytes| (megabytes as usize).saturating_mul(1024 * 1024))
+    }
+
+    /// Get the idle timeout, if one is configured.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout_seconds.map(Duration::from_secs)
+    }
+
+    /// Get the interval between checks, using the built-in default if not configured.
+    pub fn check_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.check_interval_seconds
+                .unwrap_or(DEFAULT_EVICTION_CHECK_INTERVAL_SECS)
+                .max(1),
+        )
+    }
+
+    /// Whether `startup: auto` pipelines are protected from eviction.
+    pub fn protects_startup_auto(&self) -> bool {
+        self.protect_startup_auto.unwrap_or(true)
+    }
+
+    /// Whether compiled WASM modules may be released to meet the budget.
+    pub fn evicts_wasm_modules(&self) -> bool {
+        self.evict_wasm_modules.unwrap_or(true)
+    }
+}
+
+/// Asynchronous jobs: pipeline runs submitted now and collected later.
+///
+/// When enabled, the HTTP receivers serve the job API (see [`crate::server::jobs`]). A job
+/// runs its pipeline in the background; its status, per-processor progress and result are
+/// kept in the job store until the retention period after it finished has passed.
+///
+/// # Fields
+/// * `enabled` - Whether jobs can be submitted (defaults to false)
+/// * `store` - Where jobs are kept: `type: memory` (the default) or `type: file` with a
+///   `directory`, so finished jobs survive restarts
+/// * `retention_seconds` - How long finished jobs are kept (defaults to 3600)
+/// * `max_concurrent` - Jobs executed at once; further jobs stay queued (defaults to 16)
+/// * `cleanup_interval_seconds` - How often expired jobs are removed (defaults to 60, at
+///   least 1)
+///
+/// # Example
+/// ```yaml
+/// jobs:
+///   enabled: true
+///   store:
+///     type: file
+///     directory: /var/lib/dagwood/jobs
+///   retention_seconds: 86400
+///   max_concurrent: 4
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JobsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub store: JobStoreConfig,
+    pub retention_seconds: Option<u64>,
+    pub max_concurrent: Option<usize>,
+    pub cleanup_interval_seconds: Option<u64>,
+}
+
+impl JobsConfig {
+    /// Get how long finished jobs are kept, using the built-in default if not configured.
+    pub fn retention(&self) -> Duration {
+        Duration::from_secs(self.retention_seconds.unwrap_or(DEFAULT_JOB_RETENTION_SECS))
+    }
+
+    /// Get the number of jobs executed at once, using the built-in default if not configured.
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+            .unwrap_or(DEFAULT_MAX_CONCURRENT_JOBS)
+            .max(1)
+    }
+
+    /// Get the interval between removals of expired jobs, using the built-in default if not
+    /// configured.
+    pub fn cleanup_interval(&self) -> Duration {
+        Duration::from_secs(
+            self.cleanup_interval_seconds
+                .unwrap_or(DEFAULT_JOB_CLEANUP_INTERVAL_SECS)
+                .max(1),
+        )
+    }
+}
+
+/// Where asynchronous jobs are kept.
+///
+/// # Variants
+/// * `Memory` - In process memory; jobs are lost on restart
+/// * `File` - One JSON file per job in `directory`, created if missing
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum JobStoreConfig {
+    #[default]
+    Memory,
+    File {
+        directory: PathBuf,
+    },
+}
+
+/// Authentication and authorization of the server's clients.
+///
+/// When enabled, every request must present the credentials of a configured client: an
+/// API key in the `X-Api-Key` header (`x-api-key` gRPC metadata), the same key as a bearer
+/// token (`Authorization: Bearer ...`), or a client certificate on a gRPC receiver with
+/// mutual TLS. Clients execute the pipelines of their allowlist, read pipeline and job
+/// status and, with `admin`, use the admin API. Health checks need no credentials (see
+/// [`crate::server::auth`]).
+///
+/// # Fields
+/// * `enabled` - Whether clients must authenticate (defaults to false)
+/// * `clients` - The clients allowed to call the server
+///
+/// # Example
+/// ```yaml
+/// auth:
+///   enabled: true
+///   clients:
+///     - name: dashboard
+///       api_key_env: DASHBOARD_API_KEY
+///       pipelines: [text_analysis]
+///       quota:
+///         requests_per_second: 5
+///     - name: operator
+///       api_key: "change-me"
+///       admin: true
+/// ```
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AuthConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub clients: Vec<AuthClientConfig>,
+}
+
+/// A client of the server and what it may access.
+///
+/// Every client has exactly one credential: an `api_key`, the environment variable
+/// `api_key_env` holding its key, or the `certificate_sha256` fingerprint (hex SHA-256 of
+/// the DER encoding) of its TLS client certificate.
+///
+/// # Fields
+/// * `name` - Name of the client in logs and errors
+/// * `api_key`, `api_key_env` or `certificate_sha256` - The client's credential
+/// * `pipelines` - Pipelines the client may execute (defaults to every pipeline)
+/// * `admin` - Whether the client may use the admin API (defaults to false)
+/// * `quota` - Limits on the client's requests across all pipelines (optional)
+#[derive(Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AuthClientConfig {
+    pub name: String,
+    pub api_key: Option<String>,
+    pub api_key_env: Option<String>,
+    pub certificate_sha256: Option<String>,
+    pub pipelines: Option<Vec<String>>,
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub quota: Option<QuotaConfig>,
+}
+
+impl std::fmt::Debug for AuthClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The key stays out of logs and error messages
+        f.debug_struct("AuthClientConfig")
+            .field("name", &self.name)
+            .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+            .field("api_key_env", &self.api_key_env)
+            .field("certificate_sha256", &self.certificate_sha256)
+            .field("pipelines", &self.pipelines)
+            .field("admin", &self.admin)
+            .field("quota", &self.quota)
+            .finish()
+    }
+}
+
+/// Configuration of a single protocol receiver.
+///
+/// # Fields
+/// * `protocol` - The protocol served by the receiver (`type` in YAML)
+/// * `name` - Name of the receiver in logs (optional, defaults to the protocol name)
+/// * `options` - Protocol-specific options such as the bind address
+///
+/// # Example
+/// ```yaml
+/// type: http
+/// name: public
+/// options:
+///   host: "0.0.0.0"
+///   port: 8080
+///   max_body_bytes: 1048576
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProtocolConfig {
+    #[serde(rename = "type")]
+    pub protocol: ProtocolType,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub options: HashMap<String, serde_yaml::Value>, // protocol-specific options
+}
+
+impl ProtocolConfig {
+    /// Receiver of the given protocol with default options.
+    pub fn new(protocol: ProtocolType) -> Self {
+        Self {
+            protocol,
+            name: None,
+            options: HashMap::new(),
+        }
+    }
+
+    /// Name of the receiver: the configured name, or the protocol name.
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(self.protocol.as_str())
+    }
+}
+
+/// Protocol served by a protocol receiver.
+///
+/// # Variants
+/// * `Http` - JSON or raw bytes over HTTP/1.1
+/// * `Grpc` - pipelines served as the `processor.v1.Processor` gRPC service
+/// * `UnixSocket` - length-prefixed protobuf frames over a Unix domain socket
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolType {
+    Http,
+    Grpc,
+    UnixSocket,
+}
+
+impl ProtocolType {
+    /// Stable, lowercase name of the protocol for logs and configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProtocolType::Http => "http",
+            ProtocolType::Grpc => "grpc",
+            ProtocolType::UnixSocket => "unix_socket",
+        }
+    }
+}
+
+/// Designation of a pipeline output.
+///
+/// Each output names a processor whose payload is returned as part of the pipeline
+/// result. Designating several outputs supports fan-out pipelines with multiple sinks.
+///
+/// # Fields
+/// * `processor` - ID of the processor whose payload forms this output
+/// * `name` - Name of the output in the result (optional, defaults to the processor ID)
+/// * `metadata_keys` - Metadata keys from the processor's response to include (optional)
+///
+/// # Example
+/// ```yaml
+/// outputs:
+///   - processor: "reverse"
+///   - processor: "counter"
+///     name: "stats"
+///     metadata_keys: ["word_count", "char_count"]
+/// ```
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct OutputConfig {
+    pub processor: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub metadata_keys: Vec<String>,
+}
+
+impl OutputConfig {
+    /// Create an output for a processor, named after the processor.
+    pub fn new(processor: impl Into<String>) -> Self {
+        Self {
+            processor: processor.into(),
+            name: None,
+            metadata_keys: Vec::new(),
+        }
+    }
+
+    /// Name of the output, defaulting to the processor ID.
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.processor)
+    }
 }
 
 /// Execution strategy for DAG processing.
@@ -60,7 +984,7 @@ pub struct Config {
 /// * `Level` - Executes processors level by level based on dependency depth
 /// * `Reactive` - Event-driven execution based on data availability
 /// * `Hybrid` - Combines multiple strategies for optimal performance
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Strategy {
     WorkQueue,
@@ -69,6 +993,18 @@ pub enum Strategy {
     Hybrid,
 }
 
+impl Strategy {
+    /// Name of the strategy as written in configuration files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Strategy::WorkQueue => "work_queue",
+            Strategy::Level => "level",
+            Strategy::Reactive => "reactive",
+            Strategy::Hybrid => "hybrid",
+        }
+    }
+}
+
 /// Executor-specific configuration options.
 ///
 /// These options control how the DAG executor behaves during execution.
@@ -79,7 +1015,7 @@ pub enum Strategy {
 /// * `timeout_seconds` - Timeout for individual processor execution in seconds (optional)
 /// * `retry_attempts` - Number of retry attempts for failed processors (optional)
 /// * `batch_size` - Batch size for batch processing executors (optional)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct ExecutorOptions {
     pub max_concurrency: Option<usize>,
     pub timeout_seconds: Option<u64>,
@@ -114,7 +1050,7 @@ impl Default for ExecutorOptions {
 ///     minimum: 1000000
 ///     maximum: 500000000
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct WasmConfig {
     #[serde(default)]
     pub fuel: FuelConfig,
@@ -150,7 +1086,7 @@ impl Default for WasmConfig {
 ///   minimum: 1000000     # 1 million instructions
 ///   maximum: 500000000   # 500 million instructions (hard limit)
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct FuelConfig {
     pub default: Option<u64>,
     pub minimum: Option<u64>,
@@ -234,7 +1170,7 @@ impl FuelConfig {
 /// processor: "DataProcessor"
 /// depends_on: ["input_validator"]
 /// ```
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, PartialEq)]
 pub struct ProcessorConfig {
     pub id: String,
     #[serde(rename = "type")]
@@ -301,6 +1237,65 @@ pub fn load_and_validate_config<P: AsRef<Path>>(
     Ok(cfg)
 }
 
+/// Load a multi-pipeline config from a YAML file
+///
+/// Legacy single-pipeline files are wrapped as a single pipeline named `default`.
+pub fn load_pipelines_config<P: AsRef<Path>>(
+    path: P,
+) -> Result<PipelinesConfig, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    PipelinesConfig::from_yaml(&content)
+}
+
+/// Load and validate a multi-pipeline config from a YAML file
+///
+/// This function loads the configuration and validates that pipeline names are unique and
+/// that every pipeline's dependency graph is acyclic with all references resolved.
+pub fn load_and_validate_pipelines_config<P: AsRef<Path>>(
+    path: P,
+) -> Result<PipelinesConfig, Box<dyn std::error::Error>> {
+    let cfg = load_pipelines_config(path)?;
+
+    if let Err(validation_errors) = crate::config::validate_pipelines(&cfg) {
+        let error_messages: Vec<String> = validation_errors.iter().map(|e| e.to_string()).collect();
+        let combined_error = format!(
+            "Configuration validation failed:\n{}",
+            error_messages.join("\n")
+        );
+        return Err(combined_error.into());
+    }
+
+    Ok(cfg)
+}
+
+/// Load a server config (protocols and pipelines) from a YAML file
+pub fn load_server_config<P: AsRef<Path>>(
+    path: P,
+) -> Result<ServerConfig, Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(path)?;
+    ServerConfig::from_yaml(&content)
+}
+
+/// Load and validate a server config from a YAML file
+///
+/// The pipelines are validated like [`load_and_validate_pipelines_config`] does.
+pub fn load_and_validate_server_config<P: AsRef<Path>>(
+    path: P,
+) -> Result<ServerConfig, Box<dyn std::error::Error>> {
+    let cfg = load_server_config(path)?;
+
+    if let Err(validation_errors) = crate::config::validate_pipelines(&cfg.pipelines) {
+        let error_messages: Vec<String> = validation_errors.iter().map(|e| e.to_string()).collect();
+        let combined_error = format!(
+            "Configuration validation failed:\n{}",
+            error_messages.join("\n")
+        );
+        return Err(combined_error.into());
+    }
+
+    Ok(cfg)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -528,4 +1523,258 @@ processors:
         assert_eq!(config.validate_and_clamp(100), 1_000_000); // Below min
         assert_eq!(config.validate_and_clamp(1_000_000_000), 500_000_000); // Above max
     }
+
+    #[test]
+    fn test_parse_multi_pipeline_config() {
+        let yaml = r#"
+pipelines:
+  - name: fast
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: thorough
+    strategy: level
+    failure_strategy: best_effort
+    executor_options:
+      max_concurrency: 2
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+      - id: reverse
+        type: local
+        processor: reverse_text
+        depends_on: [upper]
+"#;
+
+        let cfg = PipelinesConfig::from_yaml(yaml).unwrap();
+        assert_eq!(cfg.names(), vec!["fast", "thorough"]);
+
+        let fast = cfg.pipeline("fast").unwrap();
+        assert_eq!(fast.config.strategy, Strategy::WorkQueue);
+        assert_eq!(fast.config.failure_strategy, FailureStrategy::FailFast);
+
+        let thorough = cfg.pipeline("thorough").unwrap();
+        assert_eq!(thorough.config.strategy, Strategy::Level);
+        assert_eq!(
+            thorough.config.failure_strategy,
+            FailureStrategy::BestEffort
+        );
+        assert_eq!(thorough.config.executor_options.max_concurrency, Some(2));
+        assert_eq!(thorough.config.processors[1].depends_on, vec!["upper"]);
+    }
+
+    #[test]
+    fn test_legacy_config_wrapped_as_default_pipeline() {
+        let yaml = r#"
+strategy: reactive
+processors:
+  - id: upper
+    type: local
+    processor: change_text_case_upper
+"#;
+
+        let cfg = PipelinesConfig::from_yaml(yaml).unwrap();
+        assert_eq!(cfg.names(), vec![DEFAULT_PIPELINE_NAME]);
+        assert_eq!(cfg.pipelines[0].config.strategy, Strategy::Reactive);
+        assert_eq!(cfg.pipelines[0].config.processors.len(), 1);
+    }
+
+    #[test]
+    fn test_mixed_pipeline_formats_rejected() {
+        let yaml = r#"
+strategy: work_queue
+processors: []
+pipelines: []
+"#;
+
+        let error = PipelinesConfig::from_yaml(yaml).unwrap_err().to_string();
+        assert!(error.contains("mixes top-level 'pipelines'"));
+    }
+
+    #[test]
+    fn test_pipeline_startup_and_initialization_settings() {
+        let yaml = r#"
+pipelines:
+  - name: eager
+    strategy: work_queue
+    processors: []
+  - name: lazy
+    startup: on-demand
+    initialization:
+      max_retries: 1
+      retry_backoff: fixed
+      retry_delay_ms: 250
+      timeout_seconds: 10
+    strategy: work_queue
+    processors: []
+"#;
+
+        let cfg = PipelinesConfig::from_yaml(yaml).unwrap();
+
+        let eager = cfg.pipeline("eager").unwrap();
+        assert_eq!(eager.startup, StartupMode::Auto);
+        assert_eq!(eager.initialization.max_attempts(), 4);
+        assert_eq!(eager.initialization.timeout(), None);
+
+        let lazy = cfg.pipeline("lazy").unwrap();
+        assert_eq!(lazy.startup, StartupMode::OnDemand);
+        assert_eq!(lazy.initialization.max_attempts(), 2);
+        assert_eq!(
+            lazy.initialization.retry_delay(1),
+            Duration::from_millis(250)
+        );
+        assert_eq!(
+            lazy.initialization.retry_delay(2),
+            Duration::from_millis(250)
+        );
+        assert_eq!(lazy.initialization.timeout(), Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_exponential_retry_delay_is_capped() {
+        let config = InitializationConfig::default();
+
+        assert_eq!(config.retry_delay(1), Duration::from_millis(1_000));
+        assert_eq!(config.retry_delay(2), Duration::from_millis(2_000));
+        assert_eq!(config.retry_delay(7), Duration::from_millis(60_000));
+        assert_eq!(config.retry_delay(100), Duration::from_millis(60_000));
+    }
+
+    #[test]
+    fn test_parse_server_config() {
+        let yaml = r#"
+protocols:
+  - type: http
+    name: public
+    options:
+      host: "0.0.0.0"
+      port: 8080
+  - type: http
+shutdown:
+  drain_timeout_seconds: 5
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#;
+
+        let cfg = ServerConfig::from_yaml(yaml).unwrap();
+        assert_eq!(cfg.protocols.len(), 2);
+        assert_eq!(cfg.protocols[0].protocol, ProtocolType::Http);
+        assert_eq!(cfg.protocols[0].name(), "public");
+        assert_eq!(cfg.protocols[0].options["port"].as_u64(), Some(8080));
+        assert_eq!(cfg.protocols[1].name(), "http");
+        assert!(cfg.protocols[1].options.is_empty());
+        assert_eq!(cfg.shutdown.drain_timeout(), Duration::from_secs(5));
+        assert_eq!(cfg.pipelines.names(), vec!["upper"]);
+    }
+
+    #[test]
+    fn test_server_config_without_protocols() {
+        let yaml = r#"
+strategy: work_queue
+processors:
+  - id: upper
+    type: local
+    processor: change_text_case_upper
+"#;
+
+        let cfg = ServerConfig::from_yaml(yaml).unwrap();
+        assert!(cfg.protocols.is_empty());
+        assert_eq!(
+            cfg.shutdown.drain_timeout(),
+            Duration::from_secs(DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SECS)
+        );
+        assert_eq!(cfg.pipelines.names(), vec![DEFAULT_PIPELINE_NAME]);
+
+        let unknown = "protocols:\n  - type: carrier_pigeon\npipelines: []\n";
+        assert!(ServerConfig::from_yaml(unknown).is_err());
+        let unknown = "shutdown:\n  timeout: 5\npipelines: []\n";
+        assert!(ServerConfig::from_yaml(unknown).is_err());
+    }
+
+    #[test]
+    fn test_memory_management_config() {
+        let yaml = r#"
+memory_management:
+  enabled: true
+  max_memory_mb: 64
+  idle_timeout_seconds: 600
+  check_interval_seconds: 0
+  protect_startup_auto: false
+  protected_pipelines: [upper]
+pipelines: []
+"#;
+
+        let cfg = ServerConfig::from_yaml(yaml).unwrap().memory_management;
+        assert!(cfg.enabled);
+        assert_eq!(cfg.budget_bytes(), Some(64 * 1024 * 1024));
+        assert_eq!(cfg.max_pipelines, None);
+        assert_eq!(cfg.idle_timeout(), Some(Duration::from_secs(600)));
+        assert_eq!(cfg.check_interval(), Duration::from_secs(1));
+        assert!(!cfg.protects_startup_auto());
+        assert_eq!(cfg.protected_pipelines, vec!["upper"]);
+        assert!(cfg.evicts_wasm_modules());
+
+        let defaults = ServerConfig::from_yaml("pipelines: []\n")
+            .unwrap()
+            .memory_management;
+        assert!(!defaults.enabled);
+        assert_eq!(defaults.budget_bytes(), None);
+        assert_eq!(
+            defaults.check_interval(),
+            Duration::from_secs(DEFAULT_EVICTION_CHECK_INTERVAL_SECS)
+        );
+        assert!(defaults.protects_startup_auto());
+
+        let unknown = "memory_management:\n  max_memory: 64\npipelines: []\n";
+        assert!(ServerConfig::from_yaml(unknown).is_err());
+    }
+
+    #[test]
+    fn test_jobs_config() {
+        let yaml = r#"
+jobs:
+  enabled: true
+  store:
+    type: file
+    directory: /var/lib/dagwood/jobs
+  retention_seconds: 60
+  max_concurrent: 0
+pipelines: []
+"#;
+
+        let cfg = ServerConfig::from_yaml(yaml).unwrap().jobs;
+        assert!(cfg.enabled);
+        assert_eq!(
+            cfg.store,
+            JobStoreConfig::File {
+                directory: PathBuf::from("/var/lib/dagwood/jobs")
+            }
+        );
+        assert_eq!(cfg.retention(), Duration::from_secs(60));
+        assert_eq!(cfg.max_concurrent(), 1);
+        assert_eq!(
+            cfg.cleanup_interval(),
+            Duration::from_secs(DEFAULT_JOB_CLEANUP_INTERVAL_SECS)
+        );
+
+        let defaults = ServerConfig::from_yaml("pipelines: []\n").unwrap().jobs;
+        assert!(!defaults.enabled);
+        assert_eq!(defaults.store, JobStoreConfig::Memory);
+        assert_eq!(
+            defaults.retention(),
+            Duration::from_secs(DEFAULT_JOB_RETENTION_SECS)
+        );
+        assert_eq!(defaults.max_concurrent(), DEFAULT_MAX_CONCURRENT_JOBS);
+
+        let missing_directory = "jobs:\n  store:\n    type: file\npipelines: []\n";
+        assert!(ServerConfig::from_yaml(missing_directory).is_err());
+    }
 }
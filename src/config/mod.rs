@@ -15,9 +15,15 @@ mod integration_tests;
 pub use dependency_graph::DependencyGraph;
 pub use entry_points::EntryPoints;
 pub use loader::{
-    load_and_validate_config, load_config, BackendType, Config, ExecutorOptions, FuelConfig,
-    ProcessorConfig, Strategy, WasmConfig,
+    load_and_validate_config, load_and_validate_pipelines_config, load_and_validate_server_config,
+    load_config, load_pipelines_config, load_server_config, AuthClientConfig, AuthConfig,
+    BackendType, Config, ExecutorOptions, FuelConfig, HotReloadConfig, InitializationConfig,
+    JobStoreConfig, JobsConfig, MemoryManagementConfig, OutputConfig, PipelineConfig,
+    PipelinesConfig, ProcessorConfig, ProtocolConfig, ProtocolType, QuotaConfig, RetryBackoff,
+    ServerConfig, ShutdownConfig, StartupMode, Strategy, WasmConfig, WebhookConfig,
 };
 pub use processor_map::ProcessorMap;
-pub use runtime::RuntimeBuilder;
-pub use validation::validate_dependency_graph;
+pub use runtime::{CompiledRuntime, RuntimeBuilder};
+pub use validation::{
+    validate_dependency_graph, validate_diamond_patterns, validate_pipelines, validate_webhook_url,
+};
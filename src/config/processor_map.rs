@@ -213,6 +213,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![],
                 },
                 expected_processor_count: 0,
@@ -225,6 +226,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![ProcessorConfig {
                         id: "local_proc".to_string(),
                         backend: BackendType::Local,
@@ -245,6 +247,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![ProcessorConfig {
                         id: "loadable_proc".to_string(),
                         backend: BackendType::Loadable,
@@ -265,6 +268,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![ProcessorConfig {
                         id: "grpc_proc".to_string(),
                         backend: BackendType::Grpc,
@@ -285,6 +289,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![ProcessorConfig {
                         id: "http_proc".to_string(),
                         backend: BackendType::Http,
@@ -305,6 +310,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![ProcessorConfig {
                         id: "wasm_proc".to_string(),
                         backend: BackendType::Wasm,
@@ -325,6 +331,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![
                         ProcessorConfig {
                             id: "local1".to_string(),
@@ -365,6 +372,7 @@ mod tests {
                     failure_strategy: FailureStrategy::FailFast,
                     executor_options: ExecutorOptions::default(),
                     wasm: WasmConfig::default(),
+                    outputs: vec![],
                     processors: vec![
                         ProcessorConfig {
                             id: "input".to_string(),
@@ -480,6 +488,7 @@ mod tests {
                 failure_strategy: FailureStrategy::FailFast,
                 executor_options: ExecutorOptions::default(),
                 wasm: WasmConfig::default(),
+                outputs: vec![],
                 processors: vec![ProcessorConfig {
                     id: format!("processor_{}", i),
                     backend: backend_type.clone(),
@@ -533,6 +542,7 @@ mod tests {
             failure_strategy: FailureStrategy::FailFast,
             executor_options: ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 ProcessorConfig {
                     id: "duplicate".to_string(),
@@ -1,11 +1,17 @@
 // Copyright (c) 2025 Steve Wagner (ciroque@live.com)
 // SPDX-License-Identifier: MIT
 
+use std::sync::Arc;
+
 use crate::config::{Config, ProcessorMap};
 use crate::engine::factory::ExecutorFactory;
+use crate::engine::ExecutionPlan;
 use crate::errors::FailureStrategy;
 use crate::traits::DagExecutor;
 
+/// Compiled runtime components: a shareable execution plan, its executor, and failure handling.
+pub type CompiledRuntime = (Arc<ExecutionPlan>, Box<dyn DagExecutor>, FailureStrategy);
+
 /// DAG runtime builder - orchestrates processor map and executor creation from configuration.
 ///
 /// The `RuntimeBuilder` provides a clean interface for creating complete DAG runtime
@@ -25,6 +31,7 @@ use crate::traits::DagExecutor;
 /// #     executor_options: the_dagwood::config::ExecutorOptions::default(),
 /// #     wasm: the_dagwood::config::WasmConfig::default(),
 /// #     processors: vec![],
+/// #     outputs: vec![],
 /// # };
 ///
 /// let (processors, executor, failure_strategy) = RuntimeBuilder::from_config(&config).unwrap();
@@ -54,4 +61,18 @@ impl RuntimeBuilder {
         let executor = ExecutorFactory::from_config(cfg);
         Ok((processors, executor, cfg.failure_strategy))
     }
+
+    /// Build a DAG runtime with a compiled execution plan from configuration.
+    ///
+    /// Like `from_config`, but the processor registry and dependency graph are compiled into
+    /// an `ExecutionPlan` once, so the returned `Arc<ExecutionPlan>` can be reused for every
+    /// execution of this pipeline via `DagExecutor::execute_plan`.
+    ///
+    /// # Returns
+    /// A tuple of (Arc<ExecutionPlan>, DagExecutor, FailureStrategy) ready for repeated DAG execution
+    pub fn compile(cfg: &Config) -> Result<CompiledRuntime, String> {
+        let (processors, executor, failure_strategy) = Self::from_config(cfg)?;
+        let plan = ExecutionPlan::from_config(cfg, processors).map_err(|e| e.to_string())?;
+        Ok((Arc::new(plan), executor, failure_strategy))
+    }
 }
@@ -10,11 +10,12 @@
 //!
 //! # Validation Pipeline
 //!
-//! The validation process follows a three-stage pipeline:
+//! The validation process follows a four-stage pipeline:
 //!
 //! 1. **Uniqueness Validation**: Ensures all processor IDs are unique
 //! 2. **Reference Validation**: Verifies all dependencies point to existing processors  
-//! 3. **Cycle Detection**: Uses DFS to detect circular dependencies
+//! 3. **Output Validation**: Verifies designated outputs name existing processors and are unique
+//! 4. **Cycle Detection**: Uses DFS to detect circular dependencies
 //!
 //! This ordering is important because cycle detection requires a valid graph structure,
 //! so reference validation must pass first.
@@ -48,6 +49,7 @@
 //!     failure_strategy: FailureStrategy::FailFast,
 //!     executor_options: ExecutorOptions::default(),
 //!     wasm: WasmConfig::default(),
+//!     outputs: vec![],
 //!     processors: vec![
 //!         ProcessorConfig {
 //!             id: "processor1".to_string(),
@@ -84,6 +86,7 @@
 //!     failure_strategy: FailureStrategy::FailFast,
 //!     executor_options: ExecutorOptions::default(),
 //!     wasm: WasmConfig::default(),
+//!     outputs: vec![],
 //!     processors: vec![
 //!         ProcessorConfig {
 //!             id: "processor1".to_string(),
@@ -114,12 +117,21 @@
 //!                 eprintln!("Warning: Diamond pattern at '{}' may cause non-deterministic behavior",
 //!                          convergence_processor);
 //!             }
+//!             ValidationError::UnresolvedOutput { output_name, processor_id } => {
+//!                 eprintln!("Output '{}' references missing processor '{}'",
+//!                          output_name, processor_id);
+//!             }
+//!             ValidationError::DuplicateOutputName { output_name } => {
+//!                 eprintln!("Duplicate output name: '{}'", output_name);
+//!             }
+//!             // Pipeline naming errors only come from `validate_pipelines`
+//!             other => eprintln!("Validation error: {}", other),
 //!         }
 //!     }
 //! }
 //! ```
 
-use crate::config::Config;
+use crate::config::{Config, PipelinesConfig, QuotaConfig};
 use crate::errors::ValidationError;
 use crate::observability::messages::validation::DiamondPatternDetected;
 use std::collections::{HashMap, HashSet};
@@ -158,6 +170,7 @@ use std::collections::{HashMap, HashSet};
 ///     failure_strategy: FailureStrategy::FailFast,
 ///     executor_options: ExecutorOptions::default(),
 ///     wasm: WasmConfig::default(),
+///     outputs: vec![],
 ///     processors: vec![
 ///         ProcessorConfig {
 ///             id: "input".to_string(),
@@ -214,6 +227,11 @@ pub fn validate_dependency_graph(config: &Config) -> Result<(), Vec<ValidationEr
         errors.extend(unresolved_errors);
     }
 
+    // Check that designated outputs reference existing processors
+    if let Err(output_errors) = validate_output_references(config) {
+        errors.extend(output_errors);
+    }
+
     // Check for cycles (only if no unresolved dependencies, as cycles detection needs valid graph)
     if errors.is_empty() {
         if let Err(cycle_errors) = validate_acyclic_graph(config) {
@@ -246,6 +264,97 @@ pub fn validate_dependency_graph(config: &Config) -> Result<(), Vec<ValidationEr
     }
 }
 
+/// Validates a multi-pipeline configuration.
+///
+/// Every pipeline must have a non-empty name that is unique within the configuration, every
+/// pipeline's dependency graph must pass [`validate_dependency_graph`], every webhook must
+/// have somewhere to deliver to (see [`validate_webhook_url`]) and every quota must be able
+/// to admit requests (see [`QuotaConfig::validate`]). Errors found
+/// inside a pipeline are reported as a single [`ValidationError::InvalidPipeline`] naming
+/// the pipeline, so the same processor ID can be reported for different pipelines.
+///
+/// # Arguments
+///
+/// * `config` - The multi-pipeline configuration to validate
+///
+/// # Returns
+///
+/// * `Ok(())` - All pipelines are uniquely named and valid
+/// * `Err(Vec<ValidationError>)` - List of all naming and per-pipeline errors found
+pub fn validate_pipelines(config: &PipelinesConfig) -> Result<(), Vec<ValidationError>> {
+    let mut seen_names = HashSet::new();
+    let mut errors = Vec::new();
+
+    for pipeline in &config.pipelines {
+        if pipeline.name.trim().is_empty() {
+            errors.push(ValidationError::EmptyPipelineName);
+        } else if !seen_names.insert(&pipeline.name) {
+            errors.push(ValidationError::DuplicatePipelineName {
+                pipeline_name: pipeline.name.clone(),
+            });
+        }
+
+        if let Err(pipeline_errors) = validate_dependency_graph(&pipeline.config) {
+            errors.push(ValidationError::InvalidPipeline {
+                pipeline_name: pipeline.name.clone(),
+                errors: pipeline_errors,
+            });
+        }
+
+        if let Some(webhook) = &pipeline.webhook {
+            let result = match &webhook.url {
+                Some(url) => validate_webhook_url(url),
+                None if webhook.allow_request_callbacks => Ok(()),
+                None => Err("a url or allow_request_callbacks is required".to_string()),
+            };
+            if let Err(reason) = result {
+                errors.push(ValidationError::InvalidWebhook {
+                    pipeline_name: pipeline.name.clone(),
+                    reason,
+                });
+            }
+        }
+
+        if let Some(Err(reason)) = pipeline.quota.as_ref().map(QuotaConfig::validate) {
+            errors.push(ValidationError::InvalidQuota {
+                pipeline_name: pipeline.name.clone(),
+                reason,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Validates a URL webhook notifications are delivered to.
+///
+/// Notifications are HTTP requests, so only absolute `http://` and `https://` URLs are accepted.
+///
+/// # Example
+/// ```
+/// use the_dagwood::config::validate_webhook_url;
+///
+/// assert!(validate_webhook_url("http://127.0.0.1:9000/hooks").is_ok());
+/// assert!(validate_webhook_url("https://hooks.example.com/dagwood").is_ok());
+/// assert!(validate_webhook_url("ftp://example.com").is_err());
+/// ```
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let uri: hyper::Uri = url
+        .parse()
+        .map_err(|error| format!("'{}' is not a valid URL: {}", url, error))?;
+    if !matches!(uri.scheme_str(), Some("http" | "https")) || uri.host().is_none() {
+        return Err(format!(
+            "'{}' is not an absolute http:// or https:// URL",
+            url
+        ));
+    }
+    Ok(())
+}
+
 /// Validates that all processor IDs are unique within the configuration.
 ///
 /// Processor IDs must be unique because they serve as the primary key for:
@@ -347,6 +456,45 @@ fn validate_dependency_references(config: &Config) -> Result<(), Vec<ValidationE
     }
 }
 
+/// Validates the designated pipeline outputs.
+///
+/// Every output must name an existing processor, and output names (which default to the
+/// processor ID) must be unique since they key the pipeline result.
+///
+/// # Arguments
+///
+/// * `config` - The configuration to validate
+///
+/// # Returns
+///
+/// * `Ok(())` - All outputs are valid (or none are designated)
+/// * `Err(Vec<ValidationError>)` - List of unresolved processors and duplicate output names
+fn validate_output_references(config: &Config) -> Result<(), Vec<ValidationError>> {
+    let processor_ids: HashSet<&String> = config.processors.iter().map(|p| &p.id).collect();
+    let mut seen_names = HashSet::new();
+    let mut errors = Vec::new();
+
+    for output in &config.outputs {
+        if !processor_ids.contains(&output.processor) {
+            errors.push(ValidationError::UnresolvedOutput {
+                output_name: output.name().to_string(),
+                processor_id: output.processor.clone(),
+            });
+        }
+        if !seen_names.insert(output.name()) {
+            errors.push(ValidationError::DuplicateOutputName {
+                output_name: output.name().to_string(),
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 /// Validates that the dependency graph is acyclic using DFS-based cycle detection.
 ///
 /// Cyclic dependencies make DAG execution impossible because processors would wait
@@ -548,7 +696,7 @@ fn dfs_cycle_detection(
 /// - **Simple Diamond**: `A → [B, C] → D` (A is common ancestor, B and C are parallel, D converges)
 /// - **Complex Diamond**: `A → B → [C, D] → E` (B is common ancestor, C and D are parallel, E converges)
 /// - **Nested Diamonds**: Multiple diamond patterns within the same DAG
-fn validate_diamond_patterns(config: &Config) -> Result<(), Vec<ValidationError>> {
+pub fn validate_diamond_patterns(config: &Config) -> Result<(), Vec<ValidationError>> {
     // Build forward adjacency list (dependency -> [dependents])
     let mut graph: HashMap<&String, Vec<&String>> = HashMap::new();
 
@@ -560,7 +708,9 @@ fn validate_diamond_patterns(config: &Config) -> Result<(), Vec<ValidationError>
     // Add edges (dependencies -> dependents)
     for processor in &config.processors {
         for dependency in &processor.depends_on {
-            graph.get_mut(dependency).unwrap().push(&processor.id);
+            if let Some(dependents) = graph.get_mut(dependency) {
+                dependents.push(&processor.id);
+            }
         }
     }
 
@@ -671,7 +821,9 @@ fn has_path_between(from: &str, to: &str, graph: &HashMap<&String, Vec<&String>>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{BackendType, ProcessorConfig, Strategy, WasmConfig};
+    use crate::config::{
+        BackendType, OutputConfig, PipelineConfig, ProcessorConfig, Strategy, WasmConfig,
+    };
 
     fn create_test_processor(id: &str, depends_on: Vec<&str>) -> ProcessorConfig {
         ProcessorConfig {
@@ -692,6 +844,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![],
         };
 
@@ -705,6 +858,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![create_test_processor("a", vec![])],
         };
 
@@ -718,6 +872,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec![]),
                 create_test_processor("b", vec!["a"]),
@@ -735,6 +890,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec![]),
                 create_test_processor("b", vec!["a"]),
@@ -753,6 +909,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec![]),
                 create_test_processor("a", vec![]), // Duplicate
@@ -776,6 +933,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec![]),
                 create_test_processor("b", vec!["nonexistent"]),
@@ -799,6 +957,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec!["b"]),
                 create_test_processor("b", vec!["a"]),
@@ -822,6 +981,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![create_test_processor("a", vec!["a"])],
         };
 
@@ -842,6 +1002,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec!["b"]),
                 create_test_processor("b", vec!["c"]),
@@ -867,6 +1028,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec!["nonexistent"]),
                 create_test_processor("a", vec![]), // Duplicate ID
@@ -887,6 +1049,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("entry", vec![]),
                 create_test_processor("left", vec!["entry"]),
@@ -929,6 +1092,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec![]),
                 create_test_processor("b", vec!["a"]),
@@ -947,6 +1111,7 @@ mod tests {
             failure_strategy: crate::errors::FailureStrategy::FailFast,
             executor_options: crate::config::ExecutorOptions::default(),
             wasm: WasmConfig::default(),
+            outputs: vec![],
             processors: vec![
                 create_test_processor("a", vec![]),
                 create_test_processor("b", vec!["a"]),
@@ -958,4 +1123,196 @@ mod tests {
         let result = validate_dependency_graph(&config);
         assert!(result.is_ok()); // No convergence point, so no diamond
     }
+
+    #[test]
+    fn test_unresolved_output() {
+        let config = Config {
+            strategy: Strategy::WorkQueue,
+            failure_strategy: crate::errors::FailureStrategy::FailFast,
+            executor_options: crate::config::ExecutorOptions::default(),
+            wasm: WasmConfig::default(),
+            outputs: vec![OutputConfig::new("a"), OutputConfig::new("missing")],
+            processors: vec![create_test_processor("a", vec![])],
+        };
+
+        let result = validate_dependency_graph(&config);
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0],
+            ValidationError::UnresolvedOutput {
+                output_name: "missing".to_string(),
+                processor_id: "missing".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicate_output_name() {
+        let mut named_output = OutputConfig::new("b");
+        named_output.name = Some("a".to_string());
+
+        let config = Config {
+            strategy: Strategy::WorkQueue,
+            failure_strategy: crate::errors::FailureStrategy::FailFast,
+            executor_options: crate::config::ExecutorOptions::default(),
+            wasm: WasmConfig::default(),
+            outputs: vec![OutputConfig::new("a"), named_output],
+            processors: vec![
+                create_test_processor("a", vec![]),
+                create_test_processor("b", vec!["a"]),
+            ],
+        };
+
+        let result = validate_dependency_graph(&config);
+        assert!(result.is_err());
+        let errors = result.unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            ValidationError::DuplicateOutputName { .. }
+        ));
+    }
+
+    fn create_test_pipeline(name: &str, processors: Vec<ProcessorConfig>) -> PipelineConfig {
+        PipelineConfig {
+            name: name.to_string(),
+            startup: crate::config::StartupMode::Auto,
+            initialization: crate::config::InitializationConfig::default(),
+            hot_reload: crate::config::HotReloadConfig::default(),
+            webhook: None,
+            quota: None,
+            config: Config {
+                strategy: Strategy::WorkQueue,
+                failure_strategy: crate::errors::FailureStrategy::FailFast,
+                executor_options: crate::config::ExecutorOptions::default(),
+                wasm: WasmConfig::default(),
+                outputs: vec![],
+                processors,
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_pipelines_accepts_same_processor_ids_across_pipelines() {
+        let config = PipelinesConfig {
+            pipelines: vec![
+                create_test_pipeline("a", vec![create_test_processor("p1", vec![])]),
+                create_test_pipeline("b", vec![create_test_processor("p1", vec![])]),
+            ],
+        };
+
+        assert!(validate_pipelines(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_pipelines_duplicate_and_empty_names() {
+        let config = PipelinesConfig {
+            pipelines: vec![
+                create_test_pipeline("a", vec![]),
+                create_test_pipeline("a", vec![]),
+                create_test_pipeline(" ", vec![]),
+            ],
+        };
+
+        let errors = validate_pipelines(&config).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::DuplicatePipelineName {
+                    pipeline_name: "a".to_string()
+                },
+                ValidationError::EmptyPipelineName,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_pipelines_reports_pipeline_of_graph_error() {
+        let config = PipelinesConfig {
+            pipelines: vec![
+                create_test_pipeline("valid", vec![create_test_processor("p1", vec![])]),
+                create_test_pipeline("broken", vec![create_test_processor("p1", vec!["missing"])]),
+            ],
+        };
+
+        let errors = validate_pipelines(&config).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::InvalidPipeline { pipeline_name, errors }
+                if pipeline_name == "broken" && errors.len() == 1
+        ));
+        assert!(errors[0]
+            .to_string()
+            .starts_with("Pipeline 'broken' is invalid: Processor 'p1' depends on 'missing'"));
+    }
+
+    #[test]
+    fn test_validate_pipelines_webhooks() {
+        let webhook = |url: Option<&str>, allow_request_callbacks| {
+            let mut pipeline = create_test_pipeline("hooked", vec![]);
+            pipeline.webhook = Some(crate::config::WebhookConfig {
+                url: url.map(str::to_string),
+                allow_request_callbacks,
+                ..Default::default()
+            });
+            PipelinesConfig {
+                pipelines: vec![pipeline],
+            }
+        };
+
+        assert!(validate_pipelines(&webhook(Some("http://localhost:9000/hook"), false)).is_ok());
+        assert!(validate_pipelines(&webhook(Some("https://example.com/hook"), false)).is_ok());
+        assert!(validate_pipelines(&webhook(None, true)).is_ok());
+        for (url, allow_request_callbacks) in [
+            (None, false),
+            (Some("ftp://example.com/hook"), false),
+            (Some("/hook"), true),
+            (Some("not a url"), false),
+        ] {
+            let errors = validate_pipelines(&webhook(url, allow_request_callbacks)).unwrap_err();
+            assert!(
+                matches!(
+                    &errors[..],
+                    [ValidationError::InvalidWebhook { pipeline_name, .. }] if pipeline_name == "hooked"
+                ),
+                "{:?}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_pipelines_quotas() {
+        let quota = |yaml: &str| {
+            let mut pipeline = create_test_pipeline("limited", vec![]);
+            pipeline.quota = Some(serde_yaml::from_str(yaml).unwrap());
+            PipelinesConfig {
+                pipelines: vec![pipeline],
+            }
+        };
+
+        assert!(
+            validate_pipelines(&quota("{max_concurrent: 2, requests_per_second: 0.5}")).is_ok()
+        );
+        for invalid in [
+            "{max_concurrent: 0}",
+            "{requests_per_second: 0}",
+            "{burst: 5}",
+            "{requests_per_second: 1, burst: 0}",
+            "{fuel_per_window: 1000, fuel_window_seconds: 0}",
+        ] {
+            let errors = validate_pipelines(&quota(invalid)).unwrap_err();
+            assert!(
+                matches!(
+                    &errors[..],
+                    [ValidationError::InvalidQuota { pipeline_name, .. }] if pipeline_name == "limited"
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
 }
@@ -0,0 +1,225 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Live execution events emitted by every executor.
+//!
+//! Events are delivered to an [`ExecutionObserver`] passed to
+//! `DagExecutor::execute_plan_observed`. Each event carries a wall-clock timestamp and the
+//! offset from the start of the execution, so consumers can render timelines without
+//! correlating log lines.
+//!
+//! # Examples
+//!
+//! ## Streaming events to a progress task
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use the_dagwood::engine::events::{BroadcastObserver, ExecutionEventKind};
+//!
+//! let observer = Arc::new(BroadcastObserver::new(256));
+//! let mut events = observer.subscribe();
+//!
+//! tokio::spawn(async move {
+//!     while let Ok(event) = events.recv().await {
+//!         if let ExecutionEventKind::ProcessorCompleted { processor_id, duration, .. } = &event.kind {
+//!             println!("{} finished in {:?}", processor_id, duration);
+//!         }
+//!     }
+//! });
+//!
+//! let report = executor
+//!     .execute_plan_observed(plan, input, PipelineMetadata::new(), FailureStrategy::FailFast, observer)
+//!     .await?;
+//! ```
+
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::broadcast;
+
+use crate::errors::ExecutionError;
+use crate::traits::ExecutionObserver;
+
+use super::execution_report::ProcessorStatus;
+
+/// A single progress event from a DAG execution
+#[derive(Debug, Clone)]
+pub struct ExecutionEvent {
+    /// Wall-clock time the event was emitted
+    pub timestamp: SystemTime,
+    /// Offset from the start of the execution
+    pub elapsed: Duration,
+    pub kind: ExecutionEventKind,
+}
+
+/// What happened during an execution
+#[derive(Debug, Clone)]
+pub enum ExecutionEventKind {
+    /// The executor started running the plan
+    ExecutionStarted {
+        strategy: &'static str,
+        processor_count: usize,
+    },
+    /// All dependencies of the processor succeeded; it is queued to run
+    ProcessorScheduled { processor_id: String },
+    /// An attempt of the processor started
+    ProcessorStarted {
+        processor_id: String,
+        attempt: u32,
+        input_size: usize,
+    },
+    /// An attempt failed or timed out and the processor will be retried
+    ProcessorRetried {
+        processor_id: String,
+        failed_attempt: u32,
+        error: ExecutionError,
+    },
+    /// The processor succeeded
+    ProcessorCompleted {
+        processor_id: String,
+        attempts: u32,
+        duration: Duration,
+        output_size: usize,
+    },
+    /// The processor failed or timed out after exhausting its attempts
+    ProcessorFailed {
+        processor_id: String,
+        status: ProcessorStatus,
+        attempts: u32,
+        duration: Duration,
+        error: Option<ExecutionError>,
+    },
+    /// The processor will not run because a dependency did not succeed
+    ProcessorBlocked {
+        processor_id: String,
+        failed_dependency: String,
+    },
+    /// A Transform processor replaced the canonical payload
+    CanonicalPayloadUpdated { processor_id: String, size: usize },
+    /// The execution finished and its report was built
+    ExecutionCompleted {
+        strategy: &'static str,
+        duration: Duration,
+        succeeded: bool,
+    },
+}
+
+impl ExecutionEventKind {
+    /// Name of the event, for logs and event streams
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExecutionEventKind::ExecutionStarted { .. } => "execution_started",
+            ExecutionEventKind::ProcessorScheduled { .. } => "processor_scheduled",
+            ExecutionEventKind::ProcessorStarted { .. } => "processor_started",
+            ExecutionEventKind::ProcessorRetried { .. } => "processor_retried",
+            ExecutionEventKind::ProcessorCompleted { .. } => "processor_completed",
+            ExecutionEventKind::ProcessorFailed { .. } => "processor_failed",
+            ExecutionEventKind::ProcessorBlocked { .. } => "processor_blocked",
+            ExecutionEventKind::CanonicalPayloadUpdated { .. } => "canonical_payload_updated",
+            ExecutionEventKind::ExecutionCompleted { .. } => "execution_completed",
+        }
+    }
+
+    /// Processor the event refers to, if any
+    pub fn processor_id(&self) -> Option<&str> {
+        match self {
+            ExecutionEventKind::ProcessorScheduled { processor_id }
+            | ExecutionEventKind::ProcessorStarted { processor_id, .. }
+            | ExecutionEventKind::ProcessorRetried { processor_id, .. }
+            | ExecutionEventKind::ProcessorCompleted { processor_id, .. }
+            | ExecutionEventKind::ProcessorFailed { processor_id, .. }
+            | ExecutionEventKind::ProcessorBlocked { processor_id, .. }
+            | ExecutionEventKind::CanonicalPayloadUpdated { processor_id, .. } => {
+                Some(processor_id)
+            }
+            ExecutionEventKind::ExecutionStarted { .. }
+            | ExecutionEventKind::ExecutionCompleted { .. } => None,
+        }
+    }
+}
+
+/// Observer that ignores every event; used when no observer is supplied
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopObserver;
+
+impl ExecutionObserver for NoopObserver {
+    fn on_event(&self, _event: &ExecutionEvent) {}
+}
+
+/// Observer that fans events out over a `tokio::sync::broadcast` channel.
+///
+/// Subscribers that fall behind by more than `capacity` events receive
+/// `RecvError::Lagged` instead of slowing down the execution.
+#[derive(Debug, Clone)]
+pub struct BroadcastObserver {
+    sender: broadcast::Sender<ExecutionEvent>,
+}
+
+impl BroadcastObserver {
+    /// Create a broadcast observer buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Subscribe to events emitted after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl ExecutionObserver for BroadcastObserver {
+    fn on_event(&self, event: &ExecutionEvent) {
+        // No subscribers is not an error - events are simply dropped
+        let _ = self.sender.send(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: ExecutionEventKind) -> ExecutionEvent {
+        ExecutionEvent {
+            timestamp: SystemTime::now(),
+            elapsed: Duration::ZERO,
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_processor_id() {
+        let scheduled = ExecutionEventKind::ProcessorScheduled {
+            processor_id: "a".to_string(),
+        };
+        let started = ExecutionEventKind::ExecutionStarted {
+            strategy: "Test",
+            processor_count: 1,
+        };
+
+        assert_eq!(scheduled.processor_id(), Some("a"));
+        assert_eq!(started.processor_id(), None);
+        assert_eq!(scheduled.name(), "processor_scheduled");
+        assert_eq!(started.name(), "execution_started");
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_observer_delivers_to_subscribers() {
+        let observer = BroadcastObserver::new(8);
+
+        // Events without subscribers are dropped silently
+        observer.on_event(&event(ExecutionEventKind::ProcessorScheduled {
+            processor_id: "dropped".to_string(),
+        }));
+
+        let mut receiver = observer.subscribe();
+        observer.on_event(&event(ExecutionEventKind::CanonicalPayloadUpdated {
+            processor_id: "a".to_string(),
+            size: 5,
+        }));
+
+        let received = receiver.recv().await.unwrap();
+        assert!(matches!(
+            received.kind,
+            ExecutionEventKind::CanonicalPayloadUpdated { ref processor_id, size: 5 } if processor_id == "a"
+        ));
+    }
+}
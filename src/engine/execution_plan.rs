@@ -0,0 +1,540 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Compiled, immutable execution plans shared across DAG executions.
+//!
+//! Every executor needs the same derived structures before it can run a DAG: reverse
+//! dependencies, dependency counts, topological ranks, topological levels and each
+//! processor's declared intent. Computing these on every request is wasted work for
+//! server-style workloads where the same pipeline is executed many times.
+//!
+//! An [`ExecutionPlan`] performs that analysis once ("compile") and is then shared via
+//! `Arc<ExecutionPlan>` by any number of concurrent executions.
+//!
+//! # Examples
+//!
+//! ## Compiling once and reusing the plan
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use the_dagwood::engine::{ExecutionPlan, ReactiveExecutor};
+//! use the_dagwood::traits::DagExecutor;
+//!
+//! let plan = Arc::new(ExecutionPlan::compile(processors, graph, entrypoints)?);
+//! let executor = ReactiveExecutor::new(4);
+//!
+//! for input in requests {
+//!     let report = executor
+//!         .execute_plan(plan.clone(), input, PipelineMetadata::new(), FailureStrategy::FailFast)
+//!         .await?;
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::config::{Config, DependencyGraph, EntryPoints, OutputConfig, ProcessorMap};
+use crate::errors::ExecutionError;
+use crate::traits::processor::ProcessorIntent;
+
+use super::priority_work_queue::PrioritizedTask;
+
+/// Immutable, pre-analyzed representation of a DAG ready for execution.
+///
+/// The plan owns the processor registry, the dependency graph and every structure the
+/// executors derive from them. Nothing in the plan changes during execution; per-request
+/// mutable state (remaining dependency counts, canonical payload, results) is created by
+/// the executor from the plan at the start of each run.
+///
+/// ## Pre-computed Structures
+/// - **Reverse dependencies**: processor → processors it depends on
+/// - **Dependency counts**: number of incoming edges per processor
+/// - **Topological ranks**: position of each processor in a topological sort
+/// - **Topological levels**: processors grouped by dependency depth
+/// - **Intents**: each processor's declared `ProcessorIntent`
+/// - **Priorities**: the `PrioritizedTask` used by the work queue for each processor
+/// - **Outputs**: the processors designated to form the pipeline result
+pub struct ExecutionPlan {
+    processors: ProcessorMap,
+    graph: DependencyGraph,
+    entrypoints: EntryPoints,
+    reverse_dependencies: HashMap<String, Vec<String>>,
+    dependency_counts: HashMap<String, usize>,
+    topological_ranks: HashMap<String, usize>,
+    /// Levels are only needed by level-based execution, and entry points that disagree with
+    /// the graph are tolerated by the other strategies, so a failure is kept rather than
+    /// rejecting the whole plan.
+    topological_levels: Result<Vec<Vec<String>>, ExecutionError>,
+    intents: HashMap<String, ProcessorIntent>,
+    priorities: HashMap<String, PrioritizedTask>,
+    outputs: Vec<OutputConfig>,
+}
+
+impl ExecutionPlan {
+    /// Compile an execution plan from a processor registry and its dependency graph.
+    ///
+    /// ## Error Conditions
+    /// - `ExecutionError::ProcessorNotFound` if the graph references a processor missing from the registry
+    /// - `ExecutionError::InternalError` if the graph contains cycles (should be caught in validation)
+    pub fn compile(
+        processors: ProcessorMap,
+        graph: DependencyGraph,
+        entrypoints: EntryPoints,
+    ) -> Result<Self, ExecutionError> {
+        for processor_id in graph.keys() {
+            if !processors.contains_key(processor_id) {
+                return Err(ExecutionError::ProcessorNotFound(processor_id.clone()));
+            }
+        }
+
+        let (dependency_counts, topological_ranks) = graph.dependency_counts_and_ranks()
+            .ok_or_else(|| ExecutionError::InternalError {
+                message: "Internal consistency error: dependency graph contains cycles (should have been caught during config validation)".into(),
+            })?;
+
+        let topological_levels = compute_topological_levels(&graph, &entrypoints);
+
+        let reverse_dependencies = graph.build_reverse_dependencies();
+
+        let intents: HashMap<String, ProcessorIntent> = processors
+            .0
+            .iter()
+            .map(|(id, processor)| (id.clone(), processor.declared_intent()))
+            .collect();
+
+        let priorities = intents
+            .iter()
+            .map(|(id, intent)| {
+                let rank = topological_ranks.get(id).copied().unwrap_or(0);
+                let task =
+                    PrioritizedTask::new(id.clone(), rank, *intent == ProcessorIntent::Transform);
+                (id.clone(), task)
+            })
+            .collect();
+
+        Ok(Self {
+            processors,
+            graph,
+            entrypoints,
+            reverse_dependencies,
+            dependency_counts,
+            topological_ranks,
+            topological_levels,
+            intents,
+            priorities,
+            outputs: Vec::new(),
+        })
+    }
+
+    /// Designate the processors whose payloads form the pipeline result.
+    ///
+    /// ## Error Conditions
+    /// - `ExecutionError::ProcessorNotFound` if an output names a processor missing from the registry
+    pub fn with_outputs(mut self, outputs: Vec<OutputConfig>) -> Result<Self, ExecutionError> {
+        if let Some(output) = outputs
+            .iter()
+            .find(|output| !self.processors.contains_key(&output.processor))
+        {
+            return Err(ExecutionError::ProcessorNotFound(output.processor.clone()));
+        }
+
+        self.outputs = outputs;
+        Ok(self)
+    }
+
+    /// Compile an execution plan from configuration and an already-built processor registry.
+    ///
+    /// The dependency graph and entry points are derived from each processor's `depends_on`;
+    /// the designated outputs come from the `outputs` section.
+    pub fn from_config(cfg: &Config, processors: ProcessorMap) -> Result<Self, ExecutionError> {
+        let (graph, entrypoints) = graph_from_config(cfg);
+        Self::compile(processors, graph, entrypoints)?.with_outputs(cfg.outputs.clone())
+    }
+
+    /// Designated pipeline outputs, in configuration order
+    pub fn outputs(&self) -> &[OutputConfig] {
+        &self.outputs
+    }
+
+    /// Processor registry the plan executes
+    pub fn processors(&self) -> &ProcessorMap {
+        &self.processors
+    }
+
+    /// Forward dependency graph (processor -> dependents)
+    pub fn graph(&self) -> &DependencyGraph {
+        &self.graph
+    }
+
+    /// Processors with no dependencies
+    pub fn entrypoints(&self) -> &EntryPoints {
+        &self.entrypoints
+    }
+
+    /// Reverse dependency map (processor -> processors it depends on)
+    pub fn reverse_dependencies(&self) -> &HashMap<String, Vec<String>> {
+        &self.reverse_dependencies
+    }
+
+    /// Initial dependency counts; executors clone this to track remaining dependencies
+    pub fn dependency_counts(&self) -> &HashMap<String, usize> {
+        &self.dependency_counts
+    }
+
+    /// Topological rank for every processor (0 = earliest)
+    pub fn topological_ranks(&self) -> &HashMap<String, usize> {
+        &self.topological_ranks
+    }
+
+    /// Processors grouped into levels that can execute concurrently
+    ///
+    /// Returns `ExecutionError::InternalError` if no valid entry points were found at compile time.
+    pub fn topological_levels(&self) -> Result<&[Vec<String>], ExecutionError> {
+        self.topological_levels
+            .as_ref()
+            .map(|levels| levels.as_slice())
+            .map_err(|error| error.clone())
+    }
+
+    /// Get the topological rank of a processor
+    pub fn rank(&self, processor_id: &str) -> Option<usize> {
+        self.topological_ranks.get(processor_id).copied()
+    }
+
+    /// Get the declared intent of a processor
+    pub fn intent(&self, processor_id: &str) -> Option<ProcessorIntent> {
+        self.intents.get(processor_id).copied()
+    }
+
+    /// Check whether a processor is declared as a Transform processor
+    pub fn is_transform(&self, processor_id: &str) -> bool {
+        self.intent(processor_id) == Some(ProcessorIntent::Transform)
+    }
+
+    /// Get the processors that directly depend on the given processor
+    pub fn dependents(&self, processor_id: &str) -> &[String] {
+        self.graph
+            .get_dependents(processor_id)
+            .map(|dependents| dependents.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Get the processors the given processor directly depends on
+    pub fn dependencies(&self, processor_id: &str) -> &[String] {
+        self.reverse_dependencies
+            .get(processor_id)
+            .map(|dependencies| dependencies.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Get the work queue task (rank + intent priority) for a processor
+    pub fn prioritized_task(&self, processor_id: &str) -> PrioritizedTask {
+        self.priorities
+            .get(processor_id)
+            .cloned()
+            .unwrap_or_else(|| PrioritizedTask::new(processor_id.to_string(), 0, false))
+    }
+
+    /// Number of processors in the plan
+    pub fn processor_count(&self) -> usize {
+        self.processors.len()
+    }
+}
+
+/// Build the forward dependency graph and entry points from processor `depends_on` lists.
+pub fn graph_from_config(cfg: &Config) -> (DependencyGraph, EntryPoints) {
+    let mut graph_map: HashMap<String, Vec<String>> = HashMap::new();
+    let mut entrypoints = EntryPoints::new();
+
+    for processor_config in &cfg.processors {
+        graph_map.entry(processor_config.id.clone()).or_default();
+
+        if processor_config.depends_on.is_empty() {
+            entrypoints.add(processor_config.id.clone());
+        } else {
+            for dependency_id in &processor_config.depends_on {
+                graph_map
+                    .entry(dependency_id.clone())
+                    .or_default()
+                    .push(processor_config.id.clone());
+            }
+        }
+    }
+
+    (DependencyGraph(graph_map), entrypoints)
+}
+
+/// Compute topological levels using Kahn's algorithm with reverse dependencies mapping.
+///
+/// Returns a vector where each element is a vector of processor IDs at that level.
+/// - Level 0: Entry points (processors with no dependencies)
+/// - Level N: Processors whose dependencies are all in levels 0..N-1
+///
+/// ## Error Conditions
+/// - Returns ExecutionError::InternalError if cycles are detected (should be caught in validation)
+/// - Returns ExecutionError::InternalError if no valid entry points are found
+pub fn compute_topological_levels(
+    graph: &DependencyGraph,
+    entrypoints: &EntryPoints,
+) -> Result<Vec<Vec<String>>, ExecutionError> {
+    let mut levels = Vec::new();
+    let mut queue = VecDeque::new();
+    let mut processed = HashSet::new();
+
+    // Build a mapping from processor to its dependencies (processor -> [dependencies])
+    // The graph stores forward dependencies (processor -> [dependents]), but for in-degree calculation,
+    // we need to know, for each processor, which processors it depends on.
+    let reverse_deps = graph.build_reverse_dependencies();
+
+    // Initialize in-degree count for all processors using the correct dependency format
+    let mut in_degree = HashMap::new();
+    for (processor_id, dependencies) in &reverse_deps {
+        in_degree.insert(processor_id.clone(), dependencies.len());
+    }
+
+    // Add entry points to level 0
+    let mut current_level = Vec::new();
+    for entry_id in &entrypoints.0 {
+        if in_degree.get(entry_id).copied().unwrap_or(0) == 0 {
+            current_level.push(entry_id.clone());
+            queue.push_back(entry_id.clone());
+            processed.insert(entry_id.clone());
+        }
+    }
+
+    if current_level.is_empty() {
+        return Err(ExecutionError::InternalError {
+            message: "No valid entry points found - all processors have dependencies".into(),
+        });
+    }
+
+    levels.push(current_level);
+
+    // Process levels using Kahn's algorithm
+    while !queue.is_empty() {
+        let mut next_level = Vec::new();
+        let current_level_size = queue.len();
+
+        // Process all processors in current level
+        for _ in 0..current_level_size {
+            if let Some(current_id) = queue.pop_front() {
+                // Use graph directly for O(1) lookup of dependents
+                if let Some(dependents) = graph.0.get(&current_id) {
+                    for dependent_id in dependents {
+                        if !processed.contains(dependent_id) {
+                            // Decrease in-degree with proper error handling
+                            let current_in_degree = in_degree.get_mut(dependent_id)
+                                .ok_or_else(|| ExecutionError::InternalError {
+                                    message: format!("Internal consistency error: processor '{}' not found in in-degree map during topological sorting", dependent_id)
+                                })?;
+                            *current_in_degree -= 1;
+
+                            // If in-degree becomes 0, add to next level
+                            if *current_in_degree == 0 {
+                                next_level.push(dependent_id.clone());
+                                processed.insert(dependent_id.clone());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Add next level processors to queue for processing their dependents
+        for processor_id in &next_level {
+            queue.push_back(processor_id.clone());
+        }
+
+        // Add level if it has processors
+        if !next_level.is_empty() {
+            levels.push(next_level);
+        }
+    }
+
+    // Check for cycles (if not all processors were processed)
+    // Total processors includes all processors in the graph plus entry points
+    let mut total_processors: HashSet<_> = graph.0.keys().cloned().collect();
+    for entry_id in &entrypoints.0 {
+        total_processors.insert(entry_id.clone());
+    }
+
+    if processed.len() != total_processors.len() {
+        return Err(ExecutionError::InternalError {
+            message: "Internal consistency error: dependency graph contains cycles (should have been caught during config validation)".into(),
+        });
+    }
+
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::stub::StubProcessor;
+    use crate::traits::Processor;
+    use std::sync::Arc;
+
+    fn diamond() -> (ProcessorMap, DependencyGraph, EntryPoints) {
+        let mut processors = ProcessorMap::new();
+        for id in ["A", "B", "C", "D"] {
+            processors.insert(
+                id.to_string(),
+                Arc::new(StubProcessor::new(id.to_string())) as Arc<dyn Processor>,
+            );
+        }
+
+        let graph = DependencyGraph(HashMap::from([
+            ("A".to_string(), vec!["B".to_string(), "C".to_string()]),
+            ("B".to_string(), vec!["D".to_string()]),
+            ("C".to_string(), vec!["D".to_string()]),
+            ("D".to_string(), vec![]),
+        ]));
+
+        (processors, graph, EntryPoints(vec!["A".to_string()]))
+    }
+
+    #[test]
+    fn test_compile_diamond() {
+        let (processors, graph, entrypoints) = diamond();
+        let plan = ExecutionPlan::compile(processors, graph, entrypoints).unwrap();
+
+        assert_eq!(plan.processor_count(), 4);
+        assert_eq!(plan.rank("A"), Some(0));
+        assert_eq!(plan.rank("D"), Some(3));
+        assert_eq!(plan.dependency_counts().get("D"), Some(&2));
+        assert_eq!(plan.dependencies("D").len(), 2);
+        assert_eq!(plan.dependents("A").len(), 2);
+        assert!(plan.dependents("D").is_empty());
+
+        let levels = plan.topological_levels().unwrap();
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0], vec!["A"]);
+        assert_eq!(levels[1].len(), 2);
+        assert_eq!(levels[2], vec!["D"]);
+
+        assert!(plan.is_transform("B"));
+        let task = plan.prioritized_task("D");
+        assert_eq!(task.topological_rank, 3);
+        assert!(task.is_transform);
+    }
+
+    #[test]
+    fn test_compile_missing_processor() {
+        let (mut processors, graph, entrypoints) = diamond();
+        processors.0.remove("C");
+
+        let result = ExecutionPlan::compile(processors, graph, entrypoints);
+        assert!(matches!(
+            result,
+            Err(ExecutionError::ProcessorNotFound(ref id)) if id == "C"
+        ));
+    }
+
+    #[test]
+    fn test_with_outputs() {
+        let (processors, graph, entrypoints) = diamond();
+        let plan = ExecutionPlan::compile(processors, graph, entrypoints).unwrap();
+        assert!(plan.outputs().is_empty());
+
+        let plan = plan
+            .with_outputs(vec![OutputConfig::new("B"), OutputConfig::new("D")])
+            .unwrap();
+        assert_eq!(plan.outputs().len(), 2);
+        assert_eq!(plan.outputs()[1].name(), "D");
+
+        let result = plan.with_outputs(vec![OutputConfig::new("missing")]);
+        assert!(matches!(
+            result,
+            Err(ExecutionError::ProcessorNotFound(ref id)) if id == "missing"
+        ));
+    }
+
+    #[test]
+    fn test_compile_cyclic_graph() {
+        let (processors, _, entrypoints) = diamond();
+        let graph = DependencyGraph(HashMap::from([
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec!["A".to_string()]),
+        ]));
+
+        let result = ExecutionPlan::compile(processors, graph, entrypoints);
+        assert!(matches!(result, Err(ExecutionError::InternalError { .. })));
+    }
+
+    #[test]
+    fn test_compile_empty() {
+        let plan = ExecutionPlan::compile(
+            ProcessorMap::new(),
+            DependencyGraph::new(),
+            EntryPoints::new(),
+        )
+        .unwrap();
+
+        assert_eq!(plan.processor_count(), 0);
+        assert!(plan.topological_levels().is_err());
+    }
+
+    #[test]
+    fn test_compile_tolerates_mismatched_entrypoints() {
+        let (processors, graph, _) = diamond();
+        let plan =
+            ExecutionPlan::compile(processors, graph, EntryPoints(vec!["D".to_string()])).unwrap();
+
+        assert_eq!(plan.rank("A"), Some(0));
+        assert!(matches!(
+            plan.topological_levels(),
+            Err(ExecutionError::InternalError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_topological_levels_computation() {
+        let (_, graph, entrypoints) = diamond();
+        let levels = compute_topological_levels(&graph, &entrypoints).unwrap();
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[0], vec!["A"]);
+        assert!(levels[1].contains(&"B".to_string()));
+        assert!(levels[1].contains(&"C".to_string()));
+        assert_eq!(levels[1].len(), 2);
+        assert_eq!(levels[2], vec!["D"]);
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        // Create a cycle with a valid entry point: Entry -> A -> B -> C -> A (forward dependencies)
+        let mut graph_map = HashMap::new();
+        graph_map.insert("Entry".to_string(), vec!["A".to_string()]);
+        graph_map.insert("A".to_string(), vec!["B".to_string()]);
+        graph_map.insert("B".to_string(), vec!["C".to_string()]);
+        graph_map.insert("C".to_string(), vec!["A".to_string()]); // Creates cycle
+        let graph = DependencyGraph(graph_map);
+
+        let entrypoints = EntryPoints(vec!["Entry".to_string()]);
+
+        let result = compute_topological_levels(&graph, &entrypoints);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(matches!(error, ExecutionError::InternalError { .. }));
+        if let ExecutionError::InternalError { message } = error {
+            assert!(message.contains("cycles"));
+        }
+    }
+
+    #[test]
+    fn test_no_valid_entrypoints() {
+        // All processors have dependencies
+        let mut graph_map = HashMap::new();
+        graph_map.insert("A".to_string(), vec!["B".to_string()]);
+        graph_map.insert("B".to_string(), vec!["A".to_string()]);
+        let graph = DependencyGraph(graph_map);
+
+        let entrypoints = EntryPoints(vec!["A".to_string()]);
+
+        let result = compute_topological_levels(&graph, &entrypoints);
+        assert!(result.is_err());
+        let error = result.unwrap_err();
+        assert!(matches!(error, ExecutionError::InternalError { .. }));
+        if let ExecutionError::InternalError { message } = error {
+            assert!(message.contains("No valid entry points"));
+        }
+    }
+}
@@ -0,0 +1,798 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Structured results of a DAG execution.
+//!
+//! An [`ExecutionReport`] captures what happened to every processor in the plan, not just the
+//! ones that succeeded: status, timing, attempts, the response (if any) and the error (if any).
+//! Processor failures are part of the report rather than an early `Err`, so partial results
+//! survive even under `FailFast`.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! let report = executor
+//!     .execute_plan(plan, input, PipelineMetadata::new(), FailureStrategy::BestEffort)
+//!     .await?;
+//!
+//! for (processor_id, processor) in report.processors_in_order() {
+//!     println!("{}: {} ({:?})", processor_id, processor.status, processor.duration);
+//! }
+//!
+//! // Payloads of the processors designated in the `outputs` section
+//! for output in &report.outputs {
+//!     println!("{}: {:?}", output.name, output.payload);
+//! }
+//!
+//! // Fold back into the classic results/metadata pair, surfacing failures as errors
+//! let (results, metadata) = report.into_result()?;
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+
+use crate::config::OutputConfig;
+use crate::errors::{ExecutionError, FailureStrategy};
+use crate::observability::metrics::{metrics, ActiveTaskGuard};
+use crate::proto::processor_v1::processor_response::Outcome;
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorResponse};
+use crate::traits::ExecutionObserver;
+
+use super::events::{ExecutionEvent, ExecutionEventKind, NoopObserver};
+use super::execution_plan::ExecutionPlan;
+
+/// Final status of a single processor within an execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessorStatus {
+    /// Processor returned a `NextPayload` outcome
+    Succeeded,
+    /// Processor returned an error outcome or no outcome on its last attempt
+    Failed,
+    /// Processor was not executed because a dependency did not succeed
+    Blocked,
+    /// Processor was not executed because the execution stopped early (e.g. FailFast)
+    Skipped,
+    /// Processor exceeded the configured timeout on its last attempt
+    TimedOut,
+}
+
+impl ProcessorStatus {
+    /// Whether the processor ran and did not succeed
+    pub fn is_failure(&self) -> bool {
+        matches!(self, ProcessorStatus::Failed | ProcessorStatus::TimedOut)
+    }
+}
+
+impl std::fmt::Display for ProcessorStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            ProcessorStatus::Succeeded => "succeeded",
+            ProcessorStatus::Failed => "failed",
+            ProcessorStatus::Blocked => "blocked",
+            ProcessorStatus::Skipped => "skipped",
+            ProcessorStatus::TimedOut => "timed out",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Everything known about one processor after an execution
+#[derive(Debug, Clone)]
+pub struct ProcessorReport {
+    pub status: ProcessorStatus,
+    /// Number of attempts made (0 for processors that never ran)
+    pub attempts: u32,
+    /// Offset from the start of the execution to the first attempt
+    pub started_at: Option<Duration>,
+    /// Wall-clock time across all attempts
+    pub duration: Duration,
+    /// Response from the last attempt, if the processor returned one
+    pub response: Option<ProcessorResponse>,
+    /// Why the processor did not succeed
+    pub error: Option<ExecutionError>,
+}
+
+impl ProcessorReport {
+    /// Report for a processor that never ran
+    pub fn not_run(status: ProcessorStatus, error: Option<ExecutionError>) -> Self {
+        Self {
+            status,
+            attempts: 0,
+            started_at: None,
+            duration: Duration::ZERO,
+            response: None,
+            error,
+        }
+    }
+
+    /// Offset from the start of the execution to completion, if the processor ran
+    pub fn finished_at(&self) -> Option<Duration> {
+        self.started_at.map(|started_at| started_at + self.duration)
+    }
+}
+
+/// Payload (and selected metadata) of a designated pipeline output.
+///
+/// Serializes with the payload base64-encoded, e.g. for job stores.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineOutput {
+    /// Output name from the configuration (defaults to the processor ID)
+    pub name: String,
+    pub processor_id: String,
+    pub status: ProcessorStatus,
+    /// Payload produced by the processor; `None` unless the processor succeeded
+    #[serde(with = "base64_payload::optional")]
+    pub payload: Option<Vec<u8>>,
+    /// Requested metadata keys found in the processor's response
+    pub metadata: HashMap<String, String>,
+}
+
+impl PipelineOutput {
+    fn from_report(output: &OutputConfig, report: Option<&ProcessorReport>) -> Self {
+        let status = report.map_or(ProcessorStatus::Skipped, |report| report.status);
+        let response = report
+            .filter(|report| report.status == ProcessorStatus::Succeeded)
+            .and_then(|report| report.response.as_ref());
+
+        let payload = response.map(|response| match &response.outcome {
+            Some(Outcome::NextPayload(payload)) => payload.clone(),
+            _ => Vec::new(),
+        });
+
+        let mut metadata = HashMap::new();
+        if let Some(response_metadata) = response.and_then(|response| response.metadata.as_ref()) {
+            for processor_metadata in response_metadata.metadata.values() {
+                for key in &output.metadata_keys {
+                    if let Some(value) = processor_metadata.metadata.get(key) {
+                        metadata.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+
+        Self {
+            name: output.name().to_string(),
+            processor_id: output.processor.clone(),
+            status,
+            payload,
+            metadata,
+        }
+    }
+}
+
+/// Serde helpers writing payloads as base64 strings instead of arrays of numbers
+pub(crate) mod base64_payload {
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(payload: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&BASE64.encode(payload))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        BASE64.decode(encoded).map_err(serde::de::Error::custom)
+    }
+
+    pub mod optional {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            payload: &Option<Vec<u8>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match payload {
+                Some(payload) => super::serialize(payload, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Vec<u8>>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|encoded| BASE64.decode(encoded).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// Complete outcome of a DAG execution.
+///
+/// ## Failure Handling
+/// Processor failures never abort report construction. `errors` lists the failures of
+/// processors that actually ran (`Failed` / `TimedOut`) in completion order; blocked
+/// processors carry a `DependencyFailed` error on their own report but are not repeated here.
+#[derive(Debug, Clone)]
+pub struct ExecutionReport {
+    /// Correlation ID of the execution, recorded on every log line and span of the run
+    pub run_id: String,
+    /// Executor strategy that produced the report
+    pub strategy: &'static str,
+    pub failure_strategy: FailureStrategy,
+    /// Report for every processor in the plan
+    pub processors: HashMap<String, ProcessorReport>,
+    /// Canonical payload at the end of the execution
+    pub final_output: Vec<u8>,
+    /// Designated pipeline outputs, in configuration order
+    pub outputs: Vec<PipelineOutput>,
+    pub pipeline_metadata: PipelineMetadata,
+    pub errors: Vec<ExecutionError>,
+    pub duration: Duration,
+}
+
+impl ExecutionReport {
+    /// Whether every processor succeeded
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+            && self
+                .processors
+                .values()
+                .all(|processor| processor.status == ProcessorStatus::Succeeded)
+    }
+
+    /// Get the report for a single processor
+    pub fn processor(&self, processor_id: &str) -> Option<&ProcessorReport> {
+        self.processors.get(processor_id)
+    }
+
+    /// Get a designated pipeline output by name
+    pub fn output(&self, name: &str) -> Option<&PipelineOutput> {
+        self.outputs.iter().find(|output| output.name == name)
+    }
+
+    /// Get the status of a single processor
+    pub fn status(&self, processor_id: &str) -> Option<ProcessorStatus> {
+        self.processors.get(processor_id).map(|p| p.status)
+    }
+
+    /// Sorted IDs of all processors with the given status
+    pub fn processors_with_status(&self, status: ProcessorStatus) -> Vec<&str> {
+        let mut ids: Vec<&str> = self
+            .processors
+            .iter()
+            .filter(|(_, processor)| processor.status == status)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Processors ordered by start time; processors that never ran come last, by ID
+    pub fn processors_in_order(&self) -> Vec<(&str, &ProcessorReport)> {
+        let mut ordered: Vec<(&str, &ProcessorReport)> = self
+            .processors
+            .iter()
+            .map(|(id, processor)| (id.as_str(), processor))
+            .collect();
+        ordered.sort_by(|(a_id, a), (b_id, b)| match (a.started_at, b.started_at) {
+            (Some(a_start), Some(b_start)) => a_start.cmp(&b_start).then(a_id.cmp(b_id)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => a_id.cmp(b_id),
+        });
+        ordered
+    }
+
+    /// Responses of all succeeded processors
+    pub fn results(&self) -> HashMap<String, ProcessorResponse> {
+        self.processors
+            .iter()
+            .filter(|(_, processor)| processor.status == ProcessorStatus::Succeeded)
+            .filter_map(|(id, processor)| {
+                processor
+                    .response
+                    .as_ref()
+                    .map(|response| (id.clone(), response.clone()))
+            })
+            .collect()
+    }
+
+    /// Fold the report into the classic results/metadata pair.
+    ///
+    /// - No failures: `Ok` with the responses of all processors and the pipeline metadata
+    /// - `FailFast`: the first failure in completion order
+    /// - `ContinueOnError` / `BestEffort`: `ExecutionError::MultipleFailed` with every failure
+    pub fn into_result(
+        self,
+    ) -> Result<(HashMap<String, ProcessorResponse>, PipelineMetadata), ExecutionError> {
+        if self.errors.is_empty() {
+            return Ok((self.results(), self.pipeline_metadata));
+        }
+
+        match self.failure_strategy {
+            FailureStrategy::FailFast => Err(self.errors.into_iter().next().unwrap_or_else(|| {
+                ExecutionError::InternalError {
+                    message: "Execution failed without a recorded error".into(),
+                }
+            })),
+            FailureStrategy::ContinueOnError | FailureStrategy::BestEffort => {
+                Err(ExecutionError::MultipleFailed {
+                    failures: self.errors,
+                })
+            }
+        }
+    }
+}
+
+/// Thread-safe collector of processor reports used by executors while a DAG runs.
+///
+/// The recorder is also the single place execution events are emitted from and executor
+/// metrics are updated from, so every executor reports progress to its observer and to the
+/// metrics registry the same way.
+pub(crate) struct ExecutionRecorder {
+    run_id: String,
+    execution_start: Instant,
+    strategy: &'static str,
+    reports: Mutex<HashMap<String, ProcessorReport>>,
+    observer: Arc<dyn ExecutionObserver>,
+    /// Scheduled processors that have not started yet, backing the queue depth gauge
+    queued: Mutex<HashSet<String>>,
+    /// The executor's `dag_execution` span, parent of every processor invocation span.
+    /// Released when the report is built so detached tasks cannot keep the span open.
+    span: Mutex<Span>,
+}
+
+impl ExecutionRecorder {
+    pub(crate) fn new(execution_start: Instant, strategy: &'static str) -> Self {
+        Self {
+            run_id: new_run_id(),
+            execution_start,
+            strategy,
+            reports: Mutex::new(HashMap::new()),
+            observer: Arc::new(NoopObserver),
+            queued: Mutex::new(HashSet::new()),
+            span: Mutex::new(Span::none()),
+        }
+    }
+
+    /// Use the run ID the executor recorded on its `dag_execution` span
+    pub(crate) fn with_run_id(mut self, run_id: String) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    /// Parent processor invocation spans to the given DAG execution span.
+    ///
+    /// Executors run processors in spawned tasks, which do not inherit the spawning task's
+    /// span, so the parent is set explicitly to keep each execution in a single trace.
+    pub(crate) fn with_span(self, span: Span) -> Self {
+        *lock(&self.span) = span;
+        self
+    }
+
+    /// The DAG execution span, for instrumenting tasks spawned by the executor
+    pub(crate) fn span(&self) -> Span {
+        lock(&self.span).clone()
+    }
+
+    /// Run `f` with the DAG execution span entered, so spans created inside are its children
+    pub(crate) fn in_span<T>(&self, f: impl FnOnce() -> T) -> T {
+        self.span().in_scope(f)
+    }
+
+    /// Deliver execution events to the given observer
+    pub(crate) fn with_observer(mut self, observer: Arc<dyn ExecutionObserver>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    /// Emit an execution event to the observer
+    pub(crate) fn emit(&self, kind: ExecutionEventKind) {
+        self.observer.on_event(&ExecutionEvent {
+            timestamp: SystemTime::now(),
+            elapsed: self.execution_start.elapsed(),
+            kind,
+        });
+    }
+
+    /// Emit `ExecutionStarted` for the plan
+    pub(crate) fn started(&self, plan: &ExecutionPlan) {
+        self.emit(ExecutionEventKind::ExecutionStarted {
+            strategy: self.strategy,
+            processor_count: plan.processor_count(),
+        });
+    }
+
+    /// Emit `ProcessorScheduled` for a processor whose dependencies have all succeeded
+    pub(crate) fn scheduled(&self, processor_id: &str) {
+        if lock(&self.queued).insert(processor_id.to_string()) {
+            metrics().queue_depth(self.strategy).inc();
+        }
+        self.emit(ExecutionEventKind::ProcessorScheduled {
+            processor_id: processor_id.to_string(),
+        });
+    }
+
+    /// Emit `CanonicalPayloadUpdated` after a Transform processor replaced the payload
+    pub(crate) fn payload_updated(&self, processor_id: &str, size: usize) {
+        self.emit(ExecutionEventKind::CanonicalPayloadUpdated {
+            processor_id: processor_id.to_string(),
+            size,
+        });
+    }
+
+    /// Mark a processor invocation as running: it leaves the queue and counts as an active
+    /// task until the returned guard is dropped
+    pub(crate) fn invocation_started(&self, processor_id: &str) -> ActiveTaskGuard {
+        if lock(&self.queued).remove(processor_id) {
+            metrics().queue_depth(self.strategy).dec();
+        }
+        metrics().track_active_task(self.strategy)
+    }
+
+    /// Start of the execution; invocation timings are offsets from this instant
+    pub(crate) fn execution_start(&self) -> Instant {
+        self.execution_start
+    }
+
+    /// Record the report for a processor, replacing any earlier one, and emit the
+    /// matching completed / failed / blocked event
+    pub(crate) fn record(&self, processor_id: &str, report: ProcessorReport) {
+        let processor_id_owned = processor_id.to_string();
+        let event = match report.status {
+            ProcessorStatus::Succeeded => Some(ExecutionEventKind::ProcessorCompleted {
+                processor_id: processor_id_owned,
+                attempts: report.attempts,
+                duration: report.duration,
+                output_size: report.response.as_ref().map_or(0, |response| {
+                    match &response.outcome {
+                        Some(Outcome::NextPayload(payload)) => payload.len(),
+                        _ => 0,
+                    }
+                }),
+            }),
+            ProcessorStatus::Failed | ProcessorStatus::TimedOut => {
+                Some(ExecutionEventKind::ProcessorFailed {
+                    processor_id: processor_id_owned,
+                    status: report.status,
+                    attempts: report.attempts,
+                    duration: report.duration,
+                    error: report.error.clone(),
+                })
+            }
+            ProcessorStatus::Blocked => match &report.error {
+                Some(ExecutionError::DependencyFailed {
+                    failed_dependency, ..
+                }) => Some(ExecutionEventKind::ProcessorBlocked {
+                    processor_id: processor_id_owned,
+                    failed_dependency: failed_dependency.clone(),
+                }),
+                _ => None,
+            },
+            ProcessorStatus::Skipped => None,
+        };
+
+        lock(&self.reports).insert(processor_id.to_string(), report);
+
+        if let Some(event) = event {
+            self.emit(event);
+        }
+    }
+
+    /// Record that a processor was blocked by a dependency that did not succeed
+    pub(crate) fn block(&self, processor_id: &str, failed_dependency: &str) {
+        self.record(
+            processor_id,
+            ProcessorReport::not_run(
+                ProcessorStatus::Blocked,
+                Some(ExecutionError::DependencyFailed {
+                    processor_id: processor_id.to_string(),
+                    failed_dependency: failed_dependency.to_string(),
+                }),
+            ),
+        );
+    }
+
+    /// Find a dependency of the processor that has a recorded status other than `Succeeded`
+    pub(crate) fn unsuccessful_dependency(
+        &self,
+        plan: &ExecutionPlan,
+        processor_id: &str,
+    ) -> Option<String> {
+        let reports = lock(&self.reports);
+        plan.dependencies(processor_id)
+            .iter()
+            .find(|dependency| {
+                reports
+                    .get(dependency.as_str())
+                    .is_some_and(|report| report.status != ProcessorStatus::Succeeded)
+            })
+            .cloned()
+    }
+
+    /// Whether any processor has failed or timed out so far
+    pub(crate) fn has_failures(&self) -> bool {
+        lock(&self.reports)
+            .values()
+            .any(|report| report.status.is_failure())
+    }
+
+    /// Build the final report. Processors without a recorded report are `Blocked` when one of
+    /// their dependencies did not succeed and `Skipped` otherwise.
+    ///
+    /// Takes `&self` so detached tasks still holding a reference to the recorder cannot
+    /// prevent the report from being built.
+    pub(crate) fn build_report(
+        &self,
+        plan: &ExecutionPlan,
+        failure_strategy: FailureStrategy,
+        final_output: Vec<u8>,
+        pipeline_metadata: PipelineMetadata,
+    ) -> ExecutionReport {
+        let strategy = self.strategy;
+        let duration = self.execution_start.elapsed();
+        drop(std::mem::replace(&mut *lock(&self.span), Span::none()));
+        let mut processors = lock(&self.reports).clone();
+
+        // Processors scheduled but never started (e.g. after FailFast) leave the queue now
+        let never_started = std::mem::take(&mut *lock(&self.queued)).len();
+        metrics().queue_depth(strategy).sub(never_started as i64);
+
+        // Resolve unrecorded processors in topological order so blocking propagates downstream
+        let mut unrecorded: Vec<&String> = plan
+            .processors()
+            .keys()
+            .filter(|id| !processors.contains_key(id.as_str()))
+            .collect();
+        unrecorded.sort_by_key(|id| (plan.rank(id).unwrap_or(usize::MAX), id.as_str()));
+
+        for processor_id in unrecorded {
+            let failed_dependency = plan.dependencies(processor_id).iter().find(|dependency| {
+                processors.get(dependency.as_str()).is_some_and(|report| {
+                    report.status != ProcessorStatus::Succeeded
+                        && report.status != ProcessorStatus::Skipped
+                })
+            });
+
+            let report = match failed_dependency {
+                Some(dependency) => {
+                    self.emit(ExecutionEventKind::ProcessorBlocked {
+                        processor_id: processor_id.clone(),
+                        failed_dependency: dependency.clone(),
+                    });
+                    ProcessorReport::not_run(
+                        ProcessorStatus::Blocked,
+                        Some(ExecutionError::DependencyFailed {
+                            processor_id: processor_id.clone(),
+                            failed_dependency: dependency.clone(),
+                        }),
+                    )
+                }
+                None => ProcessorReport::not_run(ProcessorStatus::Skipped, None),
+            };
+            processors.insert(processor_id.clone(), report);
+        }
+
+        let mut failures: Vec<(&String, &ProcessorReport)> = processors
+            .iter()
+            .filter(|(_, report)| report.status.is_failure())
+            .collect();
+        failures.sort_by_key(|(id, report)| (report.finished_at(), id.as_str()));
+        let errors = failures
+            .into_iter()
+            .filter_map(|(_, report)| report.error.clone())
+            .collect();
+
+        let outputs = plan
+            .outputs()
+            .iter()
+            .map(|output| PipelineOutput::from_report(output, processors.get(&output.processor)))
+            .collect();
+
+        let report = ExecutionReport {
+            run_id: self.run_id.clone(),
+            strategy,
+            failure_strategy,
+            processors,
+            final_output,
+            outputs,
+            pipeline_metadata,
+            errors,
+            duration,
+        };
+
+        metrics().execution_finished(strategy, report.is_success(), duration);
+        self.emit(ExecutionEventKind::ExecutionCompleted {
+            strategy,
+            duration,
+            succeeded: report.is_success(),
+        });
+
+        report
+    }
+}
+
+/// Generate a new run (correlation) ID for a DAG execution
+pub(crate) fn new_run_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::stub::StubProcessor;
+    use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
+    use crate::traits::Processor;
+
+    /// A -> B -> C, plus an independent D
+    fn plan() -> ExecutionPlan {
+        let mut processors = ProcessorMap::new();
+        for id in ["A", "B", "C", "D"] {
+            processors.insert(
+                id.to_string(),
+                Arc::new(StubProcessor::new(id.to_string())) as Arc<dyn Processor>,
+            );
+        }
+        let graph = DependencyGraph(HashMap::from([
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec!["C".to_string()]),
+            ("C".to_string(), vec![]),
+            ("D".to_string(), vec![]),
+        ]));
+        let entrypoints = EntryPoints(vec!["A".to_string(), "D".to_string()]);
+        ExecutionPlan::compile(processors, graph, entrypoints).unwrap()
+    }
+
+    fn ran(status: ProcessorStatus, started_ms: u64, processor_id: &str) -> ProcessorReport {
+        let (response, error) = match status {
+            ProcessorStatus::Succeeded => (
+                Some(ProcessorResponse {
+                    outcome: Some(Outcome::NextPayload(b"ok".to_vec())),
+                    metadata: None,
+                }),
+                None,
+            ),
+            _ => (
+                None,
+                Some(ExecutionError::ProcessorFailed {
+                    processor_id: processor_id.to_string(),
+                    error: "boom".to_string(),
+                }),
+            ),
+        };
+        ProcessorReport {
+            status,
+            attempts: 1,
+            started_at: Some(Duration::from_millis(started_ms)),
+            duration: Duration::from_millis(1),
+            response,
+            error,
+        }
+    }
+
+    #[test]
+    fn test_unrecorded_processors_are_blocked_or_skipped() {
+        let plan = plan();
+        let recorder = ExecutionRecorder::new(Instant::now(), "Test");
+        recorder.record("A", ran(ProcessorStatus::Failed, 0, "A"));
+
+        let report = recorder.build_report(
+            &plan,
+            FailureStrategy::ContinueOnError,
+            Vec::new(),
+            PipelineMetadata::new(),
+        );
+
+        assert_eq!(report.status("A"), Some(ProcessorStatus::Failed));
+        assert_eq!(report.status("B"), Some(ProcessorStatus::Blocked));
+        assert_eq!(report.status("C"), Some(ProcessorStatus::Blocked));
+        assert_eq!(report.status("D"), Some(ProcessorStatus::Skipped));
+        assert_eq!(report.errors.len(), 1);
+        assert!(!report.is_success());
+    }
+
+    #[test]
+    fn test_into_result_by_failure_strategy() {
+        let plan = plan();
+        let build = |failure_strategy| {
+            let recorder = ExecutionRecorder::new(Instant::now(), "Test");
+            recorder.record("A", ran(ProcessorStatus::Succeeded, 0, "A"));
+            recorder.record("D", ran(ProcessorStatus::Failed, 5, "D"));
+            recorder.record("B", ran(ProcessorStatus::Failed, 2, "B"));
+            recorder.build_report(&plan, failure_strategy, Vec::new(), PipelineMetadata::new())
+        };
+
+        let report = build(FailureStrategy::FailFast);
+        assert_eq!(report.results().len(), 1);
+        assert_eq!(
+            report.processors_with_status(ProcessorStatus::Failed),
+            vec!["B", "D"]
+        );
+        match report.into_result() {
+            Err(ExecutionError::ProcessorFailed { processor_id, .. }) => {
+                assert_eq!(processor_id, "B")
+            }
+            other => panic!("Expected first failure, got {:?}", other),
+        }
+
+        match build(FailureStrategy::BestEffort).into_result() {
+            Err(ExecutionError::MultipleFailed { failures }) => assert_eq!(failures.len(), 2),
+            other => panic!("Expected MultipleFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_successful_report() {
+        let plan = plan();
+        let recorder = ExecutionRecorder::new(Instant::now(), "Test");
+        for (index, id) in ["A", "B", "C", "D"].iter().enumerate() {
+            recorder.record(id, ran(ProcessorStatus::Succeeded, index as u64, id));
+        }
+
+        let report = recorder.build_report(
+            &plan,
+            FailureStrategy::FailFast,
+            b"ok".to_vec(),
+            PipelineMetadata::new(),
+        );
+
+        assert!(report.is_success());
+        let order: Vec<&str> = report
+            .processors_in_order()
+            .iter()
+            .map(|(id, _)| *id)
+            .collect();
+        assert_eq!(order, vec!["A", "B", "C", "D"]);
+
+        let (results, _metadata) = report.into_result().unwrap();
+        assert_eq!(results.len(), 4);
+    }
+
+    #[test]
+    fn test_designated_outputs() {
+        let mut stats = OutputConfig::new("C");
+        stats.name = Some("stats".to_string());
+        stats.metadata_keys = vec!["word_count".to_string(), "missing".to_string()];
+        let plan = plan()
+            .with_outputs(vec![OutputConfig::new("A"), stats, OutputConfig::new("D")])
+            .unwrap();
+
+        let mut analysis = ran(ProcessorStatus::Succeeded, 1, "C");
+        if let Some(response) = analysis.response.as_mut() {
+            let mut metadata = PipelineMetadata::new();
+            metadata.add_metadata("token_counter", "word_count", "2");
+            metadata.add_metadata("token_counter", "char_count", "11");
+            response.metadata = Some(metadata);
+        }
+
+        let recorder = ExecutionRecorder::new(Instant::now(), "Test");
+        recorder.record("A", ran(ProcessorStatus::Succeeded, 0, "A"));
+        recorder.record("C", analysis);
+        recorder.record("D", ran(ProcessorStatus::Failed, 0, "D"));
+
+        let report = recorder.build_report(
+            &plan,
+            FailureStrategy::ContinueOnError,
+            Vec::new(),
+            PipelineMetadata::new(),
+        );
+
+        let names: Vec<&str> = report.outputs.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["A", "stats", "D"]);
+
+        let stats = report.output("stats").unwrap();
+        assert_eq!(stats.processor_id, "C");
+        assert_eq!(stats.payload.as_deref(), Some(&b"ok"[..]));
+        assert_eq!(
+            stats.metadata,
+            HashMap::from([("word_count".to_string(), "2".to_string())])
+        );
+
+        let failed = report.output("D").unwrap();
+        assert_eq!(failed.status, ProcessorStatus::Failed);
+        assert!(failed.payload.is_none());
+    }
+}
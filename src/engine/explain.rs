@@ -0,0 +1,532 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Dry-run explanation of how each executor strategy would run a pipeline.
+//!
+//! A [`PlanExplanation`] walks a compiled [`ExecutionPlan`] the way each executor does,
+//! without invoking any processor:
+//!
+//! - **Work queue**: the dispatch order produced by [`PriorityWorkQueue`] from each
+//!   processor's [`PrioritizedTask`] (topological rank and Transform-vs-Analyze priority),
+//!   assuming processors run one at a time
+//! - **Level-by-level**: the topological levels, each run concurrently before the next
+//! - **Reactive**: the notification network, where each processor waits for a notification
+//!   from every dependency and notifies its dependents when it completes
+//!
+//! For every step it records which payload the processor receives: the pipeline input for
+//! entry points, otherwise the canonical payload last set by a Transform processor.
+//!
+//! [`PlanExplanation::from_config`] compiles the plan from configuration without
+//! instantiating WASM modules or connecting to remote backends; only the processors' declared
+//! intents are needed.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use the_dagwood::config::load_and_validate_config;
+//! use the_dagwood::engine::PlanExplanation;
+//!
+//! let config = load_and_validate_config("pipeline.yaml")?;
+//! let explanation = PlanExplanation::from_config(&config)?;
+//!
+//! for step in &explanation.work_queue.steps {
+//!     println!("{} receives {}", step.processor_id, step.input);
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::backends::local::LocalProcessorFactory;
+use crate::backends::wasm::WasmProcessor;
+use crate::config::{BackendType, Config, ProcessorMap};
+use crate::engine::priority_work_queue::{PrioritizedTask, PriorityWorkQueue};
+use crate::engine::ExecutionPlan;
+use crate::errors::{ExecutionError, ProcessorMapError};
+use crate::proto::processor_v1::{
+    processor_response::Outcome, ErrorDetail, ProcessorRequest, ProcessorResponse,
+};
+use crate::traits::processor::ProcessorIntent;
+use crate::traits::Processor;
+
+/// Which payload a processor receives, or which payload is canonical after a step
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadSource {
+    /// The original pipeline input
+    Input,
+    /// The output of a Transform processor
+    Processor(String),
+    /// The output of whichever of these Transform processors completes last
+    OneOf(Vec<String>),
+}
+
+impl PayloadSource {
+    /// Source for a set of Transform processors that may have set the canonical payload last
+    fn from_candidates(mut candidates: Vec<String>) -> Self {
+        candidates.sort();
+        match candidates.len() {
+            0 => PayloadSource::Input,
+            1 => PayloadSource::Processor(candidates.remove(0)),
+            _ => PayloadSource::OneOf(candidates),
+        }
+    }
+}
+
+impl fmt::Display for PayloadSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadSource::Input => write!(f, "pipeline input"),
+            PayloadSource::Processor(id) => write!(f, "output of '{}'", id),
+            PayloadSource::OneOf(ids) => write!(
+                f,
+                "output of one of {}",
+                ids.iter()
+                    .map(|id| format!("'{}'", id))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// A processor of the plan, as seen by the executors
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplainedProcessor {
+    pub id: String,
+    pub intent: ProcessorIntent,
+    /// Work queue priority: topological rank and Transform-vs-Analyze
+    pub task: PrioritizedTask,
+    /// Topological level
+    pub level: usize,
+    /// Processors it waits for; in the reactive network, the notifications it needs
+    pub dependencies: Vec<String>,
+    /// Processors it unblocks; in the reactive network, the processors it notifies
+    pub dependents: Vec<String>,
+}
+
+/// One processor execution in a strategy's plan
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedStep {
+    pub processor_id: String,
+    /// Topological level of the processor
+    pub level: usize,
+    /// Payload the processor receives
+    pub input: PayloadSource,
+    /// Whether the processor's output replaces the canonical payload
+    pub updates_canonical: bool,
+}
+
+/// How one strategy would run the pipeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrategyPlan {
+    /// Processors in the order the strategy starts them
+    pub steps: Vec<PlannedStep>,
+    /// Canonical payload once every processor has completed
+    pub final_payload: PayloadSource,
+}
+
+/// Dry-run explanation of a pipeline for every executor strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanExplanation {
+    /// Processors in topological rank order
+    pub processors: Vec<ExplainedProcessor>,
+    pub entry_points: Vec<String>,
+    /// Processors grouped into topological levels, each sorted by ID
+    pub levels: Vec<Vec<String>>,
+    pub work_queue: StrategyPlan,
+    pub level_by_level: StrategyPlan,
+    pub reactive: StrategyPlan,
+}
+
+impl PlanExplanation {
+    /// Explain a pipeline configuration without instantiating its processors.
+    ///
+    /// Local processors are created to read their declared intent, WASM intents come from the
+    /// `intent` option, and remote backends are assumed to be Transform processors.
+    pub fn from_config(cfg: &Config) -> Result<Self, String> {
+        let processors = dry_run_processors(cfg).map_err(|e| e.to_string())?;
+        let plan = ExecutionPlan::from_config(cfg, processors).map_err(|e| e.to_string())?;
+        Self::from_plan(&plan).map_err(|e| e.to_string())
+    }
+
+    /// Explain a compiled execution plan.
+    ///
+    /// ## Error Conditions
+    /// - `ExecutionError::InternalError` if no valid entry points were found at compile time
+    pub fn from_plan(plan: &ExecutionPlan) -> Result<Self, ExecutionError> {
+        let levels: Vec<Vec<String>> = plan
+            .topological_levels()?
+            .iter()
+            .map(|level| {
+                let mut level = level.clone();
+                level.sort();
+                level
+            })
+            .collect();
+        let level_of: HashMap<&str, usize> = levels
+            .iter()
+            .enumerate()
+            .flat_map(|(level, ids)| ids.iter().map(move |id| (id.as_str(), level)))
+            .collect();
+
+        let mut processors: Vec<ExplainedProcessor> = plan
+            .processors()
+            .keys()
+            .map(|id| {
+                let mut dependencies = plan.dependencies(id).to_vec();
+                dependencies.sort();
+                let mut dependents = plan.dependents(id).to_vec();
+                dependents.sort();
+                ExplainedProcessor {
+                    id: id.clone(),
+                    intent: plan.intent(id).unwrap_or(ProcessorIntent::Transform),
+                    task: plan.prioritized_task(id),
+                    level: level_of.get(id.as_str()).copied().unwrap_or(0),
+                    dependencies,
+                    dependents,
+                }
+            })
+            .collect();
+        processors.sort_by_key(|processor| processor.task.topological_rank);
+
+        let mut entry_points = plan.entrypoints().0.clone();
+        entry_points.sort();
+
+        let explanation = Self {
+            work_queue: explain_work_queue(plan, &level_of),
+            level_by_level: explain_level_by_level(plan, &levels),
+            reactive: explain_reactive(plan, &processors),
+            processors,
+            entry_points,
+            levels,
+        };
+        Ok(explanation)
+    }
+
+    /// Look up a processor of the plan by ID
+    pub fn processor(&self, processor_id: &str) -> Option<&ExplainedProcessor> {
+        self.processors
+            .iter()
+            .find(|processor| processor.id == processor_id)
+    }
+}
+
+/// Dispatch order of the work queue executor running one processor at a time.
+///
+/// A Transform processor only replaces the canonical payload when its rank is strictly higher
+/// than that of the last Transform processor that did.
+fn explain_work_queue(plan: &ExecutionPlan, level_of: &HashMap<&str, usize>) -> StrategyPlan {
+    let mut queue = PriorityWorkQueue::new();
+    for entrypoint in plan.entrypoints().iter() {
+        queue.push(plan.prioritized_task(entrypoint));
+    }
+    let mut dependency_counts = plan.dependency_counts().clone();
+    let blocked = HashSet::new();
+
+    let mut steps = Vec::new();
+    let mut canonical = PayloadSource::Input;
+    let mut highest_transform_rank = None;
+    while let Some(processor_id) = queue.pop_next_available(&blocked) {
+        let input = if plan.dependencies(&processor_id).is_empty() {
+            PayloadSource::Input
+        } else {
+            canonical.clone()
+        };
+
+        let rank = plan.rank(&processor_id).unwrap_or(0);
+        let updates_canonical = plan.is_transform(&processor_id)
+            && highest_transform_rank.is_none_or(|highest| rank > highest);
+        if updates_canonical {
+            canonical = PayloadSource::Processor(processor_id.clone());
+            highest_transform_rank = Some(rank);
+        }
+
+        for dependent in plan.dependents(&processor_id) {
+            if let Some(count) = dependency_counts.get_mut(dependent) {
+                *count -= 1;
+                if *count == 0 {
+                    queue.push(plan.prioritized_task(dependent));
+                }
+            }
+        }
+
+        steps.push(PlannedStep {
+            level: level_of.get(processor_id.as_str()).copied().unwrap_or(0),
+            processor_id,
+            input,
+            updates_canonical,
+        });
+    }
+
+    StrategyPlan {
+        steps,
+        final_payload: canonical,
+    }
+}
+
+/// Levels of the level-by-level executor; every Transform processor of a level replaces the
+/// canonical payload, so the last one to complete wins.
+fn explain_level_by_level(plan: &ExecutionPlan, levels: &[Vec<String>]) -> StrategyPlan {
+    let mut steps = Vec::new();
+    let mut canonical = PayloadSource::Input;
+    for (level, processor_ids) in levels.iter().enumerate() {
+        let mut transforms = Vec::new();
+        for processor_id in processor_ids {
+            let updates_canonical = plan.is_transform(processor_id);
+            if updates_canonical {
+                transforms.push(processor_id.clone());
+            }
+            steps.push(PlannedStep {
+                processor_id: processor_id.clone(),
+                level,
+                input: if plan.dependencies(processor_id).is_empty() {
+                    PayloadSource::Input
+                } else {
+                    canonical.clone()
+                },
+                updates_canonical,
+            });
+        }
+
+        if !transforms.is_empty() {
+            canonical = PayloadSource::from_candidates(transforms);
+        }
+    }
+
+    StrategyPlan {
+        steps,
+        final_payload: canonical,
+    }
+}
+
+/// Notification network of the reactive executor, in topological rank order.
+///
+/// A processor starts once every dependency has notified it and receives the canonical payload
+/// at that moment: the output of the last Transform among its ancestors to complete. Transform
+/// processors on unrelated branches finishing in between are not taken into account.
+fn explain_reactive(plan: &ExecutionPlan, processors: &[ExplainedProcessor]) -> StrategyPlan {
+    let steps = processors
+        .iter()
+        .map(|processor| PlannedStep {
+            processor_id: processor.id.clone(),
+            level: processor.level,
+            input: PayloadSource::from_candidates(latest_transforms(
+                plan,
+                &ancestors(plan, &processor.id),
+            )),
+            updates_canonical: plan.is_transform(&processor.id),
+        })
+        .collect();
+
+    let all_processors: HashSet<String> = plan.processors().keys().cloned().collect();
+    StrategyPlan {
+        steps,
+        final_payload: PayloadSource::from_candidates(latest_transforms(plan, &all_processors)),
+    }
+}
+
+/// Every processor the given processor transitively depends on
+fn ancestors(plan: &ExecutionPlan, processor_id: &str) -> HashSet<String> {
+    let mut ancestors = HashSet::new();
+    let mut pending: Vec<&str> = vec![processor_id];
+    while let Some(id) = pending.pop() {
+        for dependency in plan.dependencies(id) {
+            if ancestors.insert(dependency.clone()) {
+                pending.push(dependency);
+            }
+        }
+    }
+    ancestors
+}
+
+/// Transform processors of the set that no other Transform processor of the set depends on;
+/// one of them is the last to replace the canonical payload.
+fn latest_transforms(plan: &ExecutionPlan, processor_ids: &HashSet<String>) -> Vec<String> {
+    let transforms: Vec<&String> = processor_ids
+        .iter()
+        .filter(|id| plan.is_transform(id))
+        .collect();
+    let superseded: HashSet<String> = transforms
+        .iter()
+        .flat_map(|id| ancestors(plan, id))
+        .collect();
+    transforms
+        .into_iter()
+        .filter(|id| !superseded.contains(*id))
+        .cloned()
+        .collect()
+}
+
+/// Build a processor registry that declares the configured intents without loading WASM
+/// modules or connecting to remote backends.
+fn dry_run_processors(cfg: &Config) -> Result<ProcessorMap, ProcessorMapError> {
+    let mut processors = ProcessorMap::new();
+    for p in &cfg.processors {
+        let processor: Arc<dyn Processor> = match p.backend {
+            BackendType::Local => LocalProcessorFactory::create_processor(p).map_err(|e| {
+                ProcessorMapError::ProcessorCreationFailed {
+                    processor_id: p.id.clone(),
+                    backend: BackendType::Local,
+                    reason: e,
+                }
+            })?,
+            BackendType::Wasm => Arc::new(DryRunProcessor {
+                intent: WasmProcessor::intent_from_config(p).map_err(|e| {
+                    ProcessorMapError::ProcessorCreationFailed {
+                        processor_id: p.id.clone(),
+                        backend: BackendType::Wasm,
+                        reason: e.to_string(),
+                    }
+                })?,
+            }),
+            BackendType::Grpc | BackendType::Http | BackendType::Loadable => {
+                Arc::new(DryRunProcessor {
+                    intent: ProcessorIntent::Transform,
+                })
+            }
+        };
+        processors.insert(p.id.clone(), processor);
+    }
+    Ok(processors)
+}
+
+/// Placeholder that only declares an intent; a dry-run plan is never executed
+struct DryRunProcessor {
+    intent: ProcessorIntent,
+}
+
+#[async_trait]
+impl Processor for DryRunProcessor {
+    async fn process(&self, _req: ProcessorRequest) -> ProcessorResponse {
+        ProcessorResponse {
+            outcome: Some(Outcome::Error(ErrorDetail {
+                code: 501,
+                message: "Dry-run processors cannot be executed".to_string(),
+            })),
+            metadata: None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "dry_run"
+    }
+
+    fn declared_intent(&self) -> ProcessorIntent {
+        self.intent
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `prepare` → [`count` (Analyze), `left`, `right` (WASM Transform)] → `merge`
+    fn diamond_config() -> Config {
+        serde_yaml::from_str(
+            r#"
+strategy: work_queue
+processors:
+  - id: prepare
+    type: local
+    processor: change_text_case_upper
+  - id: count
+    type: local
+    processor: token_counter
+    depends_on: [prepare]
+  - id: left
+    type: local
+    processor: reverse_text
+    depends_on: [prepare]
+  - id: right
+    type: wasm
+    module: does/not/exist.wasm
+    depends_on: [prepare]
+  - id: merge
+    type: local
+    processor: prefix_suffix_adder
+    depends_on: [count, left, right]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_config_does_not_load_wasm_modules() {
+        let explanation = PlanExplanation::from_config(&diamond_config()).unwrap();
+
+        assert_eq!(explanation.entry_points, vec!["prepare"]);
+        assert_eq!(
+            explanation.levels,
+            vec![
+                vec!["prepare".to_string()],
+                vec!["count".to_string(), "left".to_string(), "right".to_string()],
+                vec!["merge".to_string()],
+            ]
+        );
+        assert_eq!(
+            explanation.processor("count").unwrap().intent,
+            ProcessorIntent::Analyze
+        );
+        assert_eq!(
+            explanation.processor("right").unwrap().intent,
+            ProcessorIntent::Transform
+        );
+        assert_eq!(
+            explanation.processor("prepare").unwrap().dependents,
+            vec!["count", "left", "right"]
+        );
+    }
+
+    #[test]
+    fn test_work_queue_follows_priority_order() {
+        let explanation = PlanExplanation::from_config(&diamond_config()).unwrap();
+        let steps = &explanation.work_queue.steps;
+
+        let order: Vec<&str> = steps.iter().map(|s| s.processor_id.as_str()).collect();
+        let mut expected: Vec<&ExplainedProcessor> = explanation.processors[1..4].iter().collect();
+        expected.sort_by(|a, b| b.task.cmp(&a.task));
+        assert_eq!(order[0], "prepare");
+        assert_eq!(
+            order[1..4].to_vec(),
+            expected.iter().map(|p| p.id.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(order[4], "merge");
+
+        assert_eq!(steps[0].input, PayloadSource::Input);
+        assert_eq!(steps[1].input, PayloadSource::Processor("prepare".into()));
+        let count = steps.iter().find(|s| s.processor_id == "count").unwrap();
+        assert!(!count.updates_canonical);
+    }
+
+    #[test]
+    fn test_level_by_level_and_reactive_canonical_payload() {
+        let explanation = PlanExplanation::from_config(&diamond_config()).unwrap();
+        let both_transforms = PayloadSource::OneOf(vec!["left".into(), "right".into()]);
+
+        let merge = |plan: &StrategyPlan| {
+            plan.steps
+                .iter()
+                .find(|s| s.processor_id == "merge")
+                .unwrap()
+                .input
+                .clone()
+        };
+        assert_eq!(merge(&explanation.level_by_level), both_transforms);
+        assert_eq!(merge(&explanation.reactive), both_transforms);
+        assert_eq!(
+            explanation.level_by_level.final_payload,
+            PayloadSource::Processor("merge".into())
+        );
+        assert_eq!(
+            explanation.reactive.final_payload,
+            PayloadSource::Processor("merge".into())
+        );
+        assert_eq!(
+            both_transforms.to_string(),
+            "output of one of 'left', 'right'"
+        );
+    }
+}
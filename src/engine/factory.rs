@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: MIT
 
 use crate::config::{Config, Strategy};
+use crate::engine::invocation::InvocationPolicy;
 use crate::engine::level_by_level::LevelByLevelExecutor;
 use crate::engine::reactive::ReactiveExecutor;
 use crate::engine::work_queue::WorkQueueExecutor;
@@ -18,15 +19,26 @@ impl ExecutorFactory {
                 .map(|n| n.get())
                 .unwrap_or(4)
         });
+        let invocation_policy = InvocationPolicy::from_options(&cfg.executor_options);
 
         match cfg.strategy {
-            Strategy::WorkQueue => Box::new(WorkQueueExecutor::new(max_concurrency)),
-            Strategy::Level => Box::new(LevelByLevelExecutor::new(max_concurrency)),
-            Strategy::Reactive => Box::new(ReactiveExecutor::new(max_concurrency)),
+            Strategy::WorkQueue => Box::new(
+                WorkQueueExecutor::new(max_concurrency).with_invocation_policy(invocation_policy),
+            ),
+            Strategy::Level => Box::new(
+                LevelByLevelExecutor::new(max_concurrency)
+                    .with_invocation_policy(invocation_policy),
+            ),
+            Strategy::Reactive => Box::new(
+                ReactiveExecutor::new(max_concurrency).with_invocation_policy(invocation_policy),
+            ),
             Strategy::Hybrid => {
                 // TODO: Implement Hybrid executor
                 // For now, fallback to WorkQueue
-                Box::new(WorkQueueExecutor::new(max_concurrency))
+                Box::new(
+                    WorkQueueExecutor::new(max_concurrency)
+                        .with_invocation_policy(invocation_policy),
+                )
             }
         }
     }
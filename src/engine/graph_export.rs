@@ -0,0 +1,507 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Graphviz DOT and Mermaid diagrams of pipeline DAGs.
+//!
+//! A [`GraphExport`] is built from a pipeline [`Config`] and renders the DAG as a Graphviz
+//! `digraph` ([`to_dot`](GraphExport::to_dot)) or a Mermaid `flowchart`
+//! ([`to_mermaid`](GraphExport::to_mermaid)). Every node is annotated with its backend type,
+//! processor or module, topological rank and level. Diamond patterns found by
+//! [`validate_diamond_patterns`] are highlighted: the convergence processor is outlined and
+//! the parallel edges leading into it are drawn bold.
+//!
+//! Processor intent (Transform/Analyze) is declared by processor implementations, so it is
+//! only shown once a compiled [`ExecutionPlan`] is attached with
+//! [`with_plan`](GraphExport::with_plan). Attaching an [`ExecutionReport`] with
+//! [`with_report`](GraphExport::with_report) overlays each processor's status and duration.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use the_dagwood::config::load_and_validate_config;
+//! use the_dagwood::engine::GraphExport;
+//!
+//! let config = load_and_validate_config("pipeline.yaml")?;
+//! let export = GraphExport::from_config(&config)?;
+//!
+//! std::fs::write("pipeline.dot", export.to_dot())?;
+//! std::fs::write("pipeline.mmd", export.to_mermaid())?;
+//! ```
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::config::{validate_diamond_patterns, BackendType, Config};
+use crate::engine::execution_plan::{compute_topological_levels, graph_from_config};
+use crate::engine::{ExecutionPlan, ExecutionReport, ProcessorStatus};
+use crate::errors::{ExecutionError, ValidationError};
+use crate::traits::processor::ProcessorIntent;
+
+/// Outline and edge color of diamond patterns
+const DIAMOND_COLOR: &str = "#d9534f";
+
+/// A processor in the exported graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphNode {
+    pub id: String,
+    pub backend: BackendType,
+    /// Local processor name, WASM module path, or remote endpoint
+    pub implementation: Option<String>,
+    /// Declared intent; `None` until a compiled plan is attached
+    pub intent: Option<ProcessorIntent>,
+    /// Position in the topological order
+    pub rank: usize,
+    /// Topological level (0 for entry points)
+    pub level: usize,
+    /// Whether parallel paths of a diamond pattern converge at this processor
+    pub diamond_convergence: bool,
+    /// Status and duration from an attached execution report
+    pub outcome: Option<(ProcessorStatus, Duration)>,
+}
+
+/// A dependency edge in the exported graph, from a processor to one of its dependents
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// Whether the edge is one of the parallel paths of a diamond pattern
+    pub diamond: bool,
+}
+
+/// DAG of a pipeline, ready to be rendered as a DOT or Mermaid diagram
+#[derive(Debug, Clone)]
+pub struct GraphExport {
+    /// Nodes ordered by level, then ID
+    nodes: Vec<GraphNode>,
+    /// Edges ordered by source, then target
+    edges: Vec<GraphEdge>,
+}
+
+impl GraphExport {
+    /// Build the graph of a pipeline configuration.
+    ///
+    /// ## Error Conditions
+    /// - `ExecutionError::InternalError` if the dependency graph contains cycles or has no
+    ///   entry points (should be caught in validation)
+    pub fn from_config(config: &Config) -> Result<Self, ExecutionError> {
+        let (graph, entrypoints) = graph_from_config(config);
+        let levels = compute_topological_levels(&graph, &entrypoints)?;
+        let (_, ranks) =
+            graph
+                .dependency_counts_and_ranks()
+                .ok_or_else(|| ExecutionError::InternalError {
+                    message: "dependency graph contains cycles".into(),
+                })?;
+
+        let mut convergence_processors = HashSet::new();
+        let mut diamond_edges = HashSet::new();
+        for warning in validate_diamond_patterns(config).err().unwrap_or_default() {
+            if let ValidationError::DiamondPatternWarning {
+                convergence_processor,
+                parallel_paths,
+            } = warning
+            {
+                for path in &parallel_paths {
+                    for edge in path.windows(2) {
+                        diamond_edges.insert((edge[0].clone(), edge[1].clone()));
+                    }
+                }
+                convergence_processors.insert(convergence_processor);
+            }
+        }
+
+        let mut nodes = Vec::new();
+        for (level, processor_ids) in levels.iter().enumerate() {
+            let mut processor_ids = processor_ids.clone();
+            processor_ids.sort();
+            for id in processor_ids {
+                let Some(processor) = config.processors.iter().find(|p| p.id == id) else {
+                    continue;
+                };
+                let implementation = match processor.backend {
+                    BackendType::Local => processor.processor.clone(),
+                    BackendType::Wasm => processor.module.clone(),
+                    BackendType::Grpc | BackendType::Http => processor.endpoint.clone(),
+                    BackendType::Loadable => None,
+                };
+                nodes.push(GraphNode {
+                    backend: processor.backend.clone(),
+                    implementation,
+                    intent: None,
+                    rank: ranks.get(&id).copied().unwrap_or(0),
+                    level,
+                    diamond_convergence: convergence_processors.contains(&id),
+                    outcome: None,
+                    id,
+                });
+            }
+        }
+
+        let mut edges: Vec<GraphEdge> = graph
+            .0
+            .iter()
+            .flat_map(|(from, dependents)| {
+                dependents.iter().map(|to| GraphEdge {
+                    from: from.clone(),
+                    to: to.clone(),
+                    diamond: diamond_edges.contains(&(from.clone(), to.clone())),
+                })
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+        Ok(Self { nodes, edges })
+    }
+
+    /// Annotate nodes with the intents declared by the processors of a compiled plan
+    pub fn with_plan(mut self, plan: &ExecutionPlan) -> Self {
+        for node in &mut self.nodes {
+            node.intent = plan.intent(&node.id);
+        }
+        self
+    }
+
+    /// Overlay each processor's status and duration from an execution report
+    pub fn with_report(mut self, report: &ExecutionReport) -> Self {
+        for node in &mut self.nodes {
+            node.outcome = report
+                .processor(&node.id)
+                .map(|processor| (processor.status, processor.duration));
+        }
+        self
+    }
+
+    /// Nodes ordered by level, then ID
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// Edges ordered by source, then target
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    /// Render as a Graphviz `digraph`; processors of the same level share a rank
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph dagwood {{");
+        let _ = writeln!(dot, "    rankdir=TB;");
+        let _ = writeln!(
+            dot,
+            "    node [shape=box, style=\"rounded,filled\", fillcolor=\"#ffffff\", fontname=\"Helvetica\"];"
+        );
+        let _ = writeln!(dot);
+
+        for node in &self.nodes {
+            let mut attributes = vec![format!(
+                "label=\"{}\"",
+                escape_dot(&node_label(node).join("\n")).replace('\n', "\\n")
+            )];
+            if let Some((status, _)) = node.outcome {
+                attributes.push(format!("fillcolor=\"{}\"", status_color(status)));
+            }
+            if node.diamond_convergence {
+                attributes.push(format!("color=\"{}\", penwidth=2", DIAMOND_COLOR));
+            }
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [{}];",
+                escape_dot(&node.id),
+                attributes.join(", ")
+            );
+        }
+
+        let _ = writeln!(dot);
+        for level in self.levels() {
+            let ids: Vec<String> = level
+                .iter()
+                .map(|node| format!("\"{}\";", escape_dot(&node.id)))
+                .collect();
+            let _ = writeln!(dot, "    {{ rank=same; {} }}", ids.join(" "));
+        }
+
+        let _ = writeln!(dot);
+        for edge in &self.edges {
+            let attributes = if edge.diamond {
+                format!(" [color=\"{}\", penwidth=2]", DIAMOND_COLOR)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" -> \"{}\"{};",
+                escape_dot(&edge.from),
+                escape_dot(&edge.to),
+                attributes
+            );
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Render as a Mermaid `flowchart`; diamond edges are drawn as thick links
+    pub fn to_mermaid(&self) -> String {
+        let mut mermaid = String::from("flowchart TD\n");
+
+        for node in &self.nodes {
+            let _ = writeln!(
+                mermaid,
+                "    {}[\"{}\"]",
+                mermaid_id(&node.id),
+                node_label(node)
+                    .iter()
+                    .map(|line| escape_mermaid(line))
+                    .collect::<Vec<_>>()
+                    .join("<br/>")
+            );
+        }
+
+        for edge in &self.edges {
+            let arrow = if edge.diamond { "==>" } else { "-->" };
+            let _ = writeln!(
+                mermaid,
+                "    {} {} {}",
+                mermaid_id(&edge.from),
+                arrow,
+                mermaid_id(&edge.to)
+            );
+        }
+
+        let statuses = [
+            ProcessorStatus::Succeeded,
+            ProcessorStatus::Failed,
+            ProcessorStatus::TimedOut,
+            ProcessorStatus::Blocked,
+            ProcessorStatus::Skipped,
+        ];
+        for status in statuses {
+            let ids: Vec<String> = self
+                .nodes
+                .iter()
+                .filter(|node| node.outcome.map(|(s, _)| s) == Some(status))
+                .map(|node| mermaid_id(&node.id))
+                .collect();
+            if !ids.is_empty() {
+                let class = status_class(status);
+                let _ = writeln!(
+                    mermaid,
+                    "    classDef {} fill:{}",
+                    class,
+                    status_color(status)
+                );
+                let _ = writeln!(mermaid, "    class {} {}", ids.join(","), class);
+            }
+        }
+
+        let diamonds: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|node| node.diamond_convergence)
+            .map(|node| mermaid_id(&node.id))
+            .collect();
+        if !diamonds.is_empty() {
+            let _ = writeln!(
+                mermaid,
+                "    classDef diamond stroke:{},stroke-width:3px",
+                DIAMOND_COLOR
+            );
+            let _ = writeln!(mermaid, "    class {} diamond", diamonds.join(","));
+        }
+
+        mermaid
+    }
+
+    fn levels(&self) -> Vec<Vec<&GraphNode>> {
+        let mut levels: Vec<Vec<&GraphNode>> = Vec::new();
+        for node in &self.nodes {
+            if levels.len() <= node.level {
+                levels.resize_with(node.level + 1, Vec::new);
+            }
+            levels[node.level].push(node);
+        }
+        levels
+    }
+}
+
+/// Label lines of a node: ID, backend, placement, and the execution outcome if known
+fn node_label(node: &GraphNode) -> Vec<String> {
+    let backend = format!("{:?}", node.backend).to_lowercase();
+    let mut lines = vec![
+        node.id.clone(),
+        match &node.implementation {
+            Some(implementation) => format!("{}: {}", backend, implementation),
+            None => backend,
+        },
+    ];
+
+    let placement = format!("rank {} · level {}", node.rank, node.level);
+    lines.push(match node.intent {
+        Some(intent) => format!("{:?} · {}", intent, placement),
+        None => placement,
+    });
+
+    if let Some((status, duration)) = node.outcome {
+        lines.push(format!("{} · {:.2?}", status, duration));
+    }
+    lines
+}
+
+fn status_color(status: ProcessorStatus) -> &'static str {
+    match status {
+        ProcessorStatus::Succeeded => "#dff0d8",
+        ProcessorStatus::Failed | ProcessorStatus::TimedOut => "#f2dede",
+        ProcessorStatus::Blocked => "#fcf8e3",
+        ProcessorStatus::Skipped => "#eeeeee",
+    }
+}
+
+fn status_class(status: ProcessorStatus) -> &'static str {
+    match status {
+        ProcessorStatus::Succeeded => "succeeded",
+        ProcessorStatus::Failed => "failed",
+        ProcessorStatus::TimedOut => "timed_out",
+        ProcessorStatus::Blocked => "blocked",
+        ProcessorStatus::Skipped => "skipped",
+    }
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
+
+/// Mermaid node ID: processor IDs may contain characters Mermaid does not accept in IDs
+fn mermaid_id(id: &str) -> String {
+    let sanitized: String = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("p_{}", sanitized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond_config() -> Config {
+        serde_yaml::from_str(
+            r#"
+strategy: work_queue
+processors:
+  - id: prepare
+    type: local
+    processor: change_text_case_upper
+  - id: left
+    type: local
+    processor: token_counter
+    depends_on: [prepare]
+  - id: right
+    type: wasm
+    module: wasm_components/hello.wasm
+    depends_on: [prepare]
+  - id: summary
+    type: local
+    processor: prefix_suffix_adder
+    depends_on: [left, right]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nodes_are_annotated_with_rank_level_and_diamonds() {
+        let export = GraphExport::from_config(&diamond_config()).unwrap();
+
+        let ids: Vec<&str> = export.nodes().iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["prepare", "left", "right", "summary"]);
+
+        let right = &export.nodes()[2];
+        assert_eq!(right.backend, BackendType::Wasm);
+        assert_eq!(
+            right.implementation.as_deref(),
+            Some("wasm_components/hello.wasm")
+        );
+        assert_eq!(right.level, 1);
+        assert!(!right.diamond_convergence);
+
+        let summary = &export.nodes()[3];
+        assert_eq!(summary.level, 2);
+        assert_eq!(summary.rank, 3);
+        assert!(summary.diamond_convergence);
+
+        let diamond_edges: Vec<(&str, &str)> = export
+            .edges()
+            .iter()
+            .filter(|edge| edge.diamond)
+            .map(|edge| (edge.from.as_str(), edge.to.as_str()))
+            .collect();
+        assert_eq!(
+            diamond_edges,
+            vec![("left", "summary"), ("right", "summary")]
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = GraphExport::from_config(&diamond_config())
+            .unwrap()
+            .to_dot();
+
+        assert!(dot.starts_with("digraph dagwood {\n"));
+        assert!(dot.contains(
+            "\"prepare\" [label=\"prepare\\nlocal: change_text_case_upper\\nrank 0 · level 0\"];"
+        ));
+        assert!(dot.contains("{ rank=same; \"left\"; \"right\"; }"));
+        assert!(dot.contains("\"prepare\" -> \"left\";"));
+        assert!(dot.contains("\"left\" -> \"summary\" [color=\"#d9534f\", penwidth=2];"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid_with_report_overlay() {
+        use crate::engine::ProcessorReport;
+        use crate::errors::FailureStrategy;
+        use crate::proto::processor_v1::PipelineMetadata;
+        use std::collections::HashMap;
+
+        let mut processors = HashMap::new();
+        let mut succeeded = ProcessorReport::not_run(ProcessorStatus::Succeeded, None);
+        succeeded.duration = Duration::from_millis(3);
+        processors.insert("prepare".to_string(), succeeded);
+        processors.insert(
+            "left".to_string(),
+            ProcessorReport::not_run(ProcessorStatus::Failed, None),
+        );
+        let report = ExecutionReport {
+            run_id: "run".to_string(),
+            strategy: "WorkQueue",
+            failure_strategy: FailureStrategy::FailFast,
+            processors,
+            final_output: Vec::new(),
+            outputs: Vec::new(),
+            pipeline_metadata: PipelineMetadata::new(),
+            errors: Vec::new(),
+            duration: Duration::from_millis(5),
+        };
+
+        let mermaid = GraphExport::from_config(&diamond_config())
+            .unwrap()
+            .with_report(&report)
+            .to_mermaid();
+
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains(
+            "p_prepare[\"prepare<br/>local: change_text_case_upper<br/>rank 0 · level 0<br/>succeeded · 3.00ms\"]"
+        ));
+        assert!(mermaid.contains("p_prepare --> p_left"));
+        assert!(mermaid.contains("p_left ==> p_summary"));
+        assert!(mermaid.contains("class p_prepare succeeded"));
+        assert!(mermaid.contains("class p_left failed"));
+        assert!(mermaid.contains("class p_summary diamond"));
+    }
+}
@@ -7,10 +7,14 @@ use std::sync::Arc;
 use crate::backends::local::factory::LocalProcessorFactory;
 use crate::config::{BackendType, ProcessorConfig};
 use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
-use crate::engine::{LevelByLevelExecutor, ReactiveExecutor, WorkQueueExecutor};
+use crate::engine::{
+    ExecutionEvent, ExecutionEventKind, ExecutionPlan, LevelByLevelExecutor, ProcessorStatus,
+    ReactiveExecutor, WorkQueueExecutor,
+};
+use crate::errors::FailureStrategy;
 use crate::proto::processor_v1::processor_response::Outcome;
 use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
-use crate::traits::{DagExecutor, Processor};
+use crate::traits::{DagExecutor, ExecutionObserver, Processor};
 
 /// Integration tests for the Work Queue executor using real local processors
 #[cfg(test)]
@@ -757,6 +761,7 @@ mod tests {
                 crate::errors::FailureStrategy::FailFast,
             )
             .await
+            .and_then(|report| report.into_result())
             .expect("WorkQueue execution failed");
 
         // Execute with LevelByLevel executor
@@ -771,6 +776,7 @@ mod tests {
                 crate::errors::FailureStrategy::FailFast,
             )
             .await
+            .and_then(|report| report.into_result())
             .expect("LevelByLevel execution failed");
 
         // Execute with Reactive executor
@@ -785,6 +791,7 @@ mod tests {
                 crate::errors::FailureStrategy::FailFast,
             )
             .await
+            .and_then(|report| report.into_result())
             .expect("Reactive execution failed");
 
         // Verify all executors produced the same results
@@ -850,4 +857,310 @@ mod tests {
         println!("✅ All three executors (WorkQueue, LevelByLevel, Reactive) produced identical results!");
         println!("   Input: 'hello world' -> Output: 'DLROW OLLEH'");
     }
+
+    /// Observer that keeps every event for later inspection
+    #[derive(Default)]
+    struct CollectingObserver {
+        events: std::sync::Mutex<Vec<ExecutionEvent>>,
+    }
+
+    impl ExecutionObserver for CollectingObserver {
+        fn on_event(&self, event: &ExecutionEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_executors_emit_execution_events() {
+        use crate::backends::stub::{FailingProcessor, StubProcessor};
+
+        // entry -> failing -> downstream, entry -> independent
+        let mut processors = ProcessorMap::new();
+        for id in ["entry", "independent", "downstream"] {
+            processors.insert(
+                id.to_string(),
+                Arc::new(StubProcessor::new(id.to_string())) as Arc<dyn Processor>,
+            );
+        }
+        processors.insert(
+            "failing".to_string(),
+            Arc::new(FailingProcessor::new("failing".to_string())),
+        );
+        let graph = DependencyGraph::from(HashMap::from([
+            (
+                "entry".to_string(),
+                vec!["failing".to_string(), "independent".to_string()],
+            ),
+            ("failing".to_string(), vec!["downstream".to_string()]),
+            ("independent".to_string(), vec![]),
+            ("downstream".to_string(), vec![]),
+        ]));
+        let plan = Arc::new(
+            ExecutionPlan::compile(processors, graph, EntryPoints(vec!["entry".to_string()]))
+                .unwrap(),
+        );
+
+        let executors: Vec<Box<dyn DagExecutor>> = vec![
+            Box::new(WorkQueueExecutor::new(2)),
+            Box::new(LevelByLevelExecutor::new(2)),
+            Box::new(ReactiveExecutor::new(2)),
+        ];
+
+        for executor in executors {
+            let observer = Arc::new(CollectingObserver::default());
+            let report = executor
+                .execute_plan_observed(
+                    plan.clone(),
+                    ProcessorRequest {
+                        payload: b"hello".to_vec(),
+                    },
+                    PipelineMetadata::new(),
+                    FailureStrategy::ContinueOnError,
+                    observer.clone(),
+                )
+                .await
+                .unwrap();
+
+            let events = observer.events.lock().unwrap();
+            let strategy = report.strategy;
+
+            assert!(
+                matches!(
+                    events.first().map(|e| &e.kind),
+                    Some(ExecutionEventKind::ExecutionStarted {
+                        processor_count: 4,
+                        ..
+                    })
+                ),
+                "{}: first event should be ExecutionStarted",
+                strategy
+            );
+            assert!(
+                matches!(
+                    events.last().map(|e| &e.kind),
+                    Some(ExecutionEventKind::ExecutionCompleted {
+                        succeeded: false,
+                        ..
+                    })
+                ),
+                "{}: last event should be ExecutionCompleted",
+                strategy
+            );
+
+            let position = |predicate: &dyn Fn(&ExecutionEventKind) -> bool| {
+                events.iter().position(|e| predicate(&e.kind))
+            };
+
+            for id in ["entry", "independent", "failing"] {
+                let scheduled = position(&|kind| {
+                    matches!(kind, ExecutionEventKind::ProcessorScheduled { .. })
+                        && kind.processor_id() == Some(id)
+                });
+                let started = position(&|kind| {
+                    matches!(
+                        kind,
+                        ExecutionEventKind::ProcessorStarted { attempt: 1, .. }
+                    ) && kind.processor_id() == Some(id)
+                });
+                assert!(scheduled.is_some(), "{}: {} not scheduled", strategy, id);
+                assert!(
+                    scheduled < started,
+                    "{}: {} started before scheduled",
+                    strategy,
+                    id
+                );
+            }
+
+            for id in ["entry", "independent"] {
+                assert!(
+                    position(
+                        &|kind| matches!(kind, ExecutionEventKind::ProcessorCompleted { .. })
+                            && kind.processor_id() == Some(id)
+                    )
+                    .is_some(),
+                    "{}: {} should complete",
+                    strategy,
+                    id
+                );
+            }
+
+            assert!(
+                position(&|kind| matches!(
+                    kind,
+                    ExecutionEventKind::ProcessorFailed {
+                        status: ProcessorStatus::Failed,
+                        ..
+                    }
+                ) && kind.processor_id() == Some("failing"))
+                .is_some(),
+                "{}: failing should emit ProcessorFailed",
+                strategy
+            );
+            assert!(
+                position(&|kind| matches!(
+                    kind,
+                    ExecutionEventKind::ProcessorBlocked { processor_id, failed_dependency }
+                        if processor_id == "downstream" && failed_dependency == "failing"
+                ))
+                .is_some(),
+                "{}: downstream should emit ProcessorBlocked",
+                strategy
+            );
+            assert!(
+                position(&|kind| matches!(
+                    kind,
+                    ExecutionEventKind::CanonicalPayloadUpdated { .. }
+                ))
+                .is_some(),
+                "{}: transform processors should update the canonical payload",
+                strategy
+            );
+        }
+    }
+    #[tokio::test]
+    async fn test_every_execution_gets_unique_run_id() {
+        use crate::backends::stub::StubProcessor;
+        use std::collections::HashSet;
+
+        let mut processors = ProcessorMap::new();
+        processors.insert(
+            "entry".to_string(),
+            Arc::new(StubProcessor::new("entry".to_string())) as Arc<dyn Processor>,
+        );
+        let graph = DependencyGraph::from(HashMap::from([("entry".to_string(), vec![])]));
+        let plan = Arc::new(
+            ExecutionPlan::compile(processors, graph, EntryPoints(vec!["entry".to_string()]))
+                .unwrap(),
+        );
+
+        let executors: Vec<Box<dyn DagExecutor>> = vec![
+            Box::new(WorkQueueExecutor::new(2)),
+            Box::new(LevelByLevelExecutor::new(2)),
+            Box::new(ReactiveExecutor::new(2)),
+        ];
+
+        let mut run_ids = HashSet::new();
+        for executor in &executors {
+            for _ in 0..2 {
+                let report = executor
+                    .execute_plan(
+                        plan.clone(),
+                        ProcessorRequest {
+                            payload: b"hello".to_vec(),
+                        },
+                        PipelineMetadata::new(),
+                        FailureStrategy::FailFast,
+                    )
+                    .await
+                    .unwrap();
+
+                assert!(
+                    !report.run_id.is_empty(),
+                    "{}: empty run_id",
+                    report.strategy
+                );
+                assert!(
+                    run_ids.insert(report.run_id.clone()),
+                    "{}: run_id {} reused",
+                    report.strategy,
+                    report.run_id
+                );
+            }
+        }
+    }
+
+    /// Processor that never finishes; counts the runs that started and the runs that stopped
+    #[derive(Default)]
+    struct HangingProcessor {
+        started: std::sync::atomic::AtomicUsize,
+        stopped: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    /// Counts its processor run as stopped when dropped with the run's future
+    struct StopCounter(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl Drop for StopCounter {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Processor for HangingProcessor {
+        async fn process(
+            &self,
+            _request: ProcessorRequest,
+        ) -> crate::proto::processor_v1::ProcessorResponse {
+            let _stopped = StopCounter(self.stopped.clone());
+            self.started
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            std::future::pending().await
+        }
+
+        fn name(&self) -> &'static str {
+            "HangingProcessor"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_executors_stop_processors_on_cancel() {
+        use std::sync::atomic::Ordering;
+        use tokio_util::sync::CancellationToken;
+
+        let executors: Vec<Box<dyn DagExecutor>> = vec![
+            Box::new(WorkQueueExecutor::new(2)),
+            Box::new(LevelByLevelExecutor::new(2)),
+            Box::new(ReactiveExecutor::new(2)),
+        ];
+
+        for executor in executors {
+            // first -> second; only `first` ever starts
+            let hanging = Arc::new(HangingProcessor::default());
+            let mut processors = ProcessorMap::new();
+            for id in ["first", "second"] {
+                processors.insert(id.to_string(), hanging.clone() as Arc<dyn Processor>);
+            }
+            let graph = DependencyGraph::from(HashMap::from([
+                ("first".to_string(), vec!["second".to_string()]),
+                ("second".to_string(), vec![]),
+            ]));
+            let plan = Arc::new(
+                ExecutionPlan::compile(processors, graph, EntryPoints(vec!["first".to_string()]))
+                    .unwrap(),
+            );
+
+            let cancellation = CancellationToken::new();
+            let cancel = {
+                let cancellation = cancellation.clone();
+                let hanging = hanging.clone();
+                async move {
+                    while hanging.started.load(Ordering::SeqCst) == 0 {
+                        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                    }
+                    cancellation.cancel();
+                }
+            };
+            let (result, ()) = tokio::join!(
+                executor.execute_plan_cancellable(
+                    plan,
+                    ProcessorRequest {
+                        payload: b"hello".to_vec(),
+                    },
+                    PipelineMetadata::new(),
+                    FailureStrategy::FailFast,
+                    Arc::new(crate::engine::NoopObserver),
+                    cancellation,
+                ),
+                cancel
+            );
+
+            assert!(matches!(
+                result,
+                Err(crate::errors::ExecutionError::Cancelled { .. })
+            ));
+            // The running processor was stopped before the execution returned
+            assert_eq!(hanging.started.load(Ordering::SeqCst), 1);
+            assert_eq!(hanging.stopped.load(Ordering::SeqCst), 1);
+        }
+    }
 }
@@ -0,0 +1,400 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Shared processor invocation with timeout and retry handling.
+//!
+//! All executors invoke processors through [`invoke_processor`], so timeouts, retries and
+//! per-processor timing are applied identically regardless of the execution strategy.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tracing::Instrument;
+
+use crate::config::ExecutorOptions;
+use crate::errors::ExecutionError;
+use crate::observability::chrome_trace::PROCESSOR_INVOCATION_SPAN;
+use crate::observability::messages::processor::ProcessorExecutionStarted;
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::{metrics, DIRECTION_INPUT, DIRECTION_OUTPUT};
+use crate::proto::processor_v1::processor_response::Outcome;
+use crate::proto::processor_v1::ProcessorRequest;
+use crate::traits::Processor;
+
+use super::events::ExecutionEventKind;
+use super::execution_report::{ExecutionRecorder, ProcessorReport, ProcessorStatus};
+
+/// Timeout and retry policy applied to every processor invocation.
+///
+/// Built from the `executor_options` section of the configuration:
+/// - `timeout_seconds`: upper bound for a single attempt (no timeout when absent)
+/// - `retry_attempts`: additional attempts after a failed or timed out first attempt
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InvocationPolicy {
+    pub timeout: Option<Duration>,
+    pub retry_attempts: u32,
+}
+
+impl InvocationPolicy {
+    /// Create an invocation policy from executor options
+    pub fn from_options(options: &ExecutorOptions) -> Self {
+        Self {
+            timeout: options.timeout_seconds.map(Duration::from_secs),
+            retry_attempts: options.retry_attempts.unwrap_or(0),
+        }
+    }
+
+    /// Total number of attempts a processor gets, including the first one
+    pub fn max_attempts(&self) -> u32 {
+        self.retry_attempts.saturating_add(1)
+    }
+}
+
+/// Invoke a processor according to the invocation policy and report the outcome.
+///
+/// A `NextPayload` outcome ends the invocation as `Succeeded`. An `Error` outcome, a missing
+/// outcome, or an attempt exceeding the timeout is retried until the attempts are exhausted;
+/// the last attempt determines the reported status (`Failed` or `TimedOut`).
+///
+/// The recorder anchors the report's `started_at` offset to the start of the DAG execution and
+/// receives a `ProcessorStarted` event per attempt and a `ProcessorRetried` event per retry.
+/// Execution counts, latency, failure codes and payload sizes are recorded in the metrics
+/// registry under the processor ID.
+pub(crate) async fn invoke_processor(
+    processor_id: &str,
+    processor: &Arc<dyn Processor>,
+    request: ProcessorRequest,
+    policy: InvocationPolicy,
+    recorder: &ExecutionRecorder,
+) -> ProcessorReport {
+    let _active = recorder.invocation_started(processor_id);
+    let invocation_start = Instant::now();
+    let input_size = request.payload.len();
+    metrics().processor_payload(processor_id, DIRECTION_INPUT, input_size);
+    let max_attempts = policy.max_attempts();
+    let mut request = Some(request);
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        // Only clone the request when another attempt may still need it
+        let attempt_request = if attempts < max_attempts {
+            request.clone().unwrap_or_default()
+        } else {
+            request.take().unwrap_or_default()
+        };
+
+        recorder.emit(ExecutionEventKind::ProcessorStarted {
+            processor_id: processor_id.to_string(),
+            attempt: attempts,
+            input_size,
+        });
+
+        // Each attempt runs in its own span, parented to the DAG execution span, so trace
+        // exports get one slice per attempt within the execution's trace
+        let span = recorder.in_span(|| {
+            ProcessorExecutionStarted {
+                processor_id,
+                input_size,
+            }
+            .span(PROCESSOR_INVOCATION_SPAN)
+        });
+        let attempt = processor.process(attempt_request).instrument(span);
+
+        let response = match policy.timeout {
+            Some(timeout) => tokio::time::timeout(timeout, attempt).await.ok(),
+            None => Some(attempt.await),
+        };
+
+        let (status, error) = match &response {
+            Some(response) => match &response.outcome {
+                Some(Outcome::NextPayload(_)) => (ProcessorStatus::Succeeded, None),
+                Some(Outcome::Error(error_detail)) => (
+                    ProcessorStatus::Failed,
+                    Some(ExecutionError::ProcessorFailed {
+                        processor_id: processor_id.to_string(),
+                        error: error_detail.message.clone(),
+                    }),
+                ),
+                None => (
+                    ProcessorStatus::Failed,
+                    Some(ExecutionError::ProcessorFailed {
+                        processor_id: processor_id.to_string(),
+                        error: "Processor returned no outcome".to_string(),
+                    }),
+                ),
+            },
+            None => (
+                ProcessorStatus::TimedOut,
+                Some(ExecutionError::Timeout {
+                    processor_id: processor_id.to_string(),
+                    timeout_duration: policy.timeout.unwrap_or_default(),
+                }),
+            ),
+        };
+
+        match &response {
+            Some(response) => match &response.outcome {
+                Some(Outcome::NextPayload(payload)) => {
+                    metrics().processor_payload(processor_id, DIRECTION_OUTPUT, payload.len())
+                }
+                Some(Outcome::Error(error_detail)) => {
+                    metrics().processor_failed(processor_id, &error_detail.code.to_string())
+                }
+                None => metrics().processor_failed(processor_id, "no_outcome"),
+            },
+            None => metrics().processor_failed(processor_id, "timeout"),
+        }
+
+        if status == ProcessorStatus::Succeeded || attempts >= max_attempts {
+            metrics().processor_finished(
+                processor_id,
+                &status.to_string(),
+                invocation_start.elapsed(),
+            );
+            return ProcessorReport {
+                status,
+                attempts,
+                started_at: Some(invocation_start.duration_since(recorder.execution_start())),
+                duration: invocation_start.elapsed(),
+                response,
+                error,
+            };
+        }
+
+        if let Some(error) = error {
+            recorder.emit(ExecutionEventKind::ProcessorRetried {
+                processor_id: processor_id.to_string(),
+                failed_attempt: attempts,
+                error,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::stub::{FailingProcessor, StubProcessor};
+    use crate::proto::processor_v1::ProcessorResponse;
+    use crate::traits::processor::ProcessorIntent;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct SlowProcessor;
+
+    #[async_trait]
+    impl Processor for SlowProcessor {
+        async fn process(&self, req: ProcessorRequest) -> ProcessorResponse {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            ProcessorResponse {
+                outcome: Some(Outcome::NextPayload(req.payload)),
+                metadata: None,
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        fn declared_intent(&self) -> ProcessorIntent {
+            ProcessorIntent::Transform
+        }
+    }
+
+    /// Fails until it has been called `succeed_on` times
+    struct FlakyProcessor {
+        calls: AtomicU32,
+        succeed_on: u32,
+    }
+
+    #[async_trait]
+    impl Processor for FlakyProcessor {
+        async fn process(&self, req: ProcessorRequest) -> ProcessorResponse {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call >= self.succeed_on {
+                ProcessorResponse {
+                    outcome: Some(Outcome::NextPayload(req.payload)),
+                    metadata: None,
+                }
+            } else {
+                ProcessorResponse {
+                    outcome: None,
+                    metadata: None,
+                }
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        fn declared_intent(&self) -> ProcessorIntent {
+            ProcessorIntent::Transform
+        }
+    }
+
+    fn recorder() -> ExecutionRecorder {
+        ExecutionRecorder::new(Instant::now(), "Test")
+    }
+
+    fn request() -> ProcessorRequest {
+        ProcessorRequest {
+            payload: b"input".to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_policy_from_options() {
+        let options = ExecutorOptions {
+            max_concurrency: None,
+            timeout_seconds: Some(5),
+            retry_attempts: Some(2),
+            batch_size: None,
+        };
+
+        let policy = InvocationPolicy::from_options(&options);
+        assert_eq!(policy.timeout, Some(Duration::from_secs(5)));
+        assert_eq!(policy.max_attempts(), 3);
+        assert_eq!(InvocationPolicy::default().max_attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_successful_invocation() {
+        let processor: Arc<dyn Processor> = Arc::new(StubProcessor::new("stub".to_string()));
+
+        let report = invoke_processor(
+            "stub",
+            &processor,
+            request(),
+            InvocationPolicy::default(),
+            &recorder(),
+        )
+        .await;
+
+        assert_eq!(report.status, ProcessorStatus::Succeeded);
+        assert_eq!(report.attempts, 1);
+        assert!(report.started_at.is_some());
+        assert!(report.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_invocation_uses_all_attempts() {
+        let processor: Arc<dyn Processor> = Arc::new(FailingProcessor::new("failing".to_string()));
+        let policy = InvocationPolicy {
+            timeout: None,
+            retry_attempts: 2,
+        };
+
+        let report = invoke_processor("failing", &processor, request(), policy, &recorder()).await;
+
+        assert_eq!(report.status, ProcessorStatus::Failed);
+        assert_eq!(report.attempts, 3);
+        assert!(matches!(
+            report.error,
+            Some(ExecutionError::ProcessorFailed { ref processor_id, .. }) if processor_id == "failing"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_flaky_processor() {
+        let processor: Arc<dyn Processor> = Arc::new(FlakyProcessor {
+            calls: AtomicU32::new(0),
+            succeed_on: 2,
+        });
+        let policy = InvocationPolicy {
+            timeout: None,
+            retry_attempts: 3,
+        };
+
+        let report = invoke_processor("flaky", &processor, request(), policy, &recorder()).await;
+
+        assert_eq!(report.status, ProcessorStatus::Succeeded);
+        assert_eq!(report.attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_retries_emit_events() {
+        use crate::engine::events::{BroadcastObserver, ExecutionEventKind};
+
+        let processor: Arc<dyn Processor> = Arc::new(FlakyProcessor {
+            calls: AtomicU32::new(0),
+            succeed_on: 2,
+        });
+        let policy = InvocationPolicy {
+            timeout: None,
+            retry_attempts: 1,
+        };
+        let observer = Arc::new(BroadcastObserver::new(8));
+        let mut events = observer.subscribe();
+        let recorder = ExecutionRecorder::new(Instant::now(), "Test").with_observer(observer);
+
+        invoke_processor("flaky", &processor, request(), policy, &recorder).await;
+
+        let mut kinds = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            kinds.push(event.kind);
+        }
+        assert!(matches!(
+            kinds.as_slice(),
+            [
+                ExecutionEventKind::ProcessorStarted {
+                    attempt: 1,
+                    input_size: 5,
+                    ..
+                },
+                ExecutionEventKind::ProcessorRetried {
+                    failed_attempt: 1,
+                    ..
+                },
+                ExecutionEventKind::ProcessorStarted { attempt: 2, .. },
+            ]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invocation_records_metrics() {
+        let processor: Arc<dyn Processor> = Arc::new(FailingProcessor::new("failing".to_string()));
+        let policy = InvocationPolicy {
+            timeout: None,
+            retry_attempts: 1,
+        };
+
+        // The registry is process-wide, so use a processor ID no other test records under
+        invoke_processor(
+            "metrics_failing",
+            &processor,
+            request(),
+            policy,
+            &recorder(),
+        )
+        .await;
+
+        let text = metrics().encode();
+        assert!(text.contains(
+            "dagwood_processor_failures_total{code=\"500\",processor=\"metrics_failing\"} 2"
+        ));
+        assert!(text.contains(
+            "dagwood_processor_executions_total{processor=\"metrics_failing\",status=\"failed\"} 1"
+        ));
+        assert!(text.contains(
+            "dagwood_processor_payload_bytes_sum{direction=\"input\",processor=\"metrics_failing\"} 5"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_invocation() {
+        let processor: Arc<dyn Processor> = Arc::new(SlowProcessor);
+        let policy = InvocationPolicy {
+            timeout: Some(Duration::from_millis(10)),
+            retry_attempts: 0,
+        };
+
+        let report = invoke_processor("slow", &processor, request(), policy, &recorder()).await;
+
+        assert_eq!(report.status, ProcessorStatus::TimedOut);
+        assert!(report.response.is_none());
+        assert!(matches!(report.error, Some(ExecutionError::Timeout { .. })));
+    }
+}
@@ -1,16 +1,29 @@
 // Copyright (c) 2025 Steve Wagner (ciroque@live.com)
 // SPDX-License-Identifier: MIT
 
+pub mod events;
+pub mod execution_plan;
+pub mod execution_report;
+pub mod explain;
 pub mod factory;
+pub mod graph_export;
 #[cfg(test)]
 pub mod integration_tests;
+pub mod invocation;
 pub mod level_by_level;
 pub mod pipeline_metadata;
 pub mod priority_work_queue;
 pub mod reactive;
+pub mod tasks;
 pub mod work_queue;
 
+pub use events::{BroadcastObserver, ExecutionEvent, ExecutionEventKind, NoopObserver};
+pub use execution_plan::ExecutionPlan;
+pub use execution_report::{ExecutionReport, PipelineOutput, ProcessorReport, ProcessorStatus};
+pub use explain::{PayloadSource, PlanExplanation};
 pub use factory::ExecutorFactory;
+pub use graph_export::{GraphEdge, GraphExport, GraphNode};
+pub use invocation::InvocationPolicy;
 pub use level_by_level::LevelByLevelExecutor;
 pub use reactive::ReactiveExecutor;
 pub use work_queue::WorkQueueExecutor;
@@ -151,17 +151,23 @@
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, Mutex};
 use tokio_util::sync::CancellationToken;
-
-use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
+use tracing::Instrument;
+
+use crate::engine::execution_plan::ExecutionPlan;
+use crate::engine::execution_report::{
+    new_run_id, ExecutionRecorder, ExecutionReport, ProcessorReport, ProcessorStatus,
+};
+use crate::engine::invocation::{invoke_processor, InvocationPolicy};
+use crate::engine::tasks::{cancelled, ProcessorTasks};
 use crate::errors::{ExecutionError, FailureStrategy};
 use crate::observability::messages::{engine::*, StructuredLog};
 use crate::proto::processor_v1::processor_response::Outcome;
-use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest, ProcessorResponse};
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
 use crate::traits::executor::DagExecutor;
-use crate::traits::processor::ProcessorIntent;
+use crate::traits::ExecutionObserver;
 
 /// Reactive/Event-Driven executor that uses async channels for processor communication.
 ///
@@ -244,6 +250,9 @@ pub struct ReactiveExecutor {
     /// will wait for permits before executing, but the event-driven notification
     /// system continues to operate without blocking.
     max_concurrency: usize,
+
+    /// Timeout and retry policy applied to every processor invocation.
+    invocation_policy: InvocationPolicy,
 }
 
 /// Event sent between processors in the reactive execution network
@@ -252,6 +261,7 @@ enum ProcessorEvent {
     /// Notification that a dependency has completed
     DependencyCompleted {
         dependency_id: String,
+        succeeded: bool,
         metadata: Option<PipelineMetadata>,
     },
     /// Initial trigger for entry point processors
@@ -266,6 +276,8 @@ struct ProcessorNode {
     dependents: Vec<String>,
     /// Number of dependencies this processor is waiting for
     pending_dependencies: usize,
+    /// First dependency that completed without succeeding, if any
+    failed_dependency: Option<String>,
 }
 
 impl ReactiveExecutor {
@@ -305,9 +317,16 @@ impl ReactiveExecutor {
     pub fn new(max_concurrency: usize) -> Self {
         Self {
             max_concurrency: max_concurrency.max(1), // Ensure at least 1
+            invocation_policy: InvocationPolicy::default(),
         }
     }
 
+    /// Apply a timeout and retry policy to every processor invocation.
+    pub fn with_invocation_policy(mut self, invocation_policy: InvocationPolicy) -> Self {
+        self.invocation_policy = invocation_policy;
+        self
+    }
+
     /// Creates a new Reactive executor with default concurrency based on system capabilities.
     ///
     /// The default concurrency is set to the number of available CPU cores, which provides
@@ -339,13 +358,13 @@ impl ReactiveExecutor {
         Self::new(concurrency)
     }
 
-    /// Build the event-driven notification network using DependencyGraph methods
+    /// Build the event-driven notification network from a compiled execution plan
     ///
-    /// This uses the forward graph (graph.0) for dependents and build_dependency_counts()
-    /// for initial pending dependency counts.
+    /// This uses the plan's forward graph for dependents and its pre-computed dependency
+    /// counts for initial pending dependency counts.
     fn build_notification_network(
         &self,
-        graph: &DependencyGraph,
+        plan: &ExecutionPlan,
     ) -> Result<
         (
             HashMap<String, mpsc::UnboundedSender<ProcessorEvent>>,
@@ -354,17 +373,17 @@ impl ReactiveExecutor {
         ExecutionError,
     > {
         // Get dependency counts for initial pending dependencies
-        let dependency_counts = graph.build_dependency_counts();
+        let dependency_counts = plan.dependency_counts();
 
         let mut senders = HashMap::new();
         let mut nodes = HashMap::new();
 
         // Create channels for each processor
-        for processor_id in graph.keys() {
+        for processor_id in plan.graph().keys() {
             let (sender, receiver) = mpsc::unbounded_channel();
 
-            // Use the forward graph (graph.0) to get dependents for notification network
-            let dependents = graph.0.get(processor_id).cloned().unwrap_or_default();
+            // Use the forward graph to get dependents for notification network
+            let dependents = plan.dependents(processor_id).to_vec();
 
             let pending_dependencies = dependency_counts.get(processor_id).copied().unwrap_or(0);
 
@@ -375,6 +394,7 @@ impl ReactiveExecutor {
                     receiver,
                     dependents,
                     pending_dependencies,
+                    failed_dependency: None,
                 },
             );
         }
@@ -385,12 +405,16 @@ impl ReactiveExecutor {
     /// Handle a dependency completion event
     fn handle_dependency_completed(
         node: &mut ProcessorNode,
-        _dependency_id: String,
+        dependency_id: String,
+        succeeded: bool,
         _metadata: Option<PipelineMetadata>,
     ) {
-        // Simply decrement pending dependencies count
+        // Decrement pending dependencies count, remembering the first unsuccessful dependency
         // Metadata is collected globally via pipeline_metadata_mutex
-        node.pending_dependencies -= 1;
+        node.pending_dependencies = node.pending_dependencies.saturating_sub(1);
+        if !succeeded && node.failed_dependency.is_none() {
+            node.failed_dependency = Some(dependency_id);
+        }
     }
 
     /// Handle an execute event for entry point processors
@@ -417,9 +441,10 @@ impl ReactiveExecutor {
         match event {
             ProcessorEvent::DependencyCompleted {
                 dependency_id,
+                succeeded,
                 metadata,
             } => {
-                Self::handle_dependency_completed(node, dependency_id, metadata);
+                Self::handle_dependency_completed(node, dependency_id, succeeded, metadata);
                 Ok(false) // Continue waiting for more dependencies
             }
             ProcessorEvent::Execute { metadata } => {
@@ -460,25 +485,59 @@ impl ReactiveExecutor {
         Ok(node)
     }
 
+    /// Notify all dependents that this processor has finished
+    ///
+    /// A send error means the dependent's channel is closed - the dependent processor was
+    /// likely cancelled or failed. This is expected during cancellation scenarios, so it is
+    /// not treated as an error.
+    fn notify_dependents(
+        senders: &HashMap<String, mpsc::UnboundedSender<ProcessorEvent>>,
+        dependents: &[String],
+        processor_id: &str,
+        succeeded: bool,
+        metadata: Option<PipelineMetadata>,
+    ) {
+        for dependent_id in dependents {
+            if let Some(sender) = senders.get(dependent_id) {
+                let _ = sender.send(ProcessorEvent::DependencyCompleted {
+                    dependency_id: processor_id.to_string(),
+                    succeeded,
+                    metadata: metadata.clone(),
+                });
+            }
+        }
+    }
+
     /// Spawn an async task for a processor in the reactive network
     ///
     /// This reuses the canonical payload architecture, declared_intent() pattern,
     /// and metadata collection from the existing executors to maintain consistency.
+    /// The processor outcome is recorded in the recorder; dependents of a processor that
+    /// did not succeed are notified of the failure and record themselves as blocked.
     async fn spawn_processor_task(
         processor_id: String,
         node: ProcessorNode,
-        processors: Arc<ProcessorMap>,
+        plan: Arc<ExecutionPlan>,
         canonical_payload_mutex: Arc<Mutex<Vec<u8>>>,
-        results_mutex: Arc<Mutex<HashMap<String, ProcessorResponse>>>,
+        recorder: Arc<ExecutionRecorder>,
         pipeline_metadata_mutex: Arc<Mutex<PipelineMetadata>>,
         senders: Arc<HashMap<String, mpsc::UnboundedSender<ProcessorEvent>>>,
         failure_strategy: FailureStrategy,
+        invocation_policy: InvocationPolicy,
         semaphore: Arc<tokio::sync::Semaphore>,
         cancellation_token: CancellationToken,
     ) -> Result<(), ExecutionError> {
         // Wait for all dependencies to complete
         let node = Self::wait_for_dependencies(node, &processor_id, &cancellation_token).await?;
 
+        // A dependency did not succeed - block this processor and propagate downstream
+        if let Some(failed_dependency) = &node.failed_dependency {
+            recorder.block(&processor_id, failed_dependency);
+            Self::notify_dependents(&senders, &node.dependents, &processor_id, false, None);
+            return Ok(());
+        }
+        recorder.scheduled(&processor_id);
+
         // Acquire semaphore permit for concurrency control
         let _permit = semaphore
             .acquire()
@@ -502,7 +561,8 @@ impl ReactiveExecutor {
         }
 
         // Get processor instance
-        let processor = processors
+        let processor = plan
+            .processors()
             .get(&processor_id)
             .ok_or_else(|| ExecutionError::ProcessorNotFound(processor_id.clone()))?;
 
@@ -517,144 +577,60 @@ impl ReactiveExecutor {
             payload: canonical_payload, // All processors get canonical payload
         };
 
-        // Execute processor
-        let processor_response = processor.process(processor_input).await;
-
-        // Handle processor execution result based on failure strategy
-        match &processor_response.outcome {
-            Some(Outcome::NextPayload(_)) => {
-                // Success case - update canonical payload if this is a Transform processor
-                // CRITICAL: Update canonical payload BEFORE notifying dependents to prevent race conditions
-                if processor.declared_intent() == ProcessorIntent::Transform {
-                    if let Some(Outcome::NextPayload(new_payload)) = &processor_response.outcome {
-                        // Update canonical payload and release lock immediately to reduce contention
-                        {
-                            let mut canonical_guard = canonical_payload_mutex.lock().await;
-                            *canonical_guard = new_payload.clone();
-                        } // canonical_guard dropped here - minimizes lock hold time
-
-                        // Store successful result (without holding canonical lock)
-                        {
-                            let mut results_guard = results_mutex.lock().await;
-                            results_guard.insert(processor_id.clone(), processor_response.clone());
-                        }
+        // Execute processor with timeout and retry handling
+        let report = invoke_processor(
+            &processor_id,
+            processor,
+            processor_input,
+            invocation_policy,
+            &recorder,
+        )
+        .await;
+
+        if report.status == ProcessorStatus::Succeeded {
+            let processor_response = report.response.clone().unwrap_or_default();
+
+            // Success case - update canonical payload if this is a Transform processor
+            // CRITICAL: Update canonical payload BEFORE notifying dependents to prevent race conditions
+            if plan.is_transform(&processor_id) {
+                if let Some(Outcome::NextPayload(new_payload)) = &processor_response.outcome {
+                    // Update canonical payload and release lock immediately to reduce contention
+                    let mut canonical_guard = canonical_payload_mutex.lock().await;
+                    *canonical_guard = new_payload.clone();
+                    recorder.payload_updated(&processor_id, new_payload.len());
+                } // canonical_guard dropped here - minimizes lock hold time
+            }
 
-                        // Collect metadata from processor response (without holding canonical lock)
-                        {
-                            let mut pipeline_meta = pipeline_metadata_mutex.lock().await;
-                            pipeline_meta
-                                .merge_processor_response(&processor_id, &processor_response);
-                        }
+            // Store successful result (without holding canonical lock)
+            recorder.record(&processor_id, report);
 
-                        // Notify all dependents (canonical payload already updated)
-                        for dependent_id in &node.dependents {
-                            if let Some(sender) = senders.get(dependent_id) {
-                                if let Err(_) = sender.send(ProcessorEvent::DependencyCompleted {
-                                    dependency_id: processor_id.clone(),
-                                    metadata: processor_response.metadata.clone(),
-                                }) {
-                                    // Channel closed - dependent processor likely cancelled or failed
-                                    // This is expected during cancellation scenarios, so we continue
-                                    // without treating it as an error
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    // Non-Transform processor - no canonical payload update needed
-                    // Store successful result
-                    {
-                        let mut results_guard = results_mutex.lock().await;
-                        results_guard.insert(processor_id.clone(), processor_response.clone());
-                    }
+            // Collect metadata from processor response (without holding canonical lock)
+            {
+                let mut pipeline_meta = pipeline_metadata_mutex.lock().await;
+                pipeline_meta.merge_processor_response(&processor_id, &processor_response);
+            }
 
-                    // Collect metadata from processor response
-                    {
-                        let mut pipeline_meta = pipeline_metadata_mutex.lock().await;
-                        pipeline_meta.merge_processor_response(&processor_id, &processor_response);
-                    }
+            // Notify all dependents (event-driven core; canonical payload already updated)
+            Self::notify_dependents(
+                &senders,
+                &node.dependents,
+                &processor_id,
+                true,
+                processor_response.metadata,
+            );
+        } else {
+            // Processor failed or timed out - record it and apply failure strategy
+            recorder.record(&processor_id, report);
 
-                    // Notify all dependents (event-driven core)
-                    for dependent_id in &node.dependents {
-                        if let Some(sender) = senders.get(dependent_id) {
-                            if let Err(_) = sender.send(ProcessorEvent::DependencyCompleted {
-                                dependency_id: processor_id.clone(),
-                                metadata: processor_response.metadata.clone(),
-                            }) {
-                                // Channel closed - dependent processor likely cancelled or failed
-                                // This is expected during cancellation scenarios, so we continue
-                                // without treating it as an error
-                            }
-                        }
-                    }
-                }
-            }
-            Some(Outcome::Error(error_detail)) => {
-                // Processor failed - apply failure strategy
-                match failure_strategy {
-                    FailureStrategy::FailFast => {
-                        // Cancel all other tasks before failing
-                        cancellation_token.cancel();
-                        // Fail immediately on first error
-                        return Err(ExecutionError::ProcessorFailed {
-                            processor_id: processor_id.clone(),
-                            error: error_detail.message.clone(),
-                        });
-                    }
-                    FailureStrategy::ContinueOnError | FailureStrategy::BestEffort => {
-                        // Store failed result but notify dependents with empty metadata
-                        let mut results_guard = results_mutex.lock().await;
-                        results_guard.insert(processor_id.clone(), processor_response.clone());
-
-                        // Notify dependents with None metadata to maintain dependency counting
-                        // This prevents cascade failures while allowing execution to continue
-                        for dependent_id in &node.dependents {
-                            if let Some(sender) = senders.get(dependent_id) {
-                                let _ = sender.send(ProcessorEvent::DependencyCompleted {
-                                    dependency_id: processor_id.clone(),
-                                    metadata: None, // Failed processor contributes no metadata
-                                });
-                            }
-                        }
-                    }
+            match failure_strategy {
+                FailureStrategy::FailFast => {
+                    // Cancel all other tasks; processors that have not started are skipped
+                    cancellation_token.cancel();
                 }
-            }
-            None => {
-                // Processor returned no outcome - treat as error
-                let error_msg = "Processor returned no outcome".to_string();
-                match failure_strategy {
-                    FailureStrategy::FailFast => {
-                        // Cancel all other tasks before failing
-                        cancellation_token.cancel();
-                        return Err(ExecutionError::ProcessorFailed {
-                            processor_id: processor_id.clone(),
-                            error: error_msg.clone(),
-                        });
-                    }
-                    FailureStrategy::ContinueOnError | FailureStrategy::BestEffort => {
-                        // Store error result but notify dependents with empty metadata
-                        let error_response = ProcessorResponse {
-                            outcome: Some(Outcome::Error(
-                                crate::proto::processor_v1::ErrorDetail {
-                                    code: 500,
-                                    message: error_msg,
-                                },
-                            )),
-                            metadata: None,
-                        };
-                        let mut results_guard = results_mutex.lock().await;
-                        results_guard.insert(processor_id.clone(), error_response);
-
-                        // Notify dependents with None metadata to maintain dependency counting
-                        for dependent_id in &node.dependents {
-                            if let Some(sender) = senders.get(dependent_id) {
-                                let _ = sender.send(ProcessorEvent::DependencyCompleted {
-                                    dependency_id: processor_id.clone(),
-                                    metadata: None, // Failed processor contributes no metadata
-                                });
-                            }
-                        }
-                    }
+                FailureStrategy::ContinueOnError | FailureStrategy::BestEffort => {
+                    // Notify dependents of the failure to maintain dependency counting
+                    // This prevents deadlock while blocking the failed branch
+                    Self::notify_dependents(&senders, &node.dependents, &processor_id, false, None);
                 }
             }
         }
@@ -665,18 +641,20 @@ impl ReactiveExecutor {
 
 #[async_trait]
 impl DagExecutor for ReactiveExecutor {
-    async fn execute_with_strategy(
+    async fn execute_plan_cancellable(
         &self,
-        processors: ProcessorMap,
-        graph: DependencyGraph,
-        entrypoints: EntryPoints,
+        plan: Arc<ExecutionPlan>,
         input: ProcessorRequest,
         pipeline_metadata: PipelineMetadata,
         failure_strategy: FailureStrategy,
-    ) -> Result<(HashMap<String, ProcessorResponse>, PipelineMetadata), ExecutionError> {
+        observer: Arc<dyn ExecutionObserver>,
+        cancellation: CancellationToken,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        let run_id = new_run_id();
         let start_msg = ExecutionStarted {
+            run_id: &run_id,
             strategy: "Reactive",
-            processor_count: processors.len(),
+            processor_count: plan.processor_count(),
             max_concurrency: self.max_concurrency,
         };
 
@@ -685,49 +663,56 @@ impl DagExecutor for ReactiveExecutor {
         start_msg.log();
 
         let execution_start = Instant::now();
-        let processor_count = processors.len();
 
-        // Validate dependency graph (reuse existing validation)
-        let (_dependency_counts, _topological_ranks) = graph.dependency_counts_and_ranks()
-            .ok_or_else(|| ExecutionError::InternalError {
-                message: "Internal consistency error: dependency graph contains cycles (should have been caught during config validation)".into(),
-            })?;
-
-        // Build notification network using corrected approach
-        let (senders, mut nodes) = self.build_notification_network(&graph)?;
+        // Build notification network from the compiled plan (cycles were rejected at compile time)
+        let (senders, mut nodes) = self.build_notification_network(&plan)?;
 
         // Initialize canonical payload with input payload
         let canonical_payload_mutex = Arc::new(Mutex::new(input.payload.clone()));
-        let results_mutex = Arc::new(Mutex::new(HashMap::new()));
+        let recorder = Arc::new(
+            ExecutionRecorder::new(execution_start, "Reactive")
+                .with_run_id(run_id.clone())
+                .with_observer(observer)
+                .with_span(span.clone()),
+        );
+        recorder.started(&plan);
         let pipeline_metadata_mutex = Arc::new(Mutex::new(pipeline_metadata));
         let senders_arc = Arc::new(senders);
-        let processors_arc = Arc::new(processors);
         let semaphore = Arc::new(tokio::sync::Semaphore::new(self.max_concurrency));
         let cancellation_token = CancellationToken::new();
 
-        // Spawn tasks for all processors
-        let mut tasks = Vec::new();
+        // Spawn tasks for all processors; they are aborted if the execution is cancelled or
+        // dropped
+        let mut tasks = ProcessorTasks::new();
+        let mut dependents = HashMap::new();
         for (processor_id, node) in nodes.drain() {
-            let dependents = node.dependents.clone(); // Store dependents for panic recovery
-            let task = tokio::spawn(Self::spawn_processor_task(
-                processor_id.clone(),
-                node,
-                processors_arc.clone(),
-                canonical_payload_mutex.clone(),
-                results_mutex.clone(),
-                pipeline_metadata_mutex.clone(),
-                senders_arc.clone(),
-                failure_strategy,
-                semaphore.clone(),
-                cancellation_token.clone(),
-            ));
-            tasks.push((task, processor_id, dependents));
+            dependents.insert(processor_id.clone(), node.dependents.clone()); // For panic recovery
+            tasks.spawn(
+                &processor_id,
+                Self::spawn_processor_task(
+                    processor_id.clone(),
+                    node,
+                    plan.clone(),
+                    canonical_payload_mutex.clone(),
+                    recorder.clone(),
+                    pipeline_metadata_mutex.clone(),
+                    senders_arc.clone(),
+                    failure_strategy,
+                    self.invocation_policy,
+                    semaphore.clone(),
+                    cancellation_token.clone(),
+                )
+                .instrument(span.clone()),
+            );
         }
 
         // Trigger entry point processors
-        for entrypoint in entrypoints.iter() {
+        for entrypoint in plan.entrypoints().iter() {
             if let Some(sender) = senders_arc.get(entrypoint) {
-                if let Err(_) = sender.send(ProcessorEvent::Execute { metadata: None }) {
+                if sender
+                    .send(ProcessorEvent::Execute { metadata: None })
+                    .is_err()
+                {
                     // Entry point processor channel closed - this indicates a serious issue
                     // since entry points should be ready to receive at startup
                     return Err(ExecutionError::InternalError {
@@ -740,41 +725,34 @@ impl DagExecutor for ReactiveExecutor {
             }
         }
 
-        // Wait for all tasks to complete
-        let mut processor_error = None;
-        let mut other_errors = Vec::new();
+        // Wait for all tasks to complete, unless the execution is cancelled
+        let mut internal_errors = Vec::new();
 
-        for (task, processor_id, dependents) in tasks.into_iter() {
-            match task.await {
+        loop {
+            let joined = tokio::select! {
+                joined = tasks.join_next() => joined,
+                () = cancellation.cancelled() => {
+                    tasks.cancel().await;
+                    return Err(cancelled());
+                }
+            };
+            let Some((processor_id, result)) = joined else {
+                break;
+            };
+            match result {
                 Ok(Ok(())) => {
-                    // Task completed successfully
+                    // Task completed; its outcome is in the recorder
                 }
                 Ok(Err(e)) => {
-                    match &e {
-                        ExecutionError::ProcessorFailed { .. } => {
-                            // Collect all processor failures for comprehensive error reporting
-                            if processor_error.is_none() {
-                                processor_error = Some(e); // Keep first failure as primary
-                            } else {
-                                other_errors.push(e); // Collect additional failures
-                            }
-                        }
-                        _ => {
-                            // Collect other errors (like cancellation) as fallback
-                            other_errors.push(e);
-                        }
+                    // Errors after cancellation only mean the processor was skipped
+                    if !cancellation_token.is_cancelled() {
+                        internal_errors.push(e);
                     }
                 }
                 Err(join_error) => {
                     // Task panicked or was cancelled - notify dependents to prevent deadlock
-                    for dependent_id in &dependents {
-                        if let Some(sender) = senders_arc.get(dependent_id) {
-                            let _ = sender.send(ProcessorEvent::DependencyCompleted {
-                                dependency_id: processor_id.clone(),
-                                metadata: None, // Panicked processor contributes no metadata
-                            });
-                        }
-                    }
+                    let dependents = dependents.remove(&processor_id).unwrap_or_default();
+                    Self::notify_dependents(&senders_arc, &dependents, &processor_id, false, None);
 
                     // Determine if this was a panic or cancellation
                     let error_message = if join_error.is_panic() {
@@ -783,45 +761,41 @@ impl DagExecutor for ReactiveExecutor {
                         format!("Processor '{}' task was cancelled", processor_id)
                     };
 
-                    other_errors.push(ExecutionError::InternalError {
-                        message: error_message,
-                    });
+                    recorder.record(
+                        &processor_id,
+                        ProcessorReport {
+                            status: ProcessorStatus::Failed,
+                            attempts: 1,
+                            started_at: None,
+                            duration: Duration::ZERO,
+                            response: None,
+                            error: Some(ExecutionError::InternalError {
+                                message: error_message,
+                            }),
+                        },
+                    );
                 }
             }
         }
 
-        // Return processor error first, then any other error, prioritizing actual failures
-        if let Some(error) = processor_error {
-            return Err(error);
-        } else if let Some(error) = other_errors.into_iter().next() {
+        // Executor-level problems (not processor failures) abort the execution
+        if let Some(error) = internal_errors.into_iter().next() {
             return Err(error);
         }
 
-        // Return final results
-        let final_results = Arc::try_unwrap(results_mutex)
-            .map_err(|_| ExecutionError::InternalError {
-                message: "Failed to unwrap results Arc - multiple references still exist".into(),
-            })?
-            .into_inner();
-
-        // Return collected metadata from processor responses
-        let final_metadata = Arc::try_unwrap(pipeline_metadata_mutex)
-            .map_err(|_| ExecutionError::InternalError {
-                message: "Failed to unwrap pipeline metadata Arc - multiple references still exist"
-                    .into(),
-            })?
-            .into_inner();
-
-        // Log successful completion
-        let execution_duration = execution_start.elapsed();
+        let final_output = canonical_payload_mutex.lock().await.clone();
+        let final_metadata = pipeline_metadata_mutex.lock().await.clone();
+        let report = recorder.build_report(&plan, failure_strategy, final_output, final_metadata);
+
+        // Log completion
         ExecutionCompleted {
             strategy: "Reactive",
-            processor_count,
-            duration: execution_duration,
+            processor_count: plan.processor_count(),
+            duration: report.duration,
         }
         .log();
 
-        Ok((final_results, final_metadata))
+        Ok(report)
     }
 }
 
@@ -829,6 +803,7 @@ impl DagExecutor for ReactiveExecutor {
 mod tests {
     use super::*;
     use crate::backends::stub::StubProcessor;
+    use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
     use crate::traits::Processor;
     use std::collections::HashMap;
 
@@ -872,7 +847,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_ok());
         let (responses, _metadata) = result.unwrap();
@@ -918,7 +894,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_ok());
         let (responses, _metadata) = result.unwrap();
@@ -974,7 +951,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_ok());
         let (responses, _metadata) = result.unwrap();
@@ -1025,7 +1003,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // Should fail fast on first processor failure
         assert!(result.is_err());
@@ -1071,7 +1050,7 @@ mod tests {
             payload: b"test input".to_vec(),
         };
 
-        let result = executor
+        let report = executor
             .execute_with_strategy(
                 ProcessorMap(processor_map),
                 DependencyGraph(dependency_graph),
@@ -1080,24 +1059,29 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::ContinueOnError,
             )
-            .await;
+            .await
+            .expect("processor failures are reported, not returned as errors");
 
         // Should continue execution despite failure
-        assert!(result.is_ok());
-        let (responses, _metadata) = result.unwrap();
+        assert_eq!(report.status("entry"), Some(ProcessorStatus::Succeeded));
+        assert_eq!(report.status("failing"), Some(ProcessorStatus::Failed));
+        assert_eq!(
+            report.status("independent"),
+            Some(ProcessorStatus::Succeeded)
+        );
 
-        // Should have results for entry and independent, plus failed result for failing
-        assert_eq!(responses.len(), 3);
+        // Only successful processors contribute results
+        let responses = report.results();
+        assert_eq!(responses.len(), 2);
         assert!(responses.contains_key("entry"));
-        assert!(responses.contains_key("failing"));
         assert!(responses.contains_key("independent"));
 
-        // Verify the failing processor has an error outcome
-        let failing_response = responses.get("failing").unwrap();
-        match &failing_response.outcome {
-            Some(Outcome::Error(_)) => {} // Expected
-            _ => panic!("Expected Error outcome for failing processor"),
-        }
+        // The failure is collected in the report
+        assert_eq!(report.errors.len(), 1);
+        assert!(matches!(
+            report.into_result(),
+            Err(ExecutionError::MultipleFailed { .. })
+        ));
     }
 
     #[tokio::test]
@@ -1132,7 +1116,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // Should succeed for properly configured entry point
         assert!(result.is_ok());
@@ -1172,7 +1157,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -1187,7 +1173,7 @@ mod tests {
         }
 
         // Test ContinueOnError behavior
-        let result = executor
+        let report = executor
             .execute_with_strategy(
                 ProcessorMap(processor_map),
                 DependencyGraph(dependency_graph),
@@ -1196,20 +1182,18 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::ContinueOnError,
             )
-            .await;
-
-        assert!(result.is_ok());
-        let (responses, _metadata) = result.unwrap();
-        assert_eq!(responses.len(), 1);
+            .await
+            .expect("processor failures are reported, not returned as errors");
 
-        let no_outcome_response = responses.get("no_outcome").unwrap();
-        match &no_outcome_response.outcome {
-            Some(Outcome::Error(error_detail)) => {
-                assert_eq!(error_detail.message, "Processor returned no outcome");
-                assert_eq!(error_detail.code, 500);
+        let no_outcome_report = report.processor("no_outcome").unwrap();
+        assert_eq!(no_outcome_report.status, ProcessorStatus::Failed);
+        match &no_outcome_report.error {
+            Some(ExecutionError::ProcessorFailed { error, .. }) => {
+                assert_eq!(error, "Processor returned no outcome");
             }
-            _ => panic!("Expected Error outcome for no outcome processor"),
+            _ => panic!("Expected ProcessorFailed error for no outcome processor"),
         }
+        assert!(report.results().is_empty());
     }
 
     #[tokio::test]
@@ -1244,7 +1228,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // Should succeed - this tests that our error handling improvements
         // don't break normal execution
@@ -1289,7 +1274,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_ok());
         let (responses, _metadata) = result.unwrap();
@@ -1329,7 +1315,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // Should fail due to the failing processor
         assert!(result.is_err());
@@ -1387,7 +1374,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         match result {
             Ok((responses, _metadata)) => {
@@ -1489,7 +1477,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::ContinueOnError, // Continue despite panic
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // The execution might fail due to the panic, but the key test is whether
         // the dependent processor was called (proving panic recovery worked)
@@ -1556,7 +1545,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // Should succeed because our current implementation forces pending_dependencies = 0
         // for entry points, handling the misconfiguration gracefully
@@ -1628,7 +1618,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_ok());
         let (responses, _metadata) = result.unwrap();
@@ -0,0 +1,140 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Processor tasks spawned by an execution.
+//!
+//! Executors run processors in their own tasks through [`ProcessorTasks`], which ties the
+//! tasks to the execution: dropping the execution aborts them, and a cancelled execution
+//! aborts them and waits until they stopped before it returns (see
+//! [`DagExecutor::execute_plan_cancellable`](crate::traits::DagExecutor::execute_plan_cancellable)).
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use tokio::task::{Id, JoinError, JoinSet};
+
+use crate::errors::ExecutionError;
+
+/// The processor tasks of one execution, aborted when dropped.
+pub(crate) struct ProcessorTasks<T> {
+    tasks: JoinSet<T>,
+    processor_ids: HashMap<Id, String>,
+}
+
+impl<T: Send + 'static> ProcessorTasks<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            tasks: JoinSet::new(),
+            processor_ids: HashMap::new(),
+        }
+    }
+
+    /// Spawn the task running `processor_id`.
+    pub(crate) fn spawn(
+        &mut self,
+        processor_id: &str,
+        task: impl Future<Output = T> + Send + 'static,
+    ) {
+        let handle = self.tasks.spawn(task);
+        self.processor_ids
+            .insert(handle.id(), processor_id.to_string());
+    }
+
+    /// Wait for the next task to finish, returning its processor ID and result; `None` once
+    /// every task finished.
+    pub(crate) async fn join_next(&mut self) -> Option<(String, Result<T, JoinError>)> {
+        let joined = self.tasks.join_next_with_id().await?;
+        let id = match &joined {
+            Ok((id, _)) => *id,
+            Err(join_error) => join_error.id(),
+        };
+        let processor_id = self.processor_ids.remove(&id).unwrap_or_default();
+        Some((processor_id, joined.map(|(_, output)| output)))
+    }
+
+    /// Forget the tasks that already finished, without waiting for the others.
+    pub(crate) fn reap_finished(&mut self) {
+        while let Some(joined) = self.tasks.try_join_next_with_id() {
+            let id = match &joined {
+                Ok((id, _)) => *id,
+                Err(join_error) => join_error.id(),
+            };
+            self.processor_ids.remove(&id);
+        }
+    }
+
+    /// Abort every task and wait until all of them stopped.
+    ///
+    /// A processor is stopped at its next `.await`; synchronous work in progress (such as a
+    /// WASM call) runs to completion first.
+    pub(crate) async fn cancel(&mut self) {
+        self.tasks.shutdown().await;
+        self.processor_ids.clear();
+    }
+}
+
+/// Error of an execution stopped through its cancellation token
+pub(crate) fn cancelled() -> ExecutionError {
+    ExecutionError::Cancelled {
+        reason: "execution was cancelled".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    /// Sets its flag when dropped, i.e. when the task holding it stopped
+    struct Stopped(Arc<AtomicBool>);
+
+    impl Drop for Stopped {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_join_next_reports_processor_ids() {
+        let mut tasks = ProcessorTasks::new();
+        tasks.spawn("first", async { 1 });
+        tasks.spawn("second", async { panic!("boom") });
+
+        let mut joined = Vec::new();
+        while let Some((processor_id, result)) = tasks.join_next().await {
+            joined.push((processor_id, result.ok()));
+        }
+        joined.sort();
+        assert_eq!(
+            joined,
+            vec![("first".to_string(), Some(1)), ("second".to_string(), None)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_drop_stop_tasks() {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let mut tasks = ProcessorTasks::new();
+        let guard = Stopped(stopped.clone());
+        tasks.spawn("slow", async move {
+            let _guard = guard;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        tasks.cancel().await;
+        assert!(stopped.load(Ordering::SeqCst));
+        assert!(tasks.join_next().await.is_none());
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        let guard = Stopped(stopped.clone());
+        let mut tasks = ProcessorTasks::new();
+        tasks.spawn("slow", async move {
+            let _guard = guard;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        drop(tasks);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(stopped.load(Ordering::SeqCst));
+    }
+}
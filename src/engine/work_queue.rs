@@ -167,20 +167,26 @@
 //! ```
 
 use async_trait::async_trait;
-use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
-
-use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+
+use crate::engine::execution_plan::ExecutionPlan;
+use crate::engine::execution_report::{
+    new_run_id, ExecutionRecorder, ExecutionReport, ProcessorStatus,
+};
+use crate::engine::invocation::{invoke_processor, InvocationPolicy};
+use crate::engine::tasks::{cancelled, ProcessorTasks};
 use crate::errors::{ExecutionError, FailureStrategy};
 use crate::observability::messages::{engine::*, StructuredLog};
 use crate::proto::processor_v1::processor_response::Outcome;
-use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest, ProcessorResponse};
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
 use crate::traits::executor::DagExecutor;
-use crate::traits::processor::ProcessorIntent;
+use crate::traits::ExecutionObserver;
 
-use super::priority_work_queue::{PrioritizedTask, PriorityWorkQueue};
+use super::priority_work_queue::PriorityWorkQueue;
 
 /// Work Queue executor that uses dependency counting and canonical payload tracking.
 ///
@@ -290,6 +296,9 @@ pub struct WorkQueueExecutor {
     /// running processors complete. This prevents resource exhaustion while
     /// maintaining optimal parallelism within the constraint.
     max_concurrency: usize,
+
+    /// Timeout and retry policy applied to every processor invocation.
+    invocation_policy: InvocationPolicy,
 }
 
 impl WorkQueueExecutor {
@@ -330,9 +339,28 @@ impl WorkQueueExecutor {
     pub fn new(max_concurrency: usize) -> Self {
         Self {
             max_concurrency: max_concurrency.max(1), // Ensure at least 1
+            invocation_policy: InvocationPolicy::default(),
         }
     }
 
+    /// Apply a timeout and retry policy to every processor invocation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use the_dagwood::engine::{InvocationPolicy, WorkQueueExecutor};
+    ///
+    /// let executor = WorkQueueExecutor::new(4).with_invocation_policy(InvocationPolicy {
+    ///     timeout: Some(Duration::from_secs(30)),
+    ///     retry_attempts: 2,
+    /// });
+    /// ```
+    pub fn with_invocation_policy(mut self, invocation_policy: InvocationPolicy) -> Self {
+        self.invocation_policy = invocation_policy;
+        self
+    }
+
     /// Creates a new Work Queue executor with default concurrency based on system capabilities.
     ///
     /// The default concurrency is set to the number of available CPU cores, which provides
@@ -376,7 +404,10 @@ impl WorkQueueExecutor {
 
     /// Find processors that are ready to execute (have no unresolved dependencies)
     #[cfg(test)]
-    fn find_ready_processors(&self, dependency_counts: &HashMap<String, usize>) -> Vec<String> {
+    fn find_ready_processors(
+        &self,
+        dependency_counts: &std::collections::HashMap<String, usize>,
+    ) -> Vec<String> {
         dependency_counts
             .iter()
             .filter_map(|(id, &count)| if count == 0 { Some(id.clone()) } else { None })
@@ -386,18 +417,20 @@ impl WorkQueueExecutor {
 
 #[async_trait]
 impl DagExecutor for WorkQueueExecutor {
-    async fn execute_with_strategy(
+    async fn execute_plan_cancellable(
         &self,
-        processors: ProcessorMap,
-        graph: DependencyGraph,
-        entrypoints: EntryPoints,
+        plan: Arc<ExecutionPlan>,
         input: ProcessorRequest,
         pipeline_metadata: PipelineMetadata,
         failure_strategy: FailureStrategy,
-    ) -> Result<(HashMap<String, ProcessorResponse>, PipelineMetadata), ExecutionError> {
+        observer: Arc<dyn ExecutionObserver>,
+        cancellation: CancellationToken,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        let run_id = new_run_id();
         let start_msg = ExecutionStarted {
+            run_id: &run_id,
             strategy: "WorkQueue",
-            processor_count: processors.len(),
+            processor_count: plan.processor_count(),
             max_concurrency: self.max_concurrency,
         };
 
@@ -407,41 +440,12 @@ impl DagExecutor for WorkQueueExecutor {
 
         let execution_start = Instant::now();
 
-        // === PHASE 1: VALIDATION AND SETUP ===
+        // === PHASE 1: PER-EXECUTION STATE FROM THE COMPILED PLAN ===
 
-        // Validate all processors referenced in the dependency graph actually exist in the registry
-        // This prevents runtime errors when trying to execute non-existent processors
-        for processor_id in graph.keys() {
-            if !processors.contains_key(processor_id) {
-                let error = ExecutionError::ProcessorNotFound(processor_id.clone());
-                ExecutionFailed {
-                    strategy: "WorkQueue",
-                    error: &error,
-                }
-                .log();
-                return Err(error);
-            }
-        }
-
-        // Build reverse dependency map: processor_id -> [processors that depend on it]
-        // This is used to efficiently find which processors become ready when one completes
-        let reverse_dependencies = graph.build_reverse_dependencies();
-
-        // Compute dependency counts (how many dependencies each processor has) and topological ranks
-        // (execution order) together for efficiency. Topological ranks are crucial for the canonical
-        // payload architecture - they determine which Transform processor's payload takes precedence
-        let (dependency_counts, topological_ranks) = graph.dependency_counts_and_ranks()
-            .ok_or_else(|| {
-                let error = ExecutionError::InternalError {
-                    message: "Internal consistency error: dependency graph contains cycles (should have been caught during config validation)".into() 
-                };
-                ExecutionFailed {
-                    strategy: "WorkQueue",
-                    error: &error,
-                }
-                .log();
-                error
-            })?;
+        // Processor existence, reverse dependencies, and topological ranks were validated and
+        // computed when the plan was compiled. Only the remaining dependency counts are mutated
+        // during execution, so each run starts from its own copy.
+        let dependency_counts = plan.dependency_counts().clone();
 
         // === PHASE 2: WORK QUEUE INITIALIZATION ===
 
@@ -453,13 +457,8 @@ impl DagExecutor for WorkQueueExecutor {
 
         // Start with entrypoints (processors with no dependencies)
         // These are prioritized by topological rank to ensure deterministic startup
-        for entrypoint in entrypoints.iter() {
-            let rank = topological_ranks.get(entrypoint).copied().unwrap_or(0);
-            let is_transform = processors
-                .get(entrypoint)
-                .map(|p| p.declared_intent() == ProcessorIntent::Transform)
-                .unwrap_or(false);
-            work_queue.push(PrioritizedTask::new(entrypoint.clone(), rank, is_transform));
+        for entrypoint in plan.entrypoints().iter() {
+            work_queue.push(plan.prioritized_task(entrypoint));
         }
 
         // === PHASE 3: SHARED STATE SETUP FOR CONCURRENT EXECUTION ===
@@ -470,8 +469,14 @@ impl DagExecutor for WorkQueueExecutor {
         // Track active tasks to respect concurrency limits and know when execution is complete
         let active_tasks = Arc::new(Mutex::new(0));
 
-        // Store execution results from completed processors - used for metadata merging
-        let results_mutex = Arc::new(Mutex::new(HashMap::<String, ProcessorResponse>::new()));
+        // Per-processor outcomes (status, timing, attempts, response) for the execution report
+        let recorder = Arc::new(
+            ExecutionRecorder::new(execution_start, "WorkQueue")
+                .with_run_id(run_id.clone())
+                .with_observer(observer)
+                .with_span(span.clone()),
+        );
+        recorder.started(&plan);
 
         // Track remaining dependency counts - decremented as processors complete
         let dependency_counts_mutex = Arc::new(Mutex::new(dependency_counts));
@@ -500,42 +505,40 @@ impl DagExecutor for WorkQueueExecutor {
         // This ensures deterministic behavior: later processors (higher rank) override earlier ones
         let highest_transform_rank_mutex = Arc::new(Mutex::new(None::<usize>));
 
+        // Processor tasks, aborted if the execution is cancelled or dropped
+        let mut tasks = ProcessorTasks::new();
+
         // === PHASE 4: MAIN EXECUTION LOOP ===
         // Process the work queue until all processors are complete
         loop {
+            // A cancelled execution stops its running processors and schedules no more
+            if cancellation.is_cancelled() {
+                tasks.cancel().await;
+                return Err(cancelled());
+            }
+            tasks.reap_finished();
+
             // Determine the next processor to execute (if any)
             // This block acquires multiple locks, so we scope it to release them quickly
-            let next_processor_id = {
+            let (next_processor_id, stop_scheduling) = {
                 let mut queue = work_queue_mutex.lock().await;
                 let active_count = *active_tasks.lock().await;
                 let failed = failed_processors.lock().await;
 
-                // Apply failure strategy to determine if we should continue execution
-                match failure_strategy {
-                    FailureStrategy::FailFast => {
-                        if !failed.is_empty() {
-                            // Return the first failure immediately - no further processing
-                            let first_failed = failed.iter().next().unwrap().clone();
-                            return Err(ExecutionError::ProcessorFailed {
-                                processor_id: first_failed,
-                                error: "Processor execution failed".to_string(),
-                            });
-                        }
-                    }
-                    _ => {
-                        // For ContinueOnError and BestEffort, we continue processing
-                        // but skip blocked processors (handled below)
-                    }
-                }
+                // Apply failure strategy: FailFast stops scheduling new work after the first
+                // failure and only drains processors that are already running.
+                // ContinueOnError and BestEffort keep processing, skipping blocked processors.
+                let stop_scheduling =
+                    failure_strategy == FailureStrategy::FailFast && !failed.is_empty();
 
                 // Check if we can start more tasks (respect concurrency limits) and have work to do
-                if active_count < self.max_concurrency && !queue.is_empty() {
+                if !stop_scheduling && active_count < self.max_concurrency && !queue.is_empty() {
                     // Efficiently find next available processor, skipping any that are blocked
                     // due to failed dependencies
                     let blocked = blocked_processors.lock().await;
-                    queue.pop_next_available(&blocked)
+                    (queue.pop_next_available(&blocked), stop_scheduling)
                 } else {
-                    None // Either at concurrency limit or no work available
+                    (None, stop_scheduling) // Stopped, at concurrency limit, or no work available
                 }
             };
 
@@ -549,8 +552,8 @@ impl DagExecutor for WorkQueueExecutor {
                         *active += 1;
                     }
 
-                    // Get the processor instance - this should always succeed due to earlier validation
-                    let processor = match processors.get(&processor_id) {
+                    // Get the processor instance - this should always succeed due to plan validation
+                    let processor = match plan.processors().get(&processor_id) {
                         Some(p) => p.clone(),
                         None => {
                             return Err(ExecutionError::ProcessorNotFound(processor_id));
@@ -561,53 +564,56 @@ impl DagExecutor for WorkQueueExecutor {
                     // Arc::clone is cheap - it only increments the reference count, doesn't copy data
                     let processor_id_clone = processor_id.clone();
                     let input_clone = input.clone();
-                    let graph_clone = graph.0.clone();
-                    let reverse_dependencies_clone = reverse_dependencies.clone();
+                    let plan_clone = plan.clone();
                     let active_tasks_clone = active_tasks.clone();
-                    let results_mutex_clone = results_mutex.clone();
+                    let recorder_clone = recorder.clone();
                     let dependency_counts_mutex_clone = dependency_counts_mutex.clone();
                     let work_queue_mutex_clone = work_queue_mutex.clone();
                     let failed_processors_clone = failed_processors.clone();
                     let blocked_processors_clone = blocked_processors.clone();
-                    let processors_clone = processors.clone();
                     let canonical_payload_mutex_clone = canonical_payload_mutex.clone();
                     let highest_transform_rank_mutex_clone = highest_transform_rank_mutex.clone();
-                    let topological_ranks_clone = topological_ranks.clone();
                     let pipeline_metadata_clone = pipeline_metadata_mutex.clone();
+                    let invocation_policy = self.invocation_policy;
 
                     // Spawn async task to execute the processor concurrently
                     // Each processor runs in its own async task for maximum parallelism
-                    tokio::spawn(async move {
-                        // === DEPENDENCY FAILURE CHECK ===
-                        // Before executing, check if any of this processor's dependencies have failed
-                        // If so, this processor should be blocked (not executed)
-                        let should_block = if let Some(dependencies) =
-                            reverse_dependencies_clone.get(&processor_id_clone)
-                        {
-                            let failed = failed_processors_clone.lock().await;
-                            dependencies.iter().any(|dep| failed.contains(dep))
-                        } else {
-                            false // No dependencies means nothing can block this processor
-                        };
-
-                        if should_block {
-                            // Mark this processor as blocked due to failed dependency
-                            let mut blocked = blocked_processors_clone.lock().await;
-                            blocked.insert(processor_id_clone.clone());
-
-                            // Also block all dependents of this processor
-                            if let Some(dependents) = graph_clone.get(&processor_id_clone) {
-                                for dependent in dependents {
+                    // The task runs in the DAG span so its log lines carry the run ID
+                    tasks.spawn(
+                        &processor_id,
+                        async move {
+                            // === DEPENDENCY FAILURE CHECK ===
+                            // Before executing, check if any of this processor's dependencies have failed
+                            // If so, this processor should be blocked (not executed)
+                            // No dependencies means nothing can block this processor
+                            let dependencies = plan_clone.dependencies(&processor_id_clone);
+                            let failed_dependency = if dependencies.is_empty() {
+                                None
+                            } else {
+                                let failed = failed_processors_clone.lock().await;
+                                dependencies
+                                    .iter()
+                                    .find(|dep| failed.contains(*dep))
+                                    .cloned()
+                            };
+
+                            if let Some(failed_dependency) = failed_dependency {
+                                recorder_clone.block(&processor_id_clone, &failed_dependency);
+
+                                // Mark this processor as blocked due to failed dependency
+                                let mut blocked = blocked_processors_clone.lock().await;
+                                blocked.insert(processor_id_clone.clone());
+
+                                // Also block all dependents of this processor
+                                for dependent in plan_clone.dependents(&processor_id_clone) {
                                     blocked.insert(dependent.clone());
                                 }
-                            }
-                        } else {
-                            // === PROCESSOR INPUT PREPARATION ===
-                            // Determine the input for this processor using canonical payload approach
-                            let processor_input = if let Some(dependencies) =
-                                reverse_dependencies_clone.get(&processor_id_clone)
-                            {
-                                if dependencies.is_empty() {
+                            } else {
+                                recorder_clone.scheduled(&processor_id_clone);
+
+                                // === PROCESSOR INPUT PREPARATION ===
+                                // Determine the input for this processor using canonical payload approach
+                                let processor_input = if dependencies.is_empty() {
                                     // This is an entry point processor - use the original input as-is
                                     input_clone
                                 } else {
@@ -621,62 +627,59 @@ impl DagExecutor for WorkQueueExecutor {
                                     ProcessorRequest {
                                         payload: canonical_payload,
                                     }
-                                }
-                            } else {
-                                // No dependency information, use original input
-                                input_clone
-                            };
-
-                            // === PROCESSOR EXECUTION ===
-                            // Execute the processor with the prepared input
-                            let response = processor.process(processor_input).await;
-
-                            // Check if the processor execution was successful
-                            // Success is indicated by a NextPayload outcome
-                            let execution_successful = match &response.outcome {
-                                Some(Outcome::NextPayload(_)) => true,
-                                Some(_) => false, // Other outcomes might indicate failure
-                                None => false,    // No outcome indicates failure
-                            };
-
-                            if !execution_successful {
-                                // === FAILURE HANDLING ===
-                                // Mark processor as failed
-                                let mut failed = failed_processors_clone.lock().await;
-                                failed.insert(processor_id_clone.clone());
-
-                                // Block all dependents of this processor (failure propagation)
-                                if let Some(dependents) = graph_clone.get(&processor_id_clone) {
-                                    let mut blocked = blocked_processors_clone.lock().await;
-                                    for dependent in dependents {
-                                        blocked.insert(dependent.clone());
+                                };
+
+                                // === PROCESSOR EXECUTION ===
+                                // Execute the processor with the prepared input, applying timeout and retries
+                                let report = invoke_processor(
+                                    &processor_id_clone,
+                                    &processor,
+                                    processor_input,
+                                    invocation_policy,
+                                    &recorder_clone,
+                                )
+                                .await;
+
+                                // Success is indicated by a NextPayload outcome
+                                let response = report.response.clone();
+                                let execution_successful =
+                                    report.status == ProcessorStatus::Succeeded;
+                                recorder_clone.record(&processor_id_clone, report);
+
+                                if !execution_successful {
+                                    // === FAILURE HANDLING ===
+                                    // Mark processor as failed
+                                    let mut failed = failed_processors_clone.lock().await;
+                                    failed.insert(processor_id_clone.clone());
+
+                                    // Block all dependents of this processor (failure propagation)
+                                    let dependents = plan_clone.dependents(&processor_id_clone);
+                                    if !dependents.is_empty() {
+                                        let mut blocked = blocked_processors_clone.lock().await;
+                                        for dependent in dependents {
+                                            blocked.insert(dependent.clone());
+                                        }
+                                    }
+                                } else if let Some(response) = response {
+                                    // === METADATA COLLECTION ===
+                                    // Collect metadata from this processor's response
+                                    {
+                                        let mut pipeline_meta =
+                                            pipeline_metadata_clone.lock().await;
+                                        pipeline_meta.merge_processor_response(
+                                            &processor_id_clone,
+                                            &response,
+                                        );
                                     }
-                                }
-                            } else {
-                                // === SUCCESS HANDLING ===
-                                // Store the successful result for future processors to use
-                                {
-                                    let mut results = results_mutex_clone.lock().await;
-                                    results.insert(processor_id_clone.clone(), response.clone());
-                                }
-
-                                // === METADATA COLLECTION ===
-                                // Collect metadata from this processor's response
-                                {
-                                    let mut pipeline_meta = pipeline_metadata_clone.lock().await;
-                                    pipeline_meta
-                                        .merge_processor_response(&processor_id_clone, &response);
-                                }
 
-                                // === CANONICAL PAYLOAD UPDATE (CORE ARCHITECTURE) ===
-                                // Update canonical payload if this is a Transform processor with higher topological rank
-                                if let Some(processor) = processors_clone.get(&processor_id_clone) {
-                                    if processor.declared_intent() == ProcessorIntent::Transform {
+                                    // === CANONICAL PAYLOAD UPDATE (CORE ARCHITECTURE) ===
+                                    // Update canonical payload if this is a Transform processor with higher topological rank
+                                    if plan_clone.is_transform(&processor_id_clone) {
                                         if let Some(Outcome::NextPayload(new_payload)) =
                                             &response.outcome
                                         {
-                                            if let Some(&processor_rank) =
-                                                topological_ranks_clone.get(&processor_id_clone)
+                                            if let Some(processor_rank) =
+                                                plan_clone.rank(&processor_id_clone)
                                             {
                                                 let mut highest_rank =
                                                     highest_transform_rank_mutex_clone.lock().await;
@@ -699,89 +702,62 @@ impl DagExecutor for WorkQueueExecutor {
                                                     *canonical_payload =
                                                         Arc::new(new_payload.clone());
                                                     *highest_rank = Some(processor_rank);
+                                                    recorder_clone.payload_updated(
+                                                        &processor_id_clone,
+                                                        new_payload.len(),
+                                                    );
                                                 }
                                             }
                                         }
                                     }
-                                }
 
-                                // === DEPENDENCY RESOLUTION ===
-                                // Update dependency counts for dependents and add newly ready processors to queue
-                                if let Some(dependents) = graph_clone.get(&processor_id_clone) {
-                                    let mut dependency_counts =
-                                        dependency_counts_mutex_clone.lock().await;
-                                    let mut work_queue = work_queue_mutex_clone.lock().await;
+                                    // === DEPENDENCY RESOLUTION ===
+                                    // Update dependency counts for dependents and add newly ready processors to queue
+                                    let dependents = plan_clone.dependents(&processor_id_clone);
+                                    if !dependents.is_empty() {
+                                        let mut dependency_counts =
+                                            dependency_counts_mutex_clone.lock().await;
+                                        let mut work_queue = work_queue_mutex_clone.lock().await;
+
+                                        for dependent_id in dependents {
+                                            if let Some(count) =
+                                                dependency_counts.get_mut(dependent_id)
+                                            {
+                                                *count -= 1; // One less dependency to wait for
 
-                                    for dependent_id in dependents {
-                                        if let Some(count) = dependency_counts.get_mut(dependent_id)
-                                        {
-                                            *count -= 1; // One less dependency to wait for
-
-                                            // If dependency count reaches zero, this processor is ready to execute
-                                            if *count == 0 {
-                                                let rank = topological_ranks_clone
-                                                    .get(dependent_id)
-                                                    .copied()
-                                                    .unwrap_or(0);
-                                                let is_transform = processors_clone
-                                                    .get(dependent_id)
-                                                    .map(|p| {
-                                                        p.declared_intent()
-                                                            == ProcessorIntent::Transform
-                                                    })
-                                                    .unwrap_or(false);
-                                                work_queue.push(PrioritizedTask::new(
-                                                    dependent_id.clone(),
-                                                    rank,
-                                                    is_transform,
-                                                ));
+                                                // If dependency count reaches zero, this processor is ready to execute
+                                                if *count == 0 {
+                                                    work_queue.push(
+                                                        plan_clone.prioritized_task(dependent_id),
+                                                    );
+                                                }
                                             }
                                         }
                                     }
                                 }
                             }
-                        }
 
-                        // === CLEANUP ===
-                        // Always decrement active task count, even if processor failed
-                        // This ensures accurate concurrency tracking and prevents deadlocks
-                        {
-                            let mut active = active_tasks_clone.lock().await;
-                            *active -= 1;
+                            // === CLEANUP ===
+                            // Always decrement active task count, even if processor failed
+                            // This ensures accurate concurrency tracking and prevents deadlocks
+                            {
+                                let mut active = active_tasks_clone.lock().await;
+                                *active -= 1;
+                            }
                         }
-                    });
+                        .instrument(span.clone()),
+                    );
                 }
                 None => {
                     // === EXECUTION COMPLETION CHECK ===
                     // No work available, check if we're done with all execution
                     let active_count = *active_tasks.lock().await;
                     let queue_empty = work_queue_mutex.lock().await.is_empty();
-                    let failed = failed_processors.lock().await;
-
-                    if active_count == 0 && queue_empty {
-                        // All work is complete - no active tasks and no queued work
-                        // Apply failure strategy to determine final result
-                        match failure_strategy {
-                            FailureStrategy::FailFast => {
-                                // Should have already returned on first failure
-                                break;
-                            }
-                            FailureStrategy::ContinueOnError | FailureStrategy::BestEffort => {
-                                if !failed.is_empty() {
-                                    // Collect all failures for comprehensive error reporting
-                                    let failures: Vec<ExecutionError> = failed
-                                        .iter()
-                                        .map(|id| ExecutionError::ProcessorFailed {
-                                            processor_id: id.clone(),
-                                            error: "Processor execution failed".to_string(),
-                                        })
-                                        .collect();
-
-                                    return Err(ExecutionError::MultipleFailed { failures });
-                                }
-                                break;
-                            }
-                        }
+
+                    if active_count == 0 && (queue_empty || stop_scheduling) {
+                        // All work is complete (or FailFast stopped scheduling and in-flight work
+                        // has drained). Processors left in the queue are reported as skipped.
+                        break;
                     } else if active_count == 0 && !queue_empty {
                         // === DEADLOCK DETECTION ===
                         // We have work but can't proceed - likely all remaining processors are blocked
@@ -792,52 +768,55 @@ impl DagExecutor for WorkQueueExecutor {
                             .iter()
                             .all(|task| blocked.contains(&task.processor_id))
                         {
-                            // All remaining processors are blocked due to failed dependencies
-                            // This is a deadlock situation - no progress can be made
-                            let failures: Vec<ExecutionError> = failed
-                                .iter()
-                                .map(|id| ExecutionError::ProcessorFailed {
-                                    processor_id: id.clone(),
-                                    error: "Processor execution failed".to_string(),
-                                })
-                                .collect();
-
-                            return Err(ExecutionError::MultipleFailed { failures });
+                            // All remaining processors are blocked due to failed dependencies;
+                            // no progress can be made, and the report marks them as blocked
+                            break;
                         }
                     } else {
                         // === WAIT FOR PROGRESS ===
                         // Active tasks are running or concurrency limit reached - wait briefly
-                        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+                        tokio::select! {
+                            () = tokio::time::sleep(tokio::time::Duration::from_millis(10)) => {}
+                            () = cancellation.cancelled() => {}
+                        }
                     }
                 }
             }
         }
 
-        // === PHASE 5: RESULT EXTRACTION ===
-        // All processors have completed successfully - extract the final results
-        let final_results = results_mutex.lock().await;
-        let final_pipeline_metadata = pipeline_metadata_mutex.lock().await;
-        
-        // Log successful completion
-        let execution_duration = execution_start.elapsed();
+        // === PHASE 5: REPORT CONSTRUCTION ===
+        // All spawned tasks have completed - build the execution report
+        let final_pipeline_metadata = pipeline_metadata_mutex.lock().await.clone();
+        let final_output = canonical_payload_mutex.lock().await.as_ref().clone();
+
+        let report = recorder.build_report(
+            &plan,
+            failure_strategy,
+            final_output,
+            final_pipeline_metadata,
+        );
+
+        // Log completion
         ExecutionCompleted {
             strategy: "WorkQueue",
-            processor_count: processors.len(),
-            duration: execution_duration,
+            processor_count: plan.processor_count(),
+            duration: report.duration,
         }
         .log();
-        
-        Ok((final_results.clone(), final_pipeline_metadata.clone()))
+
+        Ok(report)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
     use crate::proto::processor_v1::{
         PipelineMetadata, ProcessorMetadata, ProcessorRequest, ProcessorResponse,
     };
-    use crate::traits::processor::Processor;
+    use crate::traits::processor::{Processor, ProcessorIntent};
+    use std::collections::HashMap;
     use std::time::Duration;
     use tokio::time::sleep;
 
@@ -1272,6 +1251,7 @@ mod tests {
                 FailureStrategy::FailFast,
             )
             .await
+            .and_then(|report| report.into_result())
             .unwrap();
 
         // Verify all processors completed successfully
@@ -1344,6 +1324,7 @@ mod tests {
                 FailureStrategy::FailFast,
             )
             .await
+            .and_then(|report| report.into_result())
             .unwrap();
 
         // Verify that the final analyze processor received the canonical payload
@@ -1480,7 +1461,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::FailFast,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -1552,7 +1534,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::ContinueOnError,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -1624,6 +1607,7 @@ mod tests {
                 FailureStrategy::FailFast,
             )
             .await
+            .and_then(|report| report.into_result())
             .unwrap();
 
         // Verify all processors completed
@@ -1714,7 +1698,8 @@ mod tests {
                 PipelineMetadata::new(),
                 FailureStrategy::BestEffort,
             )
-            .await;
+            .await
+            .and_then(|report| report.into_result());
 
         // Should fail because the entire chain is blocked by the first processor failure
         assert!(result.is_err());
@@ -0,0 +1,51 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Errors for authenticating and authorizing the clients of the server.
+
+use std::fmt;
+
+/// Errors that can occur while authenticating a client or checking what it may access
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// The request carried no API key, bearer token or client certificate
+    MissingCredentials,
+
+    /// The presented credentials do not belong to any configured client
+    InvalidCredentials,
+
+    /// The client is authenticated but may not perform the requested access
+    Forbidden { client: String, access: String },
+
+    /// A client of the `auth` configuration is invalid, e.g. its key variable is unset
+    InvalidConfig { client: String, reason: String },
+}
+
+impl AuthError {
+    /// Stable, lowercase reason for logs and metric labels
+    pub fn reason(&self) -> &'static str {
+        match self {
+            AuthError::MissingCredentials => "missing_credentials",
+            AuthError::InvalidCredentials => "invalid_credentials",
+            AuthError::Forbidden { .. } => "forbidden",
+            AuthError::InvalidConfig { .. } => "invalid_config",
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingCredentials => write!(f, "Authentication required"),
+            AuthError::InvalidCredentials => write!(f, "Invalid credentials"),
+            AuthError::Forbidden { client, access } => {
+                write!(f, "Client '{}' may not {}", client, access)
+            }
+            AuthError::InvalidConfig { client, reason } => {
+                write!(f, "Auth client '{}' is invalid: {}", client, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
@@ -30,6 +30,46 @@ pub enum ValidationError {
         /// The processors that form the parallel paths
         parallel_paths: Vec<Vec<String>>,
     },
+    /// A pipeline output names a processor that doesn't exist
+    UnresolvedOutput {
+        /// The name of the output
+        output_name: String,
+        /// The processor that couldn't be resolved
+        processor_id: String,
+    },
+    /// Two pipeline outputs share the same name
+    DuplicateOutputName {
+        /// The duplicate output name
+        output_name: String,
+    },
+    /// Two pipelines in a multi-pipeline configuration share the same name
+    DuplicatePipelineName {
+        /// The duplicate pipeline name
+        pipeline_name: String,
+    },
+    /// A pipeline in a multi-pipeline configuration has an empty name
+    EmptyPipelineName,
+    /// A pipeline's webhook cannot deliver notifications
+    InvalidWebhook {
+        /// The name of the pipeline
+        pipeline_name: String,
+        /// Why the webhook is invalid
+        reason: String,
+    },
+    /// A pipeline's quota cannot admit requests
+    InvalidQuota {
+        /// The name of the pipeline
+        pipeline_name: String,
+        /// Why the quota is invalid
+        reason: String,
+    },
+    /// A named pipeline failed validation
+    InvalidPipeline {
+        /// The name of the invalid pipeline
+        pipeline_name: String,
+        /// The validation errors found in the pipeline
+        errors: Vec<ValidationError>,
+    },
 }
 
 impl fmt::Display for ValidationError {
@@ -69,6 +109,57 @@ impl fmt::Display for ValidationError {
                 write!(f, " -> {}. ", convergence_processor)?;
                 write!(f, "If any processors in parallel paths are Transform type, this may cause non-deterministic behavior in the reactive executor due to race conditions in canonical payload updates.")
             }
+            ValidationError::UnresolvedOutput {
+                output_name,
+                processor_id,
+            } => {
+                write!(
+                    f,
+                    "Output '{}' references processor '{}' which does not exist",
+                    output_name, processor_id
+                )
+            }
+            ValidationError::DuplicateOutputName { output_name } => {
+                write!(f, "Duplicate output name: '{}'", output_name)
+            }
+            ValidationError::DuplicatePipelineName { pipeline_name } => {
+                write!(f, "Duplicate pipeline name: '{}'", pipeline_name)
+            }
+            ValidationError::EmptyPipelineName => {
+                write!(f, "Pipeline name must not be empty")
+            }
+            ValidationError::InvalidWebhook {
+                pipeline_name,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Webhook of pipeline '{}' is invalid: {}",
+                    pipeline_name, reason
+                )
+            }
+            ValidationError::InvalidQuota {
+                pipeline_name,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Quota of pipeline '{}' is invalid: {}",
+                    pipeline_name, reason
+                )
+            }
+            ValidationError::InvalidPipeline {
+                pipeline_name,
+                errors,
+            } => {
+                let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(
+                    f,
+                    "Pipeline '{}' is invalid: {}",
+                    pipeline_name,
+                    messages.join("; ")
+                )
+            }
         }
     }
 }
@@ -35,6 +35,9 @@ pub enum ExecutionError {
 
     /// Executor internal error (e.g., concurrency issues, resource exhaustion)
     InternalError { message: String },
+
+    /// The execution was cancelled before it completed (e.g., its pipeline version was retired)
+    Cancelled { reason: String },
 }
 
 impl std::fmt::Display for ExecutionError {
@@ -85,6 +88,9 @@ impl std::fmt::Display for ExecutionError {
             ExecutionError::InternalError { message } => {
                 write!(f, "Executor internal error: {}", message)
             }
+            ExecutionError::Cancelled { reason } => {
+                write!(f, "Execution cancelled: {}", reason)
+            }
         }
     }
 }
@@ -105,6 +111,17 @@ pub enum FailureStrategy {
     BestEffort,
 }
 
+impl FailureStrategy {
+    /// Name of the failure strategy as written in configuration files
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FailureStrategy::FailFast => "fail_fast",
+            FailureStrategy::ContinueOnError => "continue_on_error",
+            FailureStrategy::BestEffort => "best_effort",
+        }
+    }
+}
+
 impl Default for FailureStrategy {
     fn default() -> Self {
         FailureStrategy::FailFast
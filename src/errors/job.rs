@@ -0,0 +1,55 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Errors for asynchronous jobs and the stores keeping them.
+
+use crate::errors::PipelineError;
+use crate::server::JobState;
+use std::fmt;
+
+/// Errors that can occur while submitting, inspecting or cancelling an asynchronous job
+#[derive(Debug, Clone)]
+pub enum JobError {
+    /// No job with the requested ID exists, or it expired
+    NotFound { id: String },
+
+    /// The job has no result yet; it may be fetched again later
+    NotFinished { id: String, state: JobState },
+
+    /// The job already finished and can no longer be cancelled
+    AlreadyFinished { id: String, state: JobState },
+
+    /// The job was cancelled and has no result
+    Cancelled { id: String },
+
+    /// The job could not be submitted to its pipeline
+    Pipeline(PipelineError),
+
+    /// The job store could not read or write a job
+    StoreFailed { reason: String },
+}
+
+impl fmt::Display for JobError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobError::NotFound { id } => write!(f, "Job '{}' not found", id),
+            JobError::NotFinished { id, state } => {
+                write!(f, "Job '{}' has not finished ({})", id, state)
+            }
+            JobError::AlreadyFinished { id, state } => {
+                write!(f, "Job '{}' already finished ({})", id, state)
+            }
+            JobError::Cancelled { id } => write!(f, "Job '{}' was cancelled", id),
+            JobError::Pipeline(error) => write!(f, "{}", error),
+            JobError::StoreFailed { reason } => write!(f, "Job store failed: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for JobError {}
+
+impl From<PipelineError> for JobError {
+    fn from(error: PipelineError) -> Self {
+        JobError::Pipeline(error)
+    }
+}
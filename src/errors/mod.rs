@@ -1,10 +1,20 @@
 // Copyright (c) 2025 Steve Wagner (ciroque@live.com)
 // SPDX-License-Identifier: MIT
 
+mod auth;
 mod config;
 mod execution;
+mod job;
+mod pipeline;
 mod processor_map;
+mod protocol;
+mod quota;
 
+pub use auth::AuthError;
 pub use config::ValidationError;
 pub use execution::{ExecutionError, FailureStrategy};
+pub use job::JobError;
+pub use pipeline::PipelineError;
 pub use processor_map::ProcessorMapError;
+pub use protocol::{ProtocolError, ServerError};
+pub use quota::QuotaError;
@@ -0,0 +1,133 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Errors for hosting, routing and executing named pipelines.
+
+use crate::errors::{AuthError, ExecutionError, QuotaError, ValidationError};
+use crate::server::PipelineState;
+use std::fmt;
+use std::time::Duration;
+
+/// Errors that can occur while registering, routing to or executing a named pipeline
+#[derive(Debug, Clone)]
+pub enum PipelineError {
+    /// No pipeline with the requested name is registered
+    NotFound { name: String },
+
+    /// A pipeline with the same name is already registered
+    DuplicateName { name: String },
+
+    /// The multi-pipeline configuration failed validation
+    InvalidConfig { errors: Vec<ValidationError> },
+
+    /// The pipeline configuration file could not be read or parsed
+    ConfigLoadFailed { path: String, reason: String },
+
+    /// The pipeline's processors or execution plan could not be built
+    InitializationFailed { name: String, reason: String },
+
+    /// The pipeline did not become ready within its initialization timeout
+    InitializationTimeout { name: String, timeout: Duration },
+
+    /// The pipeline did not become ready within the caller's wait; requests may be retried
+    NotReady { name: String, state: PipelineState },
+
+    /// Every initialization attempt failed; the pipeline rejects requests until reset
+    PermanentlyFailed {
+        name: String,
+        attempts: u32,
+        reason: String,
+    },
+
+    /// The pipeline was disabled by an operator and rejects requests until enabled
+    Disabled { name: String },
+
+    /// The server is shutting down and no longer accepts requests
+    ShuttingDown,
+
+    /// The request named a callback URL the pipeline's webhook does not accept
+    CallbackRejected { name: String, reason: String },
+
+    /// The client could not be authenticated or may not access the pipeline
+    AccessDenied(AuthError),
+
+    /// The request exceeds the quota of its client or pipeline
+    QuotaExceeded(QuotaError),
+
+    /// The pipeline's executor could not carry out the execution
+    ExecutionFailed { name: String, error: ExecutionError },
+}
+
+impl fmt::Display for PipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipelineError::NotFound { name } => {
+                write!(f, "Pipeline '{}' not found", name)
+            }
+            PipelineError::DuplicateName { name } => {
+                write!(f, "Pipeline '{}' is already registered", name)
+            }
+            PipelineError::InvalidConfig { errors } => {
+                let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(
+                    f,
+                    "Pipeline configuration is invalid: {}",
+                    messages.join("; ")
+                )
+            }
+            PipelineError::ConfigLoadFailed { path, reason } => {
+                write!(
+                    f,
+                    "Failed to load pipeline configuration '{}': {}",
+                    path, reason
+                )
+            }
+            PipelineError::InitializationFailed { name, reason } => {
+                write!(f, "Failed to initialize pipeline '{}': {}", name, reason)
+            }
+            PipelineError::InitializationTimeout { name, timeout } => {
+                write!(f, "Pipeline '{}' was not ready within {:?}", name, timeout)
+            }
+            PipelineError::NotReady { name, state } => {
+                write!(f, "Pipeline '{}' is not ready ({})", name, state)
+            }
+            PipelineError::PermanentlyFailed {
+                name,
+                attempts,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Pipeline '{}' permanently failed after {} initialization attempt(s): {}",
+                    name, attempts, reason
+                )
+            }
+            PipelineError::Disabled { name } => {
+                write!(f, "Pipeline '{}' is disabled", name)
+            }
+            PipelineError::ShuttingDown => write!(f, "Server is shutting down"),
+            PipelineError::CallbackRejected { name, reason } => {
+                write!(f, "Pipeline '{}' rejected the callback: {}", name, reason)
+            }
+            PipelineError::AccessDenied(error) => write!(f, "{}", error),
+            PipelineError::QuotaExceeded(error) => write!(f, "{}", error),
+            PipelineError::ExecutionFailed { name, error } => {
+                write!(f, "Execution of pipeline '{}' failed: {}", name, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PipelineError {}
+
+impl From<AuthError> for PipelineError {
+    fn from(error: AuthError) -> Self {
+        PipelineError::AccessDenied(error)
+    }
+}
+
+impl From<QuotaError> for PipelineError {
+    fn from(error: QuotaError) -> Self {
+        PipelineError::QuotaExceeded(error)
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Errors for protocol receivers and the server hosting them.
+
+use crate::errors::{AuthError, JobError, PipelineError};
+use std::fmt;
+
+/// Errors that can occur while configuring, starting or stopping a protocol receiver
+#[derive(Debug, Clone)]
+pub enum ProtocolError {
+    /// The receiver's `options` are missing or have the wrong type
+    InvalidOptions { protocol: String, reason: String },
+
+    /// The receiver could not bind its endpoint
+    BindFailed { endpoint: String, reason: String },
+
+    /// `start` was called on a receiver that is already running
+    AlreadyStarted { name: String },
+
+    /// The receiver stopped serving because of an error
+    ServeFailed { name: String, reason: String },
+
+    /// A client could not connect to, or lost its connection with, a receiver
+    ConnectionFailed { endpoint: String, reason: String },
+
+    /// A peer sent a frame that violates the wire protocol
+    InvalidFrame { reason: String },
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::InvalidOptions { protocol, reason } => {
+                write!(f, "Invalid {} receiver options: {}", protocol, reason)
+            }
+            ProtocolError::BindFailed { endpoint, reason } => {
+                write!(f, "Failed to bind {}: {}", endpoint, reason)
+            }
+            ProtocolError::AlreadyStarted { name } => {
+                write!(f, "Protocol receiver '{}' is already started", name)
+            }
+            ProtocolError::ServeFailed { name, reason } => {
+                write!(f, "Protocol receiver '{}' failed: {}", name, reason)
+            }
+            ProtocolError::ConnectionFailed { endpoint, reason } => {
+                write!(f, "Connection to {} failed: {}", endpoint, reason)
+            }
+            ProtocolError::InvalidFrame { reason } => write!(f, "Invalid frame: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+/// Errors that can occur while starting or running a DAGwood server
+#[derive(Debug, Clone)]
+pub enum ServerError {
+    /// The hosted pipelines could not be registered
+    Pipeline(PipelineError),
+
+    /// A protocol receiver could not be created, started or stopped
+    Protocol(ProtocolError),
+
+    /// The configuration file could not be watched for hot reload
+    ConfigWatch { path: String, reason: String },
+
+    /// The job store could not be opened or recovered
+    Job(JobError),
+
+    /// The `auth` configuration could not be loaded
+    Auth(AuthError),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerError::Pipeline(error) => write!(f, "{}", error),
+            ServerError::Protocol(error) => write!(f, "{}", error),
+            ServerError::ConfigWatch { path, reason } => {
+                write!(f, "Failed to watch configuration '{}': {}", path, reason)
+            }
+            ServerError::Job(error) => write!(f, "{}", error),
+            ServerError::Auth(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+impl From<PipelineError> for ServerError {
+    fn from(error: PipelineError) -> Self {
+        ServerError::Pipeline(error)
+    }
+}
+
+impl From<ProtocolError> for ServerError {
+    fn from(error: ProtocolError) -> Self {
+        ServerError::Protocol(error)
+    }
+}
+
+impl From<JobError> for ServerError {
+    fn from(error: JobError) -> Self {
+        ServerError::Job(error)
+    }
+}
+
+impl From<AuthError> for ServerError {
+    fn from(error: AuthError) -> Self {
+        ServerError::Auth(error)
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Errors for requests rejected by the quota of a client or pipeline.
+
+use std::fmt;
+use std::time::Duration;
+
+/// A request exceeded the quota of a client or pipeline.
+///
+/// `scope` is `client` or `pipeline` and `name` the client or pipeline owning the quota.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuotaError {
+    /// The request payload is larger than the quota allows; retrying does not help
+    PayloadTooLarge {
+        scope: &'static str,
+        name: String,
+        size: usize,
+        limit: usize,
+    },
+
+    /// The quota's maximum number of executions is already in flight
+    ConcurrencyExceeded {
+        scope: &'static str,
+        name: String,
+        limit: usize,
+        retry_after: Duration,
+    },
+
+    /// The quota's request rate is exhausted until its token bucket refills
+    RateLimited {
+        scope: &'static str,
+        name: String,
+        retry_after: Duration,
+    },
+
+    /// The WASM fuel of the quota's current window is used up
+    FuelExhausted {
+        scope: &'static str,
+        name: String,
+        retry_after: Duration,
+    },
+}
+
+impl QuotaError {
+    /// Stable, lowercase reason for logs and metric labels
+    pub fn reason(&self) -> &'static str {
+        match self {
+            QuotaError::PayloadTooLarge { .. } => "payload_too_large",
+            QuotaError::ConcurrencyExceeded { .. } => "concurrency",
+            QuotaError::RateLimited { .. } => "rate",
+            QuotaError::FuelExhausted { .. } => "fuel",
+        }
+    }
+
+    /// `client` or `pipeline`
+    pub fn scope(&self) -> &'static str {
+        match self {
+            QuotaError::PayloadTooLarge { scope, .. }
+            | QuotaError::ConcurrencyExceeded { scope, .. }
+            | QuotaError::RateLimited { scope, .. }
+            | QuotaError::FuelExhausted { scope, .. } => scope,
+        }
+    }
+
+    /// Name of the client or pipeline owning the quota
+    pub fn name(&self) -> &str {
+        match self {
+            QuotaError::PayloadTooLarge { name, .. }
+            | QuotaError::ConcurrencyExceeded { name, .. }
+            | QuotaError::RateLimited { name, .. }
+            | QuotaError::FuelExhausted { name, .. } => name,
+        }
+    }
+
+    /// How long to wait before the request may be admitted; `None` if waiting does not help
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            QuotaError::PayloadTooLarge { .. } => None,
+            QuotaError::ConcurrencyExceeded { retry_after, .. }
+            | QuotaError::RateLimited { retry_after, .. }
+            | QuotaError::FuelExhausted { retry_after, .. } => Some(*retry_after),
+        }
+    }
+}
+
+impl fmt::Display for QuotaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuotaError::PayloadTooLarge {
+                scope,
+                name,
+                size,
+                limit,
+            } => write!(
+                f,
+                "Payload of {} bytes exceeds the {} bytes allowed for {} '{}'",
+                size, limit, scope, name
+            ),
+            QuotaError::ConcurrencyExceeded {
+                scope, name, limit, ..
+            } => write!(
+                f,
+                "The {} concurrent executions allowed for {} '{}' are in flight",
+                limit, scope, name
+            ),
+            QuotaError::RateLimited { scope, name, .. } => {
+                write!(f, "Request rate allowed for {} '{}' exceeded", scope, name)
+            }
+            QuotaError::FuelExhausted { scope, name, .. } => {
+                write!(f, "WASM fuel allowed for {} '{}' is used up", scope, name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuotaError {}
@@ -8,4 +8,6 @@ pub mod errors; // error handling
 pub mod examples; // demo examples
 pub mod observability;
 pub mod proto; // generated protobufs live here
+pub mod protocols; // protocol receivers
+pub mod server; // multi-pipeline hosting
 pub mod traits; // unified abstractions
@@ -0,0 +1,392 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Execution trace export in Chrome Trace Event format.
+//!
+//! [`ChromeTraceLayer`] is a `tracing_subscriber` layer that turns the spans created through
+//! `StructuredLog::span` into "complete" (`ph: "X"`) trace events. The resulting JSON loads
+//! in `chrome://tracing` and [Perfetto](https://ui.perfetto.dev), which makes it easy to
+//! compare how `work_queue`, `level` and `reactive` schedule the same configuration.
+//!
+//! # Lanes
+//!
+//! Trace viewers draw one row per thread ID, so the layer assigns lanes instead of using
+//! real thread IDs (tokio tasks migrate between threads):
+//!
+//! * Lane 0 holds DAG-level spans (`dag_execution`) and any other top-level span
+//! * Each processor invocation (`processor_invocation`) takes the lowest free lane starting
+//!   at 1, so the number of lanes in use mirrors the executor's concurrency at that moment
+//! * Nested spans (processor internals, WASM instantiate/execute) share their parent's lane
+//!
+//! # Example
+//!
+//! ```rust
+//! use the_dagwood::observability::chrome_trace::ChromeTraceLayer;
+//! use tracing_subscriber::prelude::*;
+//!
+//! let (layer, trace) = ChromeTraceLayer::new();
+//! let subscriber = tracing_subscriber::registry().with(layer);
+//!
+//! tracing::subscriber::with_default(subscriber, || {
+//!     let span = tracing::info_span!("work", span_name = "dag_execution");
+//!     let _guard = span.enter();
+//! });
+//!
+//! // trace.write_to("trace.json")?;
+//! assert!(trace.to_json().contains("traceEvents"));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Span name used by executors for a whole DAG execution
+pub const DAG_EXECUTION_SPAN: &str = "dag_execution";
+
+/// Span name used for a single processor invocation attempt
+pub const PROCESSOR_INVOCATION_SPAN: &str = "processor_invocation";
+
+const DAG_LANE: usize = 0;
+const PROCESS_ID: u32 = 1;
+
+/// A single entry of the `traceEvents` array
+#[derive(Debug, Clone, Serialize)]
+struct TraceEvent {
+    name: String,
+    cat: String,
+    ph: &'static str,
+    ts: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dur: Option<f64>,
+    pid: u32,
+    tid: usize,
+    args: BTreeMap<String, String>,
+}
+
+/// Finished events and lane bookkeeping shared by the layer and its handle
+#[derive(Default)]
+struct TraceState {
+    events: Vec<TraceEvent>,
+    busy_lanes: BTreeSet<usize>,
+    used_lanes: BTreeSet<usize>,
+}
+
+impl TraceState {
+    fn acquire_lane(&mut self) -> usize {
+        let lane = (DAG_LANE + 1..)
+            .find(|lane| !self.busy_lanes.contains(lane))
+            .unwrap_or(DAG_LANE + 1);
+        self.busy_lanes.insert(lane);
+        self.used_lanes.insert(lane);
+        lane
+    }
+
+    fn release_lane(&mut self, lane: usize) {
+        self.busy_lanes.remove(&lane);
+    }
+}
+
+/// Per-span data kept in the span's extensions until the span closes
+struct SpanTiming {
+    category: String,
+    args: BTreeMap<String, String>,
+    start: Instant,
+    lane: usize,
+    owns_lane: bool,
+}
+
+impl SpanTiming {
+    /// Invocation slices are named after the processor; everything else after its span
+    /// name, qualified by whichever identifying field it carries.
+    fn display_name(&self) -> String {
+        let discriminator = ["processor_id", "strategy", "executor_type"]
+            .iter()
+            .find_map(|key| self.args.get(*key));
+
+        match discriminator {
+            Some(value) if self.category == PROCESSOR_INVOCATION_SPAN => value.clone(),
+            Some(value) => format!("{} ({})", self.category, value),
+            None => self.category.clone(),
+        }
+    }
+}
+
+/// Collects span fields as strings
+struct FieldVisitor<'a>(&'a mut BTreeMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+/// `tracing_subscriber` layer that records spans as Chrome trace events.
+///
+/// Create it with [`ChromeTraceLayer::new`], install it alongside any other layers and use
+/// the returned [`ChromeTrace`] handle to write the trace once execution has finished.
+pub struct ChromeTraceLayer {
+    origin: Instant,
+    state: Arc<Mutex<TraceState>>,
+}
+
+/// Handle to the events recorded by a [`ChromeTraceLayer`]
+#[derive(Clone)]
+pub struct ChromeTrace {
+    state: Arc<Mutex<TraceState>>,
+}
+
+impl ChromeTraceLayer {
+    /// Create a layer and the handle used to export what it records
+    pub fn new() -> (Self, ChromeTrace) {
+        let state = Arc::new(Mutex::new(TraceState::default()));
+        (
+            Self {
+                origin: Instant::now(),
+                state: state.clone(),
+            },
+            ChromeTrace { state },
+        )
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TraceState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<S> Layer<S> for ChromeTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut args = BTreeMap::new();
+        attrs.record(&mut FieldVisitor(&mut args));
+
+        let category = args
+            .get("span_name")
+            .cloned()
+            .unwrap_or_else(|| attrs.metadata().name().to_string());
+
+        let parent_lane = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanTiming>()
+                .map(|timing| timing.lane)
+        });
+
+        let (lane, owns_lane) = if category == PROCESSOR_INVOCATION_SPAN {
+            (self.lock().acquire_lane(), true)
+        } else if category == DAG_EXECUTION_SPAN {
+            (DAG_LANE, false)
+        } else {
+            (parent_lane.unwrap_or(DAG_LANE), false)
+        };
+
+        span.extensions_mut().insert(SpanTiming {
+            category,
+            args,
+            start: Instant::now(),
+            lane,
+            owns_lane,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                values.record(&mut FieldVisitor(&mut timing.args));
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(timing) = span.extensions_mut().remove::<SpanTiming>() else {
+            return;
+        };
+
+        let event = TraceEvent {
+            name: timing.display_name(),
+            cat: timing.category,
+            ph: "X",
+            ts: timing.start.duration_since(self.origin).as_secs_f64() * 1_000_000.0,
+            dur: Some(timing.start.elapsed().as_secs_f64() * 1_000_000.0),
+            pid: PROCESS_ID,
+            tid: timing.lane,
+            args: timing.args,
+        };
+
+        let mut state = self.lock();
+        if timing.owns_lane {
+            state.release_lane(timing.lane);
+        }
+        state.events.push(event);
+    }
+}
+
+impl ChromeTrace {
+    /// Number of spans recorded so far
+    pub fn event_count(&self) -> usize {
+        self.lock().events.len()
+    }
+
+    /// Render the recorded spans as a Chrome Trace Event JSON document
+    pub fn to_json(&self) -> String {
+        let state = self.lock();
+
+        let metadata = |name: &str, tid: usize, value: String| TraceEvent {
+            name: name.to_string(),
+            cat: "__metadata".to_string(),
+            ph: "M",
+            ts: 0.0,
+            dur: None,
+            pid: PROCESS_ID,
+            tid,
+            args: BTreeMap::from([("name".to_string(), value)]),
+        };
+
+        let mut events = vec![metadata("process_name", DAG_LANE, "the-dagwood".into())];
+        events.push(metadata("thread_name", DAG_LANE, "DAG".into()));
+        for lane in &state.used_lanes {
+            events.push(metadata("thread_name", *lane, format!("slot {}", lane)));
+        }
+        events.extend(state.events.iter().cloned());
+
+        serde_json::json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+        })
+        .to_string()
+    }
+
+    /// Write the trace to a file that can be opened in chrome://tracing or Perfetto
+    pub fn write_to(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(self.to_json().as_bytes())?;
+        writer.flush()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TraceState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use tracing_subscriber::prelude::*;
+
+    fn events(trace: &ChromeTrace) -> Vec<Value> {
+        let document: Value = serde_json::from_str(&trace.to_json()).unwrap();
+        document["traceEvents"].as_array().unwrap().clone()
+    }
+
+    fn slice<'a>(events: &'a [Value], name: &str) -> &'a Value {
+        events
+            .iter()
+            .find(|event| event["ph"] == "X" && event["name"] == name)
+            .unwrap_or_else(|| panic!("no slice named {}", name))
+    }
+
+    #[test]
+    fn test_invocations_get_concurrency_lanes() {
+        let (layer, trace) = ChromeTraceLayer::new();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let dag = tracing::info_span!(
+                "started",
+                span_name = DAG_EXECUTION_SPAN,
+                strategy = "Reactive"
+            );
+            let _dag_guard = dag.enter();
+
+            let first = tracing::info_span!(
+                "started",
+                span_name = PROCESSOR_INVOCATION_SPAN,
+                processor_id = "a"
+            );
+            let second = tracing::info_span!(
+                "started",
+                span_name = PROCESSOR_INVOCATION_SPAN,
+                processor_id = "b"
+            );
+            {
+                let _guard = second.enter();
+                let wasm = tracing::info_span!("started", span_name = "wasm_instantiate");
+                drop(wasm);
+            }
+            drop(first);
+
+            // Lane 1 is free again once "a" has finished
+            let third = tracing::info_span!(
+                "started",
+                span_name = PROCESSOR_INVOCATION_SPAN,
+                processor_id = "c"
+            );
+            drop(third);
+            drop(second);
+        });
+
+        let events = events(&trace);
+        assert_eq!(slice(&events, "dag_execution (Reactive)")["tid"], 0);
+        assert_eq!(slice(&events, "a")["tid"], 1);
+        assert_eq!(slice(&events, "b")["tid"], 2);
+        assert_eq!(slice(&events, "c")["tid"], 1);
+        assert_eq!(slice(&events, "wasm_instantiate")["tid"], 2);
+        assert_eq!(slice(&events, "a")["args"]["processor_id"], "a");
+        assert_eq!(trace.event_count(), 5);
+
+        let lane_names: Vec<&str> = events
+            .iter()
+            .filter(|event| event["name"] == "thread_name")
+            .filter_map(|event| event["args"]["name"].as_str())
+            .collect();
+        assert_eq!(lane_names, vec!["DAG", "slot 1", "slot 2"]);
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let (layer, trace) = ChromeTraceLayer::new();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("started", span_name = DAG_EXECUTION_SPAN);
+            drop(span);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+        trace.write_to(&path).unwrap();
+
+        let document: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(document["displayTimeUnit"], "ms");
+        assert!(document["traceEvents"].as_array().unwrap().len() >= 3);
+    }
+}
@@ -23,6 +23,7 @@ use tracing::Span;
 /// use the_dagwood::observability::messages::engine::ExecutionStarted;
 ///
 /// let msg = ExecutionStarted {
+///     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 ///     strategy: "WorkQueue",
 ///     processor_count: 5,
 ///     max_concurrency: 4,
@@ -31,6 +32,8 @@ use tracing::Span;
 /// tracing::info!("{}", msg);
 /// ```
 pub struct ExecutionStarted<'a> {
+    /// Correlation ID attached to every log line and span of the execution
+    pub run_id: &'a str,
     pub strategy: &'a str,
     pub processor_count: usize,
     pub max_concurrency: usize,
@@ -49,6 +52,7 @@ impl Display for ExecutionStarted<'_> {
 impl StructuredLog for ExecutionStarted<'_> {
     fn log(&self) {
         tracing::info!(
+            run_id = self.run_id,
             strategy = self.strategy,
             processor_count = self.processor_count,
             max_concurrency = self.max_concurrency,
@@ -60,6 +64,7 @@ impl StructuredLog for ExecutionStarted<'_> {
         tracing::info_span!(
             "execution",
             span_name = name,
+            run_id = self.run_id,
             strategy = self.strategy,
             processor_count = self.processor_count,
             max_concurrency = self.max_concurrency,
@@ -15,6 +15,7 @@
 //!
 //! * `engine` - DAG executor lifecycle and execution events
 //! * `processor` - Processor execution and lifecycle events  
+//! * `server` - Hosted pipeline lifecycle, server and protocol receiver events
 //! * `validation` - Configuration validation warnings and errors
 //! * `wasm` - WASM backend loading and execution events
 //!
@@ -25,6 +26,7 @@
 //! use the_dagwood::observability::messages::engine::ExecutionStarted;
 //!
 //! let msg = ExecutionStarted {
+//!     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 //!     strategy: "WorkQueue",
 //!     processor_count: 5,
 //!     max_concurrency: 4,
@@ -38,6 +40,7 @@
 //! use the_dagwood::observability::messages::{StructuredLog, engine::ExecutionStarted};
 //!
 //! let msg = ExecutionStarted {
+//!     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 //!     strategy: "WorkQueue",
 //!     processor_count: 5,
 //!     max_concurrency: 4,
@@ -52,6 +55,7 @@
 //! use the_dagwood::observability::messages::{StructuredLog, engine::ExecutionStarted};
 //!
 //! let msg = ExecutionStarted {
+//!     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 //!     strategy: "WorkQueue",
 //!     processor_count: 5,
 //!     max_concurrency: 4,
@@ -66,6 +70,7 @@
 
 pub mod engine;
 pub mod processor;
+pub mod server;
 pub mod validation;
 pub mod wasm;
 
@@ -99,12 +104,13 @@ use tracing::Span;
 /// use the_dagwood::observability::messages::{StructuredLog, engine::ExecutionStarted};
 ///
 /// let msg = ExecutionStarted {
+///     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 ///     strategy: "WorkQueue",
 ///     processor_count: 5,
 ///     max_concurrency: 4,
 /// };
 ///
-/// // Emits: INFO message + fields {strategy, processor_count, max_concurrency}
+/// // Emits: INFO message + fields {run_id, strategy, processor_count, max_concurrency}
 /// msg.log();
 /// ```
 ///
@@ -113,6 +119,7 @@ use tracing::Span;
 /// use the_dagwood::observability::messages::{StructuredLog, engine::ExecutionStarted};
 ///
 /// let msg = ExecutionStarted {
+///     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 ///     strategy: "WorkQueue",
 ///     processor_count: 5,
 ///     max_concurrency: 4,
@@ -134,6 +141,7 @@ use tracing::Span;
 ///   "level": "INFO",
 ///   "message": "Starting DAG execution with WorkQueue strategy: 5 processors, max_concurrency=4",
 ///   "fields": {
+///     "run_id": "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
 ///     "strategy": "WorkQueue",
 ///     "processor_count": 5,
 ///     "max_concurrency": 4
@@ -159,6 +167,7 @@ pub trait StructuredLog {
     /// use the_dagwood::observability::messages::{StructuredLog, engine::ExecutionStarted};
     ///
     /// ExecutionStarted {
+    ///     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
     ///     strategy: "WorkQueue",
     ///     processor_count: 5,
     ///     max_concurrency: 4,
@@ -185,6 +194,7 @@ pub trait StructuredLog {
     /// use the_dagwood::observability::messages::{StructuredLog, engine::ExecutionStarted};
     ///
     /// let msg = ExecutionStarted {
+    ///     run_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
     ///     strategy: "WorkQueue",
     ///     processor_count: 5,
     ///     max_concurrency: 4,
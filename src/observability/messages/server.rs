@@ -0,0 +1,1638 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Message types for hosted pipeline lifecycle events.
+//!
+//! This module contains message types for logging events related to:
+//! * Pipeline initialization attempts and their outcome
+//! * Initialization retries and permanent failures
+//! * Manual lifecycle resets, and pipelines being disabled, enabled or evicted
+//! * Hot reloads and the draining of replaced pipeline versions
+//! * Asynchronous jobs: submission, completion, expiry and job store failures
+//! * Webhook notifications of finished executions
+//! * Requests denied by authentication or authorization
+//! * Server startup and graceful shutdown, and the protocol receivers it runs
+
+use crate::observability::messages::StructuredLog;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+use tracing::Span;
+
+/// Pipeline initialization attempt started.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelineInitializationStarted;
+///
+/// let msg = PipelineInitializationStarted {
+///     pipeline: "text_processing",
+///     attempt: 1,
+///     max_attempts: 4,
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineInitializationStarted<'a> {
+    pub pipeline: &'a str,
+    pub attempt: u32,
+    pub max_attempts: u32,
+}
+
+impl Display for PipelineInitializationStarted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Initializing pipeline '{}' (attempt {}/{})",
+            self.pipeline, self.attempt, self.max_attempts
+        )
+    }
+}
+
+impl StructuredLog for PipelineInitializationStarted<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            attempt = self.attempt,
+            max_attempts = self.max_attempts,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_initialization",
+            span_name = name,
+            pipeline = self.pipeline,
+            attempt = self.attempt,
+            max_attempts = self.max_attempts,
+        )
+    }
+}
+
+/// Pipeline initialized and ready to serve requests.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::PipelineInitialized;
+///
+/// let msg = PipelineInitialized {
+///     pipeline: "text_processing",
+///     attempts: 1,
+///     duration: Duration::from_millis(250),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineInitialized<'a> {
+    pub pipeline: &'a str,
+    pub attempts: u32,
+    pub duration: Duration,
+}
+
+impl Display for PipelineInitialized<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipeline '{}' ready after {} attempt(s) in {:?}",
+            self.pipeline, self.attempts, self.duration
+        )
+    }
+}
+
+impl StructuredLog for PipelineInitialized<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            attempts = self.attempts,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_initialized",
+            span_name = name,
+            pipeline = self.pipeline,
+            attempts = self.attempts,
+            duration_ms = self.duration.as_millis() as u64,
+        )
+    }
+}
+
+/// Pipeline initialization attempt failed and will be retried.
+///
+/// # Log Level
+/// `warn!` - Recoverable failure
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::PipelineInitializationFailed;
+///
+/// let error = std::io::Error::new(std::io::ErrorKind::NotFound, "module.wasm not found");
+/// let msg = PipelineInitializationFailed {
+///     pipeline: "text_processing",
+///     attempt: 1,
+///     max_attempts: 4,
+///     retry_in: Duration::from_secs(1),
+///     error: &error,
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct PipelineInitializationFailed<'a> {
+    pub pipeline: &'a str,
+    pub attempt: u32,
+    pub max_attempts: u32,
+    pub retry_in: Duration,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl Display for PipelineInitializationFailed<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Initialization of pipeline '{}' failed (attempt {}/{}), retrying in {:?}: {}",
+            self.pipeline, self.attempt, self.max_attempts, self.retry_in, self.error
+        )
+    }
+}
+
+impl StructuredLog for PipelineInitializationFailed<'_> {
+    fn log(&self) {
+        tracing::warn!(
+            pipeline = self.pipeline,
+            attempt = self.attempt,
+            max_attempts = self.max_attempts,
+            retry_in_ms = self.retry_in.as_millis() as u64,
+            error = %self.error,
+            "{}", self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "pipeline_initialization_failed",
+            span_name = name,
+            pipeline = self.pipeline,
+            attempt = self.attempt,
+            max_attempts = self.max_attempts,
+            retry_in_ms = self.retry_in.as_millis() as u64,
+            error = %self.error,
+        )
+    }
+}
+
+/// Pipeline initialization failed on every attempt; requests are rejected until reset.
+///
+/// # Log Level
+/// `error!` - Pipeline unavailable
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelinePermanentlyFailed;
+///
+/// let error = std::io::Error::new(std::io::ErrorKind::NotFound, "module.wasm not found");
+/// let msg = PipelinePermanentlyFailed {
+///     pipeline: "text_processing",
+///     attempts: 4,
+///     error: &error,
+/// };
+///
+/// tracing::error!("{}", msg);
+/// ```
+pub struct PipelinePermanentlyFailed<'a> {
+    pub pipeline: &'a str,
+    pub attempts: u32,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl Display for PipelinePermanentlyFailed<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipeline '{}' permanently failed after {} attempt(s): {}",
+            self.pipeline, self.attempts, self.error
+        )
+    }
+}
+
+impl StructuredLog for PipelinePermanentlyFailed<'_> {
+    fn log(&self) {
+        tracing::error!(
+            pipeline = self.pipeline,
+            attempts = self.attempts,
+            error = %self.error,
+            "{}", self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::error_span!(
+            "pipeline_permanently_failed",
+            span_name = name,
+            pipeline = self.pipeline,
+            attempts = self.attempts,
+            error = %self.error,
+        )
+    }
+}
+
+/// Pipeline lifecycle reset to `Uninitialized`.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelineReset;
+///
+/// let msg = PipelineReset {
+///     pipeline: "text_processing",
+///     previous_state: "permanently_failed",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineReset<'a> {
+    pub pipeline: &'a str,
+    pub previous_state: &'a str,
+}
+
+impl Display for PipelineReset<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipeline '{}' reset from {} to uninitialized",
+            self.pipeline, self.previous_state
+        )
+    }
+}
+
+impl StructuredLog for PipelineReset<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            previous_state = self.previous_state,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_reset",
+            span_name = name,
+            pipeline = self.pipeline,
+            previous_state = self.previous_state,
+        )
+    }
+}
+
+/// Pipeline disabled by an operator; it rejects requests until enabled.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelineDisabled;
+///
+/// let msg = PipelineDisabled {
+///     pipeline: "text_processing",
+///     previous_state: "ready",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineDisabled<'a> {
+    pub pipeline: &'a str,
+    pub previous_state: &'a str,
+}
+
+impl Display for PipelineDisabled<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipeline '{}' disabled (was {})",
+            self.pipeline, self.previous_state
+        )
+    }
+}
+
+impl StructuredLog for PipelineDisabled<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            previous_state = self.previous_state,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_disabled",
+            span_name = name,
+            pipeline = self.pipeline,
+            previous_state = self.previous_state,
+        )
+    }
+}
+
+/// Disabled pipeline enabled again.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelineEnabled;
+///
+/// let msg = PipelineEnabled {
+///     pipeline: "text_processing",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineEnabled<'a> {
+    pub pipeline: &'a str,
+}
+
+impl Display for PipelineEnabled<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Pipeline '{}' enabled", self.pipeline)
+    }
+}
+
+impl StructuredLog for PipelineEnabled<'_> {
+    fn log(&self) {
+        tracing::info!(pipeline = self.pipeline, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_enabled",
+            span_name = name,
+            pipeline = self.pipeline
+        )
+    }
+}
+
+/// Ready pipeline evicted: its processors are released and its next request initializes it
+/// again.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelineEvicted;
+///
+/// let msg = PipelineEvicted {
+///     pipeline: "text_processing",
+///     reason: "idle_timeout",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineEvicted<'a> {
+    pub pipeline: &'a str,
+    pub reason: &'a str,
+}
+
+impl Display for PipelineEvicted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Pipeline '{}' evicted ({})", self.pipeline, self.reason)
+    }
+}
+
+impl StructuredLog for PipelineEvicted<'_> {
+    fn log(&self) {
+        tracing::info!(pipeline = self.pipeline, reason = self.reason, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_evicted",
+            span_name = name,
+            pipeline = self.pipeline,
+            reason = self.reason,
+        )
+    }
+}
+
+/// Evicted pipeline initialized again by a request.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::PipelineRestored;
+///
+/// let msg = PipelineRestored {
+///     pipeline: "text_processing",
+///     duration: Duration::from_millis(120),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineRestored<'a> {
+    pub pipeline: &'a str,
+    pub duration: Duration,
+}
+
+impl Display for PipelineRestored<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipeline '{}' restored after eviction in {:?}",
+            self.pipeline, self.duration
+        )
+    }
+}
+
+impl StructuredLog for PipelineRestored<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_restored",
+            span_name = name,
+            pipeline = self.pipeline,
+            duration_ms = self.duration.as_millis() as u64,
+        )
+    }
+}
+
+/// Memory sweep released pipelines or compiled WASM modules (ADR 28).
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::MemorySweepCompleted;
+///
+/// let msg = MemorySweepCompleted {
+///     evicted: &["text_analysis".to_string()],
+///     modules_released_bytes: 0,
+///     memory_bytes: 48 * 1024 * 1024,
+///     budget_bytes: Some(64 * 1024 * 1024),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct MemorySweepCompleted<'a> {
+    pub evicted: &'a [String],
+    pub modules_released_bytes: usize,
+    pub memory_bytes: usize,
+    pub budget_bytes: Option<usize>,
+}
+
+impl Display for MemorySweepCompleted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Memory sweep evicted {} pipeline(s) and released {} bytes of WASM modules; \
+             pipelines now hold {} bytes",
+            self.evicted.len(),
+            self.modules_released_bytes,
+            self.memory_bytes
+        )?;
+        if let Some(budget_bytes) = self.budget_bytes {
+            write!(f, " (budget {} bytes)", budget_bytes)?;
+        }
+        Ok(())
+    }
+}
+
+impl StructuredLog for MemorySweepCompleted<'_> {
+    fn log(&self) {
+        tracing::info!(
+            evicted = ?self.evicted,
+            modules_released_bytes = self.modules_released_bytes,
+            memory_bytes = self.memory_bytes,
+            budget_bytes = self.budget_bytes,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "memory_sweep_completed",
+            span_name = name,
+            evicted_count = self.evicted.len(),
+            memory_bytes = self.memory_bytes,
+        )
+    }
+}
+
+/// Asynchronous job submitted and queued.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::JobSubmitted;
+///
+/// let msg = JobSubmitted {
+///     job_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
+///     pipeline: "video_analysis",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct JobSubmitted<'a> {
+    pub job_id: &'a str,
+    pub pipeline: &'a str,
+}
+
+impl Display for JobSubmitted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Job '{}' submitted to pipeline '{}'",
+            self.job_id, self.pipeline
+        )
+    }
+}
+
+impl StructuredLog for JobSubmitted<'_> {
+    fn log(&self) {
+        tracing::info!(job_id = self.job_id, pipeline = self.pipeline, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "job_submitted",
+            span_name = name,
+            job_id = self.job_id,
+            pipeline = self.pipeline,
+        )
+    }
+}
+
+/// Asynchronous job finished: succeeded, failed or cancelled.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::JobFinished;
+///
+/// let msg = JobFinished {
+///     job_id: "5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90",
+///     pipeline: "video_analysis",
+///     state: "succeeded",
+///     duration: Duration::from_secs(95),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct JobFinished<'a> {
+    pub job_id: &'a str,
+    pub pipeline: &'a str,
+    pub state: &'a str,
+    /// Time since the job was submitted
+    pub duration: Duration,
+}
+
+impl Display for JobFinished<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Job '{}' of pipeline '{}' {} after {:?}",
+            self.job_id, self.pipeline, self.state, self.duration
+        )
+    }
+}
+
+impl StructuredLog for JobFinished<'_> {
+    fn log(&self) {
+        tracing::info!(
+            job_id = self.job_id,
+            pipeline = self.pipeline,
+            state = self.state,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "job_finished",
+            span_name = name,
+            job_id = self.job_id,
+            pipeline = self.pipeline,
+            state = self.state,
+            duration_ms = self.duration.as_millis() as u64,
+        )
+    }
+}
+
+/// Finished jobs older than the retention period removed from the job store.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::JobsExpired;
+///
+/// let msg = JobsExpired {
+///     removed: 12,
+///     retention: Duration::from_secs(3600),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct JobsExpired {
+    pub removed: usize,
+    pub retention: Duration,
+}
+
+impl Display for JobsExpired {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Removed {} job(s) finished more than {:?} ago",
+            self.removed, self.retention
+        )
+    }
+}
+
+impl StructuredLog for JobsExpired {
+    fn log(&self) {
+        tracing::info!(
+            removed = self.removed,
+            retention_ms = self.retention.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "jobs_expired",
+            span_name = name,
+            removed = self.removed,
+            retention_ms = self.retention.as_millis() as u64,
+        )
+    }
+}
+
+/// Jobs left queued or running by a previous server were marked failed.
+///
+/// # Log Level
+/// `warn!` - Clients of these jobs get no result
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::JobsInterrupted;
+///
+/// let msg = JobsInterrupted {
+///     jobs: 2,
+///     store: "file",
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct JobsInterrupted<'a> {
+    pub jobs: usize,
+    pub store: &'a str,
+}
+
+impl Display for JobsInterrupted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} job(s) in the {} job store were interrupted by a restart and marked failed",
+            self.jobs, self.store
+        )
+    }
+}
+
+impl StructuredLog for JobsInterrupted<'_> {
+    fn log(&self) {
+        tracing::warn!(jobs = self.jobs, store = self.store, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "jobs_interrupted",
+            span_name = name,
+            jobs = self.jobs,
+            store = self.store,
+        )
+    }
+}
+
+/// The job store could not be read or written; the job carries on in memory.
+///
+/// # Log Level
+/// `warn!` - Jobs may be lost on restart
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::JobStoreFailed;
+///
+/// let msg = JobStoreFailed {
+///     job_id: Some("5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90"),
+///     reason: "No space left on device",
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct JobStoreFailed<'a> {
+    /// Job being stored, if the failure concerns a single job
+    pub job_id: Option<&'a str>,
+    pub reason: &'a str,
+}
+
+impl Display for JobStoreFailed<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.job_id {
+            Some(job_id) => write!(f, "Failed to store job '{}': {}", job_id, self.reason),
+            None => write!(f, "Job store failed: {}", self.reason),
+        }
+    }
+}
+
+impl StructuredLog for JobStoreFailed<'_> {
+    fn log(&self) {
+        tracing::warn!(job_id = self.job_id, reason = self.reason, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "job_store_failed",
+            span_name = name,
+            job_id = self.job_id,
+            reason = self.reason,
+        )
+    }
+}
+
+/// Webhook notified of a finished execution.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::WebhookDelivered;
+///
+/// let msg = WebhookDelivered {
+///     pipeline: "text_processing",
+///     url: "http://127.0.0.1:9000/hooks",
+///     run_id: Some("5f0c6b9e-8a41-4c2e-9d3a-1b7e2f4c8a90"),
+///     status: 200,
+///     attempts: 1,
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct WebhookDelivered<'a> {
+    pub pipeline: &'a str,
+    pub url: &'a str,
+    /// Execution the notification is about, if it got far enough to have one
+    pub run_id: Option<&'a str>,
+    /// HTTP status the webhook answered with
+    pub status: u16,
+    pub attempts: u32,
+}
+
+impl Display for WebhookDelivered<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Webhook '{}' of pipeline '{}' notified (status {}, {} attempt(s))",
+            self.url, self.pipeline, self.status, self.attempts
+        )
+    }
+}
+
+impl StructuredLog for WebhookDelivered<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            url = self.url,
+            run_id = self.run_id,
+            status = self.status,
+            attempts = self.attempts,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "webhook_delivered",
+            span_name = name,
+            pipeline = self.pipeline,
+            url = self.url,
+            run_id = self.run_id,
+            status = self.status,
+            attempts = self.attempts,
+        )
+    }
+}
+
+/// Webhook could not be notified of a finished execution, after every attempt.
+///
+/// # Log Level
+/// `warn!` - The notification is lost
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::WebhookDeliveryFailed;
+///
+/// let msg = WebhookDeliveryFailed {
+///     pipeline: "text_processing",
+///     url: "http://127.0.0.1:9000/hooks",
+///     run_id: None,
+///     attempts: 4,
+///     reason: "timed out after 5s",
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct WebhookDeliveryFailed<'a> {
+    pub pipeline: &'a str,
+    pub url: &'a str,
+    /// Execution the notification is about, if it got far enough to have one
+    pub run_id: Option<&'a str>,
+    pub attempts: u32,
+    /// Why the last attempt failed
+    pub reason: &'a str,
+}
+
+impl Display for WebhookDeliveryFailed<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to notify webhook '{}' of pipeline '{}' after {} attempt(s): {}",
+            self.url, self.pipeline, self.attempts, self.reason
+        )
+    }
+}
+
+impl StructuredLog for WebhookDeliveryFailed<'_> {
+    fn log(&self) {
+        tracing::warn!(
+            pipeline = self.pipeline,
+            url = self.url,
+            run_id = self.run_id,
+            attempts = self.attempts,
+            reason = self.reason,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "webhook_delivery_failed",
+            span_name = name,
+            pipeline = self.pipeline,
+            url = self.url,
+            run_id = self.run_id,
+            attempts = self.attempts,
+            reason = self.reason,
+        )
+    }
+}
+
+/// Request denied because its client could not be authenticated or lacks access.
+///
+/// # Log Level
+/// `warn!` - Possible misconfiguration or unauthorized access attempt
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::AccessDenied;
+///
+/// let msg = AccessDenied {
+///     protocol: "http",
+///     client: Some("dashboard"),
+///     access: "execute pipeline 'billing'",
+///     reason: "forbidden",
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct AccessDenied<'a> {
+    pub protocol: &'a str,
+    /// Authenticated client, if the credentials were valid
+    pub client: Option<&'a str>,
+    /// What the request wanted to do
+    pub access: &'a str,
+    /// `missing_credentials`, `invalid_credentials` or `forbidden`
+    pub reason: &'a str,
+}
+
+impl Display for AccessDenied<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.client {
+            Some(client) => write!(
+                f,
+                "Denied {} request of client '{}' to {} ({})",
+                self.protocol, client, self.access, self.reason
+            ),
+            None => write!(
+                f,
+                "Denied {} request to {} ({})",
+                self.protocol, self.access, self.reason
+            ),
+        }
+    }
+}
+
+impl StructuredLog for AccessDenied<'_> {
+    fn log(&self) {
+        tracing::warn!(
+            protocol = self.protocol,
+            client = self.client,
+            access = self.access,
+            reason = self.reason,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "access_denied",
+            span_name = name,
+            protocol = self.protocol,
+            client = self.client,
+            access = self.access,
+            reason = self.reason,
+        )
+    }
+}
+
+/// Request rejected because it exceeds the quota of its client or pipeline.
+///
+/// # Log Level
+/// `warn!` - A client is sending more than its share
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::QuotaExceeded;
+///
+/// let msg = QuotaExceeded {
+///     pipeline: "billing",
+///     scope: "client",
+///     name: "dashboard",
+///     reason: "rate",
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct QuotaExceeded<'a> {
+    /// Pipeline the request wanted to execute
+    pub pipeline: &'a str,
+    /// `client` or `pipeline`
+    pub scope: &'a str,
+    /// Client or pipeline owning the exceeded quota
+    pub name: &'a str,
+    /// `payload_too_large`, `concurrency`, `rate` or `fuel`
+    pub reason: &'a str,
+}
+
+impl Display for QuotaExceeded<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Rejected request to pipeline '{}': quota of {} '{}' exceeded ({})",
+            self.pipeline, self.scope, self.name, self.reason
+        )
+    }
+}
+
+impl StructuredLog for QuotaExceeded<'_> {
+    fn log(&self) {
+        tracing::warn!(
+            pipeline = self.pipeline,
+            scope = self.scope,
+            name = self.name,
+            reason = self.reason,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "quota_exceeded",
+            span_name = name,
+            pipeline = self.pipeline,
+            scope = self.scope,
+            name = self.name,
+            reason = self.reason,
+        )
+    }
+}
+
+/// Pipeline configuration reloaded and the changed pipelines switched over.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::PipelinesReloaded;
+///
+/// let msg = PipelinesReloaded {
+///     trigger: "file_watch",
+///     added: &["text_analysis".to_string()],
+///     removed: &[],
+///     modified: &["text_processing".to_string()],
+///     duration: Duration::from_millis(80),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelinesReloaded<'a> {
+    pub trigger: &'a str,
+    pub added: &'a [String],
+    pub removed: &'a [String],
+    pub modified: &'a [String],
+    pub duration: Duration,
+}
+
+impl Display for PipelinesReloaded<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipelines reloaded ({}) in {:?}: added [{}], removed [{}], modified [{}]",
+            self.trigger,
+            self.duration,
+            self.added.join(", "),
+            self.removed.join(", "),
+            self.modified.join(", ")
+        )
+    }
+}
+
+impl StructuredLog for PipelinesReloaded<'_> {
+    fn log(&self) {
+        tracing::info!(
+            trigger = self.trigger,
+            added = self.added.len(),
+            removed = self.removed.len(),
+            modified = self.modified.len(),
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipelines_reloaded",
+            span_name = name,
+            trigger = self.trigger,
+            added = self.added.len(),
+            removed = self.removed.len(),
+            modified = self.modified.len(),
+        )
+    }
+}
+
+/// Pipeline configuration reload rejected; the running pipelines are unchanged.
+///
+/// # Log Level
+/// `warn!` - The new configuration is not in effect
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::PipelineReloadRejected;
+///
+/// let error = std::io::Error::new(std::io::ErrorKind::InvalidData, "unknown processor");
+/// let msg = PipelineReloadRejected {
+///     trigger: "admin_api",
+///     error: &error,
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct PipelineReloadRejected<'a> {
+    pub trigger: &'a str,
+    pub error: &'a dyn std::error::Error,
+}
+
+impl Display for PipelineReloadRejected<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Pipeline reload ({}) rejected, keeping the running pipelines: {}",
+            self.trigger, self.error
+        )
+    }
+}
+
+impl StructuredLog for PipelineReloadRejected<'_> {
+    fn log(&self) {
+        tracing::warn!(
+            trigger = self.trigger,
+            error = %self.error,
+            "{}", self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "pipeline_reload_rejected",
+            span_name = name,
+            trigger = self.trigger,
+            error = %self.error,
+        )
+    }
+}
+
+/// Replaced or removed pipeline version drained: its last in-flight execution finished.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::PipelineDrained;
+///
+/// let msg = PipelineDrained {
+///     pipeline: "text_processing",
+///     duration: Duration::from_millis(1500),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct PipelineDrained<'a> {
+    pub pipeline: &'a str,
+    pub duration: Duration,
+}
+
+impl Display for PipelineDrained<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Previous version of pipeline '{}' drained in {:?}",
+            self.pipeline, self.duration
+        )
+    }
+}
+
+impl StructuredLog for PipelineDrained<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline = self.pipeline,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "pipeline_drained",
+            span_name = name,
+            pipeline = self.pipeline,
+        )
+    }
+}
+
+/// Replaced or removed pipeline version did not drain in time; its executions are cancelled.
+///
+/// # Log Level
+/// `warn!` - In-flight executions were aborted
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::PipelineDrainTimedOut;
+///
+/// let msg = PipelineDrainTimedOut {
+///     pipeline: "text_processing",
+///     timeout: Duration::from_secs(30),
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct PipelineDrainTimedOut<'a> {
+    pub pipeline: &'a str,
+    pub timeout: Duration,
+}
+
+impl Display for PipelineDrainTimedOut<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Previous version of pipeline '{}' did not drain within {:?}; cancelling its executions",
+            self.pipeline, self.timeout
+        )
+    }
+}
+
+impl StructuredLog for PipelineDrainTimedOut<'_> {
+    fn log(&self) {
+        tracing::warn!(
+            pipeline = self.pipeline,
+            timeout_ms = self.timeout.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "pipeline_drain_timed_out",
+            span_name = name,
+            pipeline = self.pipeline,
+        )
+    }
+}
+
+/// Server started: pipelines registered and every protocol receiver listening.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::server::ServerStarted;
+///
+/// let msg = ServerStarted {
+///     pipelines: &["text_processing".to_string(), "text_analysis".to_string()],
+///     receivers: 2,
+///     duration: Duration::from_millis(120),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ServerStarted<'a> {
+    pub pipelines: &'a [String],
+    pub receivers: usize,
+    pub duration: Duration,
+}
+
+impl Display for ServerStarted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Server started in {:?}: {} pipeline(s) [{}] on {} protocol receiver(s)",
+            self.duration,
+            self.pipelines.len(),
+            self.pipelines.join(", "),
+            self.receivers
+        )
+    }
+}
+
+impl StructuredLog for ServerStarted<'_> {
+    fn log(&self) {
+        tracing::info!(
+            pipeline_count = self.pipelines.len(),
+            receivers = self.receivers,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "server_started",
+            span_name = name,
+            pipeline_count = self.pipelines.len(),
+            receivers = self.receivers,
+        )
+    }
+}
+
+/// Shutdown signal received from the operating system.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::ShutdownSignalReceived;
+///
+/// let msg = ShutdownSignalReceived { signal: "SIGTERM" };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ShutdownSignalReceived<'a> {
+    pub signal: &'a str,
+}
+
+impl Display for ShutdownSignalReceived<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Received {}, shutting down gracefully", self.signal)
+    }
+}
+
+impl StructuredLog for ShutdownSignalReceived<'_> {
+    fn log(&self) {
+        tracing::info!(signal = self.signal, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!("shutdown_signal", span_name = name, signal = self.signal)
+    }
+}
+
+/// Server shutting down: receivers stop accepting requests and in-flight runs drain.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::ServerStopping;
+/// use std::time::Duration;
+///
+/// let msg = ServerStopping {
+///     receivers: 2,
+///     in_flight: 3,
+///     drain_timeout: Duration::from_secs(30),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ServerStopping {
+    pub receivers: usize,
+    pub in_flight: usize,
+    pub drain_timeout: Duration,
+}
+
+impl Display for ServerStopping {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Server shutting down {} protocol receiver(s), draining {} in-flight run(s) for up to {:?}",
+            self.receivers, self.in_flight, self.drain_timeout
+        )
+    }
+}
+
+impl StructuredLog for ServerStopping {
+    fn log(&self) {
+        tracing::info!(
+            receivers = self.receivers,
+            in_flight = self.in_flight,
+            drain_timeout_ms = self.drain_timeout.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "server_stopping",
+            span_name = name,
+            receivers = self.receivers,
+            in_flight = self.in_flight,
+            drain_timeout_ms = self.drain_timeout.as_millis() as u64,
+        )
+    }
+}
+
+/// In-flight runs outlived the shutdown drain timeout and are being cancelled.
+///
+/// # Log Level
+/// `warn!` - Requests are being aborted
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::ServerDrainTimedOut;
+/// use std::time::Duration;
+///
+/// let msg = ServerDrainTimedOut {
+///     in_flight: 1,
+///     timeout: Duration::from_secs(30),
+/// };
+///
+/// tracing::warn!("{}", msg);
+/// ```
+pub struct ServerDrainTimedOut {
+    pub in_flight: usize,
+    pub timeout: Duration,
+}
+
+impl Display for ServerDrainTimedOut {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in-flight run(s) still running after {:?}, cancelling",
+            self.in_flight, self.timeout
+        )
+    }
+}
+
+impl StructuredLog for ServerDrainTimedOut {
+    fn log(&self) {
+        tracing::warn!(
+            in_flight = self.in_flight,
+            timeout_ms = self.timeout.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::warn_span!(
+            "server_drain_timed_out",
+            span_name = name,
+            in_flight = self.in_flight,
+            timeout_ms = self.timeout.as_millis() as u64,
+        )
+    }
+}
+
+/// Server stopped: receivers closed and pipelines released.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::ServerStopped;
+/// use std::time::Duration;
+///
+/// let msg = ServerStopped {
+///     cancelled: 0,
+///     pipelines: 3,
+///     duration: Duration::from_millis(120),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ServerStopped {
+    pub cancelled: usize,
+    pub pipelines: usize,
+    pub duration: Duration,
+}
+
+impl Display for ServerStopped {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Server stopped in {:?} ({} run(s) cancelled, {} pipeline(s) released)",
+            self.duration, self.cancelled, self.pipelines
+        )
+    }
+}
+
+impl StructuredLog for ServerStopped {
+    fn log(&self) {
+        tracing::info!(
+            cancelled = self.cancelled,
+            pipelines = self.pipelines,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "server_stopped",
+            span_name = name,
+            cancelled = self.cancelled,
+            pipelines = self.pipelines,
+            duration_ms = self.duration.as_millis() as u64,
+        )
+    }
+}
+
+/// Protocol receiver started and accepting requests.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::ProtocolReceiverStarted;
+///
+/// let msg = ProtocolReceiverStarted {
+///     protocol: "http",
+///     name: "public",
+///     endpoint: "http://127.0.0.1:8080",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ProtocolReceiverStarted<'a> {
+    pub protocol: &'a str,
+    pub name: &'a str,
+    pub endpoint: &'a str,
+}
+
+impl Display for ProtocolReceiverStarted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Protocol receiver '{}' ({}) listening on {}",
+            self.name, self.protocol, self.endpoint
+        )
+    }
+}
+
+impl StructuredLog for ProtocolReceiverStarted<'_> {
+    fn log(&self) {
+        tracing::info!(
+            protocol = self.protocol,
+            receiver = self.name,
+            endpoint = self.endpoint,
+            "{}",
+            self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "protocol_receiver_started",
+            span_name = name,
+            protocol = self.protocol,
+            receiver = self.name,
+            endpoint = self.endpoint,
+        )
+    }
+}
+
+/// Protocol receiver stopped after draining in-flight requests.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::server::ProtocolReceiverStopped;
+///
+/// let msg = ProtocolReceiverStopped {
+///     protocol: "http",
+///     name: "public",
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ProtocolReceiverStopped<'a> {
+    pub protocol: &'a str,
+    pub name: &'a str,
+}
+
+impl Display for ProtocolReceiverStopped<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Protocol receiver '{}' ({}) stopped",
+            self.name, self.protocol
+        )
+    }
+}
+
+impl StructuredLog for ProtocolReceiverStopped<'_> {
+    fn log(&self) {
+        tracing::info!(protocol = self.protocol, receiver = self.name, "{}", self);
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "protocol_receiver_stopped",
+            span_name = name,
+            protocol = self.protocol,
+            receiver = self.name,
+        )
+    }
+}
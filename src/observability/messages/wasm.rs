@@ -7,7 +7,9 @@
 //! * WASM module loading and validation
 //! * WASM component type detection
 //! * WASM executor creation and configuration
+//! * WASM module instantiation
 //! * WASM execution lifecycle and performance
+//! * Compiled module eviction and reload under the memory budget
 
 use crate::observability::messages::StructuredLog;
 use std::fmt::{Display, Formatter};
@@ -268,6 +270,59 @@ impl StructuredLog for ExecutionStarted<'_> {
     }
 }
 
+/// WASM module instantiation started.
+///
+/// Instantiation (store setup, fuel configuration and linking) happens on every execution,
+/// so its span separates instantiation cost from the guest's own execution time.
+///
+/// # Log Level
+/// `debug!` - Per-execution diagnostic detail
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::wasm::InstantiationStarted;
+///
+/// let msg = InstantiationStarted {
+///     executor_type: "CStyleNodeExecutor",
+///     fuel_level: 1_000_000,
+/// };
+///
+/// tracing::debug!("{}", msg);
+/// ```
+pub struct InstantiationStarted<'a> {
+    pub executor_type: &'a str,
+    pub fuel_level: u64,
+}
+
+impl Display for InstantiationStarted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Instantiating WASM module using {} executor: fuel_level={}",
+            self.executor_type, self.fuel_level
+        )
+    }
+}
+
+impl StructuredLog for InstantiationStarted<'_> {
+    fn log(&self) {
+        tracing::debug!(
+            executor_type = self.executor_type,
+            fuel_level = self.fuel_level,
+            "{}", self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "wasm_instantiation_started",
+            span_name = name,
+            executor_type = self.executor_type,
+            fuel_level = self.fuel_level,
+        )
+    }
+}
+
 /// WASM execution completed successfully.
 ///
 /// # Log Level
@@ -432,3 +487,103 @@ impl StructuredLog for EngineCreationStarted<'_> {
         )
     }
 }
+
+/// Compiled WASM module released to free memory; the next execution compiles it again.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use the_dagwood::observability::messages::wasm::ModuleEvicted;
+///
+/// let msg = ModuleEvicted {
+///     module_path: "wasm_modules/hello_world.wasm",
+///     compiled_bytes: 262144,
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ModuleEvicted<'a> {
+    pub module_path: &'a str,
+    pub compiled_bytes: usize,
+}
+
+impl Display for ModuleEvicted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Evicted compiled WASM module: {} ({} bytes released)",
+            self.module_path, self.compiled_bytes
+        )
+    }
+}
+
+impl StructuredLog for ModuleEvicted<'_> {
+    fn log(&self) {
+        tracing::info!(
+            module_path = self.module_path,
+            compiled_bytes = self.compiled_bytes,
+            "{}", self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "module_evicted",
+            span_name = name,
+            module_path = self.module_path,
+            compiled_bytes = self.compiled_bytes,
+        )
+    }
+}
+
+/// Evicted WASM module compiled again for an execution.
+///
+/// # Log Level
+/// `info!` - Important operational event
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use the_dagwood::observability::messages::wasm::ModuleReloaded;
+///
+/// let msg = ModuleReloaded {
+///     module_path: "wasm_modules/hello_world.wasm",
+///     duration: Duration::from_millis(35),
+/// };
+///
+/// tracing::info!("{}", msg);
+/// ```
+pub struct ModuleReloaded<'a> {
+    pub module_path: &'a str,
+    pub duration: std::time::Duration,
+}
+
+impl Display for ModuleReloaded<'_> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Reloaded evicted WASM module: {} in {:?}",
+            self.module_path, self.duration
+        )
+    }
+}
+
+impl StructuredLog for ModuleReloaded<'_> {
+    fn log(&self) {
+        tracing::info!(
+            module_path = self.module_path,
+            duration_ms = self.duration.as_millis() as u64,
+            "{}", self
+        );
+    }
+
+    fn span(&self, name: &str) -> Span {
+        tracing::info_span!(
+            "module_reloaded",
+            span_name = name,
+            module_path = self.module_path,
+        )
+    }
+}
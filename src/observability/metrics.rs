@@ -0,0 +1,672 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Prometheus metrics for DAG executions, processors, the WASM backend and hosted pipelines.
+//!
+//! All metrics live in a single process-wide [`Metrics`] registry returned by [`metrics()`].
+//! Executors record processor invocations through the shared invocation path, and
+//! `WasmProcessor` and the WASM executors record backend-specific measurements, so every
+//! execution strategy is counted the same way.
+//!
+//! # Exposed Metrics
+//!
+//! | Metric | Type | Labels |
+//! |--------|------|--------|
+//! | `dagwood_processor_executions_total` | counter | `processor`, `status` |
+//! | `dagwood_processor_duration_seconds` | histogram | `processor` |
+//! | `dagwood_processor_failures_total` | counter | `processor`, `code` |
+//! | `dagwood_processor_payload_bytes` | histogram | `processor`, `direction` |
+//! | `dagwood_executions_total` | counter | `strategy`, `result` |
+//! | `dagwood_execution_duration_seconds` | histogram | `strategy` |
+//! | `dagwood_executor_queue_depth` | gauge | `strategy` |
+//! | `dagwood_executor_active_tasks` | gauge | `strategy` |
+//! | `dagwood_wasm_fuel_consumed` | histogram | `artifact_type` |
+//! | `dagwood_wasm_module_load_seconds` | histogram | `module` |
+//! | `dagwood_wasm_payload_bytes` | histogram | `module`, `direction` |
+//! | `dagwood_wasm_module_evictions_total` | counter | `module` |
+//! | `dagwood_wasm_module_reloads_total` | counter | `module` |
+//! | `dagwood_pipeline_memory_bytes` | gauge | `pipeline` |
+//! | `dagwood_pipeline_evictions_total` | counter | `pipeline`, `reason` |
+//! | `dagwood_pipeline_restores_total` | counter | `pipeline` |
+//! | `dagwood_jobs_total` | counter | `pipeline`, `state` |
+//! | `dagwood_jobs_active` | gauge | `pipeline` |
+//! | `dagwood_webhook_deliveries_total` | counter | `pipeline`, `outcome` |
+//! | `dagwood_webhook_retries_total` | counter | `pipeline` |
+//! | `dagwood_auth_failures_total` | counter | `protocol`, `reason` |
+//! | `dagwood_quota_rejections_total` | counter | `scope`, `name`, `reason` |
+//! | `dagwood_quota_active_runs` | gauge | `scope`, `name` |
+//! | `dagwood_quota_fuel_consumed_total` | counter | `scope`, `name` |
+//!
+//! Jobs are counted once finished, by final state; `dagwood_jobs_active` counts the queued
+//! and running ones. Webhook notifications are counted once `delivered` or `failed` after
+//! their last attempt; every attempt after the first counts as a retry. Requests denied by
+//! authentication are counted by `reason`: `missing_credentials`, `invalid_credentials` or
+//! `forbidden`. Quota metrics are labelled by the `scope` (`client` or `pipeline`) and `name`
+//! of the quota; rejections are counted by `reason`: `payload_too_large`, `concurrency`,
+//! `rate` or `fuel`.
+//!
+//! Failures are counted per failed attempt. The `code` label is the `ErrorDetail.code` returned
+//! by the processor, `timeout` for attempts exceeding the timeout and `no_outcome` for responses
+//! without an outcome.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::time::Duration;
+//! use the_dagwood::observability::metrics::metrics;
+//!
+//! metrics().processor_finished("to_uppercase", "succeeded", Duration::from_millis(2));
+//!
+//! // Prometheus text exposition format, ready to serve from a /metrics endpoint
+//! let text = metrics().encode();
+//! assert!(text.contains("dagwood_processor_executions_total"));
+//!
+//! // metrics().write_to("metrics.prom")?;
+//! ```
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use prometheus::{
+    exponential_buckets, Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+const NAMESPACE: &str = "dagwood";
+
+/// Direction label value for request payloads
+pub const DIRECTION_INPUT: &str = "input";
+
+/// Direction label value for response payloads
+pub const DIRECTION_OUTPUT: &str = "output";
+
+/// Process-wide metrics registry
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// Prometheus registry holding every DAGwood metric
+pub struct Metrics {
+    registry: Registry,
+    processor_executions: IntCounterVec,
+    processor_duration: HistogramVec,
+    processor_failures: IntCounterVec,
+    processor_payload_bytes: HistogramVec,
+    executions: IntCounterVec,
+    execution_duration: HistogramVec,
+    queue_depth: IntGaugeVec,
+    active_tasks: IntGaugeVec,
+    wasm_fuel_consumed: HistogramVec,
+    wasm_module_load: HistogramVec,
+    wasm_payload_bytes: HistogramVec,
+    wasm_module_evictions: IntCounterVec,
+    wasm_module_reloads: IntCounterVec,
+    pipeline_memory: IntGaugeVec,
+    pipeline_evictions: IntCounterVec,
+    pipeline_restores: IntCounterVec,
+    jobs: IntCounterVec,
+    jobs_active: IntGaugeVec,
+    webhook_deliveries: IntCounterVec,
+    webhook_retries: IntCounterVec,
+    auth_failures: IntCounterVec,
+    quota_rejections: IntCounterVec,
+    quota_active_runs: IntGaugeVec,
+    quota_fuel_consumed: IntCounterVec,
+}
+
+impl Metrics {
+    /// Create a registry with all metrics registered.
+    ///
+    /// Most code should use the shared [`metrics()`] registry; a separate registry is mainly
+    /// useful in tests.
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let processor_executions = counter_vec(
+            &registry,
+            "processor_executions_total",
+            "Processor invocations by final status",
+            &["processor", "status"],
+        );
+        let processor_duration = histogram_vec(
+            &registry,
+            "processor_duration_seconds",
+            "Processor invocation latency including retries",
+            &["processor"],
+            exponential_buckets(0.0001, 4.0, 10),
+        );
+        let processor_failures = counter_vec(
+            &registry,
+            "processor_failures_total",
+            "Failed processor attempts by error code",
+            &["processor", "code"],
+        );
+        let processor_payload_bytes = histogram_vec(
+            &registry,
+            "processor_payload_bytes",
+            "Processor payload sizes",
+            &["processor", "direction"],
+            size_buckets(),
+        );
+        let executions = counter_vec(
+            &registry,
+            "executions_total",
+            "DAG executions by result",
+            &["strategy", "result"],
+        );
+        let execution_duration = histogram_vec(
+            &registry,
+            "execution_duration_seconds",
+            "DAG execution latency",
+            &["strategy"],
+            exponential_buckets(0.0001, 4.0, 10),
+        );
+        let queue_depth = gauge_vec(
+            &registry,
+            "executor_queue_depth",
+            "Processors ready to run that have not started yet",
+            &["strategy"],
+        );
+        let active_tasks = gauge_vec(
+            &registry,
+            "executor_active_tasks",
+            "Processor invocations currently running",
+            &["strategy"],
+        );
+        let wasm_fuel_consumed = histogram_vec(
+            &registry,
+            "wasm_fuel_consumed",
+            "Fuel consumed per WASM execution",
+            &["artifact_type"],
+            exponential_buckets(1_000.0, 10.0, 8),
+        );
+        let wasm_module_load = histogram_vec(
+            &registry,
+            "wasm_module_load_seconds",
+            "Time to load, detect and compile a WASM module",
+            &["module"],
+            exponential_buckets(0.001, 4.0, 8),
+        );
+        let wasm_payload_bytes = histogram_vec(
+            &registry,
+            "wasm_payload_bytes",
+            "WASM module payload sizes",
+            &["module", "direction"],
+            size_buckets(),
+        );
+        let wasm_module_evictions = counter_vec(
+            &registry,
+            "wasm_module_evictions_total",
+            "Compiled WASM modules released to free memory",
+            &["module"],
+        );
+        let wasm_module_reloads = counter_vec(
+            &registry,
+            "wasm_module_reloads_total",
+            "Evicted WASM modules compiled again for an execution",
+            &["module"],
+        );
+        let pipeline_memory = gauge_vec(
+            &registry,
+            "pipeline_memory_bytes",
+            "Estimated memory held by an initialized pipeline",
+            &["pipeline"],
+        );
+        let pipeline_evictions = counter_vec(
+            &registry,
+            "pipeline_evictions_total",
+            "Ready pipelines evicted, by reason",
+            &["pipeline", "reason"],
+        );
+        let pipeline_restores = counter_vec(
+            &registry,
+            "pipeline_restores_total",
+            "Evicted pipelines initialized again by a request",
+            &["pipeline"],
+        );
+        let jobs = counter_vec(
+            &registry,
+            "jobs_total",
+            "Asynchronous jobs finished, by final state",
+            &["pipeline", "state"],
+        );
+        let jobs_active = gauge_vec(
+            &registry,
+            "jobs_active",
+            "Asynchronous jobs queued or running",
+            &["pipeline"],
+        );
+        let webhook_deliveries = counter_vec(
+            &registry,
+            "webhook_deliveries_total",
+            "Webhook notifications of finished executions, by outcome",
+            &["pipeline", "outcome"],
+        );
+        let webhook_retries = counter_vec(
+            &registry,
+            "webhook_retries_total",
+            "Webhook delivery attempts retried after a failed attempt",
+            &["pipeline"],
+        );
+        let auth_failures = counter_vec(
+            &registry,
+            "auth_failures_total",
+            "Requests denied by authentication or authorization, by reason",
+            &["protocol", "reason"],
+        );
+        let quota_rejections = counter_vec(
+            &registry,
+            "quota_rejections_total",
+            "Requests rejected by the quota of a client or pipeline, by reason",
+            &["scope", "name", "reason"],
+        );
+        let quota_active_runs = gauge_vec(
+            &registry,
+            "quota_active_runs",
+            "Executions in flight counted against the quota of a client or pipeline",
+            &["scope", "name"],
+        );
+        let quota_fuel_consumed = counter_vec(
+            &registry,
+            "quota_fuel_consumed_total",
+            "WASM fuel charged to the quota of a client or pipeline",
+            &["scope", "name"],
+        );
+
+        Self {
+            registry,
+            processor_executions,
+            processor_duration,
+            processor_failures,
+            processor_payload_bytes,
+            executions,
+            execution_duration,
+            queue_depth,
+            active_tasks,
+            wasm_fuel_consumed,
+            wasm_module_load,
+            wasm_payload_bytes,
+            wasm_module_evictions,
+            wasm_module_reloads,
+            pipeline_memory,
+            pipeline_evictions,
+            pipeline_restores,
+            jobs,
+            jobs_active,
+            webhook_deliveries,
+            webhook_retries,
+            auth_failures,
+            quota_rejections,
+            quota_active_runs,
+            quota_fuel_consumed,
+        }
+    }
+
+    /// The underlying Prometheus registry, for registering additional collectors
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let mut buffer = Vec::new();
+        // Encoding into a Vec only fails on inconsistent metric families, which the
+        // fixed set of metrics above cannot produce
+        let _ = TextEncoder::new().encode(&self.registry.gather(), &mut buffer);
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+
+    /// Write all metrics in the Prometheus text exposition format to a file
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.encode())
+    }
+
+    /// Record a finished processor invocation
+    pub fn processor_finished(&self, processor_id: &str, status: &str, duration: Duration) {
+        self.processor_executions
+            .with_label_values(&[processor_id, status])
+            .inc();
+        self.processor_duration
+            .with_label_values(&[processor_id])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Record a failed processor attempt; `code` is the error code or failure kind
+    pub fn processor_failed(&self, processor_id: &str, code: &str) {
+        self.processor_failures
+            .with_label_values(&[processor_id, code])
+            .inc();
+    }
+
+    /// Record the size of a processor payload in the given direction
+    pub fn processor_payload(&self, processor_id: &str, direction: &str, size: usize) {
+        self.processor_payload_bytes
+            .with_label_values(&[processor_id, direction])
+            .observe(size as f64);
+    }
+
+    /// Record a finished DAG execution
+    pub fn execution_finished(&self, strategy: &str, succeeded: bool, duration: Duration) {
+        let result = if succeeded { "success" } else { "failure" };
+        self.executions.with_label_values(&[strategy, result]).inc();
+        self.execution_duration
+            .with_label_values(&[strategy])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Gauge of processors ready to run but not started yet
+    pub fn queue_depth(&self, strategy: &str) -> IntGauge {
+        self.queue_depth.with_label_values(&[strategy])
+    }
+
+    /// Mark a processor invocation as running until the returned guard is dropped
+    pub fn track_active_task(&self, strategy: &str) -> ActiveTaskGuard {
+        let gauge = self.active_tasks.with_label_values(&[strategy]);
+        gauge.inc();
+        ActiveTaskGuard { gauge }
+    }
+
+    /// Record the fuel consumed by a single WASM execution
+    pub fn wasm_fuel_consumed(&self, artifact_type: &str, fuel: u64) {
+        self.wasm_fuel_consumed
+            .with_label_values(&[artifact_type])
+            .observe(fuel as f64);
+    }
+
+    /// Record the time taken to load a WASM module
+    pub fn wasm_module_loaded(&self, module_path: &str, duration: Duration) {
+        self.wasm_module_load
+            .with_label_values(&[module_path])
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Record the size of a WASM payload in the given direction
+    pub fn wasm_payload(&self, module_path: &str, direction: &str, size: usize) {
+        self.wasm_payload_bytes
+            .with_label_values(&[module_path, direction])
+            .observe(size as f64);
+    }
+
+    /// Record a compiled WASM module released to free memory
+    pub fn wasm_module_evicted(&self, module_path: &str) {
+        self.wasm_module_evictions
+            .with_label_values(&[module_path])
+            .inc();
+    }
+
+    /// Record an evicted WASM module compiled again
+    pub fn wasm_module_reloaded(&self, module_path: &str) {
+        self.wasm_module_reloads
+            .with_label_values(&[module_path])
+            .inc();
+    }
+
+    /// Set the estimated memory held by a pipeline; 0 once it is released
+    pub fn pipeline_memory(&self, pipeline: &str, bytes: usize) {
+        self.pipeline_memory
+            .with_label_values(&[pipeline])
+            .set(bytes as i64);
+    }
+
+    /// Record a pipeline eviction; `reason` says what triggered it
+    pub fn pipeline_evicted(&self, pipeline: &str, reason: &str) {
+        self.pipeline_evictions
+            .with_label_values(&[pipeline, reason])
+            .inc();
+        self.pipeline_memory(pipeline, 0);
+    }
+
+    /// Record an evicted pipeline initialized again
+    pub fn pipeline_restored(&self, pipeline: &str) {
+        self.pipeline_restores.with_label_values(&[pipeline]).inc();
+    }
+
+    /// Record an asynchronous job submitted
+    pub fn job_submitted(&self, pipeline: &str) {
+        self.jobs_active.with_label_values(&[pipeline]).inc();
+    }
+
+    /// Record an asynchronous job finished; `state` is its final state
+    pub fn job_finished(&self, pipeline: &str, state: &str) {
+        self.jobs.with_label_values(&[pipeline, state]).inc();
+        self.jobs_active.with_label_values(&[pipeline]).dec();
+    }
+
+    /// Record a webhook notification that was delivered, or failed, after `attempts`
+    pub fn webhook_notified(&self, pipeline: &str, delivered: bool, attempts: u32) {
+        let outcome = if delivered { "delivered" } else { "failed" };
+        self.webhook_deliveries
+            .with_label_values(&[pipeline, outcome])
+            .inc();
+        self.webhook_retries
+            .with_label_values(&[pipeline])
+            .inc_by(u64::from(attempts.saturating_sub(1)));
+    }
+
+    /// Record a request denied by authentication or authorization
+    pub fn auth_denied(&self, protocol: &str, reason: &str) {
+        self.auth_failures
+            .with_label_values(&[protocol, reason])
+            .inc();
+    }
+
+    /// Record a request rejected by the quota of `scope` `name`
+    pub fn quota_rejected(&self, scope: &str, name: &str, reason: &str) {
+        self.quota_rejections
+            .with_label_values(&[scope, name, reason])
+            .inc();
+    }
+
+    /// Record the executions in flight against the quota of `scope` `name`
+    pub fn quota_active_runs(&self, scope: &str, name: &str, active: usize) {
+        self.quota_active_runs
+            .with_label_values(&[scope, name])
+            .set(active as i64);
+    }
+
+    /// Record WASM fuel charged to the quota of `scope` `name`
+    pub fn quota_fuel_consumed(&self, scope: &str, name: &str, fuel: u64) {
+        self.quota_fuel_consumed
+            .with_label_values(&[scope, name])
+            .inc_by(fuel);
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decrements the active task gauge when dropped, including when the task is cancelled
+pub struct ActiveTaskGuard {
+    gauge: IntGauge,
+}
+
+impl Drop for ActiveTaskGuard {
+    fn drop(&mut self) {
+        self.gauge.dec();
+    }
+}
+
+fn size_buckets() -> prometheus::Result<Vec<f64>> {
+    exponential_buckets(64.0, 4.0, 10)
+}
+
+fn counter_vec(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    let counter = IntCounterVec::new(Opts::new(name, help).namespace(NAMESPACE), labels)
+        .expect("metric definition is valid");
+    registry
+        .register(Box::new(counter.clone()))
+        .expect("metric is registered once");
+    counter
+}
+
+fn gauge_vec(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntGaugeVec {
+    let gauge = IntGaugeVec::new(Opts::new(name, help).namespace(NAMESPACE), labels)
+        .expect("metric definition is valid");
+    registry
+        .register(Box::new(gauge.clone()))
+        .expect("metric is registered once");
+    gauge
+}
+
+fn histogram_vec(
+    registry: &Registry,
+    name: &str,
+    help: &str,
+    labels: &[&str],
+    buckets: prometheus::Result<Vec<f64>>,
+) -> HistogramVec {
+    let histogram = HistogramVec::new(
+        HistogramOpts::new(name, help)
+            .namespace(NAMESPACE)
+            .buckets(buckets.unwrap_or_default()),
+        labels,
+    )
+    .expect("metric definition is valid");
+    registry
+        .register(Box::new(histogram.clone()))
+        .expect("metric is registered once");
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_processor_metrics() {
+        let metrics = Metrics::new();
+        metrics.processor_finished("upper", "succeeded", Duration::from_millis(3));
+        metrics.processor_failed("upper", "500");
+        metrics.processor_payload("upper", DIRECTION_INPUT, 11);
+
+        let text = metrics.encode();
+        assert!(text.contains(
+            "dagwood_processor_executions_total{processor=\"upper\",status=\"succeeded\"} 1"
+        ));
+        assert!(
+            text.contains("dagwood_processor_failures_total{code=\"500\",processor=\"upper\"} 1")
+        );
+        assert!(text.contains("dagwood_processor_duration_seconds_count{processor=\"upper\"} 1"));
+        assert!(text.contains(
+            "dagwood_processor_payload_bytes_sum{direction=\"input\",processor=\"upper\"} 11"
+        ));
+    }
+
+    #[test]
+    fn test_active_task_guard_decrements_on_drop() {
+        let metrics = Metrics::new();
+        let guard = metrics.track_active_task("Reactive");
+        let second = metrics.track_active_task("Reactive");
+        assert_eq!(
+            metrics.active_tasks.with_label_values(&["Reactive"]).get(),
+            2
+        );
+
+        drop(guard);
+        drop(second);
+        assert_eq!(
+            metrics.active_tasks.with_label_values(&["Reactive"]).get(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_encode_eviction_metrics() {
+        let metrics = Metrics::new();
+        metrics.pipeline_memory("analysis", 4096);
+        metrics.pipeline_memory("upper", 2048);
+        metrics.pipeline_evicted("upper", "idle_timeout");
+        metrics.pipeline_restored("upper");
+        metrics.wasm_module_evicted("appender.wasm");
+
+        let text = metrics.encode();
+        assert!(text.contains(
+            "dagwood_pipeline_evictions_total{pipeline=\"upper\",reason=\"idle_timeout\"} 1"
+        ));
+        assert!(text.contains("dagwood_pipeline_memory_bytes{pipeline=\"upper\"} 0"));
+        assert!(text.contains("dagwood_pipeline_memory_bytes{pipeline=\"analysis\"} 4096"));
+        assert!(text.contains("dagwood_pipeline_restores_total{pipeline=\"upper\"} 1"));
+        assert!(text.contains("dagwood_wasm_module_evictions_total{module=\"appender.wasm\"} 1"));
+    }
+
+    #[test]
+    fn test_encode_job_metrics() {
+        let metrics = Metrics::new();
+        metrics.job_submitted("analysis");
+        metrics.job_submitted("analysis");
+        metrics.job_finished("analysis", "succeeded");
+
+        let text = metrics.encode();
+        assert!(text.contains("dagwood_jobs_active{pipeline=\"analysis\"} 1"));
+        assert!(text.contains("dagwood_jobs_total{pipeline=\"analysis\",state=\"succeeded\"} 1"));
+    }
+
+    #[test]
+    fn test_encode_webhook_metrics() {
+        let metrics = Metrics::new();
+        metrics.webhook_notified("upper", true, 1);
+        metrics.webhook_notified("upper", false, 4);
+
+        let text = metrics.encode();
+        assert!(text.contains(
+            "dagwood_webhook_deliveries_total{outcome=\"delivered\",pipeline=\"upper\"} 1"
+        ));
+        assert!(text
+            .contains("dagwood_webhook_deliveries_total{outcome=\"failed\",pipeline=\"upper\"} 1"));
+        assert!(text.contains("dagwood_webhook_retries_total{pipeline=\"upper\"} 3"));
+    }
+
+    #[test]
+    fn test_encode_auth_metrics() {
+        let metrics = Metrics::new();
+        metrics.auth_denied("http", "forbidden");
+        metrics.auth_denied("http", "forbidden");
+        metrics.auth_denied("grpc", "missing_credentials");
+
+        let text = metrics.encode();
+        assert!(
+            text.contains("dagwood_auth_failures_total{protocol=\"http\",reason=\"forbidden\"} 2")
+        );
+        assert!(text.contains(
+            "dagwood_auth_failures_total{protocol=\"grpc\",reason=\"missing_credentials\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_encode_quota_metrics() {
+        let metrics = Metrics::new();
+        metrics.quota_rejected("client", "dashboard", "rate");
+        metrics.quota_active_runs("pipeline", "upper", 2);
+        metrics.quota_fuel_consumed("pipeline", "upper", 1500);
+
+        let text = metrics.encode();
+        assert!(text.contains(
+            "dagwood_quota_rejections_total{name=\"dashboard\",reason=\"rate\",scope=\"client\"} 1"
+        ));
+        assert!(text.contains("dagwood_quota_active_runs{name=\"upper\",scope=\"pipeline\"} 2"));
+        assert!(text
+            .contains("dagwood_quota_fuel_consumed_total{name=\"upper\",scope=\"pipeline\"} 1500"));
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let metrics = Metrics::new();
+        metrics.execution_finished("WorkQueue", true, Duration::from_millis(5));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.prom");
+        metrics.write_to(&path).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("# TYPE dagwood_executions_total counter"));
+        assert!(contents
+            .contains("dagwood_executions_total{result=\"success\",strategy=\"WorkQueue\"} 1"));
+    }
+}
@@ -17,9 +17,18 @@
 //! Messages are organized by subsystem:
 //! * `messages::engine` - DAG executor lifecycle and execution events
 //! * `messages::processor` - Processor execution and lifecycle events
+//! * `messages::server` - Hosted pipeline lifecycle, server and protocol receiver events
 //! * `messages::validation` - Configuration validation warnings and errors
 //! * `messages::wasm` - WASM backend loading and execution events
 //!
+//! Span-based exporters build on those messages:
+//! * `chrome_trace` - Chrome Trace Event / Perfetto export of execution spans
+//! * `otel` - OpenTelemetry OTLP export of execution spans (`otel` feature)
+//! * `trace_context` - W3C Trace Context parsing and propagation to backends
+//!
+//! Metrics are collected separately in a Prometheus registry:
+//! * `metrics` - Processor, executor and WASM metrics in Prometheus text format
+//!
 //! # Usage
 //!
 //! ```rust
@@ -38,4 +47,9 @@
 //!
 //! See ADR 18 for detailed rationale on observability implementation choices.
 
+pub mod chrome_trace;
 pub mod messages;
+pub mod metrics;
+#[cfg(feature = "otel")]
+pub mod otel;
+pub mod trace_context;
@@ -0,0 +1,240 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! OpenTelemetry OTLP trace export (requires the `otel` feature).
+//!
+//! [`OtlpTracing`] builds an OTLP/gRPC span exporter and a `tracing_subscriber` layer that
+//! turns the spans created through `StructuredLog::span` into OpenTelemetry spans. Every DAG
+//! execution becomes a trace with a `dag_execution` root (or a `dag_request` span continuing
+//! an incoming W3C trace, see [`trace_context`](super::trace_context)) and one child span per
+//! processor invocation.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use the_dagwood::observability::otel::OtlpTracing;
+//! use tracing_subscriber::prelude::*;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let otlp = OtlpTracing::new("http://localhost:4317", "the-dagwood")?;
+//! tracing_subscriber::registry().with(otlp.layer()).init();
+//!
+//! // ... run pipelines ...
+//!
+//! // Dropping the exporter flushes spans that have not been exported yet
+//! drop(otlp);
+//! # Ok(())
+//! # }
+//! ```
+
+use opentelemetry::trace::{TraceError, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+const INSTRUMENTATION_NAME: &str = "the-dagwood";
+const CRATE_TARGET: &str = "the_dagwood";
+
+/// OTLP span exporter feeding a `tracing` layer.
+///
+/// Spans are exported in batches on the tokio runtime, so the exporter must be created from
+/// within a runtime. Dropping it flushes pending spans and shuts the exporter down; like
+/// [`force_flush`](Self::force_flush), this blocks until the export completes.
+pub struct OtlpTracing {
+    provider: TracerProvider,
+}
+
+impl OtlpTracing {
+    /// Create an exporter sending spans to the OTLP/gRPC collector at `endpoint`
+    /// (e.g. `http://localhost:4317`), reporting as `service_name`.
+    pub fn new(endpoint: &str, service_name: &str) -> Result<Self, TraceError> {
+        let exporter = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(endpoint)
+            .build_span_exporter()?;
+
+        let provider = TracerProvider::builder()
+            .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+            .with_config(
+                opentelemetry_sdk::trace::config().with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", service_name.to_string()),
+                ])),
+            )
+            .build();
+
+        Ok(Self { provider })
+    }
+
+    /// Layer converting `tracing` spans into OpenTelemetry spans exported by this exporter.
+    ///
+    /// Only spans from this crate are exported; spans of the gRPC stack used by the exporter
+    /// itself would otherwise be exported in an endless loop.
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        tracing_opentelemetry::layer()
+            .with_tracer(self.tracer())
+            .with_filter(Targets::new().with_target(CRATE_TARGET, Level::TRACE))
+    }
+
+    /// Export all finished spans now, returning the first export error if any.
+    ///
+    /// Blocks the calling thread until the export completes; call it from a blocking context
+    /// (e.g. `tokio::task::spawn_blocking`) when running on a single-threaded runtime.
+    pub fn force_flush(&self) -> Result<(), TraceError> {
+        self.provider.force_flush().into_iter().collect()
+    }
+
+    fn tracer(&self) -> Tracer {
+        self.provider.tracer(INSTRUMENTATION_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::stub::StubProcessor;
+    use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
+    use crate::engine::{ExecutionPlan, WorkQueueExecutor};
+    use crate::errors::FailureStrategy;
+    use crate::observability::trace_context::TraceContext;
+    use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+    use crate::traits::{DagExecutor, Processor};
+    use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+        TraceService, TraceServiceServer,
+    };
+    use opentelemetry_proto::tonic::collector::trace::v1::{
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+    };
+    use opentelemetry_proto::tonic::trace::v1::Span as ProtoSpan;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::prelude::*;
+
+    /// OTLP collector that keeps every exported span in memory
+    #[derive(Clone, Default)]
+    struct MockCollector {
+        spans: Arc<Mutex<Vec<ProtoSpan>>>,
+    }
+
+    #[tonic::async_trait]
+    impl TraceService for MockCollector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportTraceServiceRequest>,
+        ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+            let mut spans = self.spans.lock().unwrap();
+            for resource_spans in request.into_inner().resource_spans {
+                for scope_spans in resource_spans.scope_spans {
+                    spans.extend(scope_spans.spans);
+                }
+            }
+            Ok(tonic::Response::new(ExportTraceServiceResponse {
+                partial_success: None,
+            }))
+        }
+    }
+
+    async fn start_collector() -> (MockCollector, String) {
+        let collector = MockCollector::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = TraceServiceServer::new(collector.clone());
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(server)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        (collector, endpoint)
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    // Current-thread runtime: spawned processor tasks run on the test thread, so the
+    // thread-local subscriber installed below sees their spans too
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_execution_exported_to_collector() {
+        let (collector, endpoint) = start_collector().await;
+        let otlp = OtlpTracing::new(&endpoint, "dagwood-test").unwrap();
+        let _subscriber = tracing_subscriber::registry()
+            .with(otlp.layer())
+            .set_default();
+
+        let mut processors = ProcessorMap::new();
+        for id in ["A", "B"] {
+            processors.insert(
+                id.to_string(),
+                Arc::new(StubProcessor::new(id.to_string())) as Arc<dyn Processor>,
+            );
+        }
+        let graph = DependencyGraph(HashMap::from([
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec![]),
+        ]));
+        let entrypoints = EntryPoints(vec!["A".to_string()]);
+        let plan = ExecutionPlan::compile(processors, graph, entrypoints).unwrap();
+
+        let incoming =
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        let report = WorkQueueExecutor::new(2)
+            .execute_plan_traced(
+                Arc::new(plan),
+                ProcessorRequest {
+                    payload: b"hello".to_vec(),
+                },
+                PipelineMetadata::new(),
+                FailureStrategy::FailFast,
+                Some(incoming.clone()),
+            )
+            .await
+            .unwrap();
+        assert!(report.is_success());
+
+        // The batch exporter runs on this runtime, so flush and shut it down from a blocking
+        // thread; blocking the runtime thread itself would deadlock
+        tokio::task::spawn_blocking(move || {
+            let flushed = otlp.force_flush();
+            drop(otlp);
+            flushed
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+        let spans = collector.spans.lock().unwrap().clone();
+        let request_span = spans
+            .iter()
+            .find(|span| span.name == "dag_request")
+            .expect("dag_request span exported");
+        assert_eq!(hex(&request_span.trace_id), incoming.trace_id());
+        assert_eq!(hex(&request_span.parent_span_id), incoming.span_id());
+
+        let execution_span = spans
+            .iter()
+            .find(|span| span.name == "execution")
+            .expect("dag_execution span exported");
+        assert_eq!(execution_span.parent_span_id, request_span.span_id);
+
+        // One span per processor invocation, all in the incoming trace under the DAG span
+        let invocations: Vec<&ProtoSpan> = spans
+            .iter()
+            .filter(|span| span.name == "processor_execution_started")
+            .filter(|span| span.parent_span_id == execution_span.span_id)
+            .collect();
+        assert_eq!(invocations.len(), 2);
+        assert!(invocations
+            .iter()
+            .all(|span| hex(&span.trace_id) == incoming.trace_id()));
+    }
+}
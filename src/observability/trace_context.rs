@@ -0,0 +1,303 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! W3C Trace Context propagation for DAG executions.
+//!
+//! A [`TraceContext`] is the parsed form of a W3C `traceparent` value
+//! (`00-<trace-id>-<parent-id>-<flags>`). It is used in both directions:
+//!
+//! * **Incoming**: an execution started through `DagExecutor::execute_plan_traced` with a
+//!   trace context runs inside a `dag_request` span that continues the caller's trace. The
+//!   protocol receivers read the caller's context from the `traceparent` HTTP header or gRPC
+//!   metadata entry, or the Unix socket request frame, and execute the request the same way
+//! * **Outgoing**: [`TraceContext::current`] captures the span a processor is running in, so it
+//!   can be forwarded to remote backends as gRPC metadata, HTTP headers, or a WASM processor
+//!   metadata entry
+//!
+//! Continuing a remote trace and capturing the current span's context require the OTel
+//! pipeline (`otel` feature). Without it, incoming contexts are still recorded as span fields
+//! and [`TraceContext::current`] returns `None`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use the_dagwood::observability::trace_context::TraceContext;
+//!
+//! let context = TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+//!     .expect("valid traceparent");
+//!
+//! assert!(context.is_sampled());
+//! assert_eq!(context.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+//!
+//! // Invalid values are ignored and a new trace is started instead
+//! assert!(TraceContext::parse("not-a-traceparent").is_none());
+//! ```
+
+use std::collections::HashMap;
+
+use tracing::Span;
+
+/// Header (HTTP) and metadata key (gRPC) carrying the trace context
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Key of the trace context entry in WASM processor metadata
+pub const TRACE_CONTEXT_METADATA_KEY: &str = "traceparent";
+
+/// Span name of the span that continues an incoming trace around a DAG execution
+pub const DAG_REQUEST_SPAN: &str = "dag_request";
+
+const VERSION: &str = "00";
+const SAMPLED_FLAG: u8 = 0x01;
+
+/// Parsed W3C `traceparent`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: String,
+    span_id: String,
+    trace_flags: u8,
+}
+
+impl TraceContext {
+    /// Parse a W3C `traceparent` value.
+    ///
+    /// Returns `None` for malformed values and for the all-zero trace and span IDs the
+    /// specification declares invalid; callers should then start a new trace.
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        // Version 00 has exactly four fields; future versions may append more
+        if version.len() != 2 || !is_lower_hex(version) || version == "ff" {
+            return None;
+        }
+        if version == VERSION && parts.next().is_some() {
+            return None;
+        }
+        if !is_valid_id(trace_id, 32) || !is_valid_id(span_id, 16) {
+            return None;
+        }
+        if flags.len() != 2 {
+            return None;
+        }
+        let trace_flags = u8::from_str_radix(flags, 16).ok()?;
+
+        Some(Self {
+            trace_id: trace_id.to_string(),
+            span_id: span_id.to_string(),
+            trace_flags,
+        })
+    }
+
+    /// 32 hex character trace ID shared by every span in the trace
+    pub fn trace_id(&self) -> &str {
+        &self.trace_id
+    }
+
+    /// 16 hex character ID of the parent span
+    pub fn span_id(&self) -> &str {
+        &self.span_id
+    }
+
+    /// Whether the caller sampled (recorded) the trace
+    pub fn is_sampled(&self) -> bool {
+        self.trace_flags & SAMPLED_FLAG != 0
+    }
+
+    /// Format as a W3C `traceparent` value
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "{}-{}-{}-{:02x}",
+            VERSION, self.trace_id, self.span_id, self.trace_flags
+        )
+    }
+
+    /// Trace context of the span currently being executed.
+    ///
+    /// Returns `None` when no OTel layer is installed or the current span is not recorded.
+    pub fn current() -> Option<Self> {
+        #[cfg(feature = "otel")]
+        {
+            use opentelemetry::trace::TraceContextExt;
+            use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+            let context = Span::current().context();
+            let span = context.span();
+            let span_context = span.span_context();
+            if !span_context.is_valid() {
+                return None;
+            }
+            Some(Self {
+                trace_id: span_context.trace_id().to_string(),
+                span_id: span_context.span_id().to_string(),
+                trace_flags: span_context.trace_flags().to_u8(),
+            })
+        }
+
+        #[cfg(not(feature = "otel"))]
+        {
+            None
+        }
+    }
+
+    /// Create the span a DAG execution runs in to continue this trace.
+    ///
+    /// The executor's `dag_execution` span, and every processor span below it, become children
+    /// of the returned span; with the `otel` feature the span's parent is the remote caller.
+    pub fn execution_span(&self) -> Span {
+        let span = tracing::info_span!(
+            "dag_request",
+            span_name = DAG_REQUEST_SPAN,
+            trace_id = %self.trace_id,
+            parent_span_id = %self.span_id,
+        );
+
+        #[cfg(feature = "otel")]
+        {
+            use tracing_opentelemetry::OpenTelemetrySpanExt;
+            span.set_parent(self.otel_context());
+        }
+
+        span
+    }
+
+    /// HTTP headers carrying this trace context to an HTTP backend
+    pub fn http_headers(&self) -> Vec<(&'static str, String)> {
+        vec![(TRACEPARENT_HEADER, self.to_traceparent())]
+    }
+
+    /// Read a trace context from incoming HTTP request headers
+    pub fn extract_http(headers: &hyper::HeaderMap) -> Option<Self> {
+        headers
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::parse)
+    }
+
+    /// Add this trace context to outgoing gRPC request metadata
+    pub fn inject_grpc(&self, metadata: &mut tonic::metadata::MetadataMap) {
+        if let Ok(value) = self.to_traceparent().parse() {
+            metadata.insert(TRACEPARENT_HEADER, value);
+        }
+    }
+
+    /// Read a trace context from incoming gRPC request metadata
+    pub fn extract_grpc(metadata: &tonic::metadata::MetadataMap) -> Option<Self> {
+        metadata
+            .get(TRACEPARENT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::parse)
+    }
+
+    /// Add this trace context as an entry of processor metadata (used by WASM processors)
+    pub fn inject_metadata(&self, metadata: &mut HashMap<String, String>) {
+        metadata.insert(
+            TRACE_CONTEXT_METADATA_KEY.to_string(),
+            self.to_traceparent(),
+        );
+    }
+
+    #[cfg(feature = "otel")]
+    fn otel_context(&self) -> opentelemetry::Context {
+        use opentelemetry::trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+
+        let span_context = SpanContext::new(
+            TraceId::from_hex(&self.trace_id).unwrap_or(TraceId::INVALID),
+            SpanId::from_hex(&self.span_id).unwrap_or(SpanId::INVALID),
+            TraceFlags::new(self.trace_flags),
+            true,
+            TraceState::default(),
+        );
+        opentelemetry::Context::new().with_remote_span_context(span_context)
+    }
+}
+
+fn is_lower_hex(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c))
+}
+
+fn is_valid_id(value: &str, length: usize) -> bool {
+    value.len() == length && is_lower_hex(value) && value.chars().any(|c| c != '0')
+}
+
+/// Trace IDs recorded on the `dag_request` spans of `trace`
+#[cfg(test)]
+pub(crate) fn dag_request_trace_ids(
+    trace: &crate::observability::chrome_trace::ChromeTrace,
+) -> Vec<String> {
+    let trace: serde_json::Value = serde_json::from_str(&trace.to_json()).unwrap();
+    trace["traceEvents"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|event| event["cat"] == DAG_REQUEST_SPAN)
+        .filter_map(|event| event["args"]["trace_id"].as_str().map(str::to_string))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_parse_round_trip() {
+        let context = TraceContext::parse(TRACEPARENT).unwrap();
+
+        assert_eq!(context.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(context.span_id(), "00f067aa0ba902b7");
+        assert!(context.is_sampled());
+        assert_eq!(context.to_traceparent(), TRACEPARENT);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_values() {
+        let invalid = [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-zz",
+        ];
+
+        for value in invalid {
+            assert!(TraceContext::parse(value).is_none(), "accepted {:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_propagation_carriers() {
+        let context = TraceContext::parse(TRACEPARENT).unwrap();
+
+        let mut grpc = tonic::metadata::MetadataMap::new();
+        context.inject_grpc(&mut grpc);
+        assert_eq!(TraceContext::extract_grpc(&grpc), Some(context.clone()));
+
+        assert_eq!(
+            context.http_headers(),
+            vec![(TRACEPARENT_HEADER, TRACEPARENT.to_string())]
+        );
+        let mut http = hyper::HeaderMap::new();
+        for (name, value) in context.http_headers() {
+            http.insert(name, value.parse().unwrap());
+        }
+        assert_eq!(TraceContext::extract_http(&http), Some(context.clone()));
+
+        let mut metadata = HashMap::new();
+        context.inject_metadata(&mut metadata);
+        assert_eq!(
+            metadata.get(TRACE_CONTEXT_METADATA_KEY).map(String::as_str),
+            Some(TRACEPARENT)
+        );
+    }
+}
@@ -7,3 +7,7 @@ pub mod processor_v1;
 
 // Re-export the types for easier access
 pub use processor_v1::{ProcessorRequest, ProcessorResponse};
+
+/// Encoded `FileDescriptorSet` of `processor.proto`, served by the gRPC reflection service
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/processor_descriptor.bin"));
@@ -0,0 +1,89 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use crate::config::{ProtocolConfig, ProtocolType};
+use crate::errors::ProtocolError;
+use crate::protocols::{
+    GrpcProtocolReceiver, HttpProtocolReceiver, ProtocolReceiver, UnixSocketProtocolReceiver,
+};
+
+/// Factory creating protocol receivers from their configuration.
+pub struct ProtocolReceiverFactory;
+
+impl ProtocolReceiverFactory {
+    /// Create a receiver for the configured protocol.
+    ///
+    /// The receiver's options are validated here, so configuration mistakes are reported
+    /// before any receiver binds its endpoint.
+    pub fn create(config: &ProtocolConfig) -> Result<Box<dyn ProtocolReceiver>, ProtocolError> {
+        match config.protocol {
+            ProtocolType::Http => Ok(Box::new(HttpProtocolReceiver::from_config(config)?)),
+            ProtocolType::Grpc => Ok(Box::new(GrpcProtocolReceiver::from_config(config)?)),
+            ProtocolType::UnixSocket => {
+                Ok(Box::new(UnixSocketProtocolReceiver::from_config(config)?))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+
+    #[test]
+    fn test_creates_receivers_from_config() {
+        let config = ServerConfig::from_yaml(
+            r#"
+protocols:
+  - type: http
+    name: public
+    options:
+      port: 0
+  - type: grpc
+    options:
+      port: 0
+      pipeline: upper
+  - type: unix_socket
+    name: local
+    options:
+      path: /tmp/dagwood-test.sock
+pipelines: []
+"#,
+        )
+        .unwrap();
+
+        let receiver = ProtocolReceiverFactory::create(&config.protocols[0]).unwrap();
+        assert_eq!(receiver.protocol_name(), "http");
+        assert_eq!(receiver.name(), "public");
+        assert_eq!(receiver.endpoint(), None);
+
+        let receiver = ProtocolReceiverFactory::create(&config.protocols[1]).unwrap();
+        assert_eq!(receiver.protocol_name(), "grpc");
+        assert_eq!(receiver.name(), "grpc");
+
+        let receiver = ProtocolReceiverFactory::create(&config.protocols[2]).unwrap();
+        assert_eq!(receiver.protocol_name(), "unix_socket");
+        assert_eq!(receiver.name(), "local");
+    }
+
+    #[test]
+    fn test_invalid_options_are_rejected() {
+        let config = ServerConfig::from_yaml(
+            r#"
+protocols:
+  - type: http
+    options:
+      port: "eighty"
+pipelines: []
+"#,
+        )
+        .unwrap();
+
+        let error = ProtocolReceiverFactory::create(&config.protocols[0])
+            .err()
+            .unwrap();
+        assert!(matches!(error, ProtocolError::InvalidOptions { .. }));
+        assert!(error.to_string().contains("port"));
+    }
+}
@@ -0,0 +1,1025 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! gRPC protocol receiver.
+//!
+//! Serves hosted pipelines as the canonical `processor.v1.Processor` service from
+//! `proto/processor.proto`, so a whole pipeline looks like a single processor to its callers
+//! and can be composed into other pipelines.
+//!
+//! # Addressing
+//! * `x-dagwood-pipeline` request metadata names the pipeline to execute
+//! * A receiver configured with a `pipeline` option serves only that pipeline, so the
+//!   metadata can be omitted (one port per pipeline)
+//! * `x-dagwood-callback-url` request metadata names a URL notified once the execution
+//!   finished, for pipelines whose webhook allows request callbacks
+//! * W3C `traceparent` request metadata continues the caller's trace in the execution's
+//!   spans (see [`crate::observability::trace_context`])
+//!
+//! # Responses
+//! The final payload is returned as `next_payload` with the accumulated pipeline metadata;
+//! processor failures are returned as an `error` outcome (code 500), exactly like a failing
+//! processor. The run ID is sent back in the `x-dagwood-run-id` response metadata.
+//!
+//! Requests that cannot be executed fail with a gRPC status: `NOT_FOUND` for unknown
+//! pipelines, `UNAVAILABLE` for pipelines that are initializing (with a `retry-after-ms`
+//! hint) or failed to initialize, `RESOURCE_EXHAUSTED` for requests over the quota of their
+//! client or pipeline (with a `retry-after-ms` hint where waiting helps), and
+//! `INVALID_ARGUMENT` for unaddressed requests.
+//!
+//! # Authentication & TLS
+//! When the server has `auth` enabled (see [`crate::server::auth`]), requests present their
+//! API key as `x-api-key` metadata or an `authorization: Bearer <key>` token; otherwise the
+//! client certificate of a mutual TLS connection identifies them. Unauthenticated requests
+//! fail with `UNAUTHENTICATED` and requests for pipelines outside the client's allowlist with
+//! `PERMISSION_DENIED`.
+//!
+//! With `tls_cert` and `tls_key` the receiver only accepts TLS connections, and with
+//! `tls_client_ca` only those presenting a client certificate issued by that CA (mTLS).
+//! Handshakes are run concurrently to accepting connections, so a stalled client does not
+//! hold up others.
+//!
+//! # Reflection & Health
+//! The receiver also serves `grpc.reflection.v1alpha.ServerReflection` (option `reflection`)
+//! and `grpc.health.v1.Health` (option `health`). Health is reported for the
+//! `processor.v1.Processor` service and for every pipeline by name; a pipeline is
+//! `NOT_SERVING` once it has permanently failed to initialize.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::metadata::MetadataValue;
+use tonic::server::NamedService;
+use tonic::{Code, Request, Response, Status};
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
+
+use crate::config::ProtocolConfig;
+use crate::errors::ProtocolError;
+use crate::observability::trace_context::TraceContext;
+use crate::proto::processor_v1::processor_response::Outcome;
+use crate::proto::processor_v1::processor_server::{Processor, ProcessorServer};
+use crate::proto::processor_v1::{ErrorDetail, ProcessorRequest, ProcessorResponse};
+use crate::proto::FILE_DESCRIPTOR_SET;
+use crate::protocols::options::ProtocolOptions;
+use crate::protocols::tls::{self, TlsConnectInfo};
+use crate::protocols::{ErrorKind, ErrorResponse, PipelineRequest, ProtocolReceiver};
+use crate::server::auth::API_KEY_HEADER;
+use crate::server::{Credential, PipelineRouter, PipelineState};
+
+/// Request metadata key naming the pipeline to execute
+pub const PIPELINE_METADATA_KEY: &str = "x-dagwood-pipeline";
+/// Request metadata key naming the callback URL of the execution
+pub const CALLBACK_URL_METADATA_KEY: &str = "x-dagwood-callback-url";
+/// Response metadata key carrying the run ID of the execution
+pub const RUN_ID_METADATA_KEY: &str = "x-dagwood-run-id";
+/// Response metadata key with the suggested retry delay of `UNAVAILABLE` responses
+pub const RETRY_AFTER_METADATA_KEY: &str = "retry-after-ms";
+
+/// Default bind host; only local clients can connect unless configured otherwise
+pub const DEFAULT_GRPC_HOST: &str = "127.0.0.1";
+/// Default bind port
+pub const DEFAULT_GRPC_PORT: u16 = 50051;
+/// Default maximum request and response message size (4 MiB, as tonic)
+pub const DEFAULT_MAX_MESSAGE_BYTES: usize = 4 * 1024 * 1024;
+/// Default time a request waits for its pipeline to initialize before `UNAVAILABLE`
+pub const DEFAULT_INIT_WAIT_MS: u64 = 30_000;
+/// How often pipeline health is refreshed from the pipeline lifecycle states
+pub const HEALTH_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+/// TLS connections handed to the server but not yet served
+const TLS_CONNECTION_BACKLOG: usize = 128;
+
+const PROTOCOL_NAME: &str = "grpc";
+
+/// Options of a gRPC receiver, from the `options` of its protocol configuration.
+///
+/// # Example
+/// ```yaml
+/// type: grpc
+/// options:
+///   host: "0.0.0.0"
+///   port: 50051
+///   pipeline: text_processing   # optional: serve only this pipeline
+///   reflection: true
+///   health: true
+///   max_message_bytes: 4194304
+///   init_wait_ms: 5000
+///   tls_cert: /etc/dagwood/server.pem      # optional: serve over TLS
+///   tls_key: /etc/dagwood/server.key
+///   tls_client_ca: /etc/dagwood/clients.pem # optional: require client certificates
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrpcReceiverOptions {
+    pub host: String,
+    /// Port to bind; `0` picks a free port
+    pub port: u16,
+    /// Serve only this pipeline, without requiring `x-dagwood-pipeline` metadata
+    pub pipeline: Option<String>,
+    /// Serve the gRPC server reflection service
+    pub reflection: bool,
+    /// Serve the gRPC health checking service
+    pub health: bool,
+    pub max_message_bytes: usize,
+    /// How long a request waits for an initializing pipeline before `UNAVAILABLE`
+    pub init_wait: Duration,
+    /// PEM certificate chain served over TLS, together with `tls_key`
+    pub tls_cert: Option<PathBuf>,
+    /// PEM private key of `tls_cert`
+    pub tls_key: Option<PathBuf>,
+    /// PEM bundle of the CAs issuing client certificates; connections without one are refused
+    pub tls_client_ca: Option<PathBuf>,
+}
+
+impl Default for GrpcReceiverOptions {
+    fn default() -> Self {
+        Self {
+            host: DEFAULT_GRPC_HOST.to_string(),
+            port: DEFAULT_GRPC_PORT,
+            pipeline: None,
+            reflection: true,
+            health: true,
+            max_message_bytes: DEFAULT_MAX_MESSAGE_BYTES,
+            init_wait: Duration::from_millis(DEFAULT_INIT_WAIT_MS),
+            tls_cert: None,
+            tls_key: None,
+            tls_client_ca: None,
+        }
+    }
+}
+
+impl GrpcReceiverOptions {
+    /// Parse the receiver options, falling back to the defaults for missing keys.
+    pub fn from_options(
+        options: &HashMap<String, serde_yaml::Value>,
+    ) -> Result<Self, ProtocolError> {
+        let options = ProtocolOptions::new(PROTOCOL_NAME, options);
+        let defaults = Self::default();
+
+        let port = match options.u64("port")? {
+            Some(port) => {
+                u16::try_from(port).map_err(|_| options.invalid("port", "a port number"))?
+            }
+            None => defaults.port,
+        };
+        let max_message_bytes = match options.u64("max_message_bytes")? {
+            Some(bytes) => usize::try_from(bytes)
+                .map_err(|_| options.invalid("max_message_bytes", "a byte count"))?,
+            None => defaults.max_message_bytes,
+        };
+
+        let tls_cert = options.string("tls_cert")?.map(PathBuf::from);
+        let tls_key = options.string("tls_key")?.map(PathBuf::from);
+        let tls_client_ca = options.string("tls_client_ca")?.map(PathBuf::from);
+        if tls_cert.is_some() != tls_key.is_some() {
+            return Err(options.invalid("tls_cert", "given together with 'tls_key'"));
+        }
+        if tls_client_ca.is_some() && tls_cert.is_none() {
+            return Err(options.invalid("tls_client_ca", "given with 'tls_cert' and 'tls_key'"));
+        }
+
+        Ok(Self {
+            host: options.string("host")?.unwrap_or(defaults.host),
+            port,
+            pipeline: options.string("pipeline")?,
+            reflection: options.bool("reflection")?.unwrap_or(defaults.reflection),
+            health: options.bool("health")?.unwrap_or(defaults.health),
+            max_message_bytes,
+            init_wait: options
+                .u64("init_wait_ms")?
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.init_wait),
+            tls_cert,
+            tls_key,
+            tls_client_ca,
+        })
+    }
+
+    /// `host:port` address to bind.
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Whether the receiver serves over TLS.
+    pub fn tls(&self) -> bool {
+        self.tls_cert.is_some()
+    }
+
+    /// TLS acceptor of the configured certificate, if any
+    fn tls_acceptor(&self) -> Result<Option<TlsAcceptor>, ProtocolError> {
+        let (Some(cert), Some(key)) = (&self.tls_cert, &self.tls_key) else {
+            return Ok(None);
+        };
+        let config =
+            tls::server_config(cert, key, self.tls_client_ca.as_deref()).map_err(|reason| {
+                ProtocolError::InvalidOptions {
+                    protocol: PROTOCOL_NAME.to_string(),
+                    reason,
+                }
+            })?;
+        Ok(Some(TlsAcceptor::from(Arc::new(config))))
+    }
+}
+
+/// A running server: its bound address and how to stop it
+struct RunningServer {
+    local_addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<Result<(), tonic::transport::Error>>,
+    health_refresh: Option<JoinHandle<()>>,
+    /// Accept loop handing TLS connections to the server
+    accept: Option<JoinHandle<()>>,
+}
+
+impl RunningServer {
+    /// Stop the background tasks serving alongside the server
+    fn abort_tasks(&self) {
+        for task in [&self.health_refresh, &self.accept].into_iter().flatten() {
+            task.abort();
+        }
+    }
+}
+
+/// Receiver serving pipelines as `processor.v1.Processor` over gRPC with tonic.
+pub struct GrpcProtocolReceiver {
+    name: String,
+    options: GrpcReceiverOptions,
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl GrpcProtocolReceiver {
+    /// Create a receiver with the given instance name and options.
+    pub fn new(name: impl Into<String>, options: GrpcReceiverOptions) -> Self {
+        Self {
+            name: name.into(),
+            options,
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Create a receiver from its protocol configuration.
+    pub fn from_config(config: &ProtocolConfig) -> Result<Self, ProtocolError> {
+        Ok(Self::new(
+            config.name(),
+            GrpcReceiverOptions::from_options(&config.options)?,
+        ))
+    }
+
+    /// Options the receiver was created with.
+    pub fn options(&self) -> &GrpcReceiverOptions {
+        &self.options
+    }
+
+    /// Address the receiver is listening on, once started.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|running| running.local_addr)
+    }
+
+    fn already_started(&self) -> ProtocolError {
+        ProtocolError::AlreadyStarted {
+            name: self.name.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProtocolReceiver for GrpcProtocolReceiver {
+    async fn start(&self, router: Arc<PipelineRouter>) -> Result<(), ProtocolError> {
+        if self.running.lock().unwrap().is_some() {
+            return Err(self.already_started());
+        }
+
+        let tls_acceptor = self.options.tls_acceptor()?;
+        let address = self.options.bind_address();
+        let bind_failed = |error: &dyn std::fmt::Display| ProtocolError::BindFailed {
+            endpoint: address.clone(),
+            reason: error.to_string(),
+        };
+        let listener = TcpListener::bind(&address)
+            .await
+            .map_err(|e| bind_failed(&e))?;
+        let local_addr = listener.local_addr().map_err(|e| bind_failed(&e))?;
+
+        let reflection = if self.options.reflection {
+            let service = tonic_reflection::server::Builder::configure()
+                .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+                .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+                .build()
+                .map_err(|e| ProtocolError::ServeFailed {
+                    name: self.name.clone(),
+                    reason: format!("failed to build reflection service: {}", e),
+                })?;
+            Some(service)
+        } else {
+            None
+        };
+
+        let (health, health_refresh) = if self.options.health {
+            let (reporter, service) = tonic_health::server::health_reporter();
+            let health = PipelineHealth {
+                reporter,
+                router: router.clone(),
+                pipeline: self.options.pipeline.clone(),
+            };
+            health.refresh().await;
+            (Some(service), Some(tokio::spawn(health.refresh_forever())))
+        } else {
+            (None, None)
+        };
+
+        let processor = ProcessorServer::new(PipelineProcessor {
+            router,
+            pipeline: self.options.pipeline.clone(),
+            init_wait: self.options.init_wait,
+        })
+        .max_decoding_message_size(self.options.max_message_bytes)
+        .max_encoding_message_size(self.options.max_message_bytes);
+
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        let shutdown_signal = async {
+            let _ = shutdown_signal.await;
+        };
+        let server = tonic::transport::Server::builder()
+            .add_service(processor)
+            .add_optional_service(reflection)
+            .add_optional_service(health);
+
+        let mut running = self.running.lock().unwrap();
+        if running.is_some() {
+            // Lost a race with a concurrent start; the listener is dropped unused
+            if let Some(refresh) = health_refresh {
+                refresh.abort();
+            }
+            return Err(self.already_started());
+        }
+        let (task, accept) = match tls_acceptor {
+            Some(acceptor) => {
+                let (connections, incoming) = mpsc::channel(TLS_CONNECTION_BACKLOG);
+                let accept = tls::accept_forever(PROTOCOL_NAME, listener, acceptor, connections);
+                let serve = server
+                    .serve_with_incoming_shutdown(ReceiverStream::new(incoming), shutdown_signal);
+                (tokio::spawn(serve), Some(tokio::spawn(accept)))
+            }
+            None => {
+                let serve = server.serve_with_incoming_shutdown(
+                    TcpListenerStream::new(listener),
+                    shutdown_signal,
+                );
+                (tokio::spawn(serve), None)
+            }
+        };
+        *running = Some(RunningServer {
+            local_addr,
+            shutdown,
+            task,
+            health_refresh,
+            accept,
+        });
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), ProtocolError> {
+        let running = self.running.lock().unwrap().take();
+        let Some(running) = running else {
+            return Ok(());
+        };
+
+        running.abort_tasks();
+        let _ = running.shutdown.send(());
+        let serve_failed = |reason: String| ProtocolError::ServeFailed {
+            name: self.name.clone(),
+            reason,
+        };
+        running
+            .task
+            .await
+            .map_err(|e| serve_failed(e.to_string()))?
+            .map_err(|e| serve_failed(e.to_string()))
+    }
+
+    fn protocol_name(&self) -> &str {
+        PROTOCOL_NAME
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        let scheme = if self.options.tls() { "grpcs" } else { "grpc" };
+        self.local_addr()
+            .map(|addr| format!("{}://{}", scheme, addr))
+    }
+}
+
+/// `processor.v1.Processor` implementation executing a hosted pipeline per request
+struct PipelineProcessor {
+    router: Arc<PipelineRouter>,
+    pipeline: Option<String>,
+    init_wait: Duration,
+}
+
+impl PipelineProcessor {
+    /// Pipeline addressed by the request metadata, or the receiver's pipeline; otherwise
+    /// why the request is invalid
+    fn pipeline_name(&self, request: &Request<ProcessorRequest>) -> Result<String, String> {
+        let requested = match request.metadata().get(PIPELINE_METADATA_KEY) {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .map_err(|_| format!("'{}' must be ASCII", PIPELINE_METADATA_KEY))?,
+            ),
+            None => None,
+        };
+
+        match (&self.pipeline, requested) {
+            (Some(served), Some(requested)) if served != requested => Err(format!(
+                "This endpoint serves pipeline '{}', not '{}'",
+                served, requested
+            )),
+            (Some(served), _) => Ok(served.clone()),
+            (None, Some(requested)) => Ok(requested.to_string()),
+            (None, None) => Err(format!(
+                "Missing '{}' request metadata naming the pipeline",
+                PIPELINE_METADATA_KEY
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl Processor for PipelineProcessor {
+    async fn process(
+        &self,
+        request: Request<ProcessorRequest>,
+    ) -> Result<Response<ProcessorResponse>, Status> {
+        let name = self
+            .pipeline_name(&request)
+            .map_err(Status::invalid_argument)?;
+        let callback_url = match request.metadata().get(CALLBACK_URL_METADATA_KEY) {
+            Some(value) => Some(value.to_str().map_err(|_| {
+                Status::invalid_argument(format!("'{}' must be ASCII", CALLBACK_URL_METADATA_KEY))
+            })?),
+            None => None,
+        }
+        .map(str::to_string);
+        let credential = credential(&request);
+        let trace_context = TraceContext::extract_grpc(request.metadata());
+        let mut pipeline_request = PipelineRequest::new(name, request.into_inner().payload)
+            .with_context("protocol", PROTOCOL_NAME)
+            .with_credential(credential)
+            .with_trace_context(trace_context);
+        pipeline_request.callback_url = callback_url;
+
+        let (run_id, response) = match pipeline_request
+            .dispatch(&self.router, self.init_wait)
+            .await
+        {
+            Ok(response) => (
+                response.run_id,
+                ProcessorResponse {
+                    outcome: Some(Outcome::NextPayload(response.payload)),
+                    metadata: Some(response.metadata),
+                },
+            ),
+            Err(error) if error.kind == ErrorKind::ProcessorFailed => (
+                error.run_id.clone().unwrap_or_default(),
+                ProcessorResponse {
+                    outcome: Some(Outcome::Error(ErrorDetail {
+                        code: 500,
+                        message: failure_message(&error),
+                    })),
+                    metadata: None,
+                },
+            ),
+            Err(error) => return Err(status(&error)),
+        };
+
+        let mut response = Response::new(response);
+        if let Ok(run_id) = MetadataValue::try_from(run_id) {
+            response.metadata_mut().insert(RUN_ID_METADATA_KEY, run_id);
+        }
+        Ok(response)
+    }
+}
+
+/// API key of the request metadata, or else the client certificate of its TLS connection
+fn credential(request: &Request<ProcessorRequest>) -> Option<Credential> {
+    let metadata = |key: &str| {
+        request
+            .metadata()
+            .get(key)
+            .and_then(|value| value.to_str().ok())
+    };
+    Credential::from_headers(metadata(API_KEY_HEADER), metadata("authorization")).or_else(|| {
+        request
+            .extensions()
+            .get::<TlsConnectInfo>()
+            .and_then(|info| info.client_certificate.clone())
+            .map(Credential::Certificate)
+    })
+}
+
+/// Error message listing every failed processor
+fn failure_message(error: &ErrorResponse) -> String {
+    let failures: Vec<String> = error
+        .failures
+        .iter()
+        .map(|failure| format!("{}: {}", failure.processor_id, failure.error))
+        .collect();
+    format!("{} ({})", error.message, failures.join("; "))
+}
+
+/// gRPC status of a request that could not be executed
+fn status(error: &ErrorResponse) -> Status {
+    let code = match error.kind {
+        ErrorKind::PipelineNotFound | ErrorKind::JobNotFound => Code::NotFound,
+        ErrorKind::PipelineNotReady | ErrorKind::PipelineUnavailable => Code::Unavailable,
+        ErrorKind::JobConflict => Code::FailedPrecondition,
+        ErrorKind::InvalidRequest => Code::InvalidArgument,
+        ErrorKind::Unauthenticated => Code::Unauthenticated,
+        ErrorKind::Forbidden => Code::PermissionDenied,
+        ErrorKind::PayloadTooLarge | ErrorKind::RateLimited => Code::ResourceExhausted,
+        ErrorKind::InvalidConfig => Code::FailedPrecondition,
+        ErrorKind::ProcessorFailed | ErrorKind::Internal => Code::Internal,
+    };
+
+    let mut status = Status::new(code, error.message.clone());
+    if let Some(retry_after) = error.retry_after {
+        if let Ok(value) = MetadataValue::try_from(retry_after.as_millis().to_string()) {
+            status
+                .metadata_mut()
+                .insert(RETRY_AFTER_METADATA_KEY, value);
+        }
+    }
+    status
+}
+
+/// Publishes pipeline lifecycle states through the gRPC health service
+struct PipelineHealth {
+    reporter: HealthReporter,
+    router: Arc<PipelineRouter>,
+    pipeline: Option<String>,
+}
+
+impl PipelineHealth {
+    /// Update the health of every pipeline and of the processor service
+    async fn refresh(&self) {
+        let mut reporter = self.reporter.clone();
+        let mut processor_status = ServingStatus::Serving;
+
+        for (name, state) in self.router.registry().states() {
+            let status = match state {
+                PipelineState::PermanentlyFailed | PipelineState::Disabled => {
+                    ServingStatus::NotServing
+                }
+                _ => ServingStatus::Serving,
+            };
+            if self.pipeline.as_ref() == Some(&name) {
+                processor_status = status;
+            }
+            reporter.set_service_status(&name, status).await;
+        }
+
+        if let Some(pipeline) = &self.pipeline {
+            if !self.router.registry().contains(pipeline) {
+                processor_status = ServingStatus::NotServing;
+            }
+        }
+        reporter
+            .set_service_status(
+                <ProcessorServer<PipelineProcessor> as NamedService>::NAME,
+                processor_status,
+            )
+            .await;
+    }
+
+    async fn refresh_forever(self) {
+        loop {
+            tokio::time::sleep(HEALTH_REFRESH_INTERVAL).await;
+            self.refresh().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PipelinesConfig;
+    use crate::observability::chrome_trace::ChromeTraceLayer;
+    use crate::observability::trace_context::dag_request_trace_ids;
+    use crate::proto::processor_v1::processor_client::ProcessorClient;
+    use crate::server::{Authenticator, PipelineRegistry};
+    use tonic::transport::Channel;
+    use tonic_health::pb::health_check_response::ServingStatus as HealthStatus;
+    use tonic_health::pb::health_client::HealthClient;
+    use tonic_health::pb::HealthCheckRequest;
+    use tracing_subscriber::prelude::*;
+
+    const PIPELINES: &str = r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: reverse
+    strategy: reactive
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+  - name: broken
+    startup: on-demand
+    initialization:
+      max_retries: 1
+      retry_delay_ms: 10000
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#;
+
+    async fn start_receiver(options: GrpcReceiverOptions) -> (GrpcProtocolReceiver, Channel) {
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let receiver = GrpcProtocolReceiver::new("test", options);
+        receiver
+            .start(Arc::new(PipelineRouter::new(registry)))
+            .await
+            .unwrap();
+
+        let channel = Channel::from_shared(format!("http://{}", receiver.local_addr().unwrap()))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap();
+        (receiver, channel)
+    }
+
+    fn local_options() -> GrpcReceiverOptions {
+        GrpcReceiverOptions {
+            port: 0,
+            init_wait: Duration::from_millis(50),
+            ..GrpcReceiverOptions::default()
+        }
+    }
+
+    fn request(pipeline: Option<&str>, payload: &[u8]) -> Request<ProcessorRequest> {
+        let mut request = Request::new(ProcessorRequest {
+            payload: payload.to_vec(),
+        });
+        if let Some(pipeline) = pipeline {
+            request
+                .metadata_mut()
+                .insert(PIPELINE_METADATA_KEY, pipeline.parse().unwrap());
+        }
+        request
+    }
+
+    #[tokio::test]
+    async fn test_pipelines_addressed_by_metadata() {
+        let (receiver, channel) = start_receiver(local_options()).await;
+        let mut client = ProcessorClient::new(channel);
+
+        let response = client
+            .process(request(Some("upper"), b"hello"))
+            .await
+            .unwrap();
+        assert!(response.metadata().get(RUN_ID_METADATA_KEY).is_some());
+        assert_eq!(
+            response.into_inner().outcome,
+            Some(Outcome::NextPayload(b"HELLO".to_vec()))
+        );
+
+        let response = client
+            .process(request(Some("reverse"), b"hello"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.into_inner().outcome,
+            Some(Outcome::NextPayload(b"olleh".to_vec()))
+        );
+
+        // Processor failures are reported like a failing processor
+        let response = client
+            .process(request(Some("upper"), &[0xff, 0xfe]))
+            .await
+            .unwrap();
+        match response.into_inner().outcome {
+            Some(Outcome::Error(error)) => {
+                assert_eq!(error.code, 500);
+                assert!(error.message.contains("upper"));
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        receiver.shutdown().await.unwrap();
+        assert_eq!(receiver.endpoint(), None);
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_continues_caller_trace() {
+        let (layer, trace) = ChromeTraceLayer::new();
+        let _subscriber = tracing_subscriber::registry().with(layer).set_default();
+        let (receiver, channel) = start_receiver(local_options()).await;
+        let mut client = ProcessorClient::new(channel);
+
+        let incoming =
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        let mut traced = request(Some("upper"), b"hello");
+        incoming.inject_grpc(traced.metadata_mut());
+        client.process(traced).await.unwrap();
+        client
+            .process(request(Some("upper"), b"hello"))
+            .await
+            .unwrap();
+
+        // Only the request that sent a trace context continues it
+        assert_eq!(dag_request_trace_ids(&trace), vec![incoming.trace_id()]);
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_error_statuses() {
+        let (receiver, channel) = start_receiver(local_options()).await;
+        let mut client = ProcessorClient::new(channel);
+
+        let missing = client
+            .process(request(Some("missing"), b"hello"))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), Code::NotFound);
+
+        let unaddressed = client.process(request(None, b"hello")).await.unwrap_err();
+        assert_eq!(unaddressed.code(), Code::InvalidArgument);
+
+        let not_ready = client
+            .process(request(Some("broken"), b"hello"))
+            .await
+            .unwrap_err();
+        assert_eq!(not_ready.code(), Code::Unavailable);
+        assert_eq!(
+            not_ready.metadata().get(RETRY_AFTER_METADATA_KEY).unwrap(),
+            "1000"
+        );
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_receiver_pinned_to_pipeline() {
+        let (receiver, channel) = start_receiver(GrpcReceiverOptions {
+            pipeline: Some("reverse".to_string()),
+            ..local_options()
+        })
+        .await;
+        let mut client = ProcessorClient::new(channel);
+
+        let response = client.process(request(None, b"abc")).await.unwrap();
+        assert_eq!(
+            response.into_inner().outcome,
+            Some(Outcome::NextPayload(b"cba".to_vec()))
+        );
+
+        let mismatch = client
+            .process(request(Some("upper"), b"abc"))
+            .await
+            .unwrap_err();
+        assert_eq!(mismatch.code(), Code::InvalidArgument);
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_service_reports_pipelines() {
+        let (receiver, channel) = start_receiver(local_options()).await;
+        let mut health = HealthClient::new(channel);
+
+        for service in ["", "processor.v1.Processor", "upper", "broken"] {
+            let response = health
+                .check(HealthCheckRequest {
+                    service: service.to_string(),
+                })
+                .await
+                .unwrap();
+            assert_eq!(
+                response.into_inner().status(),
+                HealthStatus::Serving,
+                "service '{}'",
+                service
+            );
+        }
+
+        let unknown = health
+            .check(HealthCheckRequest {
+                service: "missing".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(unknown.code(), Code::NotFound);
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    /// Test certificates of `tests/fixtures/tls`: a CA issuing a server certificate for
+    /// `localhost` and a client certificate
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    /// Channel to a TLS receiver, presenting the fixture client certificate if `client_auth`
+    async fn connect_tls(
+        receiver: &GrpcProtocolReceiver,
+        client_auth: bool,
+    ) -> Result<Channel, tonic::transport::Error> {
+        use rustls_pki_types::pem::PemObject;
+        use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+        use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_file(fixture("ca.pem")).unwrap())
+            .unwrap();
+        let builder = ClientConfig::builder().with_root_certificates(roots);
+        let mut config = if client_auth {
+            let chain = vec![CertificateDer::from_pem_file(fixture("client.pem")).unwrap()];
+            let key = PrivateKeyDer::from_pem_file(fixture("client.key")).unwrap();
+            builder.with_client_auth_cert(chain, key).unwrap()
+        } else {
+            builder.with_no_client_auth()
+        };
+        config.alpn_protocols = vec![b"h2".to_vec()];
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(config));
+
+        let addr = receiver.local_addr().unwrap();
+        tonic::transport::Endpoint::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect_with_connector(tower::service_fn(move |_| {
+                let connector = connector.clone();
+                async move {
+                    let stream = tokio::net::TcpStream::connect(addr).await?;
+                    let server_name = ServerName::try_from("localhost").unwrap();
+                    connector.connect(server_name, stream).await
+                }
+            }))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls_and_authentication() {
+        use rustls_pki_types::pem::PemObject;
+        use rustls_pki_types::CertificateDer;
+        use sha2::{Digest, Sha256};
+
+        let client_certificate = CertificateDer::from_pem_file(fixture("client.pem")).unwrap();
+        let fingerprint: String = Sha256::digest(client_certificate.as_ref())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        let auth: crate::config::AuthConfig = serde_yaml::from_str(&format!(
+            r#"
+enabled: true
+clients:
+  - name: batch
+    certificate_sha256: "{}"
+  - name: dashboard
+    api_key: dashboard-key
+    pipelines: [reverse]
+"#,
+            fingerprint
+        ))
+        .unwrap();
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let router = PipelineRouter::new(registry)
+            .with_auth(Arc::new(Authenticator::from_config(&auth).unwrap()));
+        let receiver = GrpcProtocolReceiver::new(
+            "test",
+            GrpcReceiverOptions {
+                tls_cert: Some(fixture("server.pem")),
+                tls_key: Some(fixture("server.key")),
+                tls_client_ca: Some(fixture("ca.pem")),
+                ..local_options()
+            },
+        );
+        receiver.start(Arc::new(router)).await.unwrap();
+        assert!(receiver.endpoint().unwrap().starts_with("grpcs://"));
+
+        // The client certificate authenticates requests without an API key
+        let mut client = ProcessorClient::new(connect_tls(&receiver, true).await.unwrap());
+        let response = client
+            .process(request(Some("upper"), b"hello"))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.into_inner().outcome,
+            Some(Outcome::NextPayload(b"HELLO".to_vec()))
+        );
+
+        // An API key takes precedence and is limited to its pipelines
+        let with_key = |pipeline: &str, key: &str| {
+            let mut request = request(Some(pipeline), b"hello");
+            request
+                .metadata_mut()
+                .insert("authorization", format!("Bearer {}", key).parse().unwrap());
+            request
+        };
+        assert!(client
+            .process(with_key("reverse", "dashboard-key"))
+            .await
+            .is_ok());
+        let forbidden = client
+            .process(with_key("upper", "dashboard-key"))
+            .await
+            .unwrap_err();
+        assert_eq!(forbidden.code(), Code::PermissionDenied);
+        let unknown = client
+            .process(with_key("upper", "guess"))
+            .await
+            .unwrap_err();
+        assert_eq!(unknown.code(), Code::Unauthenticated);
+
+        // Connections without a client certificate are refused
+        let refused = match connect_tls(&receiver, false).await {
+            Ok(channel) => ProcessorClient::new(channel)
+                .process(request(Some("upper"), b"hello"))
+                .await
+                .is_err(),
+            Err(_) => true,
+        };
+        assert!(refused);
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tls_files() {
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let receiver = GrpcProtocolReceiver::new(
+            "test",
+            GrpcReceiverOptions {
+                tls_cert: Some(fixture("missing.pem")),
+                tls_key: Some(fixture("server.key")),
+                ..local_options()
+            },
+        );
+        let error = receiver
+            .start(Arc::new(PipelineRouter::new(registry)))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ProtocolError::InvalidOptions { .. }));
+        assert!(error.to_string().contains("missing.pem"));
+        assert_eq!(receiver.endpoint(), None);
+    }
+
+    #[test]
+    fn test_options_defaults_and_overrides() {
+        let defaults = GrpcReceiverOptions::from_options(&HashMap::new()).unwrap();
+        assert_eq!(defaults, GrpcReceiverOptions::default());
+        assert_eq!(defaults.bind_address(), "127.0.0.1:50051");
+        assert!(defaults.reflection && defaults.health);
+
+        let options: HashMap<String, serde_yaml::Value> =
+            serde_yaml::from_str("{port: 6000, pipeline: upper, reflection: false}").unwrap();
+        let options = GrpcReceiverOptions::from_options(&options).unwrap();
+        assert_eq!(options.port, 6000);
+        assert_eq!(options.pipeline.as_deref(), Some("upper"));
+        assert!(!options.reflection);
+
+        let invalid: HashMap<String, serde_yaml::Value> =
+            serde_yaml::from_str("{reflection: sometimes}").unwrap();
+        let error = GrpcReceiverOptions::from_options(&invalid).unwrap_err();
+        assert!(error.to_string().contains("reflection"));
+
+        let options: HashMap<String, serde_yaml::Value> =
+            serde_yaml::from_str("{tls_cert: server.pem, tls_key: server.key}").unwrap();
+        let options = GrpcReceiverOptions::from_options(&options).unwrap();
+        assert!(options.tls() && options.tls_client_ca.is_none());
+        for invalid in ["{tls_cert: server.pem}", "{tls_client_ca: ca.pem}"] {
+            let invalid: HashMap<String, serde_yaml::Value> =
+                serde_yaml::from_str(invalid).unwrap();
+            assert!(GrpcReceiverOptions::from_options(&invalid).is_err());
+        }
+    }
+}
@@ -0,0 +1,2057 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! HTTP protocol receiver.
+//!
+//! Serves every hosted pipeline at `POST /pipelines/{name}`.
+//!
+//! # Request
+//! * `Content-Type: application/json` - a JSON object with the payload as UTF-8 text
+//!   (`payload`) or base64 (`payload_base64`), and optional string `metadata` passed to the
+//!   processors as the `initial_context` pipeline metadata:
+//!   `{"payload": "hello world", "metadata": {"source": "docs"}}`
+//! * Any other content type - the body is the raw payload
+//!
+//! A request may name a URL to notify once its execution finished, as the JSON
+//! `callback_url` or the `X-Dagwood-Callback-Url` header; pipelines whose webhook does not
+//! allow request callbacks answer `400` (see [`crate::server::webhooks`]).
+//!
+//! A W3C `traceparent` header continues the caller's trace: the execution's spans become
+//! children of the caller's span (see [`crate::observability::trace_context`]).
+//!
+//! # Response
+//! A JSON object with the pipeline, `run_id`, `strategy`, `duration_ms`, the final payload
+//! (`payload` if it is UTF-8, `payload_base64` otherwise), the designated `outputs` and the
+//! accumulated pipeline `metadata`.
+//!
+//! # Errors
+//! Failed requests get `{"error": {"kind": ..., "message": ..., ...}}` with:
+//! * `400` - the request body could not be decoded
+//! * `404` - no pipeline with that name is registered
+//! * `413` - the body exceeds `max_body_bytes`
+//! * `401` - the server requires authentication and the request presented no known
+//!   credentials (with `WWW-Authenticate: Bearer`)
+//! * `403` - the authenticated client may not execute the pipeline
+//! * `413` - the payload exceeds the quota of the client or pipeline (`payload_too_large`)
+//! * `429` - the quota of the client or pipeline allows no further request right now
+//!   (`rate_limited`, with `Retry-After`)
+//! * `500` - one or more processors failed (listed in `failures`) or the execution failed
+//! * `503` - the pipeline is still initializing (with `Retry-After`), failed to initialize or
+//!   is disabled
+//!
+//! # Streaming
+//! `POST /pipelines/{name}/stream` takes the same body and answers with server-sent events
+//! as the execution progresses, named after the executor event
+//! ([`ExecutionEventKind::name`]): `execution_started`, `processor_started`,
+//! `processor_completed` (with its `output_size`), `processor_failed`, `processor_blocked`,
+//! ... Each carries a JSON object with the event's fields, its `timestamp_ms` and
+//! `elapsed_ms`. The stream ends with a `result` event holding the response above, or an
+//! `error` event holding the HTTP `status` and `error` the request would have failed with.
+//! Events a slow client misses are reported as `lagged` with the number `skipped`.
+//!
+//! Unknown pipelines and undecodable bodies are answered without a stream.
+//!
+//! # Health and status
+//! * `GET /healthz` - `200` while the process serves requests
+//! * `GET /readyz` - `200` once every `startup: auto` pipeline is ready, `503` listing the
+//!   pipelines that are not otherwise
+//! * `GET /pipelines` and `GET /pipelines/{name}` - lifecycle state, strategy, processor count,
+//!   last initialization error, estimated memory and idle time of the pipelines, without
+//!   initializing them
+//! * `GET /metrics` - the process's metrics in the Prometheus text exposition format (see
+//!   [`crate::observability::metrics`])
+//!
+//! # Admin
+//! * `POST /admin/pipelines/{name}/reset` - return a ready or permanently failed pipeline to
+//!   `uninitialized` so its next request initializes it again
+//! * `POST /admin/pipelines/{name}/evict` - release a ready pipeline's processors until its
+//!   next request
+//! * `POST /admin/pipelines/{name}/disable` and `.../enable` - reject the pipeline's requests
+//!   (`503`) until it is enabled again
+//! * `POST /admin/reload` - reload every pipeline from the configuration file (ADR 23); only
+//!   mounted when the server was started with hot reload
+//! * `POST /admin/pipelines/{name}/reload` - rebuild one pipeline from the configuration file,
+//!   even if its configuration did not change
+//! * `GET /admin/quotas` - limits and current usage of every client and pipeline quota (see
+//!   [`crate::server::quotas`]): executions `active`, rate limit `tokens` left, `fuel_used`
+//!   and `fuel_window_remaining_ms` of the current fuel window
+//!
+//! # Authentication
+//! When the server has `auth` enabled (see [`crate::server::auth`]), requests present their
+//! API key as `X-Api-Key: <key>` or `Authorization: Bearer <key>`. Pipeline and job routes
+//! require a client allowed to execute the pipeline, the status routes any known client and
+//! the admin routes an `admin` client. The health checks stay open to probes; scrapers of
+//! `/metrics` present a key like any other status client.
+//!
+//! # Jobs
+//! When the server runs asynchronous jobs (see [`crate::server::jobs`]):
+//! * `POST /pipelines/{name}/jobs` - submit a job with the same body as a synchronous request;
+//!   answers `202` with the queued job and its `Location`
+//! * `GET /jobs` and `GET /jobs/{id}` - state, timestamps and per-processor progress of the
+//!   jobs that have not expired; failed jobs include their `error`
+//! * `GET /jobs/{id}/result` - the response the synchronous request would have returned, or
+//!   its error; `409` (with `Retry-After`) while the job is queued or running, and for
+//!   cancelled jobs
+//! * `POST /jobs/{id}/cancel` - cancel a queued or running job; `409` once it finished
+//!
+//! Unknown and expired jobs get `404` with the `job_not_found` error kind.
+//!
+//! Lifecycle actions answer with the pipeline's resulting status. Evicted and disabled
+//! pipelines finish their in-flight requests first. Reloads answer with the `added`,
+//! `removed`, `modified` and `unchanged` pipelines, or `422` if the new configuration was
+//! rejected; the running pipelines are then left untouched.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{DefaultBodyLimit, Path, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::StreamExt;
+use tower::limit::ConcurrencyLimitLayer;
+
+use crate::config::ProtocolConfig;
+use crate::engine::{BroadcastObserver, ExecutionEvent, ExecutionEventKind, PipelineOutput};
+use crate::errors::{JobError, PipelineError, ProtocolError};
+use crate::observability::metrics::metrics;
+use crate::observability::trace_context::TraceContext;
+use crate::protocols::options::ProtocolOptions;
+use crate::protocols::{
+    ErrorKind, ErrorResponse, PipelineRequest, PipelineResponse, ProtocolReceiver,
+};
+use crate::server::auth::API_KEY_HEADER;
+use crate::server::{
+    Access, Credential, Drain, EvictionReason, Job, JobManager, JobResult, JobState,
+    ManagedPipeline, Pipeline, PipelineRouter, PipelineStatus, QuotaUsage, Readiness, ReloadReport,
+    ReloadTrigger,
+};
+
+/// Default bind host; only local clients can connect unless configured otherwise
+pub const DEFAULT_HTTP_HOST: &str = "127.0.0.1";
+/// Default bind port
+pub const DEFAULT_HTTP_PORT: u16 = 8080;
+/// Default maximum request body size (10 MiB)
+pub const DEFAULT_MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+/// Default maximum number of requests handled at once; further requests wait
+pub const DEFAULT_MAX_CONCURRENCY: usize = 256;
+/// Default time a request waits for its pipeline to initialize before getting a 503
+pub const DEFAULT_INIT_WAIT_MS: u64 = 30_000;
+
+const PROTOCOL_NAME: &str = "http";
+
+/// Options of an HTTP receiver, from the `options` of its protocol configuration.
+///
+/// # Example
+/// ```yaml
+/// type: http
+/// options:
+///   host: "0.0.0.0"
+///   port: 8080
+///   max_body_bytes: 1048576
+///   max_concurrency: 64
+///   init_wait_ms: 5000
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpReceiverOptions {
+    pub host: String,
+    /// Port to bind; `0` picks a free port
+    pub port: u16,
+    pub max_body_bytes: usize,
+    pub max_concurrency: usize,
+    /// How long a request waits for an initializing pipeline before getting a 503
+    pub init_wait: Duration,
+}
+
+impl Default for HttpReceiverOptions {
+    fn default() -> Self {
+        Self {
+            host: DEFAULT_HTTP_HOST.to_string(),
+            port: DEFAULT_HTTP_PORT,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            init_wait: Duration::from_millis(DEFAULT_INIT_WAIT_MS),
+        }
+    }
+}
+
+impl HttpReceiverOptions {
+    /// Parse the receiver options, falling back to the defaults for missing keys.
+    pub fn from_options(
+        options: &HashMap<String, serde_yaml::Value>,
+    ) -> Result<Self, ProtocolError> {
+        let options = ProtocolOptions::new(PROTOCOL_NAME, options);
+        let defaults = Self::default();
+
+        let port = match options.u64("port")? {
+            Some(port) => {
+                u16::try_from(port).map_err(|_| options.invalid("port", "a port number"))?
+            }
+            None => defaults.port,
+        };
+        let max_body_bytes = match options.u64("max_body_bytes")? {
+            Some(bytes) => usize::try_from(bytes)
+                .map_err(|_| options.invalid("max_body_bytes", "a byte count"))?,
+            None => defaults.max_body_bytes,
+        };
+        let max_concurrency = match options.u64("max_concurrency")? {
+            Some(0) => return Err(options.invalid("max_concurrency", "at least 1")),
+            Some(limit) => usize::try_from(limit)
+                .map_err(|_| options.invalid("max_concurrency", "a request count"))?,
+            None => defaults.max_concurrency,
+        };
+
+        Ok(Self {
+            host: options.string("host")?.unwrap_or(defaults.host),
+            port,
+            max_body_bytes,
+            max_concurrency,
+            init_wait: options
+                .u64("init_wait_ms")?
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.init_wait),
+        })
+    }
+
+    /// `host:port` address to bind.
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
+/// A running server: its bound address and how to stop it
+struct RunningServer {
+    local_addr: SocketAddr,
+    shutdown: oneshot::Sender<()>,
+    task: JoinHandle<Result<(), hyper::Error>>,
+}
+
+/// Receiver serving pipelines over HTTP/1.1 with axum.
+pub struct HttpProtocolReceiver {
+    name: String,
+    options: HttpReceiverOptions,
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl HttpProtocolReceiver {
+    /// Create a receiver with the given instance name and options.
+    pub fn new(name: impl Into<String>, options: HttpReceiverOptions) -> Self {
+        Self {
+            name: name.into(),
+            options,
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Create a receiver from its protocol configuration.
+    pub fn from_config(config: &ProtocolConfig) -> Result<Self, ProtocolError> {
+        Ok(Self::new(
+            config.name(),
+            HttpReceiverOptions::from_options(&config.options)?,
+        ))
+    }
+
+    /// Options the receiver was created with.
+    pub fn options(&self) -> &HttpReceiverOptions {
+        &self.options
+    }
+
+    /// Address the receiver is listening on, once started.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|running| running.local_addr)
+    }
+}
+
+#[async_trait]
+impl ProtocolReceiver for HttpProtocolReceiver {
+    async fn start(&self, router: Arc<PipelineRouter>) -> Result<(), ProtocolError> {
+        let mut running = self.running.lock().unwrap();
+        if running.is_some() {
+            return Err(ProtocolError::AlreadyStarted {
+                name: self.name.clone(),
+            });
+        }
+
+        let address = self.options.bind_address();
+        let bind_failed = |error: &dyn std::fmt::Display| ProtocolError::BindFailed {
+            endpoint: address.clone(),
+            reason: error.to_string(),
+        };
+        let listener = TcpListener::bind(&address).map_err(|e| bind_failed(&e))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| bind_failed(&e))?;
+        let local_addr = listener.local_addr().map_err(|e| bind_failed(&e))?;
+        let server = axum::Server::from_tcp(listener).map_err(|e| bind_failed(&e))?;
+
+        let state = HttpState {
+            router,
+            init_wait: self.options.init_wait,
+        };
+        let mut app = Router::new()
+            .route("/healthz", get(liveness))
+            .route("/readyz", get(readiness))
+            .route("/metrics", get(scrape_metrics))
+            .route("/pipelines", get(list_pipelines))
+            .route(
+                "/pipelines/:name",
+                post(execute_pipeline).get(describe_pipeline),
+            )
+            .route("/pipelines/:name/stream", post(stream_pipeline));
+        let mut admin = Router::new()
+            .route("/admin/pipelines/:name/reset", post(reset_pipeline))
+            .route("/admin/pipelines/:name/evict", post(evict_pipeline))
+            .route("/admin/pipelines/:name/disable", post(disable_pipeline))
+            .route("/admin/pipelines/:name/enable", post(enable_pipeline))
+            .route("/admin/quotas", get(list_quotas));
+        if state.router.reloader().is_some() {
+            admin = admin
+                .route("/admin/reload", post(reload_pipelines))
+                .route("/admin/pipelines/:name/reload", post(reload_pipeline));
+        }
+        app = app
+            .merge(admin.route_layer(middleware::from_fn_with_state(state.clone(), require_admin)));
+        if state.router.jobs().is_some() {
+            app = app
+                .route("/pipelines/:name/jobs", post(submit_job))
+                .route("/jobs", get(list_jobs))
+                .route("/jobs/:id", get(describe_job))
+                .route("/jobs/:id/result", get(job_result))
+                .route("/jobs/:id/cancel", post(cancel_job));
+        }
+        let app = app
+            .layer(DefaultBodyLimit::max(self.options.max_body_bytes))
+            .layer(ConcurrencyLimitLayer::new(self.options.max_concurrency))
+            .with_state(state);
+
+        let (shutdown, shutdown_signal) = oneshot::channel();
+        let task = tokio::spawn(
+            server
+                .serve(app.into_make_service())
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_signal.await;
+                }),
+        );
+
+        *running = Some(RunningServer {
+            local_addr,
+            shutdown,
+            task,
+        });
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), ProtocolError> {
+        let running = self.running.lock().unwrap().take();
+        let Some(running) = running else {
+            return Ok(());
+        };
+
+        let _ = running.shutdown.send(());
+        let serve_failed = |reason: String| ProtocolError::ServeFailed {
+            name: self.name.clone(),
+            reason,
+        };
+        running
+            .task
+            .await
+            .map_err(|e| serve_failed(e.to_string()))?
+            .map_err(|e| serve_failed(e.to_string()))
+    }
+
+    fn protocol_name(&self) -> &str {
+        PROTOCOL_NAME
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        self.local_addr().map(|addr| format!("http://{}", addr))
+    }
+}
+
+/// State shared by the request handlers
+#[derive(Clone)]
+struct HttpState {
+    router: Arc<PipelineRouter>,
+    init_wait: Duration,
+}
+
+/// JSON request body
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonPipelineRequest {
+    payload: Option<String>,
+    payload_base64: Option<String>,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+    callback_url: Option<String>,
+}
+
+/// Header naming the callback URL of a request
+const CALLBACK_URL_HEADER: &str = "x-dagwood-callback-url";
+
+/// Events buffered for a streaming client before further events are skipped
+const STREAM_BUFFER_EVENTS: usize = 1024;
+
+/// `POST /pipelines/:name`
+async fn execute_pipeline(
+    State(state): State<HttpState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Result<Bytes, BytesRejection>,
+) -> Response {
+    let request = match pipeline_request(&state, name, &headers, body) {
+        Ok(request) => request,
+        Err(response) => return *response,
+    };
+
+    match request.dispatch(&state.router, state.init_wait).await {
+        Ok(response) => (StatusCode::OK, Json(response_json(&response))).into_response(),
+        Err(error) => error_response(status_code(error.kind), &error),
+    }
+}
+
+/// `POST /pipelines/:name/stream`
+async fn stream_pipeline(
+    State(state): State<HttpState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Result<Bytes, BytesRejection>,
+) -> Response {
+    let request = match pipeline_request(&state, name, &headers, body) {
+        Ok(request) => request,
+        Err(response) => return *response,
+    };
+    if state.router.registry().get(&request.pipeline).is_none() {
+        let error = ErrorResponse::from(PipelineError::NotFound {
+            name: request.pipeline,
+        });
+        return error_response(status_code(error.kind), &error);
+    }
+
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFER_EVENTS);
+    tokio::spawn(stream_execution(state, request, sender));
+    let events = ReceiverStream::new(receiver).map(Ok::<_, Infallible>);
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Execute the request, forwarding its executor events and then its outcome to `sender`.
+///
+/// The execution runs in its own task and is not slowed down by the client: events the
+/// client is too slow to take are skipped and reported as `lagged`. A client that
+/// disconnects does not cancel the execution.
+async fn stream_execution(state: HttpState, request: PipelineRequest, sender: mpsc::Sender<Event>) {
+    let observer = Arc::new(BroadcastObserver::new(STREAM_BUFFER_EVENTS));
+    let mut events = observer.subscribe();
+    let mut execution = tokio::spawn(async move {
+        request
+            .dispatch_observed(&state.router, state.init_wait, observer)
+            .await
+    });
+
+    let outcome = loop {
+        tokio::select! {
+            biased;
+            received = events.recv() => {
+                let event = match received {
+                    Ok(event) => execution_event(&event),
+                    Err(RecvError::Lagged(skipped)) => lagged_event(skipped),
+                    Err(RecvError::Closed) => break (&mut execution).await,
+                };
+                // A disconnected client only stops receiving events
+                let _ = sender.send(event).await;
+            }
+            outcome = &mut execution => break outcome,
+        }
+    };
+
+    // Events emitted just before the execution finished
+    loop {
+        let event = match events.try_recv() {
+            Ok(event) => execution_event(&event),
+            Err(TryRecvError::Lagged(skipped)) => lagged_event(skipped),
+            Err(_) => break,
+        };
+        let _ = sender.send(event).await;
+    }
+
+    let outcome = outcome.unwrap_or_else(|error| {
+        Err(ErrorResponse::new(
+            ErrorKind::Internal,
+            format!("execution task failed: {}", error),
+        ))
+    });
+    let event = match outcome {
+        Ok(response) => Event::default()
+            .event("result")
+            .data(response_json(&response).to_string()),
+        Err(error) => {
+            let body = json!({
+                "status": status_code(error.kind).as_u16(),
+                "error": error_json(&error),
+            });
+            Event::default().event("error").data(body.to_string())
+        }
+    };
+    let _ = sender.send(event).await;
+}
+
+fn execution_event(event: &ExecutionEvent) -> Event {
+    Event::default()
+        .event(event.kind.name())
+        .data(event_json(event).to_string())
+}
+
+fn lagged_event(skipped: u64) -> Event {
+    Event::default()
+        .event("lagged")
+        .data(json!({ "skipped": skipped }).to_string())
+}
+
+/// Decode the pipeline request of a body, or answer with why it cannot be decoded.
+///
+/// The credential in the headers is checked first, so clients not allowed to execute the
+/// pipeline learn nothing about how their body would have been decoded.
+fn pipeline_request(
+    state: &HttpState,
+    name: String,
+    headers: &HeaderMap,
+    body: Result<Bytes, BytesRejection>,
+) -> Result<PipelineRequest, Box<Response>> {
+    authorize(state, headers, Access::Pipeline(&name))?;
+    let body = match body {
+        Ok(body) => body,
+        Err(rejection) => {
+            let error = ErrorResponse {
+                pipeline: Some(name),
+                ..ErrorResponse::invalid_request(rejection.body_text())
+            };
+            return Err(Box::new(error_response(rejection.status(), &error)));
+        }
+    };
+
+    decode_request(&name, headers, &body)
+        .map(|request| {
+            request
+                .with_credential(credential(headers))
+                .with_trace_context(TraceContext::extract_http(headers))
+        })
+        .map_err(|error| Box::new(error_response(StatusCode::BAD_REQUEST, &error)))
+}
+
+/// Credential of a request: its API key or bearer token
+fn credential(headers: &HeaderMap) -> Option<Credential> {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    Credential::from_headers(
+        header(API_KEY_HEADER),
+        header(header::AUTHORIZATION.as_str()),
+    )
+}
+
+/// Answer with why the request's credential does not allow `access`, if it does not
+fn authorize(
+    state: &HttpState,
+    headers: &HeaderMap,
+    access: Access<'_>,
+) -> Result<(), Box<Response>> {
+    state
+        .router
+        .authorize(PROTOCOL_NAME, credential(headers).as_ref(), access)
+        .map(|_| ())
+        .map_err(|error| {
+            let error = ErrorResponse::from(error);
+            Box::new(error_response(status_code(error.kind), &error))
+        })
+}
+
+/// Middleware letting only clients allowed to use the admin API through
+async fn require_admin<B>(
+    State(state): State<HttpState>,
+    request: axum::http::Request<B>,
+    next: Next<B>,
+) -> Response {
+    match authorize(&state, request.headers(), Access::Admin) {
+        Ok(()) => next.run(request).await,
+        Err(response) => *response,
+    }
+}
+
+/// `GET /healthz`
+async fn liveness() -> Response {
+    (StatusCode::OK, Json(json!({"status": "ok"}))).into_response()
+}
+
+/// `GET /readyz`
+async fn readiness(State(state): State<HttpState>) -> Response {
+    let readiness = Readiness::of(state.router.registry());
+    if readiness.is_ready() {
+        return (StatusCode::OK, Json(json!({"status": "ready"}))).into_response();
+    }
+    let not_ready: Vec<Value> = readiness.not_ready.iter().map(status_json).collect();
+    let body = json!({"status": "not_ready", "pipelines": not_ready});
+    (StatusCode::SERVICE_UNAVAILABLE, Json(body)).into_response()
+}
+
+/// `GET /metrics`
+async fn scrape_metrics(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Err(response) = authorize(&state, &headers, Access::Status) {
+        return *response;
+    }
+    let content_type = HeaderValue::from_static("text/plain; version=0.0.4");
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, content_type)],
+        metrics().encode(),
+    )
+        .into_response()
+}
+
+/// `GET /pipelines`
+async fn list_pipelines(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    if let Err(response) = authorize(&state, &headers, Access::Status) {
+        return *response;
+    }
+    let pipelines: Vec<Value> = PipelineStatus::all(state.router.registry())
+        .iter()
+        .map(status_json)
+        .collect();
+    (StatusCode::OK, Json(json!({"pipelines": pipelines}))).into_response()
+}
+
+/// `GET /pipelines/:name`
+async fn describe_pipeline(
+    State(state): State<HttpState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(response) = authorize(&state, &headers, Access::Status) {
+        return *response;
+    }
+    lifecycle_action(&state, name, |_| json!({}))
+}
+
+/// `POST /admin/pipelines/:name/reset`
+async fn reset_pipeline(State(state): State<HttpState>, Path(name): Path<String>) -> Response {
+    lifecycle_action(&state, name, |pipeline| json!({"reset": pipeline.reset()}))
+}
+
+/// `POST /admin/pipelines/:name/evict`
+async fn evict_pipeline(State(state): State<HttpState>, Path(name): Path<String>) -> Response {
+    lifecycle_action(&state, name, |pipeline| {
+        let released = pipeline.evict(EvictionReason::Admin);
+        json!({"evicted": drain_released(pipeline, released)})
+    })
+}
+
+/// `POST /admin/pipelines/:name/disable`
+async fn disable_pipeline(State(state): State<HttpState>, Path(name): Path<String>) -> Response {
+    lifecycle_action(&state, name, |pipeline| {
+        let released = pipeline.disable();
+        drain_released(pipeline, released);
+        json!({})
+    })
+}
+
+/// `POST /admin/pipelines/:name/enable`
+async fn enable_pipeline(State(state): State<HttpState>, Path(name): Path<String>) -> Response {
+    lifecycle_action(
+        &state,
+        name,
+        |pipeline| json!({"enabled": pipeline.enable()}),
+    )
+}
+
+/// Apply `action` to the named pipeline and answer with its outcome merged into the
+/// pipeline's resulting status, or `404`
+fn lifecycle_action(
+    state: &HttpState,
+    name: String,
+    action: impl FnOnce(&Arc<ManagedPipeline>) -> Value,
+) -> Response {
+    let Some(pipeline) = state.router.registry().get(&name) else {
+        let error = ErrorResponse::from(PipelineError::NotFound { name });
+        return error_response(status_code(error.kind), &error);
+    };
+    let outcome = action(&pipeline);
+    let mut body = status_json(&PipelineStatus::of(&pipeline));
+    if let (Some(body), Value::Object(outcome)) = (body.as_object_mut(), outcome) {
+        body.extend(outcome);
+    }
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+/// Let the in-flight executions of a released version finish in the background
+fn drain_released(pipeline: &ManagedPipeline, released: Option<Arc<Pipeline>>) -> bool {
+    let Some(version) = released else {
+        return false;
+    };
+    let drain_timeout = pipeline.config().hot_reload.drain_timeout();
+    tokio::spawn(Drain::new(version, drain_timeout).wait());
+    true
+}
+
+fn status_json(status: &PipelineStatus) -> Value {
+    json!({
+        "pipeline": status.name,
+        "state": status.state.as_str(),
+        "startup": status.startup.as_str(),
+        "strategy": status.strategy.as_str(),
+        "failure_strategy": status.failure_strategy.as_str(),
+        "processor_count": status.processor_count,
+        "attempts": status.attempts,
+        "last_error": status.last_error,
+        "memory_bytes": status.memory_bytes,
+        "idle_seconds": status.idle_seconds,
+    })
+}
+
+/// `GET /admin/quotas`
+async fn list_quotas(State(state): State<HttpState>) -> Response {
+    let quotas: Vec<Value> = state.router.quota_usage().iter().map(quota_json).collect();
+    (StatusCode::OK, Json(json!({"quotas": quotas}))).into_response()
+}
+
+fn quota_json(usage: &QuotaUsage) -> Value {
+    let config = &usage.config;
+    json!({
+        "scope": usage.scope.as_str(),
+        "name": usage.name,
+        "limits": {
+            "max_concurrent": config.max_concurrent,
+            "requests_per_second": config.requests_per_second,
+            "burst": config.burst(),
+            "max_payload_bytes": config.max_payload_bytes,
+            "fuel_per_window": config.fuel_per_window,
+            "fuel_window_seconds": config.fuel_per_window.map(|_| config.fuel_window().as_secs()),
+        },
+        "active": usage.active,
+        "tokens": usage.tokens,
+        "fuel_used": usage.fuel_used,
+        "fuel_window_remaining_ms": usage
+            .fuel_window_remaining
+            .map(|remaining| remaining.as_millis() as u64),
+    })
+}
+
+/// `POST /admin/reload`
+async fn reload_pipelines(State(state): State<HttpState>) -> Response {
+    let Some(reloader) = state.router.reloader() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    reload_response(reloader.reload(ReloadTrigger::AdminApi).await)
+}
+
+/// `POST /admin/pipelines/:name/reload`
+async fn reload_pipeline(State(state): State<HttpState>, Path(name): Path<String>) -> Response {
+    let Some(reloader) = state.router.reloader() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    reload_response(
+        reloader
+            .reload_pipeline(&name, ReloadTrigger::AdminApi)
+            .await,
+    )
+}
+
+fn reload_response(result: Result<ReloadReport, PipelineError>) -> Response {
+    match result {
+        Ok(report) => {
+            let body = json!({
+                "trigger": ReloadTrigger::AdminApi.as_str(),
+                "added": report.added,
+                "removed": report.removed,
+                "modified": report.modified,
+                "unchanged": report.unchanged,
+            });
+            (StatusCode::OK, Json(body)).into_response()
+        }
+        Err(error) => {
+            let mut error = ErrorResponse::from(error);
+            // A pipeline of the new configuration failing to build rejects the configuration
+            if error.kind != ErrorKind::PipelineNotFound {
+                error.kind = ErrorKind::InvalidConfig;
+                error.retry_after = None;
+            }
+            error_response(status_code(error.kind), &error)
+        }
+    }
+}
+
+/// `POST /pipelines/:name/jobs`
+async fn submit_job(
+    State(state): State<HttpState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Result<Bytes, BytesRejection>,
+) -> Response {
+    let Some(jobs) = state.router.jobs() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let request = match pipeline_request(&state, name, &headers, body) {
+        Ok(request) => request,
+        Err(response) => return *response,
+    };
+
+    match jobs.submit(&state.router, request).await {
+        Ok(job) => {
+            let mut response = (StatusCode::ACCEPTED, Json(job_json(&job))).into_response();
+            if let Ok(location) = HeaderValue::from_str(&format!("/jobs/{}", job.id)) {
+                response.headers_mut().insert(header::LOCATION, location);
+            }
+            response
+        }
+        Err(error) => job_error_response(error),
+    }
+}
+
+/// `GET /jobs`
+async fn list_jobs(State(state): State<HttpState>, headers: HeaderMap) -> Response {
+    let Some(jobs) = state.router.jobs() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    if let Err(response) = authorize(&state, &headers, Access::Status) {
+        return *response;
+    }
+    // Clients only see the jobs of the pipelines they may execute
+    let client = state
+        .router
+        .auth()
+        .and_then(|auth| auth.authenticate(credential(&headers).as_ref()).ok());
+    match jobs.list().await {
+        Ok(jobs) => {
+            let jobs: Vec<Value> = jobs
+                .iter()
+                .filter(|job| {
+                    client.is_none_or(|client| client.allows(Access::Pipeline(&job.pipeline)))
+                })
+                .map(job_json)
+                .collect();
+            (StatusCode::OK, Json(json!({"jobs": jobs}))).into_response()
+        }
+        Err(error) => job_error_response(error),
+    }
+}
+
+/// `GET /jobs/:id`
+async fn describe_job(
+    State(state): State<HttpState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    job_action(&state, &headers, &id, |_, job| async move { Ok(job) }).await
+}
+
+/// `POST /jobs/:id/cancel`
+async fn cancel_job(
+    State(state): State<HttpState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    job_action(&state, &headers, &id, |jobs, job| async move {
+        jobs.cancel(&job.id).await
+    })
+    .await
+}
+
+/// `GET /jobs/:id/result`
+async fn job_result(
+    State(state): State<HttpState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(jobs) = state.router.jobs() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let job = match authorized_job(&state, &headers, jobs, &id).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+
+    match (job.state, job.result, job.error) {
+        (JobState::Succeeded, Some(result), _) => {
+            let body = result_json(&job.pipeline, &result);
+            (StatusCode::OK, Json(body)).into_response()
+        }
+        (JobState::Failed, _, Some(error)) => error_response(status_code(error.kind), &error),
+        (JobState::Cancelled, _, _) => job_error_response(JobError::Cancelled { id: job.id }),
+        (state, _, _) => job_error_response(JobError::NotFinished { id: job.id, state }),
+    }
+}
+
+/// Apply `action` to the job and answer with the job it returns, or its error
+async fn job_action<F, Fut>(state: &HttpState, headers: &HeaderMap, id: &str, action: F) -> Response
+where
+    F: FnOnce(Arc<JobManager>, Job) -> Fut,
+    Fut: std::future::Future<Output = Result<Job, JobError>>,
+{
+    let Some(jobs) = state.router.jobs() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let job = match authorized_job(state, headers, jobs, id).await {
+        Ok(job) => job,
+        Err(response) => return response,
+    };
+    match action(jobs.clone(), job).await {
+        Ok(job) => (StatusCode::OK, Json(job_json(&job))).into_response(),
+        Err(error) => job_error_response(error),
+    }
+}
+
+/// The job, if the request's credential allows it to execute the job's pipeline
+async fn authorized_job(
+    state: &HttpState,
+    headers: &HeaderMap,
+    jobs: &JobManager,
+    id: &str,
+) -> Result<Job, Response> {
+    // Authenticated first, so unknown clients cannot probe for job IDs
+    authorize(state, headers, Access::Status).map_err(|response| *response)?;
+    let job = jobs.get(id).await.map_err(job_error_response)?;
+    authorize(state, headers, Access::Pipeline(&job.pipeline)).map_err(|response| *response)?;
+    Ok(job)
+}
+
+fn job_error_response(error: JobError) -> Response {
+    let error = ErrorResponse::from(error);
+    error_response(status_code(error.kind), &error)
+}
+
+/// Milliseconds since the Unix epoch
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn job_json(job: &Job) -> Value {
+    let processors: Vec<Value> = job
+        .progress
+        .iter()
+        .map(|processor| {
+            json!({
+                "processor_id": processor.processor_id,
+                "state": processor.state.as_str(),
+                "attempts": processor.attempts,
+                "duration_ms": processor
+                    .duration
+                    .map(|duration| duration.as_secs_f64() * 1000.0),
+            })
+        })
+        .collect();
+    json!({
+        "job_id": job.id,
+        "pipeline": job.pipeline,
+        "state": job.state.as_str(),
+        "submitted_at_ms": epoch_ms(job.submitted_at),
+        "started_at_ms": job.started_at.map(epoch_ms),
+        "finished_at_ms": job.finished_at.map(epoch_ms),
+        "progress": {
+            "finished": job.finished_processors(),
+            "total": job.progress.len(),
+            "processors": processors,
+        },
+        "error": job.error.as_ref().map(error_json),
+    })
+}
+
+/// Build the pipeline request from a JSON or raw request body
+fn decode_request(
+    name: &str,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Result<PipelineRequest, ErrorResponse> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let is_json = content_type.is_some_and(|content_type| {
+        content_type
+            .split(';')
+            .next()
+            .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"))
+    });
+
+    let callback_url = headers
+        .get(CALLBACK_URL_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    if !is_json {
+        let mut request =
+            PipelineRequest::new(name, body.to_vec()).with_context("protocol", "http");
+        request.callback_url = callback_url;
+        return Ok(match content_type {
+            Some(content_type) => request.with_context("content_type", content_type),
+            None => request,
+        });
+    }
+
+    let json: JsonPipelineRequest = serde_json::from_slice(body)
+        .map_err(|e| ErrorResponse::invalid_request(format!("Invalid JSON request: {}", e)))?;
+    let payload = match (json.payload, json.payload_base64) {
+        (Some(text), None) => text.into_bytes(),
+        (None, Some(encoded)) => BASE64.decode(encoded).map_err(|e| {
+            ErrorResponse::invalid_request(format!("Invalid payload_base64: {}", e))
+        })?,
+        _ => {
+            return Err(ErrorResponse::invalid_request(
+                "JSON request must contain exactly one of 'payload' or 'payload_base64'",
+            ))
+        }
+    };
+
+    let mut request = PipelineRequest::new(name, payload);
+    request.context = json.metadata;
+    request.callback_url = json.callback_url.or(callback_url);
+    Ok(request.with_context("protocol", "http"))
+}
+
+/// HTTP status code of a failed request
+fn status_code(kind: ErrorKind) -> StatusCode {
+    match kind {
+        ErrorKind::PipelineNotFound | ErrorKind::JobNotFound => StatusCode::NOT_FOUND,
+        ErrorKind::PipelineNotReady | ErrorKind::PipelineUnavailable => {
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+        ErrorKind::JobConflict => StatusCode::CONFLICT,
+        ErrorKind::InvalidRequest => StatusCode::BAD_REQUEST,
+        ErrorKind::Unauthenticated => StatusCode::UNAUTHORIZED,
+        ErrorKind::Forbidden => StatusCode::FORBIDDEN,
+        ErrorKind::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+        ErrorKind::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+        ErrorKind::InvalidConfig => StatusCode::UNPROCESSABLE_ENTITY,
+        ErrorKind::ProcessorFailed | ErrorKind::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// `payload` for UTF-8 payloads, `payload_base64` otherwise
+fn payload_field(payload: &[u8]) -> (&'static str, Value) {
+    match std::str::from_utf8(payload) {
+        Ok(text) => ("payload", Value::from(text)),
+        Err(_) => ("payload_base64", Value::from(BASE64.encode(payload))),
+    }
+}
+
+fn event_json(event: &ExecutionEvent) -> Value {
+    let fields = match &event.kind {
+        ExecutionEventKind::ExecutionStarted {
+            strategy,
+            processor_count,
+        } => json!({"strategy": strategy, "processor_count": processor_count}),
+        ExecutionEventKind::ProcessorScheduled { processor_id } => {
+            json!({ "processor_id": processor_id })
+        }
+        ExecutionEventKind::ProcessorStarted {
+            processor_id,
+            attempt,
+            input_size,
+        } => json!({
+            "processor_id": processor_id,
+            "attempt": attempt,
+            "input_size": input_size,
+        }),
+        ExecutionEventKind::ProcessorRetried {
+            processor_id,
+            failed_attempt,
+            error,
+        } => json!({
+            "processor_id": processor_id,
+            "failed_attempt": failed_attempt,
+            "error": error.to_string(),
+        }),
+        ExecutionEventKind::ProcessorCompleted {
+            processor_id,
+            attempts,
+            duration,
+            output_size,
+        } => json!({
+            "processor_id": processor_id,
+            "attempts": attempts,
+            "duration_ms": duration.as_secs_f64() * 1000.0,
+            "output_size": output_size,
+        }),
+        ExecutionEventKind::ProcessorFailed {
+            processor_id,
+            status,
+            attempts,
+            duration,
+            error,
+        } => json!({
+            "processor_id": processor_id,
+            "status": status.to_string(),
+            "attempts": attempts,
+            "duration_ms": duration.as_secs_f64() * 1000.0,
+            "error": error.as_ref().map(ToString::to_string),
+        }),
+        ExecutionEventKind::ProcessorBlocked {
+            processor_id,
+            failed_dependency,
+        } => json!({
+            "processor_id": processor_id,
+            "failed_dependency": failed_dependency,
+        }),
+        ExecutionEventKind::CanonicalPayloadUpdated { processor_id, size } => {
+            json!({"processor_id": processor_id, "size": size})
+        }
+        ExecutionEventKind::ExecutionCompleted {
+            strategy,
+            duration,
+            succeeded,
+        } => json!({
+            "strategy": strategy,
+            "duration_ms": duration.as_secs_f64() * 1000.0,
+            "succeeded": succeeded,
+        }),
+    };
+
+    let mut value = json!({
+        "timestamp_ms": epoch_ms(event.timestamp),
+        "elapsed_ms": event.elapsed.as_secs_f64() * 1000.0,
+    });
+    if let (Some(value), Value::Object(fields)) = (value.as_object_mut(), fields) {
+        value.extend(fields);
+    }
+    value
+}
+
+fn response_json(response: &PipelineResponse) -> Value {
+    let metadata: HashMap<&str, &HashMap<String, String>> = response
+        .metadata
+        .metadata
+        .iter()
+        .map(|(key, metadata)| (key.as_str(), &metadata.metadata))
+        .collect();
+
+    let (payload_key, payload) = payload_field(&response.payload);
+    let mut value = json!({
+        "pipeline": response.pipeline,
+        "run_id": response.run_id,
+        "strategy": response.strategy,
+        "duration_ms": response.duration.as_secs_f64() * 1000.0,
+        "outputs": outputs_json(&response.outputs),
+        "metadata": metadata,
+    });
+    value[payload_key] = payload;
+    value
+}
+
+/// The body of a succeeded job's result, shaped like the synchronous response
+fn result_json(pipeline: &str, result: &JobResult) -> Value {
+    let (payload_key, payload) = payload_field(&result.payload);
+    let mut value = json!({
+        "pipeline": pipeline,
+        "run_id": result.run_id,
+        "strategy": result.strategy,
+        "duration_ms": result.duration.as_secs_f64() * 1000.0,
+        "outputs": outputs_json(&result.outputs),
+        "metadata": result.metadata,
+    });
+    value[payload_key] = payload;
+    value
+}
+
+fn outputs_json(outputs: &[PipelineOutput]) -> Vec<Value> {
+    outputs
+        .iter()
+        .map(|output| {
+            let mut value = json!({
+                "name": output.name,
+                "processor_id": output.processor_id,
+                "status": output.status.to_string(),
+                "metadata": output.metadata,
+            });
+            if let Some(payload) = &output.payload {
+                let (key, payload) = payload_field(payload);
+                value[key] = payload;
+            }
+            value
+        })
+        .collect()
+}
+
+fn error_json(error: &ErrorResponse) -> Value {
+    let failures: Vec<Value> = error
+        .failures
+        .iter()
+        .map(|failure| {
+            json!({
+                "processor_id": failure.processor_id,
+                "status": failure.status.to_string(),
+                "error": failure.error,
+            })
+        })
+        .collect();
+    json!({
+        "kind": error.kind.as_str(),
+        "message": error.message,
+        "pipeline": error.pipeline,
+        "run_id": error.run_id,
+        "failures": failures,
+    })
+}
+
+fn error_response(status: StatusCode, error: &ErrorResponse) -> Response {
+    let body = json!({ "error": error_json(error) });
+    let mut response = (status, Json(body)).into_response();
+    if let Some(retry_after) = error.retry_after {
+        // Retry-After is in whole seconds; round up so clients never retry too early
+        let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(seconds.max(1)));
+    }
+    if error.kind == ErrorKind::Unauthenticated {
+        response
+            .headers_mut()
+            .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AuthConfig, JobsConfig, PipelinesConfig};
+    use crate::observability::chrome_trace::ChromeTraceLayer;
+    use crate::observability::trace_context::{dag_request_trace_ids, TRACEPARENT_HEADER};
+    use crate::server::{Authenticator, HotReloader, PipelineRegistry};
+    use hyper::{Body, Client, Request};
+    use tracing_subscriber::prelude::*;
+
+    const PIPELINES: &str = r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: analysis
+    strategy: reactive
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+      - id: tokens
+        type: local
+        processor: token_counter
+    outputs:
+      - processor: reverse
+      - processor: tokens
+  - name: broken
+    startup: on-demand
+    initialization:
+      max_retries: 1
+      retry_delay_ms: 10000
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#;
+
+    async fn start_receiver(options: HttpReceiverOptions) -> HttpProtocolReceiver {
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let receiver = HttpProtocolReceiver::new("test", options);
+        receiver
+            .start(Arc::new(PipelineRouter::new(registry)))
+            .await
+            .unwrap();
+        receiver
+    }
+
+    fn local_options() -> HttpReceiverOptions {
+        HttpReceiverOptions {
+            port: 0,
+            ..HttpReceiverOptions::default()
+        }
+    }
+
+    async fn post(
+        receiver: &HttpProtocolReceiver,
+        path: &str,
+        content_type: Option<&str>,
+        body: impl Into<Body>,
+    ) -> (StatusCode, HeaderMap, Value) {
+        let mut request = Request::post(format!("{}{}", receiver.endpoint().unwrap(), path));
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        let response = Client::new()
+            .request(request.body(body.into()).unwrap())
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, headers, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_json_request_returns_payload_and_metadata() {
+        let receiver = start_receiver(local_options()).await;
+
+        let (status, _, body) = post(
+            &receiver,
+            "/pipelines/upper",
+            Some("application/json; charset=utf-8"),
+            r#"{"payload": "hello world", "metadata": {"source": "test"}}"#,
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["pipeline"], "upper");
+        assert_eq!(body["strategy"], "WorkQueue");
+        assert_eq!(body["payload"], "HELLO WORLD");
+        assert!(body["run_id"].as_str().is_some_and(|id| !id.is_empty()));
+        assert_eq!(body["metadata"]["initial_context"]["source"], "test");
+        assert_eq!(body["metadata"]["initial_context"]["protocol"], "http");
+
+        receiver.shutdown().await.unwrap();
+        assert_eq!(receiver.endpoint(), None);
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_continues_caller_trace() {
+        let (layer, trace) = ChromeTraceLayer::new();
+        let _subscriber = tracing_subscriber::registry().with(layer).set_default();
+        let receiver = start_receiver(local_options()).await;
+
+        for traceparent in [
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            None,
+        ] {
+            let mut request =
+                Request::post(format!("{}/pipelines/upper", receiver.endpoint().unwrap()));
+            if let Some(traceparent) = traceparent {
+                request = request.header(TRACEPARENT_HEADER, traceparent);
+            }
+            let response = Client::new()
+                .request(request.body(Body::from("abc")).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        // Only the request that sent a trace context continues it
+        assert_eq!(
+            dag_request_trace_ids(&trace),
+            vec!["4bf92f3577b34da6a3ce929d0e0e4736"]
+        );
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_raw_request_and_designated_outputs() {
+        let receiver = start_receiver(local_options()).await;
+
+        let (status, _, body) = post(
+            &receiver,
+            "/pipelines/analysis",
+            Some("text/plain"),
+            "hello world",
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["strategy"], "Reactive");
+        assert_eq!(body["outputs"][0]["name"], "reverse");
+        assert_eq!(body["outputs"][0]["payload"], "dlrow olleh");
+        assert_eq!(body["outputs"][1]["name"], "tokens");
+        assert_eq!(body["outputs"][1]["status"], "succeeded");
+
+        let encoded = BASE64.encode("hello");
+        let (status, _, body) = post(
+            &receiver,
+            "/pipelines/upper",
+            Some("application/json"),
+            format!(r#"{{"payload_base64": "{}"}}"#, encoded),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["payload"], "HELLO");
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_error_responses() {
+        let receiver = start_receiver(HttpReceiverOptions {
+            init_wait: Duration::from_millis(50),
+            max_body_bytes: 64,
+            ..local_options()
+        })
+        .await;
+
+        // Unknown pipeline
+        let (status, _, body) = post(&receiver, "/pipelines/missing", None, "hello").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["kind"], "pipeline_not_found");
+        assert_eq!(body["error"]["pipeline"], "missing");
+
+        // Pipeline waiting to retry its initialization
+        let (status, headers, body) = post(&receiver, "/pipelines/broken", None, "hello").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(headers[header::RETRY_AFTER], "1");
+        assert_eq!(body["error"]["kind"], "pipeline_not_ready");
+
+        // Processor failure on invalid UTF-8 input
+        let (status, _, body) = post(&receiver, "/pipelines/upper", None, vec![0xff, 0xfe]).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"]["kind"], "processor_failed");
+        assert_eq!(body["error"]["failures"][0]["processor_id"], "upper");
+        assert_eq!(body["error"]["failures"][0]["status"], "failed");
+        assert!(body["error"]["run_id"].is_string());
+
+        // Malformed JSON and oversized bodies
+        let (status, _, body) =
+            post(&receiver, "/pipelines/upper", Some("application/json"), "{").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["kind"], "invalid_request");
+
+        let (status, _, body) = post(&receiver, "/pipelines/upper", None, vec![b'a'; 65]).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"]["kind"], "invalid_request");
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    /// The `(event, data)` pairs of a server-sent event stream
+    async fn post_stream(
+        receiver: &HttpProtocolReceiver,
+        path: &str,
+        body: impl Into<Body>,
+    ) -> (StatusCode, Vec<(String, Value)>) {
+        let request = Request::post(format!("{}{}", receiver.endpoint().unwrap(), path));
+        let response = Client::new()
+            .request(request.body(body.into()).unwrap())
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let events = std::str::from_utf8(&body)
+            .unwrap()
+            .split("\n\n")
+            .filter_map(|event| {
+                let name = event.lines().find_map(|line| line.strip_prefix("event:"))?;
+                let data = event.lines().find_map(|line| line.strip_prefix("data:"))?;
+                Some((name.to_string(), serde_json::from_str(data).unwrap()))
+            })
+            .collect();
+        (status, events)
+    }
+
+    #[tokio::test]
+    async fn test_stream_execution_events() {
+        let receiver = start_receiver(local_options()).await;
+
+        let (status, events) = post_stream(&receiver, "/pipelines/analysis/stream", "hello").await;
+        assert_eq!(status, StatusCode::OK);
+        let names: Vec<&str> = events.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names.first(), Some(&"execution_started"));
+        assert_eq!(names[names.len() - 2..], ["execution_completed", "result"]);
+        let completed = events
+            .iter()
+            .find(|(name, data)| name == "processor_completed" && data["processor_id"] == "reverse")
+            .map(|(_, data)| data)
+            .unwrap();
+        assert_eq!(completed["output_size"], 5);
+        assert!(completed["elapsed_ms"].is_number());
+        let (_, result) = events.last().unwrap();
+        assert_eq!(result["outputs"][0]["payload"], "olleh");
+
+        // Failures stream their events before the error
+        let (status, events) = post_stream(&receiver, "/pipelines/upper/stream", vec![0xff]).await;
+        assert_eq!(status, StatusCode::OK);
+        let failed = events
+            .iter()
+            .find(|(name, _)| name == "processor_failed")
+            .map(|(_, data)| data)
+            .unwrap();
+        assert_eq!(failed["processor_id"], "upper");
+        assert_eq!(failed["status"], "failed");
+        let (name, error) = events.last().unwrap();
+        assert_eq!(name, "error");
+        assert_eq!(error["status"], 500);
+        assert_eq!(error["error"]["kind"], "processor_failed");
+
+        // Requests that cannot run are answered without a stream
+        let (status, _, body) = post(&receiver, "/pipelines/missing/stream", None, "hi").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["kind"], "pipeline_not_found");
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_reset() {
+        let receiver = start_receiver(local_options()).await;
+
+        let (status, _, body) = post(&receiver, "/admin/pipelines/upper/reset", None, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["reset"], false);
+        assert_eq!(body["state"], "uninitialized");
+
+        post(&receiver, "/pipelines/upper", None, "abc").await;
+        let (_, _, body) = post(&receiver, "/admin/pipelines/upper/reset", None, "").await;
+        assert_eq!(body["reset"], true);
+        assert_eq!(body["state"], "uninitialized");
+
+        let (status, _, body) = post(&receiver, "/admin/pipelines/missing/reset", None, "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["kind"], "pipeline_not_found");
+
+        // Reload is only served when the server supports hot reload
+        let response = Client::new()
+            .request(
+                Request::post(format!("{}/admin/reload", receiver.endpoint().unwrap()))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    async fn get(receiver: &HttpProtocolReceiver, path: &str) -> (StatusCode, Value) {
+        let url = format!("{}{}", receiver.endpoint().unwrap(), path);
+        let response = Client::new().get(url.parse().unwrap()).await.unwrap();
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_and_status_endpoints() {
+        let receiver = start_receiver(HttpReceiverOptions {
+            init_wait: Duration::from_millis(200),
+            ..local_options()
+        })
+        .await;
+
+        let (status, body) = get(&receiver, "/healthz").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+
+        let (status, body) = get(&receiver, "/readyz").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "not_ready");
+        let not_ready: Vec<&Value> = body["pipelines"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|pipeline| &pipeline["pipeline"])
+            .collect();
+        assert_eq!(not_ready, vec!["analysis", "upper"]);
+
+        post(&receiver, "/pipelines/upper", None, "abc").await;
+        post(&receiver, "/pipelines/analysis", None, "abc").await;
+        let (status, body) = get(&receiver, "/readyz").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ready");
+
+        let (status, body) = get(&receiver, "/pipelines").await;
+        assert_eq!(status, StatusCode::OK);
+        let pipelines = body["pipelines"].as_array().unwrap();
+        assert_eq!(pipelines.len(), 3);
+        let mut analysis = pipelines[0].clone();
+        assert!(analysis["idle_seconds"].is_u64());
+        analysis.as_object_mut().unwrap().remove("idle_seconds");
+        assert_eq!(
+            analysis,
+            json!({
+                "pipeline": "analysis",
+                "state": "ready",
+                "startup": "auto",
+                "strategy": "reactive",
+                "failure_strategy": "fail_fast",
+                "processor_count": 2,
+                "attempts": 1,
+                "last_error": null,
+                "memory_bytes": 0,
+            })
+        );
+        assert_eq!(pipelines[1]["state"], "uninitialized");
+        assert_eq!(pipelines[1]["startup"], "on-demand");
+
+        post(&receiver, "/pipelines/broken", None, "abc").await;
+        let (_, body) = get(&receiver, "/pipelines/broken").await;
+        assert_eq!(body["attempts"], 1);
+        assert!(body["last_error"]
+            .as_str()
+            .unwrap()
+            .contains("does_not_exist"));
+
+        let (status, body) = get(&receiver, "/pipelines/missing").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["kind"], "pipeline_not_found");
+
+        let url = format!("{}/metrics", receiver.endpoint().unwrap());
+        let response = Client::new().get(url.parse().unwrap()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain"));
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.contains("dagwood_executions_total"));
+    }
+
+    #[tokio::test]
+    async fn test_admin_evict_disable_enable() {
+        let receiver = start_receiver(local_options()).await;
+
+        let (_, _, body) = post(&receiver, "/admin/pipelines/upper/evict", None, "").await;
+        assert_eq!(body["evicted"], false);
+        assert_eq!(body["state"], "uninitialized");
+
+        post(&receiver, "/pipelines/upper", None, "abc").await;
+        let (status, _, body) = post(&receiver, "/admin/pipelines/upper/evict", None, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["evicted"], true);
+        assert_eq!(body["state"], "evicted");
+        let (status, _, body) = post(&receiver, "/pipelines/upper", None, "abc").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["payload"], "ABC");
+
+        let (_, _, body) = post(&receiver, "/admin/pipelines/upper/disable", None, "").await;
+        assert_eq!(body["state"], "disabled");
+        let (status, headers, body) = post(&receiver, "/pipelines/upper", None, "abc").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(headers.get(header::RETRY_AFTER).is_none());
+        assert_eq!(body["error"]["message"], "Pipeline 'upper' is disabled");
+
+        let (_, _, body) = post(&receiver, "/admin/pipelines/upper/enable", None, "").await;
+        assert_eq!(body["enabled"], true);
+        let (status, _, _) = post(&receiver, "/pipelines/upper", None, "abc").await;
+        assert_eq!(status, StatusCode::OK);
+        let (_, _, body) = post(&receiver, "/admin/pipelines/upper/enable", None, "").await;
+        assert_eq!(body["enabled"], false);
+
+        let (status, _, _) = post(&receiver, "/admin/pipelines/missing/disable", None, "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_admin_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pipelines.yaml");
+        std::fs::write(&path, PIPELINES).unwrap();
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let reloader = Arc::new(HotReloader::new(registry.clone(), &path));
+        let receiver = HttpProtocolReceiver::new("test", local_options());
+        receiver
+            .start(Arc::new(
+                PipelineRouter::new(registry).with_reloader(reloader),
+            ))
+            .await
+            .unwrap();
+
+        std::fs::write(
+            &path,
+            PIPELINES.replace("change_text_case_upper", "change_text_case_lower"),
+        )
+        .unwrap();
+        let (status, _, body) = post(&receiver, "/admin/reload", None, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["trigger"], "admin_api");
+        assert_eq!(body["modified"], json!(["upper"]));
+        assert_eq!(body["unchanged"], json!(["analysis", "broken"]));
+        let (_, _, body) = post(&receiver, "/pipelines/upper", None, "ABC").await;
+        assert_eq!(body["payload"], "abc");
+
+        let (status, _, body) = post(&receiver, "/admin/pipelines/analysis/reload", None, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["modified"], json!(["analysis"]));
+
+        std::fs::write(&path, PIPELINES.replace("name: analysis", "name: upper")).unwrap();
+        let (status, _, body) = post(&receiver, "/admin/reload", None, "").await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body["error"]["kind"], "invalid_config");
+        let (status, _, _) = post(&receiver, "/pipelines/upper", None, "ABC").await;
+        assert_eq!(status, StatusCode::OK);
+
+        std::fs::write(&path, PIPELINES).unwrap();
+        let (status, _, _) = post(&receiver, "/admin/pipelines/missing/reload", None, "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_jobs() {
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let jobs = Arc::new(JobManager::from_config(&JobsConfig::default()).unwrap());
+        let receiver = HttpProtocolReceiver::new("test", local_options());
+        receiver
+            .start(Arc::new(PipelineRouter::new(registry).with_jobs(jobs)))
+            .await
+            .unwrap();
+
+        let (status, headers, body) =
+            post(&receiver, "/pipelines/analysis/jobs", None, "hello world").await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let id = body["job_id"].as_str().unwrap().to_string();
+        assert_eq!(headers[header::LOCATION], format!("/jobs/{}", id));
+        assert_eq!(body["pipeline"], "analysis");
+        assert_eq!(body["progress"]["total"], 2);
+
+        let mut job = body;
+        while job["finished_at_ms"].is_null() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            job = get(&receiver, &format!("/jobs/{}", id)).await.1;
+        }
+        assert_eq!(job["state"], "succeeded");
+        assert_eq!(job["progress"]["finished"], 2);
+        assert_eq!(job["progress"]["processors"][0]["state"], "succeeded");
+        assert!(job["error"].is_null());
+
+        let (status, body) = get(&receiver, &format!("/jobs/{}/result", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["outputs"][0]["payload"], "dlrow olleh");
+        assert_eq!(body["outputs"][1]["name"], "tokens");
+
+        let (_, body) = get(&receiver, "/jobs").await;
+        assert_eq!(body["jobs"][0]["job_id"], id.as_str());
+
+        let (status, _, body) = post(&receiver, &format!("/jobs/{}/cancel", id), None, "").await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["kind"], "job_conflict");
+
+        let (status, body) = get(&receiver, "/jobs/missing/result").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["kind"], "job_not_found");
+        let (status, _, body) = post(&receiver, "/pipelines/missing/jobs", None, "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"]["kind"], "pipeline_not_found");
+
+        // Failed jobs answer their result with the error of the synchronous request
+        let (_, _, body) = post(&receiver, "/pipelines/upper/jobs", None, vec![0xff]).await;
+        let path = format!("/jobs/{}/result", body["job_id"].as_str().unwrap());
+        let (mut status, mut body) = get(&receiver, &path).await;
+        while status == StatusCode::CONFLICT {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            (status, body) = get(&receiver, &path).await;
+        }
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body["error"]["kind"], "processor_failed");
+
+        // Job routes are only served when the server runs jobs
+        let receiver = start_receiver(local_options()).await;
+        let response = Client::new()
+            .get(
+                format!("{}/jobs", receiver.endpoint().unwrap())
+                    .parse()
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    /// Send a request presenting `authorization`, returning its status and body
+    async fn send_as(
+        receiver: &HttpProtocolReceiver,
+        method: &str,
+        path: &str,
+        authorization: Option<&str>,
+    ) -> (StatusCode, HeaderMap, Value) {
+        let mut request = Request::builder().method(method).uri(format!(
+            "{}{}",
+            receiver.endpoint().unwrap(),
+            path
+        ));
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        let response = Client::new()
+            .request(request.body(Body::from("hello")).unwrap())
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (
+            status,
+            headers,
+            serde_json::from_slice(&body).unwrap_or_default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_authentication() {
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap(),
+        );
+        let auth: AuthConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+clients:
+  - name: dashboard
+    api_key: dashboard-key
+    pipelines: [upper]
+  - name: operator
+    api_key: operator-key
+    admin: true
+"#,
+        )
+        .unwrap();
+        let jobs = Arc::new(JobManager::from_config(&JobsConfig::default()).unwrap());
+        let router = PipelineRouter::new(registry)
+            .with_jobs(jobs)
+            .with_auth(Arc::new(Authenticator::from_config(&auth).unwrap()));
+        let receiver = HttpProtocolReceiver::new("test", local_options());
+        receiver.start(Arc::new(router)).await.unwrap();
+        let dashboard = Some("Bearer dashboard-key");
+        let operator = Some("Bearer operator-key");
+
+        // Health checks stay open; everything else needs credentials
+        assert_eq!(get(&receiver, "/healthz").await.0, StatusCode::OK);
+        let (status, headers, body) = send_as(&receiver, "POST", "/pipelines/upper", None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(headers[header::WWW_AUTHENTICATE], "Bearer");
+        assert_eq!(body["error"]["kind"], "unauthenticated");
+        let wrong_key = Some("Bearer guess");
+        let (status, _, _) = send_as(&receiver, "GET", "/pipelines", wrong_key).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        for path in ["/pipelines/upper", "/pipelines/upper/stream"] {
+            let request = Request::post(format!("{}{}", receiver.endpoint().unwrap(), path))
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{not json"))
+                .unwrap();
+            assert_eq!(
+                Client::new().request(request).await.unwrap().status(),
+                StatusCode::UNAUTHORIZED
+            );
+        }
+
+        // Pipelines of the client's allowlist, by key header or bearer token
+        let (status, _, body) = send_as(&receiver, "POST", "/pipelines/upper", dashboard).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["payload"], "HELLO");
+        let request = Request::post(format!("{}/pipelines/upper", receiver.endpoint().unwrap()))
+            .header(API_KEY_HEADER, "dashboard-key")
+            .body(Body::from("hi"))
+            .unwrap();
+        assert_eq!(
+            Client::new().request(request).await.unwrap().status(),
+            StatusCode::OK
+        );
+        let (status, _, body) = send_as(&receiver, "POST", "/pipelines/analysis", dashboard).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["kind"], "forbidden");
+        let (status, _, _) =
+            send_as(&receiver, "POST", "/pipelines/analysis/stream", dashboard).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        // Jobs are only visible to clients allowed to execute their pipeline
+        let (status, _, body) =
+            send_as(&receiver, "POST", "/pipelines/analysis/jobs", operator).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        let job = format!("/jobs/{}", body["job_id"].as_str().unwrap());
+        assert_eq!(
+            send_as(&receiver, "GET", &job, operator).await.0,
+            StatusCode::OK
+        );
+        assert_eq!(
+            send_as(&receiver, "GET", &job, dashboard).await.0,
+            StatusCode::FORBIDDEN
+        );
+        let (_, _, body) = send_as(&receiver, "GET", "/jobs", dashboard).await;
+        assert_eq!(body["jobs"], json!([]));
+        let (_, _, body) = send_as(&receiver, "GET", "/jobs", operator).await;
+        assert_eq!(body["jobs"].as_array().unwrap().len(), 1);
+
+        // Status for every client, admin actions for admins only
+        let (status, _, _) = send_as(&receiver, "GET", "/pipelines/analysis", dashboard).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            send_as(&receiver, "GET", "/metrics", None).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send_as(&receiver, "GET", "/metrics", dashboard).await.0,
+            StatusCode::OK
+        );
+        let reset = "/admin/pipelines/upper/reset";
+        assert_eq!(
+            send_as(&receiver, "POST", reset, None).await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send_as(&receiver, "POST", reset, dashboard).await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            send_as(&receiver, "POST", reset, operator).await.0,
+            StatusCode::OK
+        );
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_quotas() {
+        let config = PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: small
+    strategy: work_queue
+    quota:
+      max_payload_bytes: 4
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        )
+        .unwrap();
+        let auth: AuthConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+clients:
+  - name: dashboard
+    api_key: dashboard-key
+    quota:
+      requests_per_second: 0.5
+      burst: 2
+  - name: operator
+    api_key: operator-key
+    admin: true
+"#,
+        )
+        .unwrap();
+        let router = PipelineRouter::new(Arc::new(PipelineRegistry::from_config(config).unwrap()))
+            .with_auth(Arc::new(Authenticator::from_config(&auth).unwrap()));
+        let receiver = HttpProtocolReceiver::new("test", local_options());
+        receiver.start(Arc::new(router)).await.unwrap();
+        let dashboard = Some("Bearer dashboard-key");
+        let operator = Some("Bearer operator-key");
+
+        // The client's burst, then 429 until its bucket refills
+        for _ in 0..2 {
+            let (status, _, _) = send_as(&receiver, "POST", "/pipelines/upper", dashboard).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+        let (status, headers, body) =
+            send_as(&receiver, "POST", "/pipelines/upper", dashboard).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers[header::RETRY_AFTER], "2");
+        assert_eq!(body["error"]["kind"], "rate_limited");
+        let (status, _, _) = send_as(&receiver, "POST", "/pipelines/upper", operator).await;
+        assert_eq!(status, StatusCode::OK);
+
+        // Payloads over the pipeline's limit, whoever sends them
+        let (status, headers, body) =
+            send_as(&receiver, "POST", "/pipelines/small", operator).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(headers.get(header::RETRY_AFTER).is_none());
+        assert_eq!(body["error"]["kind"], "payload_too_large");
+
+        // Usage for admins only
+        let (status, _, _) = send_as(&receiver, "GET", "/admin/quotas", dashboard).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        let (status, _, body) = send_as(&receiver, "GET", "/admin/quotas", operator).await;
+        assert_eq!(status, StatusCode::OK);
+        let quotas = body["quotas"].as_array().unwrap();
+        assert_eq!(quotas.len(), 2);
+        assert_eq!(quotas[0]["scope"], "client");
+        assert_eq!(quotas[0]["name"], "dashboard");
+        assert_eq!(quotas[0]["limits"]["burst"], 2);
+        assert_eq!(quotas[0]["active"], 0);
+        assert!(quotas[0]["tokens"].as_f64().unwrap() < 1.0);
+        assert_eq!(quotas[1]["scope"], "pipeline");
+        assert_eq!(quotas[1]["name"], "small");
+        assert_eq!(quotas[1]["limits"]["max_payload_bytes"], 4);
+        assert_eq!(quotas[1]["fuel_window_remaining_ms"], Value::Null);
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_start_twice_and_bind_conflicts() {
+        let receiver = start_receiver(local_options()).await;
+        let port = receiver.local_addr().unwrap().port();
+
+        let registry = Arc::new(PipelineRegistry::new());
+        let router = Arc::new(PipelineRouter::new(registry));
+        assert!(matches!(
+            receiver.start(router.clone()).await,
+            Err(ProtocolError::AlreadyStarted { .. })
+        ));
+
+        let conflicting = HttpProtocolReceiver::new(
+            "conflicting",
+            HttpReceiverOptions {
+                port,
+                ..HttpReceiverOptions::default()
+            },
+        );
+        assert!(matches!(
+            conflicting.start(router).await,
+            Err(ProtocolError::BindFailed { .. })
+        ));
+
+        receiver.shutdown().await.unwrap();
+        conflicting.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn test_options_defaults_and_overrides() {
+        let defaults = HttpReceiverOptions::from_options(&HashMap::new()).unwrap();
+        assert_eq!(defaults, HttpReceiverOptions::default());
+        assert_eq!(defaults.bind_address(), "127.0.0.1:8080");
+
+        let options: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(
+            "{host: 0.0.0.0, port: 9000, max_concurrency: 4, init_wait_ms: 0}",
+        )
+        .unwrap();
+        let options = HttpReceiverOptions::from_options(&options).unwrap();
+        assert_eq!(options.bind_address(), "0.0.0.0:9000");
+        assert_eq!(options.max_concurrency, 4);
+        assert_eq!(options.init_wait, Duration::ZERO);
+
+        let invalid: HashMap<String, serde_yaml::Value> =
+            serde_yaml::from_str("{max_concurrency: 0}").unwrap();
+        assert!(HttpReceiverOptions::from_options(&invalid).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Pluggable protocol receivers (ADR 21).
+//!
+//! A [`ProtocolReceiver`] accepts pipeline requests over one protocol and hands them to the
+//! shared [`PipelineRouter`](crate::server::PipelineRouter), following the same trait-based
+//! pattern as processors and DAG executors. Receivers are created from the `protocols`
+//! section of a server configuration by the [`ProtocolReceiverFactory`]:
+//!
+//! ```text
+//! HTTP / gRPC / Unix socket → ProtocolReceiver → PipelineRequest → PipelineRouter → Pipeline
+//! ```
+//!
+//! # Available Receivers
+//! * [`HttpProtocolReceiver`] - `POST /pipelines/{name}` with JSON or raw-bytes bodies
+//! * [`GrpcProtocolReceiver`] - pipelines served as `processor.v1.Processor`, with
+//!   reflection and health services, optionally over (mutual) TLS
+//! * [`UnixSocketProtocolReceiver`] - length-prefixed protobuf frames over a Unix domain
+//!   socket for local callers, with the companion [`UnixSocketClient`]
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use the_dagwood::config::{ProtocolConfig, ProtocolType};
+//! use the_dagwood::protocols::ProtocolReceiverFactory;
+//! use the_dagwood::server::{PipelineRegistry, PipelineRouter};
+//!
+//! let router = Arc::new(PipelineRouter::new(Arc::new(registry)));
+//! let receiver = ProtocolReceiverFactory::create(&ProtocolConfig::new(ProtocolType::Http))?;
+//! receiver.start(router).await?;
+//! println!("Listening on {}", receiver.endpoint().unwrap());
+//!
+//! receiver.shutdown().await?;
+//! ```
+
+pub mod factory;
+pub mod grpc;
+pub mod http;
+mod options;
+pub mod receiver;
+mod tls;
+pub mod types;
+pub mod unix_socket;
+
+pub use factory::ProtocolReceiverFactory;
+pub use grpc::{GrpcProtocolReceiver, GrpcReceiverOptions};
+pub use http::{HttpProtocolReceiver, HttpReceiverOptions};
+pub use receiver::ProtocolReceiver;
+pub use types::{ErrorKind, ErrorResponse, PipelineRequest, PipelineResponse, ProcessorFailure};
+pub use unix_socket::{UnixSocketClient, UnixSocketProtocolReceiver, UnixSocketReceiverOptions};
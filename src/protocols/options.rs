@@ -0,0 +1,53 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use crate::errors::ProtocolError;
+
+/// Typed access to the `options` map of a protocol configuration.
+///
+/// Every getter returns `Ok(None)` for a missing key and `ProtocolError::InvalidOptions`
+/// naming the key for a value of the wrong type.
+pub(crate) struct ProtocolOptions<'a> {
+    protocol: &'a str,
+    options: &'a HashMap<String, serde_yaml::Value>,
+}
+
+impl<'a> ProtocolOptions<'a> {
+    pub(crate) fn new(protocol: &'a str, options: &'a HashMap<String, serde_yaml::Value>) -> Self {
+        Self { protocol, options }
+    }
+
+    pub(crate) fn string(&self, key: &str) -> Result<Option<String>, ProtocolError> {
+        self.get(key, "a string", |value| value.as_str().map(str::to_string))
+    }
+
+    pub(crate) fn u64(&self, key: &str) -> Result<Option<u64>, ProtocolError> {
+        self.get(key, "a non-negative integer", serde_yaml::Value::as_u64)
+    }
+
+    pub(crate) fn bool(&self, key: &str) -> Result<Option<bool>, ProtocolError> {
+        self.get(key, "true or false", serde_yaml::Value::as_bool)
+    }
+
+    /// Error for an option whose value is not what `expected` describes
+    pub(crate) fn invalid(&self, key: &str, expected: &str) -> ProtocolError {
+        ProtocolError::InvalidOptions {
+            protocol: self.protocol.to_string(),
+            reason: format!("option '{}' must be {}", key, expected),
+        }
+    }
+
+    fn get<T>(
+        &self,
+        key: &str,
+        expected: &str,
+        convert: impl FnOnce(&serde_yaml::Value) -> Option<T>,
+    ) -> Result<Option<T>, ProtocolError> {
+        self.options
+            .get(key)
+            .map(|value| convert(value).ok_or_else(|| self.invalid(key, expected)))
+            .transpose()
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::errors::ProtocolError;
+use crate::server::PipelineRouter;
+
+/// A receiver accepting pipeline requests over one protocol (ADR 21).
+///
+/// Receivers only translate between their wire format and the protocol-agnostic
+/// [`PipelineRequest`](crate::protocols::PipelineRequest) /
+/// [`PipelineResponse`](crate::protocols::PipelineResponse) types; routing, lifecycle and
+/// execution are shared through the [`PipelineRouter`] injected by `start`. Several receivers
+/// of the same protocol can run side by side, e.g. HTTP on two ports.
+#[async_trait]
+pub trait ProtocolReceiver: Send + Sync {
+    /// Bind the receiver's endpoint and start serving requests in the background.
+    ///
+    /// Returns once the receiver is accepting connections, so bind errors (address in use,
+    /// permission denied) are reported here rather than lost in a background task.
+    async fn start(&self, router: Arc<PipelineRouter>) -> Result<(), ProtocolError>;
+
+    /// Stop accepting requests and wait for in-flight requests to finish.
+    ///
+    /// Shutting down a receiver that is not running is a no-op.
+    async fn shutdown(&self) -> Result<(), ProtocolError>;
+
+    /// Protocol identifier for logging and configuration, e.g. `http`
+    fn protocol_name(&self) -> &str;
+
+    /// Name of this receiver instance; defaults to the protocol name
+    fn name(&self) -> &str {
+        self.protocol_name()
+    }
+
+    /// Endpoint the receiver is serving on (e.g. `127.0.0.1:8080`), once started
+    fn endpoint(&self) -> Option<String>;
+}
@@ -0,0 +1,196 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! TLS termination for receivers serving over TCP.
+//!
+//! Connections are accepted and their handshakes run concurrently, so a slow or failing
+//! client never holds up the others; established connections are handed to the server as a
+//! stream of [`TlsConnection`]s carrying the client certificate the handshake verified.
+
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{self, RootCertStore, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tonic::transport::server::Connected;
+
+use crate::observability::messages::server::AccessDenied;
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+
+/// Connections that have not completed their handshake by then are dropped
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Pause after a failed accept (e.g. out of file descriptors) before accepting again
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Server TLS configuration of a PEM certificate chain and private key, requiring client
+/// certificates issued by the PEM `client_ca` bundle if given.
+///
+/// Only `h2` is offered through ALPN, as gRPC requires.
+pub(crate) fn server_config(
+    cert: &Path,
+    key: &Path,
+    client_ca: Option<&Path>,
+) -> Result<ServerConfig, String> {
+    let chain = certificates(cert)?;
+    let key = PrivateKeyDer::from_pem_file(key)
+        .map_err(|e| format!("failed to read private key '{}': {}", key.display(), e))?;
+
+    let builder = ServerConfig::builder();
+    let builder = match client_ca {
+        Some(client_ca) => {
+            let mut roots = RootCertStore::empty();
+            for certificate in certificates(client_ca)? {
+                roots.add(certificate).map_err(|e| {
+                    format!("invalid client CA in '{}': {}", client_ca.display(), e)
+                })?;
+            }
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+                .build()
+                .map_err(|e| format!("invalid client CA '{}': {}", client_ca.display(), e))?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut config = builder
+        .with_single_cert(chain, key)
+        .map_err(|e| format!("invalid certificate '{}': {}", cert.display(), e))?;
+    config.alpn_protocols = vec![b"h2".to_vec()];
+    Ok(config)
+}
+
+/// Every certificate of a PEM file
+fn certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, String> {
+    let read_failed = |e: &dyn std::fmt::Display| {
+        format!("failed to read certificates '{}': {}", path.display(), e)
+    };
+    let certificates = CertificateDer::pem_file_iter(path)
+        .map_err(|e| read_failed(&e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| read_failed(&e))?;
+    if certificates.is_empty() {
+        return Err(read_failed(&"no certificate found"));
+    }
+    Ok(certificates)
+}
+
+/// Accept connections on `listener` until the server drops its end of `connections`,
+/// handing over each connection once its handshake succeeded.
+///
+/// Handshakes rejected for a missing or untrusted client certificate are logged as
+/// `AccessDenied` under `protocol`.
+pub(crate) async fn accept_forever(
+    protocol: &'static str,
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    connections: mpsc::Sender<io::Result<TlsConnection>>,
+) {
+    while !connections.is_closed() {
+        let (stream, _) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(_) => {
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+
+        let acceptor = acceptor.clone();
+        let connections = connections.clone();
+        tokio::spawn(async move {
+            let stream =
+                match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(error)) => return handshake_failed(protocol, &error),
+                    Err(_) => return,
+                };
+            let _ = connections.send(Ok(TlsConnection { stream })).await;
+        });
+    }
+}
+
+/// Log and count handshakes failing on the client certificate; other failures (plain-text
+/// clients, unsupported versions) only concern their client
+fn handshake_failed(protocol: &str, error: &io::Error) {
+    let reason = match error
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<rustls::Error>())
+    {
+        Some(rustls::Error::NoCertificatesPresented) => "missing_credentials",
+        Some(rustls::Error::InvalidCertificate(_)) => "invalid_credentials",
+        _ => return,
+    };
+    AccessDenied {
+        protocol,
+        client: None,
+        access: "connect",
+        reason,
+    }
+    .log();
+    metrics().auth_denied(protocol, reason);
+}
+
+/// An established TLS connection.
+pub(crate) struct TlsConnection {
+    stream: TlsStream<TcpStream>,
+}
+
+/// Peer of a [`TlsConnection`], available in the extensions of its requests.
+#[derive(Debug, Clone)]
+pub(crate) struct TlsConnectInfo {
+    /// DER encoding of the verified client certificate, if the client presented one
+    pub client_certificate: Option<Vec<u8>>,
+}
+
+impl Connected for TlsConnection {
+    type ConnectInfo = TlsConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        let (_, session) = self.stream.get_ref();
+        TlsConnectInfo {
+            client_certificate: session
+                .peer_certificates()
+                .and_then(|chain| chain.first())
+                .map(|certificate| certificate.to_vec()),
+        }
+    }
+}
+
+impl AsyncRead for TlsConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for TlsConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
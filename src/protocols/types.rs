@@ -0,0 +1,569 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Protocol-agnostic request and response types.
+//!
+//! Every protocol receiver translates its wire format into a [`PipelineRequest`], dispatches
+//! it through the [`PipelineRouter`], and translates the resulting [`PipelineResponse`] or
+//! [`ErrorResponse`] back. Keeping the translation at the edges means every protocol reports
+//! the same outcome (and the same error kinds) for the same execution.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, Span};
+
+use crate::engine::{ExecutionReport, NoopObserver, PipelineOutput, ProcessorStatus};
+use crate::errors::{AuthError, JobError, PipelineError, QuotaError};
+use crate::observability::trace_context::TraceContext;
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorMetadata, ProcessorRequest};
+use crate::server::{Access, AuthClient, Credential, PipelineRouter, Webhook};
+use crate::traits::ExecutionObserver;
+
+/// Pipeline metadata key under which the request context is handed to processors
+pub const INITIAL_CONTEXT_KEY: &str = "initial_context";
+
+/// Delay suggested to clients of a pipeline that is not ready yet
+pub const NOT_READY_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// A request to execute a named pipeline, as received by any protocol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineRequest {
+    /// Name of the pipeline to execute
+    pub pipeline: String,
+    /// Input payload for the pipeline's entry processors
+    pub payload: Vec<u8>,
+    /// Request context (client-supplied metadata, protocol, ...) passed to processors as
+    /// the `initial_context` pipeline metadata
+    pub context: HashMap<String, String>,
+    /// URL notified once the execution finished, if the pipeline's webhook accepts request
+    /// callbacks (see [`Webhook`])
+    pub callback_url: Option<String>,
+    /// Credential the client presented, checked when the server requires authentication
+    pub credential: Option<Credential>,
+    /// Trace context of the caller (W3C `traceparent`), continued by the execution's spans
+    pub trace_context: Option<TraceContext>,
+}
+
+impl PipelineRequest {
+    /// Request to execute `pipeline` with `payload` and an empty context.
+    pub fn new(pipeline: impl Into<String>, payload: Vec<u8>) -> Self {
+        Self {
+            pipeline: pipeline.into(),
+            payload,
+            context: HashMap::new(),
+            callback_url: None,
+            credential: None,
+            trace_context: None,
+        }
+    }
+
+    /// Add an entry to the request context.
+    pub fn with_context(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context.insert(key.into(), value.into());
+        self
+    }
+
+    /// Ask to be notified at `url` once the execution finished.
+    pub fn with_callback(mut self, url: impl Into<String>) -> Self {
+        self.callback_url = Some(url.into());
+        self
+    }
+
+    /// Present `credential` on behalf of the client.
+    pub fn with_credential(mut self, credential: Option<Credential>) -> Self {
+        self.credential = credential;
+        self
+    }
+
+    /// Continue the caller's trace, when it sent one, in the execution's spans.
+    pub fn with_trace_context(mut self, trace_context: Option<TraceContext>) -> Self {
+        self.trace_context = trace_context;
+        self
+    }
+
+    /// Span the request's execution runs in: a `dag_request` span continuing the caller's
+    /// trace, or the current span when the caller sent no trace context.
+    pub fn execution_span(&self) -> Span {
+        self.trace_context
+            .as_ref()
+            .map_or_else(Span::current, TraceContext::execution_span)
+    }
+
+    /// Check that the request's credential allows it to execute its pipeline, when the
+    /// server requires authentication, returning the authenticated client.
+    pub fn authorize<'r>(
+        &self,
+        router: &'r PipelineRouter,
+    ) -> Result<Option<&'r AuthClient>, PipelineError> {
+        let protocol = self
+            .context
+            .get("protocol")
+            .map_or("unknown", String::as_str);
+        router.authorize(
+            protocol,
+            self.credential.as_ref(),
+            Access::Pipeline(&self.pipeline),
+        )
+    }
+
+    /// Execute the request, waiting at most `init_wait` for the pipeline to become ready.
+    ///
+    /// Requests whose credential does not allow them to execute the pipeline, requests over
+    /// the quota of their client or pipeline, and callback URLs the pipeline does not
+    /// accept, fail before anything executes.
+    ///
+    /// Processor failures are turned into an [`ErrorKind::ProcessorFailed`] error carrying
+    /// every failed processor, whatever the pipeline's failure strategy.
+    pub async fn dispatch(
+        self,
+        router: &PipelineRouter,
+        init_wait: Duration,
+    ) -> Result<PipelineResponse, ErrorResponse> {
+        self.dispatch_observed(router, init_wait, Arc::new(NoopObserver))
+            .await
+    }
+
+    /// Execute the request like [`dispatch`](Self::dispatch), reporting live progress to
+    /// `observer`.
+    pub async fn dispatch_observed(
+        self,
+        router: &PipelineRouter,
+        init_wait: Duration,
+        observer: Arc<dyn ExecutionObserver>,
+    ) -> Result<PipelineResponse, ErrorResponse> {
+        let client = self.authorize(router)?;
+        let run = router.runs().begin().map_err(ErrorResponse::from)?;
+        let permit = router.admit(client, &self.pipeline, self.payload.len())?;
+        let pipeline = router
+            .route_within(&self.pipeline, init_wait)
+            .await
+            .map_err(ErrorResponse::from)?;
+
+        let name = self.pipeline.clone();
+        let callback_url = self.callback_url.clone();
+        if let Some(url) = &callback_url {
+            Webhook::check_callback(&name, pipeline.webhook().map(Webhook::config), url)?;
+        }
+        let span = self.execution_span();
+        let (input, pipeline_metadata) = self.into_execution();
+        let cancellation = CancellationToken::new();
+        let result = run
+            .run(
+                &cancellation,
+                pipeline.execute_cancellable(
+                    input,
+                    pipeline_metadata,
+                    observer,
+                    cancellation.clone(),
+                ),
+            )
+            .instrument(span)
+            .await;
+        if let (Some(url), Some(webhook)) = (&callback_url, pipeline.webhook()) {
+            webhook.notify_callback(url, &result);
+        }
+        if let Ok(report) = &result {
+            permit.charge_report(report);
+        }
+        let report = result.map_err(|error| {
+            ErrorResponse::from(PipelineError::ExecutionFailed {
+                name: name.clone(),
+                error,
+            })
+        })?;
+
+        if report.errors.is_empty() {
+            Ok(PipelineResponse::from_report(&name, report))
+        } else {
+            Err(ErrorResponse::from_failed_report(&name, &report))
+        }
+    }
+
+    /// Split the request into the executor's input and initial pipeline metadata.
+    pub fn into_execution(self) -> (ProcessorRequest, PipelineMetadata) {
+        let mut pipeline_metadata = PipelineMetadata::new();
+        if !self.context.is_empty() {
+            pipeline_metadata.metadata.insert(
+                INITIAL_CONTEXT_KEY.to_string(),
+                ProcessorMetadata {
+                    metadata: self.context,
+                },
+            );
+        }
+        (
+            ProcessorRequest {
+                payload: self.payload,
+            },
+            pipeline_metadata,
+        )
+    }
+}
+
+/// Outcome of a successful pipeline execution.
+#[derive(Debug, Clone)]
+pub struct PipelineResponse {
+    pub pipeline: String,
+    /// Correlation ID of the execution, as recorded in logs and traces
+    pub run_id: String,
+    /// Executor strategy that ran the pipeline
+    pub strategy: &'static str,
+    /// Canonical payload at the end of the execution
+    pub payload: Vec<u8>,
+    /// Designated pipeline outputs, in configuration order
+    pub outputs: Vec<PipelineOutput>,
+    pub metadata: PipelineMetadata,
+    pub duration: Duration,
+}
+
+impl PipelineResponse {
+    /// Build the response of `pipeline` from its execution report.
+    pub fn from_report(pipeline: &str, report: ExecutionReport) -> Self {
+        Self {
+            pipeline: pipeline.to_string(),
+            run_id: report.run_id,
+            strategy: report.strategy,
+            payload: report.final_output,
+            outputs: report.outputs,
+            metadata: report.pipeline_metadata,
+            duration: report.duration,
+        }
+    }
+}
+
+/// Category of a failed request, mapped to a status code by each protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// No pipeline with the requested name is registered
+    PipelineNotFound,
+    /// The pipeline is still initializing; the request may be retried
+    PipelineNotReady,
+    /// The pipeline failed to initialize and rejects requests until reset
+    PipelineUnavailable,
+    /// No asynchronous job with the requested ID exists, or it expired
+    JobNotFound,
+    /// The asynchronous job is not in a state that allows the request, e.g. fetching the
+    /// result of a job still running or cancelling a finished one
+    JobConflict,
+    /// The request could not be decoded
+    InvalidRequest,
+    /// The request carried no valid credentials
+    Unauthenticated,
+    /// The client may not access the pipeline or operation
+    Forbidden,
+    /// The request payload exceeds the quota of its client or pipeline
+    PayloadTooLarge,
+    /// The quota of the request's client or pipeline is exhausted; the request may be
+    /// retried later
+    RateLimited,
+    /// A configuration supplied to an admin operation is invalid; nothing was changed
+    InvalidConfig,
+    /// One or more processors failed
+    ProcessorFailed,
+    /// The server could not carry out the execution
+    #[serde(rename = "internal_error")]
+    Internal,
+}
+
+impl ErrorKind {
+    /// Stable, lowercase name of the error kind for response bodies
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::PipelineNotFound => "pipeline_not_found",
+            ErrorKind::PipelineNotReady => "pipeline_not_ready",
+            ErrorKind::PipelineUnavailable => "pipeline_unavailable",
+            ErrorKind::JobNotFound => "job_not_found",
+            ErrorKind::JobConflict => "job_conflict",
+            ErrorKind::InvalidRequest => "invalid_request",
+            ErrorKind::Unauthenticated => "unauthenticated",
+            ErrorKind::Forbidden => "forbidden",
+            ErrorKind::PayloadTooLarge => "payload_too_large",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::InvalidConfig => "invalid_config",
+            ErrorKind::ProcessorFailed => "processor_failed",
+            ErrorKind::Internal => "internal_error",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A processor that did not succeed, as reported to clients
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessorFailure {
+    pub processor_id: String,
+    pub status: ProcessorStatus,
+    pub error: String,
+}
+
+/// Structured description of a failed request.
+///
+/// Serializable so failed asynchronous jobs can keep it in their job store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// Name of the requested pipeline, if the request got far enough to know it
+    pub pipeline: Option<String>,
+    /// Correlation ID of the failed execution, for processor failures
+    pub run_id: Option<String>,
+    /// Every processor that did not succeed, in start order
+    pub failures: Vec<ProcessorFailure>,
+    /// How long the client should wait before retrying, if retrying can help
+    pub retry_after: Option<Duration>,
+}
+
+impl ErrorResponse {
+    /// Error without pipeline, run or processor details.
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            pipeline: None,
+            run_id: None,
+            failures: Vec::new(),
+            retry_after: None,
+        }
+    }
+
+    /// `InvalidRequest` error for a request that could not be decoded.
+    pub fn invalid_request(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::InvalidRequest, message)
+    }
+
+    /// `ProcessorFailed` error listing the failed processors of `report`.
+    pub fn from_failed_report(pipeline: &str, report: &ExecutionReport) -> Self {
+        let failures = report
+            .processors_in_order()
+            .into_iter()
+            .filter_map(|(processor_id, processor)| {
+                processor.error.as_ref().map(|error| ProcessorFailure {
+                    processor_id: processor_id.to_string(),
+                    status: processor.status,
+                    error: error.to_string(),
+                })
+            })
+            .collect();
+
+        Self {
+            pipeline: Some(pipeline.to_string()),
+            run_id: Some(report.run_id.clone()),
+            failures,
+            ..Self::new(
+                ErrorKind::ProcessorFailed,
+                format!(
+                    "Pipeline '{}' failed: {} processor(s) failed",
+                    pipeline,
+                    report.errors.len()
+                ),
+            )
+        }
+    }
+}
+
+impl From<PipelineError> for ErrorResponse {
+    fn from(error: PipelineError) -> Self {
+        let (kind, pipeline) = match &error {
+            PipelineError::NotFound { name } => (ErrorKind::PipelineNotFound, Some(name)),
+            PipelineError::NotReady { name, .. }
+            | PipelineError::InitializationTimeout { name, .. } => {
+                (ErrorKind::PipelineNotReady, Some(name))
+            }
+            PipelineError::InitializationFailed { name, .. }
+            | PipelineError::PermanentlyFailed { name, .. }
+            | PipelineError::Disabled { name } => (ErrorKind::PipelineUnavailable, Some(name)),
+            PipelineError::ExecutionFailed { name, .. } => (ErrorKind::Internal, Some(name)),
+            PipelineError::InvalidConfig { .. } | PipelineError::ConfigLoadFailed { .. } => {
+                (ErrorKind::InvalidConfig, None)
+            }
+            PipelineError::DuplicateName { .. } => (ErrorKind::Internal, None),
+            PipelineError::ShuttingDown => (ErrorKind::PipelineUnavailable, None),
+            PipelineError::CallbackRejected { name, .. } => (ErrorKind::InvalidRequest, Some(name)),
+            PipelineError::AccessDenied(AuthError::Forbidden { .. }) => {
+                (ErrorKind::Forbidden, None)
+            }
+            PipelineError::AccessDenied(_) => (ErrorKind::Unauthenticated, None),
+            PipelineError::QuotaExceeded(QuotaError::PayloadTooLarge { .. }) => {
+                (ErrorKind::PayloadTooLarge, None)
+            }
+            PipelineError::QuotaExceeded(_) => (ErrorKind::RateLimited, None),
+        };
+        let retry_after = match &error {
+            PipelineError::QuotaExceeded(error) => error.retry_after(),
+            _ => (kind == ErrorKind::PipelineNotReady).then_some(NOT_READY_RETRY_AFTER),
+        };
+
+        Self {
+            pipeline: pipeline.cloned(),
+            retry_after,
+            ..Self::new(kind, error.to_string())
+        }
+    }
+}
+
+impl From<JobError> for ErrorResponse {
+    fn from(error: JobError) -> Self {
+        let kind = match &error {
+            JobError::NotFound { .. } => ErrorKind::JobNotFound,
+            JobError::NotFinished { .. }
+            | JobError::AlreadyFinished { .. }
+            | JobError::Cancelled { .. } => ErrorKind::JobConflict,
+            JobError::Pipeline(error) => return Self::from(error.clone()),
+            JobError::StoreFailed { .. } => ErrorKind::Internal,
+        };
+
+        Self {
+            retry_after: matches!(error, JobError::NotFinished { .. })
+                .then_some(NOT_READY_RETRY_AFTER),
+            ..Self::new(kind, error.to_string())
+        }
+    }
+}
+
+impl fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PipelinesConfig;
+    use crate::server::{PipelineRegistry, PipelineState};
+    use std::sync::Arc;
+
+    fn router() -> PipelineRouter {
+        let config = PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        )
+        .unwrap();
+        PipelineRouter::new(Arc::new(PipelineRegistry::from_config(config).unwrap()))
+    }
+
+    #[test]
+    fn test_request_context_becomes_initial_context() {
+        let (input, metadata) = PipelineRequest::new("upper", b"hello".to_vec())
+            .with_context("protocol", "http")
+            .into_execution();
+
+        assert_eq!(input.payload, b"hello");
+        assert_eq!(
+            metadata.metadata[INITIAL_CONTEXT_KEY].metadata["protocol"],
+            "http"
+        );
+        let (_, empty) = PipelineRequest::new("upper", vec![]).into_execution();
+        assert!(empty.metadata.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_returns_final_payload() {
+        let response = PipelineRequest::new("upper", b"hello".to_vec())
+            .dispatch(&router(), Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(response.pipeline, "upper");
+        assert_eq!(response.strategy, "WorkQueue");
+        assert_eq!(response.payload, b"HELLO");
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_reports_processor_failures() {
+        let error = PipelineRequest::new("upper", vec![0xff, 0xfe])
+            .dispatch(&router(), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::ProcessorFailed);
+        assert_eq!(error.pipeline.as_deref(), Some("upper"));
+        assert!(error.run_id.is_some());
+        assert_eq!(error.failures.len(), 1);
+        assert_eq!(error.failures[0].processor_id, "upper");
+        assert_eq!(error.failures[0].status, ProcessorStatus::Failed);
+        assert!(error.failures[0].error.contains("Invalid UTF-8"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_rejects_callbacks_without_webhook() {
+        let error = PipelineRequest::new("upper", b"hello".to_vec())
+            .with_callback("http://127.0.0.1:9000/hooks")
+            .dispatch(&router(), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.kind, ErrorKind::InvalidRequest);
+        assert_eq!(error.pipeline.as_deref(), Some("upper"));
+        assert!(error.run_id.is_none());
+    }
+
+    #[test]
+    fn test_pipeline_errors_map_to_error_kinds() {
+        let not_found = ErrorResponse::from(PipelineError::NotFound {
+            name: "missing".to_string(),
+        });
+        assert_eq!(not_found.kind, ErrorKind::PipelineNotFound);
+        assert_eq!(not_found.retry_after, None);
+
+        let not_ready = ErrorResponse::from(PipelineError::NotReady {
+            name: "slow".to_string(),
+            state: PipelineState::Initializing,
+        });
+        assert_eq!(not_ready.kind, ErrorKind::PipelineNotReady);
+        assert_eq!(not_ready.pipeline.as_deref(), Some("slow"));
+        assert_eq!(not_ready.retry_after, Some(NOT_READY_RETRY_AFTER));
+
+        let failed = ErrorResponse::from(PipelineError::PermanentlyFailed {
+            name: "broken".to_string(),
+            attempts: 4,
+            reason: "missing module".to_string(),
+        });
+        assert_eq!(failed.kind, ErrorKind::PipelineUnavailable);
+        assert_eq!(failed.kind.as_str(), "pipeline_unavailable");
+
+        let missing = ErrorResponse::from(PipelineError::from(AuthError::MissingCredentials));
+        assert_eq!(missing.kind, ErrorKind::Unauthenticated);
+        let forbidden = ErrorResponse::from(PipelineError::from(AuthError::Forbidden {
+            client: "dashboard".to_string(),
+            access: "use the admin API".to_string(),
+        }));
+        assert_eq!(forbidden.kind, ErrorKind::Forbidden);
+        assert_eq!(
+            forbidden.message,
+            "Client 'dashboard' may not use the admin API"
+        );
+
+        let rate_limited = ErrorResponse::from(PipelineError::from(QuotaError::RateLimited {
+            scope: "client",
+            name: "dashboard".to_string(),
+            retry_after: Duration::from_millis(250),
+        }));
+        assert_eq!(rate_limited.kind, ErrorKind::RateLimited);
+        assert_eq!(rate_limited.retry_after, Some(Duration::from_millis(250)));
+        let too_large = ErrorResponse::from(PipelineError::from(QuotaError::PayloadTooLarge {
+            scope: "pipeline",
+            name: "upper".to_string(),
+            size: 10,
+            limit: 4,
+        }));
+        assert_eq!(too_large.kind, ErrorKind::PayloadTooLarge);
+        assert_eq!(too_large.retry_after, None);
+    }
+}
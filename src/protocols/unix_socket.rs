@@ -0,0 +1,920 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Unix domain socket protocol receiver and client.
+//!
+//! Serves hosted pipelines to processes on the same host without HTTP overhead, using a
+//! length-prefixed framing of the `processor.v1` protobuf messages.
+//!
+//! # Wire Protocol
+//! Every frame is a 4-byte big-endian length followed by that many bytes. A connection
+//! carries any number of sequential requests:
+//!
+//! ```text
+//! request:  [len][pipeline name, UTF-8][len][ProcessorRequest]
+//! response: [len][ProcessorResponse]
+//! ```
+//!
+//! The pipeline name may be followed by a newline and the caller's W3C `traceparent`, which
+//! continues the caller's trace in the execution's spans (see
+//! [`UnixSocketClient::process_traced`]).
+//!
+//! The response carries the final payload as `next_payload` with the accumulated pipeline
+//! metadata, or an `error` outcome whose code follows HTTP: `400` malformed request, `404`
+//! unknown pipeline, `413` oversized frame, `503` pipeline not ready or unavailable and
+//! `500` processor failure. After a `400` or `413` the receiver closes the connection.
+//! Requests over the quota of their client or pipeline fail with `413` for their payload
+//! and `429` otherwise, keeping the connection open.
+//!
+//! When the server has `auth` enabled (see [`crate::server::auth`]), a connection opens with
+//! one frame holding the client's API key, sent by [`UnixSocketClient::connect_with_key`].
+//! Every request of the connection is authorized as that client and fails with `401`
+//! without a known key or `403` for pipelines outside the client's allowlist.
+//!
+//! # Example
+//! ```rust,ignore
+//! use the_dagwood::protocols::UnixSocketClient;
+//!
+//! let mut client = UnixSocketClient::connect("/var/run/dagwood.sock").await?;
+//! let response = client.process("text_processing", b"hello".to_vec()).await?;
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{DirBuilder, Permissions};
+use std::io;
+use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use prost::Message;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{watch, Semaphore};
+use tokio::task::{JoinHandle, JoinSet};
+
+use crate::config::ProtocolConfig;
+use crate::errors::ProtocolError;
+use crate::observability::trace_context::TraceContext;
+use crate::proto::processor_v1::processor_response::Outcome;
+use crate::proto::processor_v1::{ErrorDetail, ProcessorRequest, ProcessorResponse};
+use crate::protocols::options::ProtocolOptions;
+use crate::protocols::{ErrorKind, ErrorResponse, PipelineRequest, ProtocolReceiver};
+use crate::server::{Credential, PipelineRouter};
+
+/// Default socket path
+pub const DEFAULT_SOCKET_PATH: &str = "/tmp/dagwood.sock";
+/// Default maximum frame size (4 MiB); larger frames are answered with `413`
+pub const DEFAULT_MAX_FRAME_BYTES: usize = 4 * 1024 * 1024;
+/// Default maximum number of open connections; further clients wait to be accepted
+pub const DEFAULT_MAX_CONNECTIONS: usize = 256;
+/// Default time a request waits for its pipeline to initialize before `503`
+pub const DEFAULT_INIT_WAIT_MS: u64 = 30_000;
+
+const PROTOCOL_NAME: &str = "unix_socket";
+
+/// Options of a Unix socket receiver, from the `options` of its protocol configuration.
+///
+/// # Example
+/// ```yaml
+/// type: unix_socket
+/// options:
+///   path: "/var/run/dagwood.sock"
+///   permissions: "0660"      # octal file mode of the socket
+///   max_frame_bytes: 1048576
+///   max_connections: 64
+///   init_wait_ms: 5000
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixSocketReceiverOptions {
+    pub path: PathBuf,
+    /// File mode of the socket; the process umask applies when unset
+    pub permissions: Option<u32>,
+    pub max_frame_bytes: usize,
+    pub max_connections: usize,
+    /// How long a request waits for an initializing pipeline before `503`
+    pub init_wait: Duration,
+}
+
+impl Default for UnixSocketReceiverOptions {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(DEFAULT_SOCKET_PATH),
+            permissions: None,
+            max_frame_bytes: DEFAULT_MAX_FRAME_BYTES,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            init_wait: Duration::from_millis(DEFAULT_INIT_WAIT_MS),
+        }
+    }
+}
+
+impl UnixSocketReceiverOptions {
+    /// Parse the receiver options, falling back to the defaults for missing keys.
+    pub fn from_options(
+        options: &HashMap<String, serde_yaml::Value>,
+    ) -> Result<Self, ProtocolError> {
+        let options = ProtocolOptions::new(PROTOCOL_NAME, options);
+        let defaults = Self::default();
+
+        let permissions = match options.string("permissions")? {
+            Some(mode) => Some(
+                u32::from_str_radix(mode.trim_start_matches("0o"), 8)
+                    .ok()
+                    .filter(|mode| *mode <= 0o777)
+                    .ok_or_else(|| options.invalid("permissions", "an octal mode like \"0660\""))?,
+            ),
+            None => None,
+        };
+        let size = |key: &str, default: usize| -> Result<usize, ProtocolError> {
+            match options.u64(key)? {
+                Some(value) => usize::try_from(value)
+                    .ok()
+                    .filter(|value| *value > 0)
+                    .ok_or_else(|| options.invalid(key, "a positive integer")),
+                None => Ok(default),
+            }
+        };
+
+        Ok(Self {
+            path: options
+                .string("path")?
+                .map(PathBuf::from)
+                .unwrap_or(defaults.path),
+            permissions,
+            max_frame_bytes: size("max_frame_bytes", defaults.max_frame_bytes)?,
+            max_connections: size("max_connections", defaults.max_connections)?,
+            init_wait: options
+                .u64("init_wait_ms")?
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.init_wait),
+        })
+    }
+}
+
+/// A running server: how to stop it
+struct RunningServer {
+    shutdown: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
+
+/// Receiver serving pipelines over a Unix domain socket.
+///
+/// Each connection is served by its own task, so slow callers do not hold up others. The
+/// socket file is created on `start` (replacing a stale socket left by a previous run) and
+/// removed on `shutdown`.
+pub struct UnixSocketProtocolReceiver {
+    name: String,
+    options: UnixSocketReceiverOptions,
+    running: Mutex<Option<RunningServer>>,
+}
+
+impl UnixSocketProtocolReceiver {
+    /// Create a receiver with the given instance name and options.
+    pub fn new(name: impl Into<String>, options: UnixSocketReceiverOptions) -> Self {
+        Self {
+            name: name.into(),
+            options,
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Create a receiver from its protocol configuration.
+    pub fn from_config(config: &ProtocolConfig) -> Result<Self, ProtocolError> {
+        Ok(Self::new(
+            config.name(),
+            UnixSocketReceiverOptions::from_options(&config.options)?,
+        ))
+    }
+
+    /// Options the receiver was created with.
+    pub fn options(&self) -> &UnixSocketReceiverOptions {
+        &self.options
+    }
+
+    /// Bind the socket, replacing a stale socket file but never a live socket or other file
+    fn bind(&self) -> Result<UnixListener, ProtocolError> {
+        let path = &self.options.path;
+        let bind_failed = |reason: String| ProtocolError::BindFailed {
+            endpoint: path.display().to_string(),
+            reason,
+        };
+
+        if let Ok(metadata) = std::fs::symlink_metadata(path) {
+            if !metadata.file_type().is_socket() {
+                return Err(bind_failed("path exists and is not a socket".to_string()));
+            }
+            if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                return Err(bind_failed("socket is in use".to_string()));
+            }
+            std::fs::remove_file(path).map_err(|e| bind_failed(e.to_string()))?;
+        }
+
+        let Some(mode) = self.options.permissions else {
+            return UnixListener::bind(path).map_err(|e| bind_failed(e.to_string()));
+        };
+
+        // Bind inside a directory only we can enter, set the mode, then rename the socket
+        // into place, so it is never reachable with the permissions of the process umask
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| bind_failed("path has no file name".to_string()))?;
+        let staging = path.with_file_name(format!(
+            ".{}.{}.bind",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        DirBuilder::new()
+            .mode(0o700)
+            .create(&staging)
+            .map_err(|e| bind_failed(format!("failed to create staging directory: {}", e)))?;
+        let staged = staging.join("socket");
+        let bound = UnixListener::bind(&staged)
+            .map_err(|e| bind_failed(e.to_string()))
+            .and_then(|listener| {
+                std::fs::set_permissions(&staged, Permissions::from_mode(mode))
+                    .map_err(|e| bind_failed(format!("failed to set permissions: {}", e)))?;
+                std::fs::rename(&staged, path).map_err(|e| bind_failed(e.to_string()))?;
+                Ok(listener)
+            });
+        let _ = std::fs::remove_file(&staged);
+        let _ = std::fs::remove_dir(&staging);
+        bound
+    }
+}
+
+#[async_trait]
+impl ProtocolReceiver for UnixSocketProtocolReceiver {
+    async fn start(&self, router: Arc<PipelineRouter>) -> Result<(), ProtocolError> {
+        let mut running = self.running.lock().unwrap();
+        if running.is_some() {
+            return Err(ProtocolError::AlreadyStarted {
+                name: self.name.clone(),
+            });
+        }
+
+        let listener = self.bind()?;
+        let (shutdown, shutdown_signal) = watch::channel(false);
+        let server = SocketServer {
+            router,
+            max_frame_bytes: self.options.max_frame_bytes,
+            init_wait: self.options.init_wait,
+        };
+        *running = Some(RunningServer {
+            shutdown,
+            task: tokio::spawn(server.serve(
+                listener,
+                Arc::new(Semaphore::new(self.options.max_connections)),
+                shutdown_signal,
+            )),
+        });
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> Result<(), ProtocolError> {
+        let running = self.running.lock().unwrap().take();
+        let Some(running) = running else {
+            return Ok(());
+        };
+
+        let _ = running.shutdown.send(true);
+        let served = running.task.await;
+        let _ = std::fs::remove_file(&self.options.path);
+        served.map_err(|e| ProtocolError::ServeFailed {
+            name: self.name.clone(),
+            reason: e.to_string(),
+        })
+    }
+
+    fn protocol_name(&self) -> &str {
+        PROTOCOL_NAME
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn endpoint(&self) -> Option<String> {
+        self.running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|_| format!("unix://{}", self.options.path.display()))
+    }
+}
+
+/// State shared by every connection of a receiver
+#[derive(Clone)]
+struct SocketServer {
+    router: Arc<PipelineRouter>,
+    max_frame_bytes: usize,
+    init_wait: Duration,
+}
+
+impl SocketServer {
+    /// Accept connections until shutdown, then wait for open connections to finish
+    async fn serve(
+        self,
+        listener: UnixListener,
+        connection_slots: Arc<Semaphore>,
+        mut shutdown: watch::Receiver<bool>,
+    ) {
+        let mut connections = JoinSet::new();
+        loop {
+            let accepted = tokio::select! {
+                _ = shutdown.changed() => break,
+                accepted = accept(&listener, &connection_slots) => accepted,
+            };
+            // Reap finished connections so the set does not grow with the connection count
+            while connections.try_join_next().is_some() {}
+
+            match accepted {
+                Ok((stream, slot)) => {
+                    let server = self.clone();
+                    let shutdown = shutdown.clone();
+                    connections.spawn(async move {
+                        server.serve_connection(stream, shutdown).await;
+                        drop(slot);
+                    });
+                }
+                // Accept errors (e.g. out of file descriptors) are transient; back off briefly
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        }
+
+        drop(listener);
+        while connections.join_next().await.is_some() {}
+    }
+
+    /// Serve sequential requests on one connection until the client disconnects or the
+    /// receiver shuts down; a request in progress is always answered
+    async fn serve_connection(&self, stream: UnixStream, mut shutdown: watch::Receiver<bool>) {
+        let (mut reader, mut writer) = stream.into_split();
+        let credential = match self.router.auth() {
+            Some(_) => {
+                let key = tokio::select! {
+                    _ = shutdown.changed() => return,
+                    key = read_frame(&mut reader, self.max_frame_bytes) => key,
+                };
+                match key {
+                    Ok(Some(key)) => Credential::from_headers(std::str::from_utf8(&key).ok(), None),
+                    _ => return,
+                }
+            }
+            None => None,
+        };
+
+        loop {
+            let name = tokio::select! {
+                _ = shutdown.changed() => return,
+                name = read_frame(&mut reader, self.max_frame_bytes) => name,
+            };
+            let request = match name {
+                Ok(Some(name)) => read_frame(&mut reader, self.max_frame_bytes)
+                    .await
+                    .map(|request| request.map(|request| (name, request))),
+                other => other.map(|_| None),
+            };
+
+            let (response, keep_open) = match request {
+                Ok(Some((name, request))) => self.process(name, request, credential.as_ref()).await,
+                // Clean disconnect between requests
+                Ok(None) => return,
+                Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                    (error_response(413, e.to_string()), false)
+                }
+                Err(_) => return,
+            };
+
+            if write_frame(&mut writer, &response.encode_to_vec())
+                .await
+                .is_err()
+                || !keep_open
+            {
+                return;
+            }
+        }
+    }
+
+    /// Execute one request; returns the response and whether the connection stays usable
+    async fn process(
+        &self,
+        name: Vec<u8>,
+        request: Vec<u8>,
+        credential: Option<&Credential>,
+    ) -> (ProcessorResponse, bool) {
+        let Ok(name) = String::from_utf8(name) else {
+            return (
+                error_response(400, "Pipeline name is not valid UTF-8"),
+                false,
+            );
+        };
+        let (name, trace_context) = match name.split_once('\n') {
+            Some((name, traceparent)) => (name.to_string(), TraceContext::parse(traceparent)),
+            None => (name, None),
+        };
+        let request = match ProcessorRequest::decode(request.as_slice()) {
+            Ok(request) => request,
+            Err(e) => {
+                return (
+                    error_response(400, format!("Invalid ProcessorRequest: {}", e)),
+                    false,
+                )
+            }
+        };
+
+        let response = match PipelineRequest::new(name, request.payload)
+            .with_context("protocol", PROTOCOL_NAME)
+            .with_credential(credential.cloned())
+            .with_trace_context(trace_context)
+            .dispatch(&self.router, self.init_wait)
+            .await
+        {
+            Ok(response) => ProcessorResponse {
+                outcome: Some(Outcome::NextPayload(response.payload)),
+                metadata: Some(response.metadata),
+            },
+            Err(error) => error_response(error_code(error.kind), failure_message(&error)),
+        };
+        (response, true)
+    }
+}
+
+/// Wait for a free connection slot, then accept the next connection
+async fn accept(
+    listener: &UnixListener,
+    connection_slots: &Arc<Semaphore>,
+) -> io::Result<(UnixStream, tokio::sync::OwnedSemaphorePermit)> {
+    let slot = connection_slots
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("connection semaphore is never closed");
+    let (stream, _) = listener.accept().await?;
+    Ok((stream, slot))
+}
+
+/// HTTP-style error code reported for a request that failed
+fn error_code(kind: ErrorKind) -> i32 {
+    match kind {
+        ErrorKind::PipelineNotFound | ErrorKind::JobNotFound => 404,
+        ErrorKind::PipelineNotReady | ErrorKind::PipelineUnavailable => 503,
+        ErrorKind::JobConflict => 409,
+        ErrorKind::InvalidRequest => 400,
+        ErrorKind::Unauthenticated => 401,
+        ErrorKind::Forbidden => 403,
+        ErrorKind::PayloadTooLarge => 413,
+        ErrorKind::RateLimited => 429,
+        ErrorKind::InvalidConfig => 422,
+        ErrorKind::ProcessorFailed | ErrorKind::Internal => 500,
+    }
+}
+
+/// Error message, listing the failed processors of a processor failure
+fn failure_message(error: &ErrorResponse) -> String {
+    if error.failures.is_empty() {
+        return error.message.clone();
+    }
+    let failures: Vec<String> = error
+        .failures
+        .iter()
+        .map(|failure| format!("{}: {}", failure.processor_id, failure.error))
+        .collect();
+    format!("{} ({})", error.message, failures.join("; "))
+}
+
+fn error_response(code: i32, message: impl Into<String>) -> ProcessorResponse {
+    ProcessorResponse {
+        outcome: Some(Outcome::Error(ErrorDetail {
+            code,
+            message: message.into(),
+        })),
+        metadata: None,
+    }
+}
+
+/// Read one length-prefixed frame; `Ok(None)` if the peer closed the connection first.
+///
+/// Frames above `max_bytes` fail with `InvalidData` without reading their body.
+async fn read_frame(
+    reader: &mut (impl AsyncRead + Unpin),
+    max_bytes: usize,
+) -> io::Result<Option<Vec<u8>>> {
+    let len = match reader.read_u32().await {
+        Ok(len) => len as usize,
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if len > max_bytes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "frame of {} bytes exceeds the limit of {} bytes",
+                len, max_bytes
+            ),
+        ));
+    }
+
+    let mut frame = vec![0; len];
+    reader.read_exact(&mut frame).await?;
+    Ok(Some(frame))
+}
+
+/// Write `frames` as consecutive length-prefixed frames
+async fn write_frames(writer: &mut (impl AsyncWrite + Unpin), frames: &[&[u8]]) -> io::Result<()> {
+    let mut buffer = Vec::with_capacity(frames.iter().map(|frame| frame.len() + 4).sum());
+    for frame in frames {
+        let len = u32::try_from(frame.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame exceeds 4 GiB"))?;
+        buffer.extend_from_slice(&len.to_be_bytes());
+        buffer.extend_from_slice(frame);
+    }
+    writer.write_all(&buffer).await?;
+    writer.flush().await
+}
+
+async fn write_frame(writer: &mut (impl AsyncWrite + Unpin), frame: &[u8]) -> io::Result<()> {
+    write_frames(writer, &[frame]).await
+}
+
+/// Client for pipelines served by a [`UnixSocketProtocolReceiver`].
+///
+/// A client holds one connection and sends requests over it one at a time; open several
+/// clients for concurrent requests.
+pub struct UnixSocketClient {
+    endpoint: String,
+    stream: UnixStream,
+    max_frame_bytes: usize,
+}
+
+impl UnixSocketClient {
+    /// Connect to the receiver listening on `path`.
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, ProtocolError> {
+        let endpoint = path.as_ref().display().to_string();
+        let stream = UnixStream::connect(path.as_ref()).await.map_err(|e| {
+            ProtocolError::ConnectionFailed {
+                endpoint: endpoint.clone(),
+                reason: e.to_string(),
+            }
+        })?;
+        Ok(Self {
+            endpoint,
+            stream,
+            max_frame_bytes: DEFAULT_MAX_FRAME_BYTES,
+        })
+    }
+
+    /// Connect to the receiver listening on `path`, authenticating with `api_key`.
+    ///
+    /// Required when the server has `auth` enabled; the key frame opens the connection.
+    pub async fn connect_with_key(
+        path: impl AsRef<Path>,
+        api_key: &str,
+    ) -> Result<Self, ProtocolError> {
+        let mut client = Self::connect(path).await?;
+        write_frame(&mut client.stream, api_key.as_bytes())
+            .await
+            .map_err(|e| client.connection_failed(e))?;
+        Ok(client)
+    }
+
+    /// Limit the size of responses the client accepts (defaults to 4 MiB).
+    pub fn with_max_frame_bytes(mut self, max_frame_bytes: usize) -> Self {
+        self.max_frame_bytes = max_frame_bytes;
+        self
+    }
+
+    /// Execute `pipeline` with `payload`.
+    ///
+    /// Pipeline and processor failures are returned as the response's `error` outcome;
+    /// `Err` means the exchange itself failed and the connection should be discarded.
+    pub async fn process(
+        &mut self,
+        pipeline: &str,
+        payload: impl Into<Vec<u8>>,
+    ) -> Result<ProcessorResponse, ProtocolError> {
+        self.process_traced(pipeline, payload, None).await
+    }
+
+    /// Execute `pipeline` with `payload` like [`process`](Self::process), continuing the
+    /// caller's trace when `trace_context` is present.
+    pub async fn process_traced(
+        &mut self,
+        pipeline: &str,
+        payload: impl Into<Vec<u8>>,
+        trace_context: Option<&TraceContext>,
+    ) -> Result<ProcessorResponse, ProtocolError> {
+        let name = match trace_context {
+            Some(trace_context) => format!("{}\n{}", pipeline, trace_context.to_traceparent()),
+            None => pipeline.to_string(),
+        };
+        let request = ProcessorRequest {
+            payload: payload.into(),
+        };
+        write_frames(
+            &mut self.stream,
+            &[name.as_bytes(), &request.encode_to_vec()],
+        )
+        .await
+        .map_err(|e| self.connection_failed(e))?;
+
+        let response = match read_frame(&mut self.stream, self.max_frame_bytes).await {
+            Ok(Some(response)) => response,
+            Ok(None) => {
+                return Err(self.connection_failed(io::ErrorKind::UnexpectedEof.into()));
+            }
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
+                return Err(ProtocolError::InvalidFrame {
+                    reason: e.to_string(),
+                });
+            }
+            Err(e) => return Err(self.connection_failed(e)),
+        };
+        ProcessorResponse::decode(response.as_slice()).map_err(|e| ProtocolError::InvalidFrame {
+            reason: format!("invalid ProcessorResponse: {}", e),
+        })
+    }
+
+    fn connection_failed(&self, error: io::Error) -> ProtocolError {
+        ProtocolError::ConnectionFailed {
+            endpoint: self.endpoint.clone(),
+            reason: error.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AuthConfig, PipelinesConfig};
+    use crate::observability::chrome_trace::ChromeTraceLayer;
+    use crate::observability::trace_context::dag_request_trace_ids;
+    use crate::server::{Authenticator, PipelineRegistry};
+    use tracing_subscriber::prelude::*;
+
+    const PIPELINES: &str = r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: reverse
+    strategy: reactive
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+"#;
+
+    fn router() -> Arc<PipelineRouter> {
+        let registry =
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap();
+        Arc::new(PipelineRouter::new(Arc::new(registry)))
+    }
+
+    fn options(dir: &tempfile::TempDir) -> UnixSocketReceiverOptions {
+        UnixSocketReceiverOptions {
+            path: dir.path().join("dagwood.sock"),
+            ..UnixSocketReceiverOptions::default()
+        }
+    }
+
+    fn outcome(response: ProcessorResponse) -> Outcome {
+        response.outcome.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_response_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let receiver = UnixSocketProtocolReceiver::new("local", options(&dir));
+        receiver.start(router()).await.unwrap();
+        assert_eq!(
+            receiver.endpoint().unwrap(),
+            format!("unix://{}", receiver.options().path.display())
+        );
+
+        let mut client = UnixSocketClient::connect(&receiver.options().path)
+            .await
+            .unwrap();
+        let response = client.process("upper", "hello").await.unwrap();
+        assert!(response.metadata.is_some());
+        assert_eq!(outcome(response), Outcome::NextPayload(b"HELLO".to_vec()));
+
+        // The connection serves further requests
+        let response = client.process("reverse", "hello").await.unwrap();
+        assert_eq!(outcome(response), Outcome::NextPayload(b"olleh".to_vec()));
+
+        match outcome(client.process("missing", "hello").await.unwrap()) {
+            Outcome::Error(error) => assert_eq!(error.code, 404),
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        match outcome(client.process("upper", vec![0xff, 0xfe]).await.unwrap()) {
+            Outcome::Error(error) => {
+                assert_eq!(error.code, 500);
+                assert!(error.message.contains("upper"));
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_traceparent_continues_caller_trace() {
+        let (layer, trace) = ChromeTraceLayer::new();
+        let _subscriber = tracing_subscriber::registry().with(layer).set_default();
+        let dir = tempfile::tempdir().unwrap();
+        let receiver = UnixSocketProtocolReceiver::new("local", options(&dir));
+        receiver.start(router()).await.unwrap();
+
+        let incoming =
+            TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+        let mut client = UnixSocketClient::connect(&receiver.options().path)
+            .await
+            .unwrap();
+        let response = client
+            .process_traced("upper", "hello", Some(&incoming))
+            .await
+            .unwrap();
+        assert_eq!(outcome(response), Outcome::NextPayload(b"HELLO".to_vec()));
+        client.process("upper", "hello").await.unwrap();
+
+        // Only the request that sent a trace context continues it
+        assert_eq!(dag_request_trace_ids(&trace), vec![incoming.trace_id()]);
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_api_key_frame_authenticates_connection() {
+        let auth: AuthConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+clients:
+  - name: dashboard
+    api_key: dashboard-key
+    pipelines: [upper]
+"#,
+        )
+        .unwrap();
+        let registry =
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(PIPELINES).unwrap()).unwrap();
+        let router = PipelineRouter::new(Arc::new(registry))
+            .with_auth(Arc::new(Authenticator::from_config(&auth).unwrap()));
+        let dir = tempfile::tempdir().unwrap();
+        let receiver = UnixSocketProtocolReceiver::new("local", options(&dir));
+        receiver.start(Arc::new(router)).await.unwrap();
+        let path = &receiver.options().path;
+        let error_code = |response: ProcessorResponse| match outcome(response) {
+            Outcome::Error(error) => error.code,
+            other => panic!("unexpected outcome: {:?}", other),
+        };
+
+        let mut client = UnixSocketClient::connect_with_key(path, "dashboard-key")
+            .await
+            .unwrap();
+        let response = client.process("upper", "hello").await.unwrap();
+        assert_eq!(outcome(response), Outcome::NextPayload(b"HELLO".to_vec()));
+        assert_eq!(
+            error_code(client.process("reverse", "hello").await.unwrap()),
+            403
+        );
+
+        let mut client = UnixSocketClient::connect_with_key(path, "guess")
+            .await
+            .unwrap();
+        assert_eq!(
+            error_code(client.process("upper", "hello").await.unwrap()),
+            401
+        );
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_connections() {
+        let dir = tempfile::tempdir().unwrap();
+        let receiver = UnixSocketProtocolReceiver::new("local", options(&dir));
+        receiver.start(router()).await.unwrap();
+
+        let mut requests = JoinSet::new();
+        for i in 0..8 {
+            let path = receiver.options().path.clone();
+            requests.spawn(async move {
+                let mut client = UnixSocketClient::connect(path).await.unwrap();
+                let response = client.process("reverse", format!("abc{}", i)).await;
+                (i, outcome(response.unwrap()))
+            });
+        }
+        while let Some(result) = requests.join_next().await {
+            let (i, outcome) = result.unwrap();
+            assert_eq!(
+                outcome,
+                Outcome::NextPayload(format!("{}cba", i).into_bytes())
+            );
+        }
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_socket_permissions_and_cleanup() {
+        let dir = tempfile::tempdir().unwrap();
+        let receiver = UnixSocketProtocolReceiver::new(
+            "local",
+            UnixSocketReceiverOptions {
+                permissions: Some(0o600),
+                ..options(&dir)
+            },
+        );
+        let path = receiver.options().path.clone();
+
+        receiver.start(router()).await.unwrap();
+        let metadata = std::fs::metadata(&path).unwrap();
+        assert!(metadata.file_type().is_socket());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        let entries: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(entries, vec![path.clone()]);
+        assert!(std::os::unix::net::UnixStream::connect(&path).is_ok());
+
+        receiver.shutdown().await.unwrap();
+        assert!(!path.exists());
+        assert_eq!(receiver.endpoint(), None);
+    }
+
+    #[tokio::test]
+    async fn test_stale_socket_is_replaced_but_live_socket_is_not() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = options(&dir).path;
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+        let receiver = UnixSocketProtocolReceiver::new("first", options(&dir));
+        receiver.start(router()).await.unwrap();
+
+        let second = UnixSocketProtocolReceiver::new("second", options(&dir));
+        let error = second.start(router()).await.unwrap_err();
+        assert!(matches!(error, ProtocolError::BindFailed { .. }));
+
+        receiver.shutdown().await.unwrap();
+
+        std::fs::write(&path, "not a socket").unwrap();
+        let error = second.start(router()).await.unwrap_err();
+        assert!(error.to_string().contains("not a socket"));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_frame_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let receiver = UnixSocketProtocolReceiver::new(
+            "local",
+            UnixSocketReceiverOptions {
+                max_frame_bytes: 16,
+                ..options(&dir)
+            },
+        );
+        receiver.start(router()).await.unwrap();
+
+        let mut client = UnixSocketClient::connect(&receiver.options().path)
+            .await
+            .unwrap();
+        match outcome(client.process("upper", vec![b'a'; 64]).await.unwrap()) {
+            Outcome::Error(error) => assert_eq!(error.code, 413),
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+        // The connection is closed after a framing error
+        let error = client.process("upper", "a").await.unwrap_err();
+        assert!(matches!(error, ProtocolError::ConnectionFailed { .. }));
+
+        receiver.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn test_options_defaults_and_overrides() {
+        let defaults = UnixSocketReceiverOptions::from_options(&HashMap::new()).unwrap();
+        assert_eq!(defaults, UnixSocketReceiverOptions::default());
+
+        let options: HashMap<String, serde_yaml::Value> =
+            serde_yaml::from_str("{path: /run/dagwood.sock, permissions: \"0660\"}").unwrap();
+        let options = UnixSocketReceiverOptions::from_options(&options).unwrap();
+        assert_eq!(options.path, PathBuf::from("/run/dagwood.sock"));
+        assert_eq!(options.permissions, Some(0o660));
+
+        for invalid in [
+            "{permissions: \"0999\"}",
+            "{permissions: 660}",
+            "{max_connections: 0}",
+        ] {
+            let options: HashMap<String, serde_yaml::Value> =
+                serde_yaml::from_str(invalid).unwrap();
+            assert!(
+                UnixSocketReceiverOptions::from_options(&options).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,427 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Authentication and per-pipeline authorization of the server's clients.
+//!
+//! An [`Authenticator`] built from the [`AuthConfig`] is the layer shared by every protocol
+//! receiver: receivers only extract a [`Credential`] from their wire format, and the
+//! [`PipelineRouter`](crate::server::PipelineRouter) checks it against the requested
+//! [`Access`] before anything executes.
+//!
+//! | Protocol    | Credential                                                              |
+//! |-------------|-------------------------------------------------------------------------|
+//! | HTTP        | `X-Api-Key` header or `Authorization: Bearer` token                     |
+//! | gRPC        | `x-api-key` or `authorization` metadata, or the TLS client certificate |
+//! | Unix socket | API key frame opening the connection                                    |
+//!
+//! API keys are compared by their SHA-256 digest and client certificates by their SHA-256
+//! fingerprint, so neither is kept or logged in the clear. Denied requests are logged as
+//! `AccessDenied` events and counted in `dagwood_auth_failures_total` by protocol and reason.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use sha2::{Digest, Sha256};
+
+use crate::config::{AuthClientConfig, AuthConfig, QuotaConfig};
+use crate::errors::AuthError;
+use crate::observability::messages::server::AccessDenied;
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+
+/// HTTP header (and gRPC metadata key) carrying an API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// SHA-256 digest of a key or certificate
+type Digest256 = [u8; 32];
+
+/// Credential presented by a client.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credential {
+    /// API key, sent as such or as a bearer token
+    ApiKey(String),
+    /// DER encoding of a TLS client certificate the receiver verified
+    Certificate(Vec<u8>),
+}
+
+impl Credential {
+    /// Credential of a request's API key header or `Authorization` header, preferring the
+    /// API key; `None` if neither carries one.
+    ///
+    /// # Example
+    /// ```
+    /// use the_dagwood::server::auth::Credential;
+    ///
+    /// let bearer = Credential::from_headers(None, Some("Bearer s3cret"));
+    /// assert_eq!(bearer, Some(Credential::ApiKey("s3cret".to_string())));
+    /// assert_eq!(Credential::from_headers(None, Some("Basic czNjcmV0")), None);
+    /// ```
+    pub fn from_headers(api_key: Option<&str>, authorization: Option<&str>) -> Option<Self> {
+        let bearer = authorization.and_then(|value| {
+            let (scheme, token) = value.trim().split_once(' ')?;
+            scheme
+                .eq_ignore_ascii_case("bearer")
+                .then_some(token.trim())
+        });
+        // An empty header does not hide a key sent the other way
+        let non_empty = |key: &&str| !key.is_empty();
+        api_key
+            .map(str::trim)
+            .filter(non_empty)
+            .or(bearer.filter(non_empty))
+            .map(|key| Credential::ApiKey(key.to_string()))
+    }
+}
+
+impl fmt::Debug for Credential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Keys stay out of logs and error messages
+        match self {
+            Credential::ApiKey(_) => write!(f, "ApiKey(<redacted>)"),
+            Credential::Certificate(der) => write!(f, "Certificate({} bytes)", der.len()),
+        }
+    }
+}
+
+/// What a request wants to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access<'a> {
+    /// Execute the named pipeline, synchronously or as a job, or read its jobs
+    Pipeline(&'a str),
+    /// Read the status of the pipelines
+    Status,
+    /// Use the admin API (reload, reset, evict, disable, enable)
+    Admin,
+}
+
+impl fmt::Display for Access<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Access::Pipeline(name) => write!(f, "execute pipeline '{}'", name),
+            Access::Status => write!(f, "read the pipeline status"),
+            Access::Admin => write!(f, "use the admin API"),
+        }
+    }
+}
+
+/// A configured client and what it may access.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthClient {
+    name: String,
+    /// Pipelines the client may execute; `None` for every pipeline
+    pipelines: Option<HashSet<String>>,
+    admin: bool,
+    quota: Option<QuotaConfig>,
+}
+
+impl AuthClient {
+    /// Name of the client in logs and errors.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Limits on the client's requests across every pipeline, if any.
+    pub fn quota(&self) -> Option<&QuotaConfig> {
+        self.quota.as_ref()
+    }
+
+    /// Whether the client may perform `access`.
+    pub fn allows(&self, access: Access<'_>) -> bool {
+        match access {
+            Access::Pipeline(name) => self
+                .pipelines
+                .as_ref()
+                .is_none_or(|pipelines| pipelines.contains(name)),
+            Access::Status => true,
+            Access::Admin => self.admin,
+        }
+    }
+}
+
+/// Authenticates clients by their credentials and authorizes their requests.
+#[derive(Debug)]
+pub struct Authenticator {
+    clients: Vec<AuthClient>,
+    /// Client index by SHA-256 digest of its API key
+    keys: HashMap<Digest256, usize>,
+    /// Client index by SHA-256 fingerprint of its certificate
+    certificates: HashMap<Digest256, usize>,
+}
+
+impl Authenticator {
+    /// Build the authenticator of the configured clients, reading keys from the
+    /// environment where configured.
+    ///
+    /// Fails if a client does not have exactly one credential, its key variable is unset,
+    /// its fingerprint is not hex SHA-256, or two clients share a name or credential.
+    pub fn from_config(config: &AuthConfig) -> Result<Self, AuthError> {
+        let mut authenticator = Self {
+            clients: Vec::with_capacity(config.clients.len()),
+            keys: HashMap::new(),
+            certificates: HashMap::new(),
+        };
+        for client in &config.clients {
+            authenticator.add(client)?;
+        }
+        Ok(authenticator)
+    }
+
+    fn add(&mut self, config: &AuthClientConfig) -> Result<(), AuthError> {
+        let invalid = |reason: String| AuthError::InvalidConfig {
+            client: config.name.clone(),
+            reason,
+        };
+        if self.clients.iter().any(|client| client.name == config.name) {
+            return Err(invalid("another client has the same name".to_string()));
+        }
+        if let Some(quota) = &config.quota {
+            quota
+                .validate()
+                .map_err(|reason| invalid(format!("invalid quota: {}", reason)))?;
+        }
+
+        let (credentials, digest) = match (
+            &config.api_key,
+            &config.api_key_env,
+            &config.certificate_sha256,
+        ) {
+            (Some(key), None, None) => (&mut self.keys, digest(key.as_bytes())),
+            (None, Some(variable), None) => {
+                let key = std::env::var(variable)
+                    .ok()
+                    .filter(|key| !key.is_empty())
+                    .ok_or_else(|| {
+                        invalid(format!("environment variable '{}' is not set", variable))
+                    })?;
+                (&mut self.keys, digest(key.as_bytes()))
+            }
+            (None, None, Some(fingerprint)) => {
+                let fingerprint = parse_fingerprint(fingerprint).ok_or_else(|| {
+                    invalid("certificate_sha256 must be 64 hex digits".to_string())
+                })?;
+                (&mut self.certificates, fingerprint)
+            }
+            _ => {
+                return Err(invalid(
+                    "exactly one of api_key, api_key_env and certificate_sha256 is required"
+                        .to_string(),
+                ))
+            }
+        };
+        if credentials.contains_key(&digest) {
+            return Err(invalid(
+                "another client has the same credential".to_string(),
+            ));
+        }
+        credentials.insert(digest, self.clients.len());
+
+        self.clients.push(AuthClient {
+            name: config.name.clone(),
+            pipelines: config
+                .pipelines
+                .as_ref()
+                .map(|pipelines| pipelines.iter().cloned().collect()),
+            admin: config.admin,
+            quota: config.quota,
+        });
+        Ok(())
+    }
+
+    /// The configured clients.
+    pub fn clients(&self) -> &[AuthClient] {
+        &self.clients
+    }
+
+    /// The client the credential belongs to.
+    pub fn authenticate(&self, credential: Option<&Credential>) -> Result<&AuthClient, AuthError> {
+        let index = match credential {
+            None => return Err(AuthError::MissingCredentials),
+            Some(Credential::ApiKey(key)) => self.keys.get(&digest(key.as_bytes())),
+            Some(Credential::Certificate(der)) => self.certificates.get(&digest(der)),
+        };
+        index
+            .map(|&index| &self.clients[index])
+            .ok_or(AuthError::InvalidCredentials)
+    }
+
+    /// The client the credential belongs to, if it may perform `access`.
+    ///
+    /// Denials are logged and counted under `protocol`.
+    pub fn authorize(
+        &self,
+        protocol: &str,
+        credential: Option<&Credential>,
+        access: Access<'_>,
+    ) -> Result<&AuthClient, AuthError> {
+        let result = self
+            .authenticate(credential)
+            .and_then(|client| match client.allows(access) {
+                true => Ok(client),
+                false => Err(AuthError::Forbidden {
+                    client: client.name.clone(),
+                    access: access.to_string(),
+                }),
+            });
+
+        if let Err(error) = &result {
+            let client = match error {
+                AuthError::Forbidden { client, .. } => Some(client.as_str()),
+                _ => None,
+            };
+            AccessDenied {
+                protocol,
+                client,
+                access: &access.to_string(),
+                reason: error.reason(),
+            }
+            .log();
+            metrics().auth_denied(protocol, error.reason());
+        }
+        result
+    }
+}
+
+fn digest(bytes: &[u8]) -> Digest256 {
+    Sha256::digest(bytes).into()
+}
+
+/// Parse a hex SHA-256 fingerprint, optionally with `:` separators as printed by openssl
+fn parse_fingerprint(fingerprint: &str) -> Option<Digest256> {
+    let hex: Vec<u8> = fingerprint.bytes().filter(|&byte| byte != b':').collect();
+    if hex.len() != 64 {
+        return None;
+    }
+    let mut digest = [0u8; 32];
+    for (byte, pair) in digest.iter_mut().zip(hex.chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authenticator(yaml: &str) -> Result<Authenticator, AuthError> {
+        Authenticator::from_config(&serde_yaml::from_str(yaml).unwrap())
+    }
+
+    fn key(key: &str) -> Credential {
+        Credential::ApiKey(key.to_string())
+    }
+
+    #[test]
+    fn test_authorize_by_key_and_certificate() {
+        std::env::set_var("DAGWOOD_TEST_AUTH_KEY", "from-env");
+        let certificate = b"certificate".to_vec();
+        let fingerprint: String = digest(&certificate)
+            .iter()
+            .map(|byte| format!("{:02X}:", byte))
+            .collect();
+        let auth = authenticator(&format!(
+            r#"
+enabled: true
+clients:
+  - name: dashboard
+    api_key: "dashboard-key"
+    pipelines: [upper]
+  - name: operator
+    api_key_env: DAGWOOD_TEST_AUTH_KEY
+    admin: true
+  - name: batch
+    certificate_sha256: "{}"
+"#,
+            fingerprint.trim_end_matches(':')
+        ))
+        .unwrap();
+
+        let dashboard = Some(key("dashboard-key"));
+        let client = auth
+            .authorize("http", dashboard.as_ref(), Access::Pipeline("upper"))
+            .unwrap();
+        assert_eq!(client.name(), "dashboard");
+        assert!(auth
+            .authorize("http", dashboard.as_ref(), Access::Status)
+            .is_ok());
+        assert_eq!(
+            auth.authorize("http", dashboard.as_ref(), Access::Pipeline("lower")),
+            Err(AuthError::Forbidden {
+                client: "dashboard".to_string(),
+                access: "execute pipeline 'lower'".to_string(),
+            })
+        );
+        assert!(matches!(
+            auth.authorize("http", dashboard.as_ref(), Access::Admin),
+            Err(AuthError::Forbidden { .. })
+        ));
+
+        let operator = Some(key("from-env"));
+        assert!(auth
+            .authorize("grpc", operator.as_ref(), Access::Admin)
+            .is_ok());
+        assert!(auth
+            .authorize("grpc", operator.as_ref(), Access::Pipeline("lower"))
+            .is_ok());
+
+        let batch = Some(Credential::Certificate(certificate));
+        assert_eq!(auth.authenticate(batch.as_ref()).unwrap().name(), "batch");
+
+        assert_eq!(
+            auth.authorize("http", None, Access::Status),
+            Err(AuthError::MissingCredentials)
+        );
+        assert_eq!(
+            auth.authorize("http", Some(&key("guess")), Access::Status),
+            Err(AuthError::InvalidCredentials)
+        );
+    }
+
+    #[test]
+    fn test_invalid_clients() {
+        let reason = |yaml: &str| match authenticator(yaml) {
+            Err(AuthError::InvalidConfig { reason, .. }) => reason,
+            other => panic!("expected an invalid client, got {:?}", other),
+        };
+
+        assert!(reason("clients: [{name: a}]").contains("exactly one"));
+        assert!(
+            reason("clients: [{name: a, api_key: k, certificate_sha256: ab}]")
+                .contains("exactly one")
+        );
+        assert!(
+            reason("clients: [{name: a, api_key_env: DAGWOOD_TEST_UNSET_KEY}]")
+                .contains("DAGWOOD_TEST_UNSET_KEY")
+        );
+        assert!(reason("clients: [{name: a, certificate_sha256: abc}]").contains("64 hex"));
+        assert!(
+            reason("clients: [{name: a, api_key: k}, {name: a, api_key: l}]").contains("same name")
+        );
+        assert!(
+            reason("clients: [{name: a, api_key: k}, {name: b, api_key: k}]")
+                .contains("same credential")
+        );
+        assert!(
+            reason("clients: [{name: a, api_key: k, quota: {max_concurrent: 0}}]")
+                .contains("invalid quota")
+        );
+    }
+
+    #[test]
+    fn test_credential_from_headers() {
+        assert_eq!(
+            Credential::from_headers(Some("key"), Some("Bearer token")),
+            Some(key("key"))
+        );
+        assert_eq!(
+            Credential::from_headers(None, Some("bearer  token ")),
+            Some(key("token"))
+        );
+        assert_eq!(Credential::from_headers(Some(""), None), None);
+        assert_eq!(
+            Credential::from_headers(Some(" "), Some("Bearer token")),
+            Some(key("token"))
+        );
+        assert_eq!(Credential::from_headers(None, Some("Bearer ")), None);
+        assert_eq!(format!("{:?}", key("secret")), "ApiKey(<redacted>)");
+    }
+}
@@ -0,0 +1,596 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinSet;
+
+use crate::config::{
+    MemoryManagementConfig, ProtocolConfig, ProtocolType, ServerConfig, ShutdownConfig,
+};
+use crate::errors::{ProtocolError, ServerError};
+use crate::observability::messages::server::{
+    ProtocolReceiverStarted, ProtocolReceiverStopped, ServerDrainTimedOut, ServerStarted,
+    ServerStopped, ServerStopping,
+};
+use crate::observability::messages::StructuredLog;
+use crate::protocols::{ProtocolReceiver, ProtocolReceiverFactory};
+use crate::server::{
+    Authenticator, ConfigWatcher, EvictionTask, HotReloader, JobCleanupTask, JobManager,
+    MemoryManager, PipelineRegistry, PipelineRouter,
+};
+
+/// A DAGwood server: hosted pipelines plus the protocol receivers serving them (ADR 21).
+///
+/// Every receiver shares one [`PipelineRouter`], so a pipeline behaves the same whichever
+/// protocol a request arrives on, and shutdown drains the runs of every protocol together
+/// (ADR 27, see [`crate::server::shutdown`]).
+pub struct DagwoodServer {
+    registry: Arc<PipelineRegistry>,
+    router: Arc<PipelineRouter>,
+    receivers: Vec<Arc<dyn ProtocolReceiver>>,
+    drain_timeout: Duration,
+    watch_config: bool,
+    watcher: Mutex<Option<ConfigWatcher>>,
+    memory_manager: Option<Arc<MemoryManager>>,
+    eviction: Mutex<Option<EvictionTask>>,
+    job_cleanup: Mutex<Option<JobCleanupTask>>,
+}
+
+impl DagwoodServer {
+    /// Create a server over a registry and a set of receivers.
+    pub fn new(registry: Arc<PipelineRegistry>, receivers: Vec<Box<dyn ProtocolReceiver>>) -> Self {
+        Self {
+            router: Arc::new(PipelineRouter::new(registry.clone())),
+            registry,
+            receivers: receivers.into_iter().map(Arc::from).collect(),
+            drain_timeout: ShutdownConfig::default().drain_timeout(),
+            watch_config: false,
+            watcher: Mutex::new(None),
+            memory_manager: None,
+            eviction: Mutex::new(None),
+            job_cleanup: Mutex::new(None),
+        }
+    }
+
+    /// How long shutdown lets in-flight runs finish before cancelling them.
+    pub fn with_drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+
+    /// Evict idle pipelines within the limits of `config` while the server runs (ADR 28).
+    ///
+    /// Has no effect unless `config.enabled` is set.
+    pub fn with_memory_management(mut self, config: MemoryManagementConfig) -> Self {
+        self.memory_manager = config
+            .enabled
+            .then(|| Arc::new(MemoryManager::new(self.registry.clone(), config)));
+        self
+    }
+
+    /// Enable hot reload of the pipelines from `config_path` through the admin API (ADR 23).
+    pub fn with_hot_reload(mut self, config_path: impl Into<PathBuf>) -> Self {
+        let reloader = Arc::new(HotReloader::new(self.registry.clone(), config_path));
+        self.router = Arc::new(self.router.as_ref().clone().with_reloader(reloader));
+        self
+    }
+
+    /// Accept asynchronous jobs, run by `jobs` (see [`crate::server::jobs`]).
+    pub fn with_jobs(mut self, jobs: Arc<JobManager>) -> Self {
+        self.router = Arc::new(self.router.as_ref().clone().with_jobs(jobs));
+        self
+    }
+
+    /// Require every request to present the credentials of a client of `auth`.
+    pub fn with_auth(mut self, auth: Arc<Authenticator>) -> Self {
+        self.router = Arc::new(self.router.as_ref().clone().with_auth(auth));
+        self
+    }
+
+    /// Also reload whenever the configuration file changes, while the server runs.
+    ///
+    /// Has no effect without [`DagwoodServer::with_hot_reload`].
+    pub fn watch_config(mut self) -> Self {
+        self.watch_config = true;
+        self
+    }
+
+    /// Register the configured pipelines and create the configured receivers.
+    ///
+    /// A configuration without `protocols` is served over HTTP with the default options.
+    /// Fails if a pipeline, receiver or auth client is invalid, or the job store cannot be
+    /// opened.
+    pub fn from_config(config: ServerConfig) -> Result<Self, ServerError> {
+        let registry = Arc::new(PipelineRegistry::from_config(config.pipelines)?);
+
+        let mut protocols = config.protocols;
+        if protocols.is_empty() {
+            protocols.push(ProtocolConfig::new(ProtocolType::Http));
+        }
+        let receivers = protocols
+            .iter()
+            .map(ProtocolReceiverFactory::create)
+            .collect::<Result<Vec<_>, ProtocolError>>()?;
+
+        let mut server = Self::new(registry, receivers)
+            .with_drain_timeout(config.shutdown.drain_timeout())
+            .with_memory_management(config.memory_management);
+        if config.jobs.enabled {
+            server = server.with_jobs(Arc::new(JobManager::from_config(&config.jobs)?));
+        }
+        if config.auth.enabled {
+            server = server.with_auth(Arc::new(Authenticator::from_config(&config.auth)?));
+        }
+        Ok(server)
+    }
+
+    /// Pipelines hosted by this server.
+    pub fn registry(&self) -> &Arc<PipelineRegistry> {
+        &self.registry
+    }
+
+    /// Memory manager evicting idle pipelines, if memory management is enabled.
+    pub fn memory_manager(&self) -> Option<&Arc<MemoryManager>> {
+        self.memory_manager.as_ref()
+    }
+
+    /// Job manager running asynchronous jobs, if jobs are enabled.
+    pub fn jobs(&self) -> Option<&Arc<JobManager>> {
+        self.router.jobs()
+    }
+
+    /// Router shared by every receiver.
+    pub fn router(&self) -> &Arc<PipelineRouter> {
+        &self.router
+    }
+
+    /// Protocol receivers, in configuration order.
+    pub fn receivers(&self) -> &[Arc<dyn ProtocolReceiver>] {
+        &self.receivers
+    }
+
+    /// Initialize the `startup: auto` pipelines, then start every receiver concurrently.
+    ///
+    /// Fails if an auto-started pipeline cannot be initialized, the jobs interrupted by a
+    /// previous server cannot be recovered or a receiver cannot start; receivers that did
+    /// start are shut down again before the error is returned.
+    pub async fn start(&self) -> Result<(), ServerError> {
+        let started_at = Instant::now();
+        self.registry.start().await?;
+        if let Some(jobs) = self.router.jobs() {
+            jobs.recover().await?;
+        }
+
+        let mut starts = JoinSet::new();
+        for receiver in &self.receivers {
+            let receiver = receiver.clone();
+            let router = self.router.clone();
+            starts.spawn(async move { receiver.start(router).await });
+        }
+
+        let mut first_error = None;
+        while let Some(result) = starts.join_next().await {
+            let result = result.unwrap_or_else(|join_error| {
+                Err(ProtocolError::ServeFailed {
+                    name: String::new(),
+                    reason: join_error.to_string(),
+                })
+            });
+            if let Err(error) = result {
+                first_error.get_or_insert(error);
+            }
+        }
+        if let Some(error) = first_error {
+            let _ = self.stop_receivers().await;
+            return Err(error.into());
+        }
+
+        if let (true, Some(reloader)) = (self.watch_config, self.router.reloader()) {
+            match reloader.watch() {
+                Ok(watcher) => {
+                    *self.watcher.lock().expect("config watcher lock poisoned") = Some(watcher)
+                }
+                Err(error) => {
+                    let _ = self.stop_receivers().await;
+                    return Err(error);
+                }
+            }
+        }
+
+        if let Some(memory_manager) = &self.memory_manager {
+            *self.eviction.lock().expect("eviction task lock poisoned") =
+                Some(memory_manager.start());
+        }
+        if let Some(jobs) = self.router.jobs() {
+            *self.job_cleanup.lock().expect("job cleanup lock poisoned") = Some(jobs.start());
+        }
+
+        for receiver in &self.receivers {
+            ProtocolReceiverStarted {
+                protocol: receiver.protocol_name(),
+                name: receiver.name(),
+                endpoint: &receiver.endpoint().unwrap_or_default(),
+            }
+            .log();
+        }
+        ServerStarted {
+            pipelines: &self.registry.names(),
+            receivers: self.receivers.len(),
+            duration: started_at.elapsed(),
+        }
+        .log();
+        Ok(())
+    }
+
+    /// Shut down gracefully (ADR 27).
+    ///
+    /// New runs are rejected at once and the receivers stop accepting connections. Runs in
+    /// flight get the drain timeout to finish; any still running are then cancelled, and
+    /// awaited until their processors stopped. Finally the pipelines are released, dropping
+    /// their executors and processors.
+    pub async fn shutdown(&self) -> Result<(), ServerError> {
+        let started_at = Instant::now();
+        let runs = self.router.runs();
+        runs.stop_accepting();
+        self.watcher
+            .lock()
+            .expect("config watcher lock poisoned")
+            .take();
+        self.eviction
+            .lock()
+            .expect("eviction task lock poisoned")
+            .take();
+        self.job_cleanup
+            .lock()
+            .expect("job cleanup lock poisoned")
+            .take();
+        ServerStopping {
+            receivers: self.receivers.len(),
+            in_flight: runs.active(),
+            drain_timeout: self.drain_timeout,
+        }
+        .log();
+
+        // Receivers finish once their in-flight requests are answered, drained or cancelled
+        let draining = async {
+            if runs.wait_idle(self.drain_timeout).await {
+                return 0;
+            }
+            let in_flight = runs.active();
+            ServerDrainTimedOut {
+                in_flight,
+                timeout: self.drain_timeout,
+            }
+            .log();
+            runs.cancel();
+            runs.idle().await;
+            in_flight
+        };
+        let (stopped, cancelled) = tokio::join!(self.stop_receivers(), draining);
+
+        let pipelines = self.registry.release_all();
+        ServerStopped {
+            cancelled,
+            pipelines,
+            duration: started_at.elapsed(),
+        }
+        .log();
+        stopped.map_err(ServerError::from)
+    }
+
+    /// Start the server, serve until `signal` completes, then shut down.
+    pub async fn run_until(&self, signal: impl Future<Output = ()>) -> Result<(), ServerError> {
+        self.start().await?;
+        signal.await;
+        self.shutdown().await
+    }
+
+    /// Shut down every receiver concurrently, returning the first error
+    async fn stop_receivers(&self) -> Result<(), ProtocolError> {
+        let mut stops = JoinSet::new();
+        for receiver in &self.receivers {
+            let receiver = receiver.clone();
+            stops.spawn(async move {
+                let result = receiver.shutdown().await;
+                ProtocolReceiverStopped {
+                    protocol: receiver.protocol_name(),
+                    name: receiver.name(),
+                }
+                .log();
+                result
+            });
+        }
+
+        let mut first_error = None;
+        while let Some(result) = stops.join_next().await {
+            let result = result.unwrap_or_else(|join_error| {
+                Err(ProtocolError::ServeFailed {
+                    name: String::new(),
+                    reason: join_error.to_string(),
+                })
+            });
+            if let Err(error) = result {
+                first_error.get_or_insert(error);
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+impl std::fmt::Debug for DagwoodServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let receivers: Vec<&str> = self
+            .receivers
+            .iter()
+            .map(|receiver| receiver.name())
+            .collect();
+        f.debug_struct("DagwoodServer")
+            .field("pipelines", &self.registry.names())
+            .field("receivers", &receivers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::stub::SlowProcessor;
+    use crate::engine::{ExecutionReport, NoopObserver};
+    use crate::errors::{ExecutionError, PipelineError};
+    use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+    use crate::server::{Pipeline, PipelineState, ShutdownSignal};
+    use hyper::{Body, Client, Request};
+    use std::sync::atomic::Ordering;
+    use tokio::task::JoinHandle;
+    use tokio_util::sync::CancellationToken;
+
+    fn server(yaml: &str) -> DagwoodServer {
+        DagwoodServer::from_config(ServerConfig::from_yaml(yaml).unwrap()).unwrap()
+    }
+
+    const UPPER: &str = r#"
+protocols:
+  - type: http
+    options: {port: 0}
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#;
+
+    /// Run a pipeline whose only processor takes `duration` through the server's run tracking
+    async fn in_flight_run(
+        server: &DagwoodServer,
+        duration: Duration,
+    ) -> (
+        JoinHandle<Result<ExecutionReport, ExecutionError>>,
+        Arc<SlowProcessor>,
+    ) {
+        let processor = Arc::new(SlowProcessor::new(duration));
+        let pipeline = Pipeline::with_processor("slow", processor.clone());
+
+        let run = server.router().runs().begin().unwrap();
+        let running = tokio::spawn(async move {
+            let cancellation = CancellationToken::new();
+            run.run(
+                &cancellation,
+                pipeline.execute_cancellable(
+                    ProcessorRequest {
+                        payload: b"hello".to_vec(),
+                    },
+                    PipelineMetadata::new(),
+                    Arc::new(NoopObserver),
+                    cancellation.clone(),
+                ),
+            )
+            .await
+        });
+        processor.wait_started().await;
+        (running, processor)
+    }
+
+    #[tokio::test]
+    async fn test_starts_receivers_and_serves_requests() {
+        let server = server(
+            r#"
+protocols:
+  - type: http
+    name: first
+    options: {port: 0}
+  - type: http
+    name: second
+    options: {port: 0}
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        );
+        server.start().await.unwrap();
+        assert_eq!(
+            server.registry().get("upper").unwrap().state(),
+            PipelineState::Ready
+        );
+
+        for receiver in server.receivers() {
+            let request =
+                Request::post(format!("{}/pipelines/upper", receiver.endpoint().unwrap()))
+                    .body(Body::from("hello"))
+                    .unwrap();
+            let response = Client::new().request(request).await.unwrap();
+            assert_eq!(response.status(), hyper::StatusCode::OK);
+        }
+
+        server.shutdown().await.unwrap();
+        assert!(server.receivers().iter().all(|r| r.endpoint().is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_run_until_shuts_down_on_signal() {
+        let server = server(
+            r#"
+protocols:
+  - type: http
+    options: {port: 0}
+strategy: work_queue
+processors:
+  - id: reverse
+    type: local
+    processor: reverse_text
+"#,
+        );
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        stop.send(()).unwrap();
+
+        server
+            .run_until(async {
+                let _ = stopped.await;
+            })
+            .await
+            .unwrap();
+        assert!(server.receivers()[0].endpoint().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_signal_drains_in_flight_runs() {
+        let server = server(UPPER).with_drain_timeout(Duration::from_secs(10));
+        let (raise, signal) = ShutdownSignal::channel();
+        server.start().await.unwrap();
+
+        let (running, processor) = in_flight_run(&server, Duration::from_millis(300)).await;
+        raise.send("SIGTERM").unwrap();
+
+        assert_eq!(signal.recv().await, "SIGTERM");
+        server.shutdown().await.unwrap();
+
+        // The run in flight when the signal arrived finished before shutdown returned
+        assert_eq!(server.router().runs().active(), 0);
+        assert!(processor.finished.load(Ordering::SeqCst));
+        let report = running.await.unwrap().unwrap();
+        assert_eq!(report.final_output, b"hello");
+        assert!(!server.router().runs().is_cancelled());
+        assert!(server.registry().names().is_empty());
+        assert!(server.receivers()[0].endpoint().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_runs_past_drain_timeout() {
+        let server = server(UPPER).with_drain_timeout(Duration::from_millis(50));
+        server.start().await.unwrap();
+
+        let (running, processor) = in_flight_run(&server, Duration::from_secs(30)).await;
+        server.shutdown().await.unwrap();
+
+        // The cancelled run's processor stopped before shutdown returned, without finishing
+        assert!(processor.stopped.load(Ordering::SeqCst));
+        assert!(!processor.finished.load(Ordering::SeqCst));
+        assert_eq!(server.router().runs().active(), 0);
+        let error = running.await.unwrap().unwrap_err();
+        assert!(matches!(error, ExecutionError::Cancelled { .. }));
+        assert!(server.router().runs().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_new_runs_once_shutting_down() {
+        let server = server(UPPER);
+        server.start().await.unwrap();
+        server.shutdown().await.unwrap();
+
+        let error = server
+            .router()
+            .execute(
+                "upper",
+                ProcessorRequest {
+                    payload: b"hello".to_vec(),
+                },
+                PipelineMetadata::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, PipelineError::ShuttingDown));
+    }
+
+    #[tokio::test]
+    async fn test_memory_management_evicts_idle_pipelines_while_running() {
+        let server = server(
+            r#"
+protocols:
+  - type: http
+    options: {port: 0}
+memory_management:
+  enabled: true
+  idle_timeout_seconds: 0
+  check_interval_seconds: 1
+pipelines:
+  - name: upper
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        );
+        assert!(server.memory_manager().is_some());
+        server.start().await.unwrap();
+        let upper = server.registry().get("upper").unwrap();
+        upper.pipeline().await.unwrap();
+        assert_eq!(upper.state(), PipelineState::Ready);
+
+        let mut state = upper.state();
+        for _ in 0..60 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            state = upper.state();
+            if state == PipelineState::Evicted {
+                break;
+            }
+        }
+        assert_eq!(state, PipelineState::Evicted);
+        server.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_auto_pipeline_fails_startup() {
+        let server = server(
+            r#"
+protocols:
+  - type: http
+    options: {port: 0}
+pipelines:
+  - name: broken
+    initialization: {max_retries: 0}
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#,
+        );
+
+        let error = server.start().await.unwrap_err();
+        assert!(matches!(error, ServerError::Pipeline(_)));
+        assert!(server.receivers()[0].endpoint().is_none());
+    }
+
+    #[test]
+    fn test_defaults_to_http_receiver() {
+        let server = server(
+            r#"
+strategy: work_queue
+processors: []
+"#,
+        );
+        assert_eq!(server.receivers().len(), 1);
+        assert_eq!(server.receivers()[0].protocol_name(), "http");
+    }
+}
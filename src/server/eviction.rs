@@ -0,0 +1,459 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Memory management of hosted pipelines (ADR 28): LRU eviction under a memory budget.
+//!
+//! A [`MemoryManager`] periodically sweeps the registry, least recently used pipeline first:
+//!
+//! ```text
+//! 1. Evict    ready pipelines idle past the timeout, over max_pipelines, or over the budget
+//! 2. Release  compiled WASM modules of idle pipelines while still over the budget
+//! ```
+//!
+//! Evicted pipelines return to the uninitialized `Evicted` state and are restored by their
+//! next request. Released WASM modules stay with their (still `Ready`) pipeline and are
+//! compiled again by the next execution, so protected pipelines can shed memory too.
+//! Pipelines with executions in flight are skipped.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::config::{MemoryManagementConfig, StartupMode};
+use crate::observability::messages::server::MemorySweepCompleted;
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+use crate::server::{ManagedPipeline, PipelineRegistry, PipelineState};
+
+/// Why a pipeline was evicted, for logs and metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+    /// An admin API request
+    Admin,
+    /// Unused for longer than the idle timeout
+    IdleTimeout,
+    /// More pipelines initialized than `max_pipelines`
+    MaxPipelines,
+    /// Initialized pipelines over the memory budget
+    MemoryBudget,
+}
+
+impl EvictionReason {
+    /// Stable, lowercase name of the reason for logs, metrics and APIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EvictionReason::Admin => "admin",
+            EvictionReason::IdleTimeout => "idle_timeout",
+            EvictionReason::MaxPipelines => "max_pipelines",
+            EvictionReason::MemoryBudget => "memory_budget",
+        }
+    }
+}
+
+/// Outcome of a sweep.
+#[derive(Debug, Default, PartialEq)]
+pub struct SweepReport {
+    /// Pipelines evicted, least recently used first
+    pub evicted: Vec<(String, EvictionReason)>,
+    /// Bytes of compiled WASM modules released
+    pub modules_released_bytes: usize,
+    /// Estimated memory held by the ready pipelines after the sweep
+    pub memory_bytes: usize,
+}
+
+impl SweepReport {
+    /// Whether the sweep released anything.
+    pub fn has_changes(&self) -> bool {
+        !self.evicted.is_empty() || self.modules_released_bytes > 0
+    }
+
+    /// Names of the evicted pipelines.
+    pub fn evicted_names(&self) -> Vec<String> {
+        self.evicted.iter().map(|(name, _)| name.clone()).collect()
+    }
+}
+
+/// Keeps the initialized pipelines of a registry within the configured limits.
+#[derive(Debug)]
+pub struct MemoryManager {
+    registry: Arc<PipelineRegistry>,
+    config: MemoryManagementConfig,
+}
+
+impl MemoryManager {
+    /// Create a manager for `registry`; nothing is evicted until a sweep runs.
+    pub fn new(registry: Arc<PipelineRegistry>, config: MemoryManagementConfig) -> Self {
+        Self { registry, config }
+    }
+
+    /// The limits this manager enforces.
+    pub fn config(&self) -> &MemoryManagementConfig {
+        &self.config
+    }
+
+    /// Whether `pipeline` is never evicted.
+    pub fn is_protected(&self, pipeline: &ManagedPipeline) -> bool {
+        (self.config.protects_startup_auto() && pipeline.startup() == StartupMode::Auto)
+            || self
+                .config
+                .protected_pipelines
+                .iter()
+                .any(|name| name == pipeline.name())
+    }
+
+    /// Estimated memory held by the ready pipelines, in bytes.
+    pub fn memory_usage(&self) -> usize {
+        self.ready_pipelines()
+            .iter()
+            .map(|pipeline| pipeline.memory_footprint())
+            .sum()
+    }
+
+    /// Evict and release until the registry is within the limits, as far as possible.
+    pub fn sweep(&self) -> SweepReport {
+        let now = Instant::now();
+        let idle_timeout = self.config.idle_timeout();
+        let budget = self.config.budget_bytes();
+
+        let mut ready: Vec<(Arc<ManagedPipeline>, usize)> = self
+            .ready_pipelines()
+            .into_iter()
+            .map(|pipeline| {
+                let bytes = pipeline.memory_footprint();
+                (pipeline, bytes)
+            })
+            .collect();
+        ready.sort_by_key(|(pipeline, _)| pipeline.last_used());
+        let mut initialized = ready.len();
+        let mut memory: usize = ready.iter().map(|(_, bytes)| bytes).sum();
+
+        let mut report = SweepReport::default();
+        let mut kept = Vec::new();
+        for (pipeline, bytes) in ready {
+            let reason = if self.is_protected(&pipeline) || pipeline.is_executing() {
+                None
+            } else if idle_timeout.is_some_and(|timeout| {
+                now.saturating_duration_since(pipeline.last_used()) >= timeout
+            }) {
+                Some(EvictionReason::IdleTimeout)
+            } else if self
+                .config
+                .max_pipelines
+                .is_some_and(|max_pipelines| initialized > max_pipelines)
+            {
+                Some(EvictionReason::MaxPipelines)
+            } else if budget.is_some_and(|budget| memory > budget) {
+                Some(EvictionReason::MemoryBudget)
+            } else {
+                None
+            };
+
+            match reason.filter(|&reason| pipeline.evict(reason).is_some()) {
+                Some(reason) => {
+                    initialized -= 1;
+                    memory -= bytes;
+                    report.evicted.push((pipeline.name().to_string(), reason));
+                }
+                None => kept.push(pipeline),
+            }
+        }
+
+        if let Some(budget) = budget.filter(|_| self.config.evicts_wasm_modules()) {
+            for pipeline in &kept {
+                if memory <= budget {
+                    break;
+                }
+                if pipeline.is_executing() {
+                    continue;
+                }
+                if let Some(version) = pipeline.ready_pipeline() {
+                    let released = version.release_memory();
+                    memory = memory.saturating_sub(released);
+                    report.modules_released_bytes += released;
+                }
+            }
+        }
+
+        for pipeline in &kept {
+            metrics().pipeline_memory(pipeline.name(), pipeline.memory_footprint());
+        }
+        report.memory_bytes = memory;
+        if report.has_changes() {
+            MemorySweepCompleted {
+                evicted: &report.evicted_names(),
+                modules_released_bytes: report.modules_released_bytes,
+                memory_bytes: report.memory_bytes,
+                budget_bytes: budget,
+            }
+            .log();
+        }
+        report
+    }
+
+    /// Sweep every `check_interval_seconds` until the returned task is dropped.
+    pub fn start(self: &Arc<Self>) -> EvictionTask {
+        let manager = self.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(manager.config.check_interval());
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes at once; nothing has been idle yet
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                manager.sweep();
+            }
+        });
+        EvictionTask { task }
+    }
+
+    fn ready_pipelines(&self) -> Vec<Arc<ManagedPipeline>> {
+        self.registry
+            .names()
+            .iter()
+            .filter_map(|name| self.registry.get(name))
+            .filter(|pipeline| pipeline.state() == PipelineState::Ready)
+            .collect()
+    }
+}
+
+/// Periodic sweeps of a [`MemoryManager`]; stops when dropped.
+pub struct EvictionTask {
+    task: JoinHandle<()>,
+}
+
+impl Drop for EvictionTask {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for EvictionTask {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvictionTask").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ServerConfig;
+    use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+    use std::time::Duration;
+
+    fn manager(memory_management: &str, pipelines: &str) -> MemoryManager {
+        let yaml = format!("memory_management:\n{}\n{}", memory_management, pipelines);
+        let config = ServerConfig::from_yaml(&yaml).unwrap();
+        let registry = PipelineRegistry::from_config(config.pipelines).unwrap();
+        MemoryManager::new(Arc::new(registry), config.memory_management)
+    }
+
+    fn registry(manager: &MemoryManager) -> &PipelineRegistry {
+        &manager.registry
+    }
+
+    /// Use the pipelines in order, so the first is the least recently used
+    async fn use_in_order(manager: &MemoryManager, names: &[&str]) {
+        for name in names {
+            registry(manager)
+                .get(name)
+                .unwrap()
+                .pipeline()
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+    }
+
+    fn state(manager: &MemoryManager, name: &str) -> PipelineState {
+        registry(manager).get(name).unwrap().state()
+    }
+
+    /// C-style module echoing its input
+    fn echo_module() -> tempfile::NamedTempFile {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (global $next (mut i32) (i32.const 1024))
+              (func (export "allocate") (param $size i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $size)))
+                (local.get $ptr))
+              (func (export "deallocate") (param i32 i32))
+              (func (export "process") (param $ptr i32) (param $len i32) (param $out i32) (result i32)
+                (i32.store (local.get $out) (local.get $len))
+                (local.get $ptr)))
+            "#,
+        )
+        .unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &wasm).unwrap();
+        file
+    }
+
+    const ON_DEMAND: &str = r#"
+pipelines:
+  - name: first
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {id: upper, type: local, processor: change_text_case_upper}
+  - name: second
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {id: reverse, type: local, processor: reverse_text}
+  - name: third
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {id: lower, type: local, processor: change_text_case_lower}
+"#;
+
+    #[tokio::test]
+    async fn test_evicts_least_recently_used_over_max_pipelines() {
+        let manager = manager("  enabled: true\n  max_pipelines: 2", ON_DEMAND);
+        use_in_order(&manager, &["second", "first", "third"]).await;
+
+        let report = manager.sweep();
+        assert_eq!(
+            report.evicted,
+            vec![("second".to_string(), EvictionReason::MaxPipelines)]
+        );
+        assert_eq!(state(&manager, "second"), PipelineState::Evicted);
+        assert_eq!(state(&manager, "first"), PipelineState::Ready);
+        assert!(!manager.sweep().has_changes());
+
+        // The next request restores the evicted pipeline
+        use_in_order(&manager, &["second"]).await;
+        assert_eq!(state(&manager, "second"), PipelineState::Ready);
+        let report = manager.sweep();
+        assert_eq!(
+            report.evicted,
+            vec![("first".to_string(), EvictionReason::MaxPipelines)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idle_timeout_spares_protected_and_executing_pipelines() {
+        let pipelines = r#"
+pipelines:
+  - name: auto
+    strategy: work_queue
+    processors:
+      - {id: upper, type: local, processor: change_text_case_upper}
+  - name: listed
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {id: upper, type: local, processor: change_text_case_upper}
+  - name: busy
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {id: upper, type: local, processor: change_text_case_upper}
+  - name: idle
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {id: upper, type: local, processor: change_text_case_upper}
+"#;
+        let manager = manager(
+            "  enabled: true\n  idle_timeout_seconds: 0\n  protected_pipelines: [listed]",
+            pipelines,
+        );
+        use_in_order(&manager, &["auto", "listed", "busy", "idle"]).await;
+        let execution = registry(&manager).get("busy").unwrap().ready_pipeline();
+
+        let report = manager.sweep();
+        assert_eq!(
+            report.evicted,
+            vec![("idle".to_string(), EvictionReason::IdleTimeout)]
+        );
+        assert_eq!(state(&manager, "auto"), PipelineState::Ready);
+        assert_eq!(state(&manager, "listed"), PipelineState::Ready);
+        assert_eq!(state(&manager, "busy"), PipelineState::Ready);
+
+        drop(execution);
+        let report = manager.sweep();
+        assert_eq!(
+            report.evicted,
+            vec![("busy".to_string(), EvictionReason::IdleTimeout)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_memory_budget_evicts_pipelines_then_releases_modules() {
+        let module = echo_module();
+        let pipelines = format!(
+            r#"
+pipelines:
+  - name: critical
+    strategy: work_queue
+    processors:
+      - {{id: echo, type: wasm, module: "{path}"}}
+  - name: spare
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - {{id: echo, type: wasm, module: "{path}"}}
+"#,
+            path = module.path().display()
+        );
+        let manager = manager("  enabled: true\n  max_memory_mb: 0", &pipelines);
+        registry(&manager).start().await.unwrap();
+        use_in_order(&manager, &["spare"]).await;
+        let critical = registry(&manager).get("critical").unwrap();
+        let footprint = critical.memory_footprint();
+        assert!(footprint > 0);
+        assert_eq!(manager.memory_usage(), 2 * footprint);
+
+        let report = manager.sweep();
+        assert_eq!(
+            report.evicted,
+            vec![("spare".to_string(), EvictionReason::MemoryBudget)]
+        );
+        assert_eq!(report.modules_released_bytes, footprint);
+        assert_eq!(report.memory_bytes, 0);
+        assert_eq!(critical.state(), PipelineState::Ready);
+        assert_eq!(critical.memory_footprint(), 0);
+
+        // The protected pipeline compiles its module again on the next execution
+        let report = critical
+            .pipeline()
+            .await
+            .unwrap()
+            .execute(
+                ProcessorRequest {
+                    payload: b"hello".to_vec(),
+                },
+                PipelineMetadata::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.final_output, b"hello");
+        assert_eq!(critical.memory_footprint(), footprint);
+    }
+
+    #[tokio::test]
+    async fn test_module_release_can_be_turned_off() {
+        let module = echo_module();
+        let pipelines = format!(
+            "pipelines:\n  - name: critical\n    strategy: work_queue\n    processors:\n      \
+             - {{id: echo, type: wasm, module: \"{}\"}}\n",
+            module.path().display()
+        );
+        let manager = manager(
+            "  enabled: true\n  max_memory_mb: 0\n  evict_wasm_modules: false",
+            &pipelines,
+        );
+        registry(&manager).start().await.unwrap();
+
+        let report = manager.sweep();
+        assert!(!report.has_changes());
+        assert!(report.memory_bytes > 0);
+    }
+}
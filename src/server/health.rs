@@ -0,0 +1,193 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Server readiness and per-pipeline status, for orchestrator probes and admin views.
+//!
+//! * Liveness needs no state: a server that can answer is alive.
+//! * Readiness requires every `startup: auto` pipeline to be `Ready`. Pipelines an operator
+//!   evicted or disabled are idle by choice and do not hold readiness back, and
+//!   `startup: on-demand` pipelines are initialized by traffic, so they never do.
+//! * [`PipelineStatus`] describes one pipeline from its configuration and lifecycle, without
+//!   initializing it, including the memory it holds and how long it has been unused (the
+//!   inputs of eviction, see [`crate::server::eviction`]).
+
+use crate::config::{StartupMode, Strategy};
+use crate::errors::FailureStrategy;
+use crate::server::{ManagedPipeline, PipelineRegistry, PipelineState};
+
+/// Snapshot of one hosted pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PipelineStatus {
+    /// Name requests are routed by
+    pub name: String,
+    /// Lifecycle state
+    pub state: PipelineState,
+    /// When the pipeline is initialized
+    pub startup: StartupMode,
+    /// Execution strategy
+    pub strategy: Strategy,
+    /// Failure handling
+    pub failure_strategy: FailureStrategy,
+    /// Number of configured processors
+    pub processor_count: usize,
+    /// Initialization attempts since the pipeline was last uninitialized
+    pub attempts: u32,
+    /// Error of the most recent failed initialization attempt
+    pub last_error: Option<String>,
+    /// Estimated memory held while initialized, in bytes
+    pub memory_bytes: usize,
+    /// Whole seconds since a request last asked for the pipeline
+    pub idle_seconds: u64,
+}
+
+impl PipelineStatus {
+    /// Describe a pipeline from its current configuration and lifecycle.
+    pub fn of(pipeline: &ManagedPipeline) -> Self {
+        let config = pipeline.config();
+        Self {
+            name: pipeline.name().to_string(),
+            state: pipeline.state(),
+            startup: config.startup,
+            strategy: config.config.strategy,
+            failure_strategy: config.config.failure_strategy,
+            processor_count: config.config.processors.len(),
+            attempts: pipeline.attempts(),
+            last_error: pipeline.last_error().map(|error| error.to_string()),
+            memory_bytes: pipeline.memory_footprint(),
+            idle_seconds: pipeline.last_used().elapsed().as_secs(),
+        }
+    }
+
+    /// Status of every registered pipeline, sorted by name.
+    pub fn all(registry: &PipelineRegistry) -> Vec<Self> {
+        registry
+            .names()
+            .iter()
+            .filter_map(|name| registry.get(name))
+            .map(|pipeline| Self::of(&pipeline))
+            .collect()
+    }
+
+    /// Whether this pipeline lets the server report ready.
+    pub fn is_ready(&self) -> bool {
+        self.startup == StartupMode::OnDemand
+            || self.state == PipelineState::Ready
+            || self.state.is_idle()
+    }
+}
+
+/// Whether the server should receive traffic, and which pipelines hold it back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Readiness {
+    /// `startup: auto` pipelines that are not ready yet
+    pub not_ready: Vec<PipelineStatus>,
+}
+
+impl Readiness {
+    /// Check the pipelines of a registry.
+    pub fn of(registry: &PipelineRegistry) -> Self {
+        Self {
+            not_ready: PipelineStatus::all(registry)
+                .into_iter()
+                .filter(|status| !status.is_ready())
+                .collect(),
+        }
+    }
+
+    /// Whether every `startup: auto` pipeline is ready.
+    pub fn is_ready(&self) -> bool {
+        self.not_ready.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PipelinesConfig;
+    use crate::server::EvictionReason;
+    use std::sync::Arc;
+
+    fn registry() -> PipelineRegistry {
+        PipelineRegistry::from_config(
+            PipelinesConfig::from_yaml(
+                r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: analysis
+    startup: on-demand
+    strategy: reactive
+    failure_strategy: best_effort
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+      - id: tokens
+        type: local
+        processor: token_counter
+"#,
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_readiness_follows_auto_pipelines() {
+        let registry = registry();
+        let readiness = Readiness::of(&registry);
+        assert!(!readiness.is_ready());
+        let not_ready: Vec<&str> = readiness
+            .not_ready
+            .iter()
+            .map(|status| status.name.as_str())
+            .collect();
+        assert_eq!(not_ready, vec!["upper"]);
+
+        registry.start().await.unwrap();
+        assert!(Readiness::of(&registry).is_ready());
+
+        // Operator actions do not make the server unready
+        let upper = registry.get("upper").unwrap();
+        assert!(upper.evict(EvictionReason::Admin).is_some());
+        assert!(Readiness::of(&registry).is_ready());
+        upper.disable();
+        assert!(Readiness::of(&registry).is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_status_without_initializing() {
+        let registry = registry();
+        let statuses = PipelineStatus::all(&registry);
+        assert_eq!(
+            statuses[0],
+            PipelineStatus {
+                name: "analysis".to_string(),
+                state: PipelineState::Uninitialized,
+                startup: StartupMode::OnDemand,
+                strategy: Strategy::Reactive,
+                failure_strategy: FailureStrategy::BestEffort,
+                processor_count: 2,
+                attempts: 0,
+                last_error: None,
+                memory_bytes: 0,
+                idle_seconds: 0,
+            }
+        );
+        assert_eq!(statuses[1].name, "upper");
+        assert_eq!(
+            registry.get("analysis").unwrap().state(),
+            PipelineState::Uninitialized
+        );
+
+        let analysis: Arc<ManagedPipeline> = registry.get("analysis").unwrap();
+        analysis.pipeline().await.unwrap();
+        let status = PipelineStatus::of(&analysis);
+        assert_eq!(status.state, PipelineState::Ready);
+        assert_eq!(status.attempts, 1);
+    }
+}
@@ -0,0 +1,516 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Hot reload of hosted pipelines with drain-and-switch (ADR 23).
+//!
+//! A reload compares a new configuration with the running registry and applies the
+//! difference without a restart:
+//!
+//! ```text
+//! 1. Validate the new configuration            (invalid → rejected, nothing changes)
+//! 2. Build the new versions in the background  (build error → rejected, nothing changes)
+//! 3. Switch: new requests use the new versions, removed pipelines stop accepting requests
+//! 4. Drain: in-flight executions finish on the previous versions, which are then dropped
+//! ```
+//!
+//! Only versions that startup would build are built: running pipelines and `startup: auto`
+//! pipelines. An `on-demand` pipeline that has not been requested yet just takes the new
+//! configuration and is built by its first request, as at startup; evicted and disabled
+//! pipelines take it and stay idle.
+//!
+//! Reloads are triggered by the [`HotReloader`], either from the admin API or by a
+//! [`ConfigWatcher`] watching the configuration file.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use notify::{RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use crate::config::{load_server_config, PipelinesConfig};
+use crate::errors::{PipelineError, ServerError};
+use crate::observability::messages::server::{
+    PipelineDrainTimedOut, PipelineDrained, PipelineReloadRejected, PipelinesReloaded,
+};
+use crate::observability::messages::StructuredLog;
+use crate::server::{Pipeline, PipelineRegistry};
+
+/// How often a draining version is checked for remaining executions
+pub const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// Quiet period after a configuration file change before reloading, so the burst of events
+/// an editor produces while saving triggers a single reload
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// What triggered a reload, for logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadTrigger {
+    /// The configuration file changed
+    FileWatch,
+    /// An admin API request
+    AdminApi,
+}
+
+impl ReloadTrigger {
+    /// Stable, lowercase name of the trigger for logs and APIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReloadTrigger::FileWatch => "file_watch",
+            ReloadTrigger::AdminApi => "admin_api",
+        }
+    }
+}
+
+/// Outcome of a reload: which pipelines changed, and the versions that are draining.
+#[derive(Debug, Default)]
+pub struct ReloadReport {
+    /// Pipelines registered by the reload
+    pub added: Vec<String>,
+    /// Pipelines removed from the registry; their in-flight executions drain
+    pub removed: Vec<String>,
+    /// Pipelines switched to a new configuration (or rebuilt on request)
+    pub modified: Vec<String>,
+    /// Pipelines whose configuration did not change
+    pub unchanged: Vec<String>,
+    /// Previous versions still referenced by in-flight executions when the reload switched
+    pub drains: Vec<Drain>,
+}
+
+impl ReloadReport {
+    /// Whether the reload added, removed or modified any pipeline.
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty())
+    }
+}
+
+/// A replaced or removed pipeline version whose in-flight executions are finishing.
+///
+/// Only a weak reference is kept: the version is dropped as soon as its last execution
+/// releases it, whether or not anyone waits for the drain.
+#[derive(Debug, Clone)]
+pub struct Drain {
+    pipeline: String,
+    version: Weak<Pipeline>,
+    timeout: Duration,
+}
+
+impl Drain {
+    /// Track the draining of `version`, allowing its executions `timeout` to finish.
+    pub fn new(version: Arc<Pipeline>, timeout: Duration) -> Self {
+        Self {
+            pipeline: version.name().to_string(),
+            version: Arc::downgrade(&version),
+            timeout,
+        }
+    }
+
+    /// Name of the pipeline the version belongs to.
+    pub fn pipeline(&self) -> &str {
+        &self.pipeline
+    }
+
+    /// How long the version's executions may take to finish.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Whether every execution of the version has finished and the version was dropped.
+    pub fn is_drained(&self) -> bool {
+        self.version.strong_count() == 0
+    }
+
+    /// Wait for the version to drain, cancelling its executions once the timeout passes.
+    ///
+    /// Returns `true` if the version drained in time.
+    pub async fn wait(self) -> bool {
+        let started_at = Instant::now();
+        let drained = tokio::time::timeout(self.timeout, async {
+            while !self.is_drained() {
+                tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .is_ok();
+
+        if drained {
+            PipelineDrained {
+                pipeline: &self.pipeline,
+                duration: started_at.elapsed(),
+            }
+            .log();
+        } else {
+            PipelineDrainTimedOut {
+                pipeline: &self.pipeline,
+                timeout: self.timeout,
+            }
+            .log();
+            if let Some(version) = self.version.upgrade() {
+                version.cancel();
+            }
+        }
+        drained
+    }
+}
+
+/// Reloads the pipelines of a registry from the configuration file they were loaded from.
+///
+/// Only pipelines are reloaded; changes to `protocols` take effect on restart.
+#[derive(Debug)]
+pub struct HotReloader {
+    registry: Arc<PipelineRegistry>,
+    config_path: PathBuf,
+}
+
+impl HotReloader {
+    /// Create a reloader applying `config_path` to `registry`.
+    pub fn new(registry: Arc<PipelineRegistry>, config_path: impl Into<PathBuf>) -> Self {
+        Self {
+            registry,
+            config_path: config_path.into(),
+        }
+    }
+
+    /// The registry reloads are applied to.
+    pub fn registry(&self) -> &Arc<PipelineRegistry> {
+        &self.registry
+    }
+
+    /// Configuration file reloads are read from.
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Reload every pipeline from the configuration file.
+    ///
+    /// Changed pipelines are switched to their new version and the previous versions are
+    /// drained in the background. An invalid configuration is rejected without touching the
+    /// running pipelines.
+    pub async fn reload(&self, trigger: ReloadTrigger) -> Result<ReloadReport, PipelineError> {
+        let started_at = Instant::now();
+        let result = match self.load() {
+            Ok(config) => self.registry.reload(config).await,
+            Err(error) => Err(error),
+        };
+        self.finish(trigger, started_at, result)
+    }
+
+    /// Reload one pipeline from the configuration file, rebuilding it even if its
+    /// configuration is unchanged (e.g. to pick up a new WASM module).
+    pub async fn reload_pipeline(
+        &self,
+        name: &str,
+        trigger: ReloadTrigger,
+    ) -> Result<ReloadReport, PipelineError> {
+        let started_at = Instant::now();
+        let result = match self.load() {
+            Ok(config) => self.registry.reload_pipeline(config, name).await,
+            Err(error) => Err(error),
+        };
+        self.finish(trigger, started_at, result)
+    }
+
+    /// Reload whenever the configuration file changes, until the watcher is dropped.
+    ///
+    /// The file's directory is watched, so files replaced by a rename (as many editors and
+    /// deployment tools do) are picked up too.
+    pub fn watch(self: &Arc<Self>) -> Result<ConfigWatcher, ServerError> {
+        let watch_failed = |reason: String| ServerError::ConfigWatch {
+            path: self.config_path.display().to_string(),
+            reason,
+        };
+        let file_name = self
+            .config_path
+            .file_name()
+            .ok_or_else(|| watch_failed("path does not name a file".to_string()))?
+            .to_os_string();
+        let directory = match self.config_path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let (changed, mut changes) = mpsc::unbounded_channel();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                let Ok(event) = event else {
+                    return;
+                };
+                let relevant = (event.kind.is_modify() || event.kind.is_create())
+                    && event
+                        .paths
+                        .iter()
+                        .any(|path| path.file_name() == Some(file_name.as_os_str()));
+                if relevant {
+                    let _ = changed.send(());
+                }
+            })
+            .map_err(|e| watch_failed(e.to_string()))?;
+        watcher
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .map_err(|e| watch_failed(e.to_string()))?;
+
+        let reloader = self.clone();
+        let task = tokio::spawn(async move {
+            while changes.recv().await.is_some() {
+                tokio::time::sleep(WATCH_DEBOUNCE).await;
+                while changes.try_recv().is_ok() {}
+                // Rejections are logged; the running pipelines stay in place
+                let _ = reloader.reload(ReloadTrigger::FileWatch).await;
+            }
+        });
+
+        Ok(ConfigWatcher {
+            _watcher: watcher,
+            task,
+        })
+    }
+
+    fn load(&self) -> Result<PipelinesConfig, PipelineError> {
+        load_server_config(&self.config_path)
+            .map(|config| config.pipelines)
+            .map_err(|e| PipelineError::ConfigLoadFailed {
+                path: self.config_path.display().to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Log the outcome and start draining the replaced versions
+    fn finish(
+        &self,
+        trigger: ReloadTrigger,
+        started_at: Instant,
+        result: Result<ReloadReport, PipelineError>,
+    ) -> Result<ReloadReport, PipelineError> {
+        match &result {
+            Ok(report) => {
+                PipelinesReloaded {
+                    trigger: trigger.as_str(),
+                    added: &report.added,
+                    removed: &report.removed,
+                    modified: &report.modified,
+                    duration: started_at.elapsed(),
+                }
+                .log();
+                for drain in &report.drains {
+                    tokio::spawn(drain.clone().wait());
+                }
+            }
+            Err(error) => PipelineReloadRejected {
+                trigger: trigger.as_str(),
+                error,
+            }
+            .log(),
+        }
+        result
+    }
+}
+
+/// Watches a configuration file and reloads on change; stops when dropped.
+pub struct ConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for ConfigWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConfigWatcher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::PipelineState;
+
+    const UPPER: &str = r#"
+pipelines:
+  - name: text
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#;
+
+    const REVERSE_AND_EXTRA: &str = r#"
+pipelines:
+  - name: text
+    strategy: work_queue
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+  - name: extra
+    startup: on-demand
+    strategy: reactive
+    processors:
+      - id: lower
+        type: local
+        processor: change_text_case_lower
+"#;
+
+    const BROKEN: &str = r#"
+pipelines:
+  - name: text
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#;
+
+    async fn started(dir: &tempfile::TempDir) -> Arc<HotReloader> {
+        let path = dir.path().join("pipelines.yaml");
+        std::fs::write(&path, UPPER).unwrap();
+        let registry = Arc::new(
+            PipelineRegistry::from_config(PipelinesConfig::from_yaml(UPPER).unwrap()).unwrap(),
+        );
+        registry.start().await.unwrap();
+        Arc::new(HotReloader::new(registry, path))
+    }
+
+    async fn run(reloader: &HotReloader, name: &str, payload: &str) -> Vec<u8> {
+        let pipeline = reloader.registry().get(name).unwrap().pipeline().await;
+        let report = pipeline
+            .unwrap()
+            .execute(
+                crate::proto::processor_v1::ProcessorRequest {
+                    payload: payload.as_bytes().to_vec(),
+                },
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        report.final_output
+    }
+
+    #[tokio::test]
+    async fn test_reload_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let reloader = started(&dir).await;
+        assert_eq!(run(&reloader, "text", "abc").await, b"ABC");
+
+        std::fs::write(reloader.config_path(), REVERSE_AND_EXTRA).unwrap();
+        let report = reloader.reload(ReloadTrigger::AdminApi).await.unwrap();
+        assert_eq!(report.modified, vec!["text"]);
+        assert_eq!(report.added, vec!["extra"]);
+        assert_eq!(run(&reloader, "text", "abc").await, b"cba");
+
+        // Unreadable and invalid files are rejected and leave the pipelines untouched
+        std::fs::write(reloader.config_path(), "pipelines: [").unwrap();
+        let error = reloader.reload(ReloadTrigger::AdminApi).await.unwrap_err();
+        assert!(matches!(error, PipelineError::ConfigLoadFailed { .. }));
+
+        std::fs::write(reloader.config_path(), BROKEN).unwrap();
+        let error = reloader.reload(ReloadTrigger::AdminApi).await.unwrap_err();
+        assert!(matches!(error, PipelineError::InitializationFailed { .. }));
+        assert_eq!(run(&reloader, "text", "abc").await, b"cba");
+        assert!(reloader.registry().contains("extra"));
+    }
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let reloader = started(&dir).await;
+        let in_flight = reloader.registry().get("text").unwrap().pipeline().await;
+        let in_flight = in_flight.unwrap();
+
+        std::fs::write(reloader.config_path(), REVERSE_AND_EXTRA).unwrap();
+        let mut report = reloader.reload(ReloadTrigger::AdminApi).await.unwrap();
+        let drain = report.drains.pop().unwrap();
+        assert_eq!(drain.pipeline(), "text");
+        assert!(!drain.is_drained());
+
+        // The held version still runs the previous configuration
+        let report = in_flight
+            .execute(
+                crate::proto::processor_v1::ProcessorRequest {
+                    payload: b"abc".to_vec(),
+                },
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(report.final_output, b"ABC");
+
+        drop(in_flight);
+        assert!(drain.is_drained());
+        assert!(drain.wait().await);
+    }
+
+    #[tokio::test]
+    async fn test_drain_timeout_cancels_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let reloader = started(&dir).await;
+        let in_flight = reloader.registry().get("text").unwrap().pipeline().await;
+        let in_flight = in_flight.unwrap();
+
+        std::fs::write(reloader.config_path(), REVERSE_AND_EXTRA).unwrap();
+        let mut report = reloader.reload(ReloadTrigger::AdminApi).await.unwrap();
+        let drain = Drain {
+            timeout: Duration::from_millis(10),
+            ..report.drains.pop().unwrap()
+        };
+
+        assert!(!drain.wait().await);
+        assert!(in_flight.is_cancelled());
+        let error = in_flight
+            .execute(
+                crate::proto::processor_v1::ProcessorRequest {
+                    payload: b"abc".to_vec(),
+                },
+                Default::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("cancelled"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_single_pipeline_rebuilds_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let reloader = started(&dir).await;
+        let before = reloader.registry().get("text").unwrap().ready_pipeline();
+
+        let report = reloader
+            .reload_pipeline("text", ReloadTrigger::AdminApi)
+            .await
+            .unwrap();
+        assert_eq!(report.modified, vec!["text"]);
+        let after = reloader.registry().get("text").unwrap().ready_pipeline();
+        assert!(!Arc::ptr_eq(&before.unwrap(), &after.unwrap()));
+
+        let error = reloader
+            .reload_pipeline("missing", ReloadTrigger::AdminApi)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, PipelineError::NotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reloads_on_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let reloader = started(&dir).await;
+        let _watcher = reloader.watch().unwrap();
+
+        std::fs::write(reloader.config_path(), REVERSE_AND_EXTRA).unwrap();
+        let extra = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let Some(extra) = reloader.registry().get("extra") {
+                    return extra;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(extra.state(), PipelineState::Uninitialized);
+        assert_eq!(run(&reloader, "text", "abc").await, b"cba");
+    }
+}
@@ -0,0 +1,1023 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Asynchronous jobs: pipeline runs submitted now and collected later.
+//!
+//! Some pipelines run for minutes, longer than a client should hold a request open. A
+//! [`JobManager`] runs such executions in the background and answers a submission with the
+//! job's ID at once:
+//!
+//! ```text
+//! submit → queued → running → succeeded | failed | cancelled → expired (removed)
+//! ```
+//!
+//! * Up to `max_concurrent` jobs run at a time; the others wait in the `queued` state.
+//! * While a job runs, the executor's live events (see [`crate::engine::events`]) update the
+//!   progress of each of its processors.
+//! * Finished jobs keep their result, or why they failed, in a [`JobStore`] until their
+//!   retention period has passed. The [`FileJobStore`] keeps them across restarts; jobs that
+//!   were still queued or running when the server stopped are marked failed on
+//!   [`JobManager::recover`].
+//! * Job runs are in-flight runs of the [`PipelineRouter`], so shutdown drains and cancels
+//!   them like any other request.
+//! * A job submitted with a callback URL notifies it once its execution finished, through
+//!   the pipeline's [`Webhook`]; cancelled jobs are not notified.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use the_dagwood::protocols::PipelineRequest;
+//! use the_dagwood::server::{InMemoryJobStore, JobManager};
+//!
+//! let jobs = Arc::new(JobManager::new(Arc::new(InMemoryJobStore::new()), &config.jobs));
+//! let job = jobs
+//!     .submit(&router, PipelineRequest::new("video_analysis", payload))
+//!     .await?;
+//!
+//! // ... later
+//! let job = jobs.get(&job.id).await?;
+//! if let Some(result) = &job.result {
+//!     println!("{} finished: {} bytes", job.id, result.payload.len());
+//! }
+//! ```
+
+pub mod store;
+
+pub use store::{FileJobStore, InMemoryJobStore, JobStore};
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::config::{JobStoreConfig, JobsConfig};
+use crate::engine::{
+    ExecutionEvent, ExecutionEventKind, ExecutionReport, PipelineOutput, ProcessorStatus,
+};
+use crate::errors::{JobError, PipelineError};
+use crate::observability::messages::server::{
+    JobFinished, JobStoreFailed, JobSubmitted, JobsExpired, JobsInterrupted,
+};
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+use crate::protocols::{ErrorKind, ErrorResponse, PipelineRequest, PipelineResponse};
+use crate::server::{PipelineRouter, QuotaPermit, Webhook};
+use crate::traits::ExecutionObserver;
+
+/// Where a job is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    /// Submitted and waiting for a free execution slot
+    Queued,
+    /// The pipeline is executing
+    Running,
+    /// Every processor succeeded; the result can be fetched
+    Succeeded,
+    /// A processor failed, or the pipeline could not run
+    Failed,
+    /// Cancelled by a client before it finished
+    Cancelled,
+}
+
+impl JobState {
+    /// Stable, lowercase name of the state for logs and responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running => "running",
+            JobState::Succeeded => "succeeded",
+            JobState::Failed => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+
+    /// Whether the job is done and will not change any more
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobState::Succeeded | JobState::Failed | JobState::Cancelled
+        )
+    }
+}
+
+impl fmt::Display for JobState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a processor of a job's pipeline is, as far as the job knows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessorProgressState {
+    /// Waiting for its dependencies
+    Pending,
+    /// Dependencies succeeded; queued to run
+    Scheduled,
+    Running,
+    Succeeded,
+    Failed,
+    TimedOut,
+    /// Will not run because a dependency did not succeed
+    Blocked,
+    /// Never ran because the execution stopped early
+    Skipped,
+}
+
+impl ProcessorProgressState {
+    /// Stable, lowercase name of the state for responses
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessorProgressState::Pending => "pending",
+            ProcessorProgressState::Scheduled => "scheduled",
+            ProcessorProgressState::Running => "running",
+            ProcessorProgressState::Succeeded => "succeeded",
+            ProcessorProgressState::Failed => "failed",
+            ProcessorProgressState::TimedOut => "timed_out",
+            ProcessorProgressState::Blocked => "blocked",
+            ProcessorProgressState::Skipped => "skipped",
+        }
+    }
+
+    /// Whether the processor is done for this execution
+    pub fn is_finished(&self) -> bool {
+        !matches!(
+            self,
+            ProcessorProgressState::Pending
+                | ProcessorProgressState::Scheduled
+                | ProcessorProgressState::Running
+        )
+    }
+}
+
+impl From<ProcessorStatus> for ProcessorProgressState {
+    fn from(status: ProcessorStatus) -> Self {
+        match status {
+            ProcessorStatus::Succeeded => ProcessorProgressState::Succeeded,
+            ProcessorStatus::Failed => ProcessorProgressState::Failed,
+            ProcessorStatus::TimedOut => ProcessorProgressState::TimedOut,
+            ProcessorStatus::Blocked => ProcessorProgressState::Blocked,
+            ProcessorStatus::Skipped => ProcessorProgressState::Skipped,
+        }
+    }
+}
+
+/// Progress of one processor of a job's pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessorProgress {
+    pub processor_id: String,
+    pub state: ProcessorProgressState,
+    /// Attempts started so far
+    pub attempts: u32,
+    /// Time across all attempts, once the processor finished
+    pub duration: Option<Duration>,
+}
+
+/// Output of a succeeded job, as a synchronous request would have returned it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobResult {
+    /// Correlation ID of the execution, as recorded in logs and traces
+    pub run_id: String,
+    /// Executor strategy that ran the pipeline
+    pub strategy: String,
+    /// Canonical payload at the end of the execution
+    #[serde(with = "crate::engine::execution_report::base64_payload")]
+    pub payload: Vec<u8>,
+    /// Designated pipeline outputs, in configuration order
+    pub outputs: Vec<PipelineOutput>,
+    /// Accumulated pipeline metadata, by processor
+    pub metadata: HashMap<String, HashMap<String, String>>,
+    pub duration: Duration,
+}
+
+impl From<PipelineResponse> for JobResult {
+    fn from(response: PipelineResponse) -> Self {
+        Self {
+            run_id: response.run_id,
+            strategy: response.strategy.to_string(),
+            payload: response.payload,
+            outputs: response.outputs,
+            metadata: response
+                .metadata
+                .metadata
+                .into_iter()
+                .map(|(key, metadata)| (key, metadata.metadata))
+                .collect(),
+            duration: response.duration,
+        }
+    }
+}
+
+/// An asynchronous pipeline run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    /// ID clients poll the job by
+    pub id: String,
+    pub pipeline: String,
+    pub state: JobState,
+    pub submitted_at: SystemTime,
+    pub started_at: Option<SystemTime>,
+    pub finished_at: Option<SystemTime>,
+    /// Progress of every processor of the pipeline, in configuration order
+    pub progress: Vec<ProcessorProgress>,
+    /// Output of a succeeded job
+    pub result: Option<JobResult>,
+    /// Why a failed job failed
+    pub error: Option<ErrorResponse>,
+}
+
+impl Job {
+    /// A new queued job for `pipeline`, whose processors have not started.
+    pub fn queued<'a>(
+        pipeline: impl Into<String>,
+        processor_ids: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            pipeline: pipeline.into(),
+            state: JobState::Queued,
+            submitted_at: SystemTime::now(),
+            started_at: None,
+            finished_at: None,
+            progress: processor_ids
+                .into_iter()
+                .map(|processor_id| ProcessorProgress {
+                    processor_id: processor_id.to_string(),
+                    state: ProcessorProgressState::Pending,
+                    attempts: 0,
+                    duration: None,
+                })
+                .collect(),
+            result: None,
+            error: None,
+        }
+    }
+
+    /// Number of processors done for this execution.
+    pub fn finished_processors(&self) -> usize {
+        self.progress
+            .iter()
+            .filter(|processor| processor.state.is_finished())
+            .count()
+    }
+
+    /// Whether the job finished longer than `retention` ago.
+    pub fn is_expired(&self, retention: Duration) -> bool {
+        self.finished_at
+            .is_some_and(|finished_at| finished_at.elapsed().unwrap_or_default() >= retention)
+    }
+
+    /// Apply a live execution event to the processor progress
+    fn record(&mut self, event: &ExecutionEventKind) {
+        let Some(progress) = event.processor_id().and_then(|processor_id| {
+            self.progress
+                .iter_mut()
+                .find(|progress| progress.processor_id == processor_id)
+        }) else {
+            return;
+        };
+        match event {
+            ExecutionEventKind::ProcessorScheduled { .. } => {
+                progress.state = ProcessorProgressState::Scheduled
+            }
+            ExecutionEventKind::ProcessorStarted { attempt, .. } => {
+                progress.state = ProcessorProgressState::Running;
+                progress.attempts = *attempt;
+            }
+            ExecutionEventKind::ProcessorCompleted {
+                attempts, duration, ..
+            } => {
+                progress.state = ProcessorProgressState::Succeeded;
+                progress.attempts = *attempts;
+                progress.duration = Some(*duration);
+            }
+            ExecutionEventKind::ProcessorFailed {
+                status,
+                attempts,
+                duration,
+                ..
+            } => {
+                progress.state = (*status).into();
+                progress.attempts = *attempts;
+                progress.duration = Some(*duration);
+            }
+            ExecutionEventKind::ProcessorBlocked { .. } => {
+                progress.state = ProcessorProgressState::Blocked
+            }
+            _ => {}
+        }
+    }
+
+    /// Take the final processor states from the execution report
+    fn record_report(&mut self, report: &ExecutionReport) {
+        for progress in &mut self.progress {
+            if let Some(processor) = report.processor(&progress.processor_id) {
+                progress.state = processor.status.into();
+                progress.attempts = processor.attempts;
+                progress.duration = Some(processor.duration);
+            }
+        }
+    }
+
+    /// Move the job into its final state
+    fn finish(&mut self, outcome: JobOutcome) {
+        self.finished_at = Some(SystemTime::now());
+        match outcome {
+            JobOutcome::Succeeded(result) => {
+                self.state = JobState::Succeeded;
+                self.result = Some(result);
+            }
+            JobOutcome::Failed(error) => {
+                self.state = JobState::Failed;
+                self.error = Some(error);
+            }
+            JobOutcome::Cancelled => self.state = JobState::Cancelled,
+        }
+    }
+}
+
+/// How a job ended
+enum JobOutcome {
+    Succeeded(JobResult),
+    Failed(ErrorResponse),
+    Cancelled,
+}
+
+/// A job that has not finished yet, shared with its run
+struct ActiveJob {
+    job: Mutex<Job>,
+    cancellation: CancellationToken,
+    finished: watch::Sender<bool>,
+}
+
+impl ActiveJob {
+    fn snapshot(&self) -> Job {
+        self.job.lock().expect("job lock poisoned").clone()
+    }
+}
+
+/// Updates the processor progress of a running job from the executor's events
+struct ProgressObserver {
+    job: Arc<ActiveJob>,
+}
+
+impl ExecutionObserver for ProgressObserver {
+    fn on_event(&self, event: &ExecutionEvent) {
+        self.job
+            .job
+            .lock()
+            .expect("job lock poisoned")
+            .record(&event.kind);
+    }
+}
+
+/// Runs pipelines as asynchronous jobs and keeps track of them.
+///
+/// Jobs that have not finished are held in memory, where their progress is updated live;
+/// the job store is written when a job is submitted, starts and finishes.
+pub struct JobManager {
+    store: Arc<dyn JobStore>,
+    retention: Duration,
+    cleanup_interval: Duration,
+    slots: Semaphore,
+    active: Mutex<HashMap<String, Arc<ActiveJob>>>,
+}
+
+impl JobManager {
+    /// Create a job manager keeping its jobs in `store`.
+    pub fn new(store: Arc<dyn JobStore>, config: &JobsConfig) -> Self {
+        Self {
+            store,
+            retention: config.retention(),
+            cleanup_interval: config.cleanup_interval(),
+            slots: Semaphore::new(config.max_concurrent()),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a job manager with the configured store.
+    pub fn from_config(config: &JobsConfig) -> Result<Self, JobError> {
+        let store: Arc<dyn JobStore> = match &config.store {
+            JobStoreConfig::Memory => Arc::new(InMemoryJobStore::new()),
+            JobStoreConfig::File { directory } => Arc::new(FileJobStore::open(directory)?),
+        };
+        Ok(Self::new(store, config))
+    }
+
+    /// The store finished jobs are kept in.
+    pub fn store(&self) -> &Arc<dyn JobStore> {
+        &self.store
+    }
+
+    /// How long finished jobs are kept.
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Submit `request` as a job, returning the queued job.
+    ///
+    /// Fails if the request's credential does not allow it to execute the pipeline, the
+    /// pipeline is not registered, the request is over the quota of its client or pipeline
+    /// or the server is shutting down; everything else, including the pipeline failing to
+    /// initialize, fails the job instead. Queued jobs count against `max_concurrent` quotas.
+    pub async fn submit(
+        self: &Arc<Self>,
+        router: &PipelineRouter,
+        request: PipelineRequest,
+    ) -> Result<Job, JobError> {
+        let client = request.authorize(router)?;
+        if !router.runs().is_accepting() {
+            return Err(PipelineError::ShuttingDown.into());
+        }
+        let pipeline =
+            router
+                .registry()
+                .get(&request.pipeline)
+                .ok_or_else(|| PipelineError::NotFound {
+                    name: request.pipeline.clone(),
+                })?;
+        let permit = router.admit(client, &request.pipeline, request.payload.len())?;
+        let config = pipeline.config();
+        let callback = match &request.callback_url {
+            Some(url) => {
+                Webhook::check_callback(&request.pipeline, config.webhook.as_ref(), url)?;
+                config
+                    .webhook
+                    .clone()
+                    .map(|webhook| (Webhook::new(&request.pipeline, webhook), url.clone()))
+            }
+            None => None,
+        };
+        let job = Job::queued(
+            &request.pipeline,
+            config
+                .config
+                .processors
+                .iter()
+                .map(|processor| processor.id.as_str()),
+        );
+        self.store.save(&job).await?;
+
+        let active = Arc::new(ActiveJob {
+            job: Mutex::new(job.clone()),
+            cancellation: CancellationToken::new(),
+            finished: watch::Sender::new(false),
+        });
+        self.active
+            .lock()
+            .expect("active jobs lock poisoned")
+            .insert(job.id.clone(), active.clone());
+        metrics().job_submitted(&job.pipeline);
+        JobSubmitted {
+            job_id: &job.id,
+            pipeline: &job.pipeline,
+        }
+        .log();
+
+        let manager = self.clone();
+        let router = router.clone();
+        tokio::spawn(async move {
+            let outcome = manager
+                .run(&router, request, callback, &active, permit)
+                .await;
+            manager.finish(&active, outcome).await;
+        });
+        Ok(job)
+    }
+
+    /// The job with the given ID.
+    ///
+    /// Fails with `JobError::NotFound` for unknown and expired jobs.
+    pub async fn get(&self, id: &str) -> Result<Job, JobError> {
+        if let Some(active) = self.active_job(id) {
+            return Ok(active.snapshot());
+        }
+        self.store
+            .load(id)
+            .await?
+            .filter(|job| !job.is_expired(self.retention))
+            .ok_or_else(|| JobError::NotFound { id: id.to_string() })
+    }
+
+    /// Every job that has not expired, oldest first.
+    pub async fn list(&self) -> Result<Vec<Job>, JobError> {
+        let mut jobs: HashMap<String, Job> = self
+            .store
+            .list()
+            .await?
+            .into_iter()
+            .filter(|job| !job.is_expired(self.retention))
+            .map(|job| (job.id.clone(), job))
+            .collect();
+        let active: Vec<Arc<ActiveJob>> = self
+            .active
+            .lock()
+            .expect("active jobs lock poisoned")
+            .values()
+            .cloned()
+            .collect();
+        for active in active {
+            let job = active.snapshot();
+            jobs.insert(job.id.clone(), job);
+        }
+
+        let mut jobs: Vec<Job> = jobs.into_values().collect();
+        jobs.sort_by(|a, b| (a.submitted_at, &a.id).cmp(&(b.submitted_at, &b.id)));
+        Ok(jobs)
+    }
+
+    /// Cancel a queued or running job, returning the cancelled job.
+    ///
+    /// Returns once the job's processors stopped and its execution slot and quota were
+    /// released. Fails with `JobError::AlreadyFinished` if the job finished first.
+    pub async fn cancel(&self, id: &str) -> Result<Job, JobError> {
+        let Some(active) = self.active_job(id) else {
+            let job = self.get(id).await?;
+            return Err(JobError::AlreadyFinished {
+                id: job.id,
+                state: job.state,
+            });
+        };
+        active.cancellation.cancel();
+        let mut finished = active.finished.subscribe();
+        // The sender lives in `active`, so the channel cannot close while waiting
+        let _ = finished.wait_for(|finished| *finished).await;
+
+        let job = active.snapshot();
+        if job.state == JobState::Cancelled {
+            Ok(job)
+        } else {
+            Err(JobError::AlreadyFinished {
+                id: job.id,
+                state: job.state,
+            })
+        }
+    }
+
+    /// Remove the finished jobs older than the retention period, returning how many.
+    pub async fn purge_expired(&self) -> Result<usize, JobError> {
+        let mut removed = 0;
+        for job in self.store.list().await? {
+            if job.is_expired(self.retention) && self.store.remove(&job.id).await? {
+                removed += 1;
+            }
+        }
+        if removed > 0 {
+            JobsExpired {
+                removed,
+                retention: self.retention,
+            }
+            .log();
+        }
+        Ok(removed)
+    }
+
+    /// Fail the stored jobs left queued or running by a previous server, returning how many.
+    ///
+    /// Called once when the server starts, before jobs are submitted.
+    pub async fn recover(&self) -> Result<usize, JobError> {
+        let mut interrupted = 0;
+        for mut job in self.store.list().await? {
+            if job.state.is_finished() || self.active_job(&job.id).is_some() {
+                continue;
+            }
+            job.finish(JobOutcome::Failed(ErrorResponse {
+                pipeline: Some(job.pipeline.clone()),
+                ..ErrorResponse::new(
+                    ErrorKind::Internal,
+                    "Job was interrupted by a server restart",
+                )
+            }));
+            self.store.save(&job).await?;
+            interrupted += 1;
+        }
+        if interrupted > 0 {
+            JobsInterrupted {
+                jobs: interrupted,
+                store: self.store.name(),
+            }
+            .log();
+        }
+        Ok(interrupted)
+    }
+
+    /// Remove expired jobs every `cleanup_interval_seconds` until the returned task is
+    /// dropped.
+    pub fn start(self: &Arc<Self>) -> JobCleanupTask {
+        let manager = self.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(manager.cleanup_interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(error) = manager.purge_expired().await {
+                    JobStoreFailed {
+                        job_id: None,
+                        reason: &error.to_string(),
+                    }
+                    .log();
+                }
+            }
+        });
+        JobCleanupTask { task }
+    }
+
+    fn active_job(&self, id: &str) -> Option<Arc<ActiveJob>> {
+        self.active
+            .lock()
+            .expect("active jobs lock poisoned")
+            .get(id)
+            .cloned()
+    }
+
+    /// Wait for an execution slot, then execute the job's pipeline and notify the request's
+    /// callback; a cancelled job returns once its processors stopped
+    async fn run(
+        &self,
+        router: &PipelineRouter,
+        request: PipelineRequest,
+        callback: Option<(Webhook, String)>,
+        active: &Arc<ActiveJob>,
+        permit: QuotaPermit,
+    ) -> JobOutcome {
+        let _slot = tokio::select! {
+            slot = self.slots.acquire() => slot.expect("job slots are never closed"),
+            () = active.cancellation.cancelled() => return JobOutcome::Cancelled,
+        };
+        let job = {
+            let mut job = active.job.lock().expect("job lock poisoned");
+            job.state = JobState::Running;
+            job.started_at = Some(SystemTime::now());
+            job.clone()
+        };
+        self.save(&job).await;
+
+        let name = request.pipeline.clone();
+        let span = request.execution_span();
+        let (input, pipeline_metadata) = request.into_execution();
+        let observer = Arc::new(ProgressObserver {
+            job: active.clone(),
+        });
+        let result = router
+            .execute_cancellable(
+                &name,
+                input,
+                pipeline_metadata,
+                observer,
+                active.cancellation.clone(),
+            )
+            .instrument(span)
+            .await;
+        if result.is_err() && active.cancellation.is_cancelled() {
+            return JobOutcome::Cancelled;
+        }
+        if let Some((webhook, url)) = &callback {
+            webhook.notify_callback(url, &result);
+        }
+        let report = match result {
+            Ok(report) => report,
+            Err(error) => return JobOutcome::Failed(ErrorResponse::from(error)),
+        };
+        permit.charge_report(&report);
+
+        active
+            .job
+            .lock()
+            .expect("job lock poisoned")
+            .record_report(&report);
+        if report.errors.is_empty() {
+            JobOutcome::Succeeded(PipelineResponse::from_report(&name, report).into())
+        } else {
+            JobOutcome::Failed(ErrorResponse::from_failed_report(&name, &report))
+        }
+    }
+
+    /// Record how the job ended, once its run stopped
+    async fn finish(&self, active: &ActiveJob, outcome: JobOutcome) {
+        let job = {
+            let mut job = active.job.lock().expect("job lock poisoned");
+            job.finish(outcome);
+            job.clone()
+        };
+        // Stored before it leaves the active jobs, so it can always be found
+        self.save(&job).await;
+        self.active
+            .lock()
+            .expect("active jobs lock poisoned")
+            .remove(&job.id);
+
+        metrics().job_finished(&job.pipeline, job.state.as_str());
+        JobFinished {
+            job_id: &job.id,
+            pipeline: &job.pipeline,
+            state: job.state.as_str(),
+            duration: job
+                .finished_at
+                .and_then(|finished_at| finished_at.duration_since(job.submitted_at).ok())
+                .unwrap_or_default(),
+        }
+        .log();
+        active.finished.send_replace(true);
+    }
+
+    /// Store a job, logging failures; the job carries on in memory
+    async fn save(&self, job: &Job) {
+        if let Err(error) = self.store.save(job).await {
+            JobStoreFailed {
+                job_id: Some(&job.id),
+                reason: &error.to_string(),
+            }
+            .log();
+        }
+    }
+}
+
+impl fmt::Debug for JobManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobManager")
+            .field("store", &self.store.name())
+            .field("retention", &self.retention)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Periodic removal of expired jobs by a [`JobManager`]; stops when dropped.
+pub struct JobCleanupTask {
+    task: JoinHandle<()>,
+}
+
+impl Drop for JobCleanupTask {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl fmt::Debug for JobCleanupTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobCleanupTask").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::stub::SlowProcessor;
+    use crate::config::ServerConfig;
+    use crate::server::{Pipeline, PipelineRegistry};
+    use std::sync::atomic::Ordering;
+
+    fn setup(jobs: &str) -> (Arc<JobManager>, PipelineRouter) {
+        let yaml = format!(
+            r#"
+jobs:
+{}
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: analysis
+    strategy: reactive
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+      - id: tokens
+        type: local
+        processor: token_counter
+        depends_on: [reverse]
+"#,
+            jobs
+        );
+        let config = ServerConfig::from_yaml(&yaml).unwrap();
+        let registry = PipelineRegistry::from_config(config.pipelines).unwrap();
+        let jobs = JobManager::from_config(&config.jobs).unwrap();
+        (Arc::new(jobs), PipelineRouter::new(Arc::new(registry)))
+    }
+
+    async fn wait_finished(jobs: &JobManager, id: &str) -> Job {
+        for _ in 0..500 {
+            let job = jobs.get(id).await.unwrap();
+            if job.state.is_finished() {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish", id);
+    }
+
+    #[tokio::test]
+    async fn test_job_runs_in_background_with_progress() {
+        let (jobs, router) = setup("  enabled: true");
+
+        let submitted = jobs
+            .submit(
+                &router,
+                PipelineRequest::new("analysis", b"hello world".to_vec()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(submitted.state, JobState::Queued);
+        assert_eq!(submitted.finished_processors(), 0);
+        assert_eq!(submitted.progress.len(), 2);
+
+        let job = wait_finished(&jobs, &submitted.id).await;
+        assert_eq!(job.state, JobState::Succeeded);
+        assert!(job.started_at.is_some());
+        assert_eq!(job.finished_processors(), 2);
+        assert!(job.progress.iter().all(|processor| processor.state
+            == ProcessorProgressState::Succeeded
+            && processor.attempts == 1));
+        let result = job.result.unwrap();
+        assert_eq!(result.strategy, "Reactive");
+        assert!(!result.run_id.is_empty());
+        assert!(job.error.is_none());
+
+        let listed = jobs.list().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, submitted.id);
+    }
+
+    #[tokio::test]
+    async fn test_failed_and_rejected_jobs() {
+        let (jobs, router) = setup("  enabled: true");
+
+        let job = jobs
+            .submit(&router, PipelineRequest::new("upper", vec![0xff, 0xfe]))
+            .await
+            .unwrap();
+        let job = wait_finished(&jobs, &job.id).await;
+        assert_eq!(job.state, JobState::Failed);
+        assert!(job.result.is_none());
+        let error = job.error.unwrap();
+        assert_eq!(error.kind, ErrorKind::ProcessorFailed);
+        assert_eq!(error.failures[0].processor_id, "upper");
+        assert_eq!(job.progress[0].state, ProcessorProgressState::Failed);
+
+        let missing = jobs
+            .submit(&router, PipelineRequest::new("missing", vec![]))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            missing,
+            JobError::Pipeline(PipelineError::NotFound { .. })
+        ));
+
+        let callback = PipelineRequest::new("upper", vec![]).with_callback("http://localhost/cb");
+        assert!(matches!(
+            jobs.submit(&router, callback).await,
+            Err(JobError::Pipeline(PipelineError::CallbackRejected { .. }))
+        ));
+
+        router.runs().stop_accepting();
+        let shutting_down = jobs
+            .submit(&router, PipelineRequest::new("upper", vec![]))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            shutting_down,
+            JobError::Pipeline(PipelineError::ShuttingDown)
+        ));
+
+        assert!(matches!(
+            jobs.get("missing").await,
+            Err(JobError::NotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_queued_job() {
+        let (jobs, router) = setup("  enabled: true\n  max_concurrent: 1");
+        // Occupy the only execution slot so the job stays queued
+        let slot = jobs.slots.acquire().await.unwrap();
+
+        let job = jobs
+            .submit(&router, PipelineRequest::new("upper", b"abc".to_vec()))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(jobs.get(&job.id).await.unwrap().state, JobState::Queued);
+
+        let cancelled = jobs.cancel(&job.id).await.unwrap();
+        assert_eq!(cancelled.state, JobState::Cancelled);
+        assert!(cancelled.finished_at.is_some());
+        drop(slot);
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let job = jobs.get(&job.id).await.unwrap();
+        assert_eq!(job.state, JobState::Cancelled);
+        assert!(job.result.is_none());
+        assert!(matches!(
+            jobs.cancel(&job.id).await,
+            Err(JobError::AlreadyFinished {
+                state: JobState::Cancelled,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_running_job_stops_its_processors() {
+        let (jobs, router) = setup("  enabled: true\n  max_concurrent: 1");
+        let slow = Arc::new(SlowProcessor::new(Duration::from_secs(30)));
+        let upper = router.registry().get("upper").unwrap();
+        upper.replace(
+            upper.config(),
+            Some(Pipeline::with_processor("upper", slow.clone())),
+        );
+
+        let job = jobs
+            .submit(&router, PipelineRequest::new("upper", b"abc".to_vec()))
+            .await
+            .unwrap();
+        slow.wait_started().await;
+        assert_eq!(jobs.get(&job.id).await.unwrap().state, JobState::Running);
+
+        let cancelled = jobs.cancel(&job.id).await.unwrap();
+        assert_eq!(cancelled.state, JobState::Cancelled);
+        // The processor stopped, and the run and its slot were released, before cancel returned
+        assert!(slow.stopped.load(Ordering::SeqCst));
+        assert!(!slow.finished.load(Ordering::SeqCst));
+        assert_eq!(router.runs().active(), 0);
+        assert_eq!(jobs.slots.available_permits(), 1);
+        assert!(jobs.active_job(&job.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_expired_jobs_are_removed() {
+        let (jobs, router) = setup("  enabled: true\n  retention_seconds: 0");
+
+        let job = jobs
+            .submit(&router, PipelineRequest::new("upper", b"abc".to_vec()))
+            .await
+            .unwrap();
+        for _ in 0..500 {
+            if jobs.active_job(&job.id).is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert!(matches!(
+            jobs.get(&job.id).await,
+            Err(JobError::NotFound { .. })
+        ));
+        assert!(jobs.list().await.unwrap().is_empty());
+        assert_eq!(jobs.store().list().await.unwrap().len(), 1);
+        assert_eq!(jobs.purge_expired().await.unwrap(), 1);
+        assert!(jobs.store().list().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_file_store_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let jobs_config = format!(
+            "  enabled: true\n  store:\n    type: file\n    directory: {}",
+            dir.path().display()
+        );
+        let (jobs, router) = setup(&jobs_config);
+
+        let finished = jobs
+            .submit(&router, PipelineRequest::new("upper", b"abc".to_vec()))
+            .await
+            .unwrap();
+        let finished = wait_finished(&jobs, &finished.id).await;
+        let interrupted = Job::queued("upper", ["upper"]);
+        jobs.store().save(&interrupted).await.unwrap();
+        drop(jobs);
+
+        let (restarted, _) = setup(&jobs_config);
+        assert_eq!(restarted.recover().await.unwrap(), 1);
+        assert_eq!(restarted.get(&finished.id).await.unwrap(), finished);
+        assert_eq!(
+            restarted
+                .get(&finished.id)
+                .await
+                .unwrap()
+                .result
+                .unwrap()
+                .payload,
+            b"ABC"
+        );
+        let interrupted = restarted.get(&interrupted.id).await.unwrap();
+        assert_eq!(interrupted.state, JobState::Failed);
+        assert!(interrupted
+            .error
+            .unwrap()
+            .message
+            .contains("interrupted by a server restart"));
+        assert_eq!(restarted.recover().await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,247 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Pluggable job stores.
+//!
+//! A [`JobStore`] keeps the [`Job`] records of a [`JobManager`](super::JobManager), following
+//! the same trait-based pattern as processors and protocol receivers:
+//!
+//! * [`InMemoryJobStore`] - jobs live in process memory and are lost on restart
+//! * [`FileJobStore`] - one JSON file per job in a directory, so finished jobs survive restarts
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use async_trait::async_trait;
+
+use crate::errors::JobError;
+use crate::server::jobs::Job;
+
+/// Storage for asynchronous jobs.
+#[async_trait]
+pub trait JobStore: Send + Sync {
+    /// Insert a job, or replace the stored job with the same ID.
+    async fn save(&self, job: &Job) -> Result<(), JobError>;
+
+    /// The job with the given ID, if stored.
+    async fn load(&self, id: &str) -> Result<Option<Job>, JobError>;
+
+    /// Every stored job, in no particular order.
+    async fn list(&self) -> Result<Vec<Job>, JobError>;
+
+    /// Remove a job, returning whether it was stored.
+    async fn remove(&self, id: &str) -> Result<bool, JobError>;
+
+    /// Name of the store for logs.
+    fn name(&self) -> &'static str;
+}
+
+/// Job store keeping jobs in process memory.
+#[derive(Debug, Default)]
+pub struct InMemoryJobStore {
+    jobs: RwLock<HashMap<String, Job>>,
+}
+
+impl InMemoryJobStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl JobStore for InMemoryJobStore {
+    async fn save(&self, job: &Job) -> Result<(), JobError> {
+        self.jobs
+            .write()
+            .expect("job store lock poisoned")
+            .insert(job.id.clone(), job.clone());
+        Ok(())
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<Job>, JobError> {
+        Ok(self
+            .jobs
+            .read()
+            .expect("job store lock poisoned")
+            .get(id)
+            .cloned())
+    }
+
+    async fn list(&self) -> Result<Vec<Job>, JobError> {
+        Ok(self
+            .jobs
+            .read()
+            .expect("job store lock poisoned")
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, JobError> {
+        Ok(self
+            .jobs
+            .write()
+            .expect("job store lock poisoned")
+            .remove(id)
+            .is_some())
+    }
+
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+/// Job store keeping every job as `<id>.json` in a directory.
+///
+/// Jobs are written to a temporary file first and renamed into place, so a crash never
+/// leaves a half-written job behind. Files in the directory that are not jobs are ignored.
+#[derive(Debug)]
+pub struct FileJobStore {
+    directory: PathBuf,
+}
+
+impl FileJobStore {
+    /// Open a store in `directory`, creating the directory if needed.
+    pub fn open(directory: impl Into<PathBuf>) -> Result<Self, JobError> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory).map_err(|error| JobError::StoreFailed {
+            reason: format!("cannot create '{}': {}", directory.display(), error),
+        })?;
+        Ok(Self { directory })
+    }
+
+    /// Directory the jobs are kept in.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Path of a job's file; `None` for IDs that are not job IDs (e.g. `../secrets`)
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        let is_job_id = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        is_job_id.then(|| self.directory.join(format!("{}.json", id)))
+    }
+
+    fn failed(&self, action: &str, error: impl std::fmt::Display) -> JobError {
+        JobError::StoreFailed {
+            reason: format!(
+                "cannot {} in '{}': {}",
+                action,
+                self.directory.display(),
+                error
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl JobStore for FileJobStore {
+    async fn save(&self, job: &Job) -> Result<(), JobError> {
+        let path = self
+            .path(&job.id)
+            .ok_or_else(|| self.failed("save job", format!("invalid job ID '{}'", job.id)))?;
+        let content = serde_json::to_vec(job).map_err(|e| self.failed("save job", e))?;
+        let temporary = path.with_extension("json.tmp");
+        tokio::fs::write(&temporary, content)
+            .await
+            .map_err(|e| self.failed("save job", e))?;
+        tokio::fs::rename(&temporary, &path)
+            .await
+            .map_err(|e| self.failed("save job", e))
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<Job>, JobError> {
+        let Some(path) = self.path(id) else {
+            return Ok(None);
+        };
+        match tokio::fs::read(&path).await {
+            Ok(content) => serde_json::from_slice(&content)
+                .map(Some)
+                .map_err(|e| self.failed("read job", e)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(self.failed("read job", error)),
+        }
+    }
+
+    async fn list(&self) -> Result<Vec<Job>, JobError> {
+        let mut entries = tokio::fs::read_dir(&self.directory)
+            .await
+            .map_err(|e| self.failed("list jobs", e))?;
+        let mut jobs = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| self.failed("list jobs", e))?
+        {
+            let path = entry.path();
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            // Removed by a concurrent cleanup, or not a job
+            let Ok(content) = tokio::fs::read(&path).await else {
+                continue;
+            };
+            if let Ok(job) = serde_json::from_slice(&content) {
+                jobs.push(job);
+            }
+        }
+        Ok(jobs)
+    }
+
+    async fn remove(&self, id: &str) -> Result<bool, JobError> {
+        let Some(path) = self.path(id) else {
+            return Ok(false);
+        };
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(self.failed("remove job", error)),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::JobState;
+
+    async fn round_trip(store: &dyn JobStore) {
+        let mut job = Job::queued("upper", ["upper"]);
+        store.save(&job).await.unwrap();
+        job.state = JobState::Running;
+        store.save(&job).await.unwrap();
+
+        assert_eq!(store.load(&job.id).await.unwrap(), Some(job.clone()));
+        assert_eq!(store.list().await.unwrap(), vec![job.clone()]);
+        assert!(store.remove(&job.id).await.unwrap());
+        assert!(!store.remove(&job.id).await.unwrap());
+        assert_eq!(store.load(&job.id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        round_trip(&InMemoryJobStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn test_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileJobStore::open(dir.path().join("jobs")).unwrap();
+        round_trip(&store).await;
+
+        // Stray files are not jobs, and IDs never escape the directory
+        std::fs::write(store.directory().join("notes.txt"), "hello").unwrap();
+        std::fs::write(store.directory().join("broken.json"), "{").unwrap();
+        std::fs::write(dir.path().join("secret.json"), "{}").unwrap();
+        assert!(store.list().await.unwrap().is_empty());
+        assert_eq!(store.load("../secret").await.unwrap(), None);
+        assert!(!store.remove("../secret").await.unwrap());
+        assert!(dir.path().join("secret.json").exists());
+    }
+}
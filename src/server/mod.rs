@@ -0,0 +1,103 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Multi-pipeline hosting: one process serving many named DAGs.
+//!
+//! Following ADR 20 (multi-pipeline architecture & registry pattern), pipelines are compiled
+//! from a [`PipelinesConfig`](crate::config::PipelinesConfig) into a [`PipelineRegistry`],
+//! and requests are dispatched to them by name through a [`PipelineRouter`]:
+//!
+//! ```text
+//! Request → PipelineRouter → PipelineRegistry → Pipeline → Executor → Processors
+//! ```
+//!
+//! Each [`Pipeline`] owns its compiled execution plan, executor and failure strategy, so
+//! pipelines hosted side by side can use different strategies and failure handling.
+//!
+//! Following ADR 22, every registered pipeline moves through the [`PipelineState`] lifecycle
+//! managed by [`ManagedPipeline`]: `startup: auto` pipelines are initialized by
+//! [`PipelineRegistry::start`], `startup: on-demand` pipelines by their first request, and
+//! failed initializations are retried with backoff.
+//!
+//! A [`DagwoodServer`] puts the registry behind the protocol receivers of a
+//! [`ServerConfig`](crate::config::ServerConfig) (see [`crate::protocols`]); this is what
+//! `dagwood serve` runs.
+//!
+//! Following ADR 23, a [`HotReloader`] applies configuration changes to a running registry
+//! with drain-and-switch: new versions are built in the background, new requests switch to
+//! them at once, and in-flight executions finish on the previous versions.
+//!
+//! [`Readiness`] and [`PipelineStatus`] report on the registry for orchestrator probes and
+//! admin views without initializing anything.
+//!
+//! Following ADR 28, a [`MemoryManager`] evicts idle pipelines, least recently used first,
+//! and releases compiled WASM modules to keep the registry within a memory budget.
+//!
+//! A [`JobManager`] runs pipelines as asynchronous jobs for executions too long for a
+//! synchronous request: clients submit a job, poll its progress and fetch its result later,
+//! while a pluggable [`JobStore`] keeps finished jobs for their retention period.
+//!
+//! A pipeline configured with a webhook notifies it of every finished execution through a
+//! [`Webhook`], and requests may name their own callback URL when the webhook allows it.
+//!
+//! When `auth` is enabled, an [`Authenticator`] shared through the router checks the
+//! credentials of every request against the configured clients and their pipeline
+//! allowlists, whichever protocol the request arrived on.
+//!
+//! [`Quotas`] admit every request against the quota of its pipeline and of its client: the
+//! executions in flight, the request rate, the payload size and the WASM fuel used per time
+//! window. Requests over a limit are rejected with a hint of when to retry.
+//!
+//! Following ADR 27, shutdown drains the runs in flight (tracked by [`InFlightRuns`]) for up
+//! to a drain timeout, cancels the rest and then releases the pipelines.
+//!
+//! # Examples
+//!
+//! ```rust,ignore
+//! use std::sync::Arc;
+//! use the_dagwood::config::load_and_validate_pipelines_config;
+//! use the_dagwood::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+//! use the_dagwood::server::{PipelineRegistry, PipelineRouter};
+//!
+//! let config = load_and_validate_pipelines_config("pipelines.yaml")?;
+//! let registry = Arc::new(PipelineRegistry::from_config(config)?);
+//! registry.start().await?;
+//! let router = PipelineRouter::new(registry);
+//!
+//! let report = router
+//!     .execute(
+//!         "text_processing",
+//!         ProcessorRequest { payload: b"hello".to_vec() },
+//!         PipelineMetadata::new(),
+//!     )
+//!     .await?;
+//! ```
+
+pub mod auth;
+pub mod dagwood_server;
+pub mod eviction;
+pub mod health;
+pub mod hot_reload;
+pub mod jobs;
+pub mod pipeline_lifecycle;
+pub mod pipeline_registry;
+pub mod pipeline_router;
+pub mod quotas;
+pub mod shutdown;
+pub mod webhooks;
+
+pub use auth::{Access, AuthClient, Authenticator, Credential};
+pub use dagwood_server::DagwoodServer;
+pub use eviction::{EvictionReason, EvictionTask, MemoryManager, SweepReport};
+pub use health::{PipelineStatus, Readiness};
+pub use hot_reload::{ConfigWatcher, Drain, HotReloader, ReloadReport, ReloadTrigger};
+pub use jobs::{
+    FileJobStore, InMemoryJobStore, Job, JobCleanupTask, JobManager, JobResult, JobState, JobStore,
+    ProcessorProgress, ProcessorProgressState,
+};
+pub use pipeline_lifecycle::{ManagedPipeline, PipelineState};
+pub use pipeline_registry::{Pipeline, PipelineRegistry};
+pub use pipeline_router::PipelineRouter;
+pub use quotas::{Quota, QuotaPermit, QuotaScope, QuotaUsage, Quotas};
+pub use shutdown::{InFlightRuns, RunGuard, ShutdownSignal};
+pub use webhooks::{ExecutionStatus, ExecutionSummary, ProcessorSummary, Webhook};
@@ -0,0 +1,940 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Pipeline lifecycle states and lazy, single-flight initialization (ADR 22).
+//!
+//! ```text
+//! Uninitialized
+//!     ↓ (first request OR startup: auto)
+//! Initializing ──(failure)──→ Failed ──(retry after backoff)──→ Initializing
+//!     ↓ (success)                 ↓ (max retries exceeded)
+//! Ready                       PermanentlyFailed (rejects requests until reset)
+//!     ↓ (removed from the registry)
+//! Draining (in-flight executions finish, new requests are rejected)
+//!
+//! Ready ──(evict)──→ Evicted ──(next request)──→ Initializing
+//! any ──(disable)──→ Disabled ──(enable)──→ Uninitialized
+//! ```
+//!
+//! Operators can evict a ready pipeline to release its processors (notably WASM modules)
+//! until it is needed again, or disable a pipeline so it rejects requests; in both cases
+//! in-flight executions finish on the released version. The memory manager evicts idle
+//! pipelines the same way (ADR 28, see [`crate::server::eviction`]), picking the least
+//! recently used first.
+//!
+//! A reload replaces the configuration and, for running pipelines, switches to a new version
+//! built in the background (ADR 23); the state of the pipeline itself does not change, and
+//! the previous version drains while new requests use the new one. Every replacement starts
+//! a new generation, so an initialization still running for a superseded configuration
+//! cannot overwrite it.
+//!
+//! Initialization runs in a background task, so it is carried out exactly once no matter
+//! how many requests arrive, and is not cancelled when a waiting request gives up. Processor
+//! instantiation (notably WASM module compilation) runs on the blocking thread pool so a slow
+//! pipeline does not hold up requests to other pipelines.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::watch;
+
+use crate::config::{PipelineConfig, StartupMode};
+use crate::errors::PipelineError;
+use crate::observability::messages::server::{
+    PipelineDisabled, PipelineEnabled, PipelineEvicted, PipelineInitializationFailed,
+    PipelineInitializationStarted, PipelineInitialized, PipelinePermanentlyFailed, PipelineReset,
+    PipelineRestored,
+};
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+use crate::server::{EvictionReason, Pipeline};
+
+/// Lifecycle state of a hosted pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PipelineState {
+    /// Not initialized yet; the first request (or `startup: auto`) initializes it
+    Uninitialized,
+    /// Initialization is in progress; requests queue until it finishes
+    Initializing,
+    /// Initialized and serving requests
+    Ready,
+    /// The last initialization attempt failed and a retry is scheduled
+    Failed,
+    /// Every initialization attempt failed; requests are rejected until reset
+    PermanentlyFailed,
+    /// Removed from its registry; in-flight executions finish, new requests are rejected
+    Draining,
+    /// Released after being ready; the next request initializes it again
+    Evicted,
+    /// Disabled by an operator; requests are rejected until it is enabled
+    Disabled,
+}
+
+impl PipelineState {
+    /// Stable, lowercase name of the state for logs, metrics and APIs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelineState::Uninitialized => "uninitialized",
+            PipelineState::Initializing => "initializing",
+            PipelineState::Ready => "ready",
+            PipelineState::Failed => "failed",
+            PipelineState::PermanentlyFailed => "permanently_failed",
+            PipelineState::Draining => "draining",
+            PipelineState::Evicted => "evicted",
+            PipelineState::Disabled => "disabled",
+        }
+    }
+
+    /// Whether the pipeline is idle by choice: not built, but not expected to be either.
+    ///
+    /// Readiness checks accept idle pipelines, and reloads do not build them.
+    pub fn is_idle(&self) -> bool {
+        matches!(self, PipelineState::Evicted | PipelineState::Disabled)
+    }
+}
+
+impl fmt::Display for PipelineState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Current state plus whatever the state carries
+struct Lifecycle {
+    state: PipelineState,
+    config: Arc<PipelineConfig>,
+    generation: u64,
+    pipeline: Option<Arc<Pipeline>>,
+    attempts: u32,
+    last_error: Option<PipelineError>,
+}
+
+/// A hosted pipeline together with its lifecycle.
+///
+/// The pipeline configuration is kept so the pipeline can be (re)initialized at any time;
+/// the compiled [`Pipeline`] only exists while the state is `Ready`.
+pub struct ManagedPipeline {
+    name: String,
+    lifecycle: watch::Sender<Lifecycle>,
+    last_used: Mutex<Instant>,
+}
+
+impl ManagedPipeline {
+    /// Create an uninitialized pipeline from its configuration.
+    pub fn new(config: PipelineConfig) -> Self {
+        Self::with_shared_config(Arc::new(config))
+    }
+
+    /// Create an uninitialized pipeline from a configuration shared with its builds
+    pub(crate) fn with_shared_config(config: Arc<PipelineConfig>) -> Self {
+        Self {
+            name: config.name.clone(),
+            lifecycle: watch::Sender::new(Lifecycle {
+                state: PipelineState::Uninitialized,
+                config,
+                generation: 0,
+                pipeline: None,
+                attempts: 0,
+                last_error: None,
+            }),
+            last_used: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Name used to route requests to this pipeline.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Configuration the pipeline is (or will be) initialized from.
+    pub fn config(&self) -> Arc<PipelineConfig> {
+        self.lifecycle.borrow().config.clone()
+    }
+
+    /// When the pipeline is initialized.
+    pub fn startup(&self) -> StartupMode {
+        self.lifecycle.borrow().config.startup
+    }
+
+    /// Current lifecycle state.
+    pub fn state(&self) -> PipelineState {
+        self.lifecycle.borrow().state
+    }
+
+    /// Initialization attempts made since the pipeline was last uninitialized.
+    pub fn attempts(&self) -> u32 {
+        self.lifecycle.borrow().attempts
+    }
+
+    /// Error of the most recent failed initialization attempt, if any.
+    pub fn last_error(&self) -> Option<PipelineError> {
+        self.lifecycle.borrow().last_error.clone()
+    }
+
+    /// The initialized pipeline, if the state is `Ready`, without waiting.
+    pub fn ready_pipeline(&self) -> Option<Arc<Pipeline>> {
+        self.lifecycle.borrow().pipeline.clone()
+    }
+
+    /// When a request last asked for the pipeline (or when it was registered).
+    pub fn last_used(&self) -> Instant {
+        *self
+            .last_used
+            .lock()
+            .expect("pipeline last used lock poisoned")
+    }
+
+    /// Whether executions are running on the ready version.
+    pub fn is_executing(&self) -> bool {
+        self.lifecycle
+            .borrow()
+            .pipeline
+            .as_ref()
+            .is_some_and(|pipeline| Arc::strong_count(pipeline) > 1)
+    }
+
+    /// Estimated memory held by the ready version, in bytes; 0 when not ready.
+    pub fn memory_footprint(&self) -> usize {
+        self.lifecycle
+            .borrow()
+            .pipeline
+            .as_ref()
+            .map_or(0, |pipeline| pipeline.memory_footprint())
+    }
+
+    /// Start initializing the pipeline in the background if it is `Uninitialized` or
+    /// `Evicted`.
+    ///
+    /// Returns `true` if this call started the initialization. Concurrent callers race
+    /// for the transition, so exactly one of them starts it.
+    pub fn initialize(self: &Arc<Self>) -> bool {
+        let mut generation = 0;
+        let mut restoring = false;
+        let started = self.lifecycle.send_if_modified(|lifecycle| {
+            if !matches!(
+                lifecycle.state,
+                PipelineState::Uninitialized | PipelineState::Evicted
+            ) {
+                return false;
+            }
+            restoring = lifecycle.state == PipelineState::Evicted;
+            lifecycle.state = PipelineState::Initializing;
+            lifecycle.attempts = 0;
+            lifecycle.last_error = None;
+            generation = lifecycle.generation;
+            true
+        });
+
+        if started {
+            tokio::spawn(self.clone().run_initialization(generation, restoring));
+        }
+        started
+    }
+
+    /// Get the ready pipeline, initializing it first if needed.
+    ///
+    /// Requests for a pipeline that is not ready wait behind the single in-flight
+    /// initialization (including its retries), bounded by `initialization.timeout_seconds`.
+    pub async fn pipeline(self: &Arc<Self>) -> Result<Arc<Pipeline>, PipelineError> {
+        *self
+            .last_used
+            .lock()
+            .expect("pipeline last used lock poisoned") = Instant::now();
+        let settled = async {
+            let mut lifecycle = self.lifecycle.subscribe();
+            loop {
+                self.initialize();
+                let lifecycle = lifecycle
+                    .wait_for(|lifecycle| {
+                        matches!(
+                            lifecycle.state,
+                            PipelineState::Ready
+                                | PipelineState::PermanentlyFailed
+                                | PipelineState::Draining
+                                | PipelineState::Disabled
+                                | PipelineState::Uninitialized
+                                | PipelineState::Evicted
+                        )
+                    })
+                    .await?;
+                // Evicted or reset before this request got it: initialize it again
+                if !matches!(
+                    lifecycle.state,
+                    PipelineState::Uninitialized | PipelineState::Evicted
+                ) {
+                    return Ok(self.settled(&lifecycle));
+                }
+            }
+        };
+
+        let initialization_timeout = self.config().initialization.timeout();
+        match initialization_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, settled).await.map_err(|_| {
+                PipelineError::InitializationTimeout {
+                    name: self.name().to_string(),
+                    timeout,
+                }
+            })?,
+            None => settled.await,
+        }
+        .map_err(
+            |_: watch::error::RecvError| PipelineError::InitializationFailed {
+                name: self.name().to_string(),
+                reason: "pipeline lifecycle closed".to_string(),
+            },
+        )?
+    }
+
+    /// The ready pipeline of a settled lifecycle, or why there is none
+    fn settled(&self, lifecycle: &Lifecycle) -> Result<Arc<Pipeline>, PipelineError> {
+        match &lifecycle.pipeline {
+            Some(pipeline) => Ok(pipeline.clone()),
+            // A removed pipeline answers like one that was never registered
+            None if lifecycle.state == PipelineState::Draining => Err(PipelineError::NotFound {
+                name: self.name().to_string(),
+            }),
+            None if lifecycle.state == PipelineState::Disabled => Err(PipelineError::Disabled {
+                name: self.name().to_string(),
+            }),
+            None => Err(PipelineError::PermanentlyFailed {
+                name: self.name().to_string(),
+                attempts: lifecycle.attempts,
+                reason: lifecycle
+                    .last_error
+                    .as_ref()
+                    .map(|error| error.to_string())
+                    .unwrap_or_default(),
+            }),
+        }
+    }
+
+    /// Get the ready pipeline, waiting at most `wait` for it to become ready.
+    ///
+    /// Like [`pipeline`](Self::pipeline), but gives up with `PipelineError::NotReady` once
+    /// `wait` has passed. The initialization keeps running in the background, so protocol
+    /// receivers can answer "try again later" instead of holding the request open.
+    pub async fn pipeline_within(
+        self: &Arc<Self>,
+        wait: Duration,
+    ) -> Result<Arc<Pipeline>, PipelineError> {
+        match tokio::time::timeout(wait, self.pipeline()).await {
+            Ok(result) => result,
+            Err(_) => Err(PipelineError::NotReady {
+                name: self.name().to_string(),
+                state: self.state(),
+            }),
+        }
+    }
+
+    /// Return the pipeline to `Uninitialized` so the next request initializes it again.
+    ///
+    /// Only settled pipelines (`Ready` or `PermanentlyFailed`) can be reset; returns `false`
+    /// while an initialization is in progress or the pipeline is already uninitialized.
+    /// In-flight executions of a reset `Ready` pipeline finish on the released instance.
+    pub fn reset(&self) -> bool {
+        let mut previous_state = PipelineState::Uninitialized;
+        let reset = self.lifecycle.send_if_modified(|lifecycle| {
+            if !matches!(
+                lifecycle.state,
+                PipelineState::Ready | PipelineState::PermanentlyFailed
+            ) {
+                return false;
+            }
+            previous_state = lifecycle.state;
+            lifecycle.state = PipelineState::Uninitialized;
+            lifecycle.generation += 1;
+            lifecycle.pipeline = None;
+            lifecycle.attempts = 0;
+            lifecycle.last_error = None;
+            true
+        });
+
+        if reset {
+            PipelineReset {
+                pipeline: self.name(),
+                previous_state: previous_state.as_str(),
+            }
+            .log();
+            self.record_memory();
+        }
+        reset
+    }
+
+    /// Switch the pipeline to a new configuration, returning the version it replaces.
+    ///
+    /// With a `version` built from `config`, the pipeline becomes `Ready` with it at once.
+    /// Without one, an evicted or disabled pipeline stays so, and any other pipeline returns
+    /// to `Uninitialized` and initializes again right away if it was initialized (or
+    /// initializing) before, so waiting requests get the new version. Executions holding the
+    /// returned version finish on it.
+    pub fn replace(
+        self: &Arc<Self>,
+        config: Arc<PipelineConfig>,
+        version: Option<Pipeline>,
+    ) -> Option<Arc<Pipeline>> {
+        let mut previous_state = PipelineState::Uninitialized;
+        let mut previous = None;
+        self.lifecycle.send_modify(|lifecycle| {
+            previous_state = lifecycle.state;
+            previous = lifecycle.pipeline.take();
+            lifecycle.state = match version {
+                Some(_) => PipelineState::Ready,
+                None if previous_state.is_idle() => previous_state,
+                None => PipelineState::Uninitialized,
+            };
+            lifecycle.config = config;
+            lifecycle.generation += 1;
+            lifecycle.pipeline = version.map(Arc::new);
+            lifecycle.attempts = 0;
+            lifecycle.last_error = None;
+        });
+
+        self.record_memory();
+        if self.state() == PipelineState::Uninitialized
+            && previous_state != PipelineState::Uninitialized
+        {
+            self.initialize();
+        }
+        previous
+    }
+
+    /// Release the processors of a `Ready` pipeline until its next request.
+    ///
+    /// Returns the released version, whose in-flight executions finish on it, or `None` if
+    /// the pipeline was not ready.
+    pub fn evict(&self, reason: EvictionReason) -> Option<Arc<Pipeline>> {
+        let mut previous = None;
+        self.lifecycle.send_if_modified(|lifecycle| {
+            if lifecycle.state != PipelineState::Ready {
+                return false;
+            }
+            previous = lifecycle.pipeline.take();
+            lifecycle.state = PipelineState::Evicted;
+            lifecycle.generation += 1;
+            true
+        });
+
+        if previous.is_some() {
+            PipelineEvicted {
+                pipeline: self.name(),
+                reason: reason.as_str(),
+            }
+            .log();
+            metrics().pipeline_evicted(self.name(), reason.as_str());
+        }
+        previous
+    }
+
+    /// Reject requests until [`enable`](Self::enable) is called, abandoning any
+    /// initialization in progress.
+    ///
+    /// Returns the released version if the pipeline was ready; its in-flight executions
+    /// finish on it.
+    pub fn disable(&self) -> Option<Arc<Pipeline>> {
+        let mut previous_state = PipelineState::Disabled;
+        let mut previous = None;
+        let disabled = self.lifecycle.send_if_modified(|lifecycle| {
+            if matches!(
+                lifecycle.state,
+                PipelineState::Disabled | PipelineState::Draining
+            ) {
+                return false;
+            }
+            previous_state = lifecycle.state;
+            previous = lifecycle.pipeline.take();
+            lifecycle.state = PipelineState::Disabled;
+            lifecycle.generation += 1;
+            lifecycle.attempts = 0;
+            lifecycle.last_error = None;
+            true
+        });
+
+        if disabled {
+            PipelineDisabled {
+                pipeline: self.name(),
+                previous_state: previous_state.as_str(),
+            }
+            .log();
+            self.record_memory();
+        }
+        previous
+    }
+
+    /// Accept requests again after [`disable`](Self::disable).
+    ///
+    /// The pipeline returns to `Uninitialized`, and `startup: auto` pipelines initialize right
+    /// away. Returns `false` if the pipeline was not disabled.
+    pub fn enable(self: &Arc<Self>) -> bool {
+        let enabled = self.lifecycle.send_if_modified(|lifecycle| {
+            if lifecycle.state != PipelineState::Disabled {
+                return false;
+            }
+            lifecycle.state = PipelineState::Uninitialized;
+            lifecycle.generation += 1;
+            true
+        });
+
+        if enabled {
+            PipelineEnabled {
+                pipeline: self.name(),
+            }
+            .log();
+            if self.startup() == StartupMode::Auto {
+                self.initialize();
+            }
+        }
+        enabled
+    }
+
+    /// Mark the pipeline as removed, returning the version whose executions are draining.
+    ///
+    /// Requests still holding this pipeline are rejected as not found, and an initialization
+    /// in progress is abandoned.
+    pub fn drain(&self) -> Option<Arc<Pipeline>> {
+        let mut previous = None;
+        self.lifecycle.send_modify(|lifecycle| {
+            previous = lifecycle.pipeline.take();
+            lifecycle.state = PipelineState::Draining;
+            lifecycle.generation += 1;
+        });
+        self.record_memory();
+        previous
+    }
+
+    /// Apply `update` unless the lifecycle moved on to a newer generation.
+    ///
+    /// Returns `false` if `generation` was superseded by a reload, reset or removal.
+    fn update(&self, generation: u64, update: impl FnOnce(&mut Lifecycle)) -> bool {
+        let mut current = false;
+        self.lifecycle.send_if_modified(|lifecycle| {
+            current = lifecycle.generation == generation;
+            if current {
+                update(lifecycle);
+            }
+            current
+        });
+        current
+    }
+
+    /// Record the estimated memory of the ready version, or 0 when there is none.
+    fn record_memory(&self) {
+        metrics().pipeline_memory(self.name(), self.memory_footprint());
+    }
+
+    /// Initialize the pipeline, retrying with backoff until it is ready or out of attempts.
+    ///
+    /// `restoring` marks the initialization of an evicted pipeline. Stops as soon as
+    /// `generation` is superseded.
+    async fn run_initialization(self: Arc<Self>, generation: u64, restoring: bool) {
+        let config = self.config();
+        let initialization = config.initialization;
+        let max_attempts = initialization.max_attempts();
+        let started_at = Instant::now();
+
+        for attempt in 1..=max_attempts {
+            let current = self.update(generation, |lifecycle| {
+                lifecycle.state = PipelineState::Initializing;
+                lifecycle.attempts = attempt;
+            });
+            if !current {
+                return;
+            }
+            PipelineInitializationStarted {
+                pipeline: self.name(),
+                attempt,
+                max_attempts,
+            }
+            .log();
+
+            let error = match build(config.clone()).await {
+                Ok(pipeline) => {
+                    let current = self.update(generation, |lifecycle| {
+                        lifecycle.state = PipelineState::Ready;
+                        lifecycle.pipeline = Some(Arc::new(pipeline));
+                        lifecycle.last_error = None;
+                    });
+                    if !current {
+                        return;
+                    }
+                    PipelineInitialized {
+                        pipeline: self.name(),
+                        attempts: attempt,
+                        duration: started_at.elapsed(),
+                    }
+                    .log();
+                    if restoring {
+                        PipelineRestored {
+                            pipeline: self.name(),
+                            duration: started_at.elapsed(),
+                        }
+                        .log();
+                        metrics().pipeline_restored(self.name());
+                    }
+                    self.record_memory();
+                    return;
+                }
+                Err(error) => error,
+            };
+
+            if attempt == max_attempts {
+                let current = self.update(generation, |lifecycle| {
+                    lifecycle.state = PipelineState::PermanentlyFailed;
+                    lifecycle.last_error = Some(error.clone());
+                });
+                if current {
+                    PipelinePermanentlyFailed {
+                        pipeline: self.name(),
+                        attempts: attempt,
+                        error: &error,
+                    }
+                    .log();
+                }
+                return;
+            }
+
+            let retry_in = initialization.retry_delay(attempt);
+            let current = self.update(generation, |lifecycle| {
+                lifecycle.state = PipelineState::Failed;
+                lifecycle.last_error = Some(error.clone());
+            });
+            if !current {
+                return;
+            }
+            PipelineInitializationFailed {
+                pipeline: self.name(),
+                attempt,
+                max_attempts,
+                retry_in,
+                error: &error,
+            }
+            .log();
+            tokio::time::sleep(retry_in).await;
+        }
+    }
+}
+
+/// Build a pipeline version on the blocking thread pool.
+///
+/// Processor instantiation (notably WASM compilation) is CPU-bound, so it must not hold up
+/// the async workers serving other pipelines.
+pub(crate) async fn build(config: Arc<PipelineConfig>) -> Result<Pipeline, PipelineError> {
+    let name = config.name.clone();
+    tokio::task::spawn_blocking(move || Pipeline::from_config(&config))
+        .await
+        .unwrap_or_else(|join_error| {
+            Err(PipelineError::InitializationFailed {
+                name,
+                reason: format!("initialization task failed: {}", join_error),
+            })
+        })
+}
+
+impl fmt::Debug for ManagedPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManagedPipeline")
+            .field("name", &self.name())
+            .field("startup", &self.startup())
+            .field("state", &self.state())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PipelinesConfig;
+    use std::future::Future;
+    use std::task::Poll;
+
+    fn managed(yaml: &str) -> Arc<ManagedPipeline> {
+        let mut config = PipelinesConfig::from_yaml(yaml).unwrap();
+        Arc::new(ManagedPipeline::new(config.pipelines.remove(0)))
+    }
+
+    fn valid_pipeline() -> Arc<ManagedPipeline> {
+        managed(
+            r#"
+pipelines:
+  - name: upper
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        )
+    }
+
+    fn broken_pipeline() -> Arc<ManagedPipeline> {
+        managed(
+            r#"
+pipelines:
+  - name: broken
+    startup: on-demand
+    initialization:
+      max_retries: 2
+      retry_backoff: exponential
+      retry_delay_ms: 20
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_on_demand_pipeline_initializes_on_first_request() {
+        let managed = valid_pipeline();
+        assert_eq!(managed.state(), PipelineState::Uninitialized);
+        assert!(managed.ready_pipeline().is_none());
+
+        let pipeline = managed.pipeline().await.unwrap();
+        assert_eq!(pipeline.name(), "upper");
+        assert_eq!(managed.state(), PipelineState::Ready);
+        assert_eq!(managed.attempts(), 1);
+        assert!(Arc::ptr_eq(&pipeline, &managed.ready_pipeline().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_trigger_single_initialization() {
+        let managed = valid_pipeline();
+
+        let requests: Vec<_> = (0..10)
+            .map(|_| {
+                let managed = managed.clone();
+                tokio::spawn(async move { managed.pipeline().await })
+            })
+            .collect();
+
+        let mut pipelines = Vec::new();
+        for request in requests {
+            pipelines.push(request.await.unwrap().unwrap());
+        }
+
+        assert_eq!(managed.attempts(), 1);
+        assert!(pipelines.iter().all(|p| Arc::ptr_eq(p, &pipelines[0])));
+        assert!(!managed.initialize());
+    }
+
+    #[tokio::test]
+    async fn test_failed_initialization_retries_with_backoff() {
+        let managed = broken_pipeline();
+        let started = Instant::now();
+
+        let error = managed.pipeline().await.unwrap_err();
+
+        // Three attempts separated by 20ms and 40ms backoff delays
+        assert!(started.elapsed() >= Duration::from_millis(60));
+        assert!(matches!(
+            error,
+            PipelineError::PermanentlyFailed { ref name, attempts: 3, .. } if name == "broken"
+        ));
+        assert!(error.to_string().contains("does_not_exist"));
+        assert_eq!(managed.state(), PipelineState::PermanentlyFailed);
+        assert!(matches!(
+            managed.last_error(),
+            Some(PipelineError::InitializationFailed { .. })
+        ));
+
+        // Permanently failed pipelines reject requests without initializing again
+        assert!(managed.pipeline().await.is_err());
+        assert_eq!(managed.attempts(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reset_returns_pipeline_to_uninitialized() {
+        let managed = broken_pipeline();
+        assert!(!managed.reset());
+
+        managed.pipeline().await.unwrap_err();
+        assert!(managed.reset());
+        assert_eq!(managed.state(), PipelineState::Uninitialized);
+        assert_eq!(managed.attempts(), 0);
+        assert!(managed.last_error().is_none());
+
+        assert!(managed.initialize());
+        assert_ne!(managed.state(), PipelineState::Uninitialized);
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_within_gives_up_while_retrying() {
+        let managed = managed(
+            r#"
+pipelines:
+  - name: broken
+    initialization:
+      max_retries: 1
+      retry_delay_ms: 10000
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#,
+        );
+
+        let error = managed
+            .pipeline_within(Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            PipelineError::NotReady { ref name, state: PipelineState::Failed } if name == "broken"
+        ));
+        assert_eq!(error.to_string(), "Pipeline 'broken' is not ready (failed)");
+
+        let ready = valid_pipeline()
+            .pipeline_within(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(ready.name(), "upper");
+    }
+
+    #[tokio::test]
+    async fn test_replace_switches_to_new_version() {
+        let managed = valid_pipeline();
+        let first = managed.pipeline().await.unwrap();
+
+        let config = managed.config();
+        let version = build(config.clone()).await.unwrap();
+        let previous = managed.replace(config.clone(), Some(version)).unwrap();
+        assert!(Arc::ptr_eq(&previous, &first));
+        assert_eq!(managed.state(), PipelineState::Ready);
+        assert!(!Arc::ptr_eq(&managed.pipeline().await.unwrap(), &first));
+
+        // Without a version, an initialized pipeline initializes again with the new config
+        assert!(managed.replace(config, None).is_some());
+        assert_ne!(managed.state(), PipelineState::Uninitialized);
+        assert_eq!(managed.pipeline().await.unwrap().name(), "upper");
+    }
+
+    #[tokio::test]
+    async fn test_replace_supersedes_running_initialization() {
+        let managed = broken_pipeline();
+        assert!(managed.initialize());
+
+        let mut fixed = PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: broken
+    startup: on-demand
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        )
+        .unwrap();
+        assert!(managed
+            .replace(Arc::new(fixed.pipelines.remove(0)), None)
+            .is_none());
+
+        // The superseded retries of the broken configuration no longer affect the pipeline
+        let pipeline = managed.pipeline().await.unwrap();
+        assert_eq!(pipeline.name(), "broken");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(managed.state(), PipelineState::Ready);
+        assert_eq!(managed.attempts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_drain_rejects_new_requests() {
+        let managed = valid_pipeline();
+        let pipeline = managed.pipeline().await.unwrap();
+
+        let draining = managed.drain().unwrap();
+        assert!(Arc::ptr_eq(&draining, &pipeline));
+        assert_eq!(managed.state(), PipelineState::Draining);
+        assert!(matches!(
+            managed.pipeline().await,
+            Err(PipelineError::NotFound { .. })
+        ));
+        assert!(!managed.initialize());
+    }
+
+    #[tokio::test]
+    async fn test_evict_releases_until_next_request() {
+        let managed = valid_pipeline();
+        assert!(managed.evict(EvictionReason::Admin).is_none());
+
+        let first = managed.pipeline().await.unwrap();
+        let released = managed.evict(EvictionReason::Admin).unwrap();
+        assert!(Arc::ptr_eq(&released, &first));
+        assert_eq!(managed.state(), PipelineState::Evicted);
+        assert!(managed.ready_pipeline().is_none());
+
+        let second = managed.pipeline().await.unwrap();
+        assert!(!Arc::ptr_eq(&second, &first));
+        assert_eq!(managed.state(), PipelineState::Ready);
+    }
+
+    #[tokio::test]
+    async fn test_waiting_request_reinitializes_pipeline_released_before_it_woke() {
+        for release in [
+            |managed: &ManagedPipeline| assert!(managed.evict(EvictionReason::Admin).is_some()),
+            |managed: &ManagedPipeline| assert!(managed.reset()),
+        ] {
+            let managed = valid_pipeline();
+            let waiting = managed.pipeline();
+            tokio::pin!(waiting);
+            // Start the initialization and wait behind it
+            let first_poll = std::future::poll_fn(|cx| Poll::Ready(waiting.as_mut().poll(cx)));
+            assert!(first_poll.await.is_pending());
+
+            // The pipeline becomes ready and is released before the request sees it
+            while managed.state() != PipelineState::Ready {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            release(&managed);
+
+            let pipeline = tokio::time::timeout(Duration::from_secs(5), waiting)
+                .await
+                .expect("request re-initialized the pipeline")
+                .unwrap();
+            assert!(Arc::ptr_eq(&pipeline, &managed.ready_pipeline().unwrap()));
+            assert_eq!(managed.state(), PipelineState::Ready);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disable_rejects_requests_until_enabled() {
+        let managed = broken_pipeline();
+        assert!(managed.initialize());
+        assert!(managed.disable().is_none());
+        assert_eq!(managed.state(), PipelineState::Disabled);
+        assert!(matches!(
+            managed.pipeline().await,
+            Err(PipelineError::Disabled { ref name }) if name == "broken"
+        ));
+
+        // The abandoned initialization no longer changes the state
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(managed.state(), PipelineState::Disabled);
+
+        // Reloads keep disabled pipelines disabled
+        let config = managed.config();
+        assert!(managed.replace(config, None).is_none());
+        assert_eq!(managed.state(), PipelineState::Disabled);
+
+        assert!(managed.enable());
+        assert!(!managed.enable());
+        assert_eq!(managed.state(), PipelineState::Uninitialized);
+    }
+
+    #[test]
+    fn test_state_names() {
+        assert_eq!(PipelineState::Ready.to_string(), "ready");
+        assert_eq!(
+            PipelineState::PermanentlyFailed.as_str(),
+            "permanently_failed"
+        );
+        assert_eq!(PipelineState::Draining.as_str(), "draining");
+        assert!(PipelineState::Evicted.is_idle());
+        assert!(!PipelineState::Uninitialized.is_idle());
+    }
+}
@@ -0,0 +1,811 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{
+    validate_pipelines, PipelineConfig, PipelinesConfig, RuntimeBuilder, StartupMode, Strategy,
+};
+use crate::engine::{ExecutionPlan, ExecutionReport, NoopObserver};
+use crate::errors::{ExecutionError, FailureStrategy, PipelineError};
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+use crate::server::hot_reload::{Drain, ReloadReport};
+use crate::server::pipeline_lifecycle::build;
+use crate::server::shutdown::cancel_when;
+use crate::server::{ManagedPipeline, PipelineState, Webhook};
+use crate::traits::{DagExecutor, ExecutionObserver};
+
+/// A named, compiled pipeline ready for repeated execution.
+///
+/// A `Pipeline` bundles everything needed to run one DAG: the shared execution plan, the
+/// executor built for the pipeline's strategy, and the pipeline's failure strategy. It is
+/// shared as `Arc<Pipeline>` so concurrent requests reuse the same plan and executor, and a
+/// replaced version lives on until its last in-flight execution releases it.
+pub struct Pipeline {
+    name: String,
+    strategy: Strategy,
+    failure_strategy: FailureStrategy,
+    plan: Arc<ExecutionPlan>,
+    executor: Box<dyn DagExecutor>,
+    cancellation: CancellationToken,
+    webhook: Option<Webhook>,
+}
+
+impl Pipeline {
+    /// Build a pipeline from its named configuration.
+    ///
+    /// Instantiates the processors, compiles the execution plan and creates the executor.
+    pub fn from_config(cfg: &PipelineConfig) -> Result<Self, PipelineError> {
+        let (plan, executor, failure_strategy) =
+            RuntimeBuilder::compile(&cfg.config).map_err(|reason| {
+                PipelineError::InitializationFailed {
+                    name: cfg.name.clone(),
+                    reason,
+                }
+            })?;
+
+        Ok(Self {
+            name: cfg.name.clone(),
+            strategy: cfg.config.strategy,
+            failure_strategy,
+            plan,
+            executor,
+            cancellation: CancellationToken::new(),
+            webhook: cfg
+                .webhook
+                .clone()
+                .map(|webhook| Webhook::new(&cfg.name, webhook)),
+        })
+    }
+
+    /// Build a `work_queue` pipeline running `processor` alone, without a webhook.
+    #[cfg(test)]
+    pub(crate) fn with_processor(name: &str, processor: Arc<dyn crate::traits::Processor>) -> Self {
+        let mut processors = crate::config::ProcessorMap::new();
+        processors.insert(name.to_string(), processor);
+        let plan = ExecutionPlan::compile(
+            processors,
+            crate::config::DependencyGraph::from(HashMap::from([(name.to_string(), vec![])])),
+            crate::config::EntryPoints(vec![name.to_string()]),
+        )
+        .expect("a single processor always compiles");
+        Self {
+            name: name.to_string(),
+            strategy: Strategy::WorkQueue,
+            failure_strategy: FailureStrategy::FailFast,
+            plan: Arc::new(plan),
+            executor: Box::new(crate::engine::WorkQueueExecutor::new(2)),
+            cancellation: CancellationToken::new(),
+            webhook: None,
+        }
+    }
+
+    /// Name used to route requests to this pipeline.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Execution strategy of this pipeline.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Failure strategy applied to every execution of this pipeline.
+    pub fn failure_strategy(&self) -> FailureStrategy {
+        self.failure_strategy
+    }
+
+    /// The compiled execution plan shared by every execution.
+    pub fn plan(&self) -> &Arc<ExecutionPlan> {
+        &self.plan
+    }
+
+    /// Webhook notified of finished executions, if configured.
+    pub fn webhook(&self) -> Option<&Webhook> {
+        self.webhook.as_ref()
+    }
+
+    /// Execute the pipeline once with the given input.
+    ///
+    /// The pipeline's webhook is notified once the execution finished.
+    pub async fn execute(
+        &self,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        self.execute_observed(input, pipeline_metadata, Arc::new(NoopObserver))
+            .await
+    }
+
+    /// Execute the pipeline once, reporting live progress to `observer`.
+    ///
+    /// The pipeline's webhook is notified once the execution finished.
+    pub async fn execute_observed(
+        &self,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        observer: Arc<dyn ExecutionObserver>,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        self.execute_cancellable(input, pipeline_metadata, observer, CancellationToken::new())
+            .await
+    }
+
+    /// Execute the pipeline once like [`execute_observed`](Self::execute_observed), until
+    /// `cancellation` is cancelled.
+    ///
+    /// A cancelled execution, or one of a retired version, returns once its processors
+    /// stopped and fails with `ExecutionError::Cancelled`.
+    pub async fn execute_cancellable(
+        &self,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        observer: Arc<dyn ExecutionObserver>,
+        cancellation: CancellationToken,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        let cancellation = cancellation.child_token();
+        let execution = self.executor.execute_plan_cancellable(
+            self.plan.clone(),
+            input,
+            pipeline_metadata,
+            self.failure_strategy,
+            observer,
+            cancellation.clone(),
+        );
+        let result = cancel_when(
+            execution,
+            &cancellation,
+            self.cancellation.cancelled(),
+            || format!("pipeline '{}' version was retired", self.name),
+        )
+        .await;
+        self.notify(&result);
+        result
+    }
+
+    fn notify(&self, result: &Result<ExecutionReport, ExecutionError>) {
+        if let Some(webhook) = &self.webhook {
+            webhook.notify(result);
+        }
+    }
+
+    /// Estimated memory held by the processors of this version, in bytes.
+    pub fn memory_footprint(&self) -> usize {
+        self.plan
+            .processors()
+            .0
+            .values()
+            .map(|processor| processor.memory_footprint())
+            .sum()
+    }
+
+    /// Release processor memory that is rebuilt on demand, such as compiled WASM modules.
+    ///
+    /// The pipeline stays usable; returns the bytes released.
+    pub fn release_memory(&self) -> usize {
+        self.plan
+            .processors()
+            .0
+            .values()
+            .map(|processor| processor.release_memory())
+            .sum()
+    }
+
+    /// Abort every in-flight and future execution of this pipeline version.
+    ///
+    /// Cancelled executions fail with `ExecutionError::Cancelled`; used to retire a replaced
+    /// version whose executions outlive the drain timeout.
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether this pipeline version has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
+
+impl std::fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("name", &self.name)
+            .field("strategy", &self.strategy)
+            .field("failure_strategy", &self.failure_strategy)
+            .field("processor_count", &self.plan.processor_count())
+            .finish()
+    }
+}
+
+/// Thread-safe registry of the named pipelines hosted by one process.
+///
+/// Every pipeline is registered together with its lifecycle (see [`ManagedPipeline`]) and is
+/// initialized either by [`PipelineRegistry::start`] (`startup: auto`) or by its first
+/// request (`startup: on-demand`). Pipelines can be registered and removed at runtime;
+/// lookups hand out `Arc`s so a removed pipeline stays alive until its in-flight executions
+/// finish.
+///
+/// # Examples
+///
+/// ```
+/// use the_dagwood::config::PipelinesConfig;
+/// use the_dagwood::server::{PipelineRegistry, PipelineState};
+///
+/// let config = PipelinesConfig::from_yaml(r#"
+/// pipelines:
+///   - name: upper
+///     startup: on-demand
+///     strategy: work_queue
+///     processors:
+///       - id: upper
+///         type: local
+///         processor: change_text_case_upper
+/// "#).unwrap();
+///
+/// let registry = PipelineRegistry::from_config(config).unwrap();
+/// assert_eq!(registry.names(), vec!["upper"]);
+/// assert_eq!(registry.get("upper").unwrap().state(), PipelineState::Uninitialized);
+/// assert!(registry.get("missing").is_none());
+/// ```
+#[derive(Debug, Default)]
+pub struct PipelineRegistry {
+    pipelines: RwLock<HashMap<String, Arc<ManagedPipeline>>>,
+    /// Serializes reloads so two reloads never interleave their build and switch
+    reload_lock: tokio::sync::Mutex<()>,
+}
+
+impl PipelineRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate a multi-pipeline configuration and register every pipeline in it.
+    ///
+    /// Pipelines are registered `Uninitialized`; call [`PipelineRegistry::start`] to
+    /// initialize the `startup: auto` pipelines.
+    pub fn from_config(cfg: PipelinesConfig) -> Result<Self, PipelineError> {
+        validate_pipelines(&cfg).map_err(|errors| PipelineError::InvalidConfig { errors })?;
+
+        let registry = Self::new();
+        for pipeline_config in cfg.pipelines {
+            registry.register(ManagedPipeline::new(pipeline_config))?;
+        }
+        Ok(registry)
+    }
+
+    /// Initialize every `startup: auto` pipeline and wait until they are ready.
+    ///
+    /// Pipelines initialize concurrently, each with its own retries. Returns the first
+    /// pipeline that failed permanently or timed out; `startup: on-demand` pipelines stay
+    /// uninitialized.
+    pub async fn start(&self) -> Result<(), PipelineError> {
+        let mut initializations = JoinSet::new();
+        for pipeline in self.pipelines_with_startup(StartupMode::Auto) {
+            initializations.spawn(async move { pipeline.pipeline().await.map(|_| ()) });
+        }
+
+        let mut first_error = None;
+        while let Some(result) = initializations.join_next().await {
+            let result = result.unwrap_or_else(|join_error| {
+                Err(PipelineError::InitializationFailed {
+                    name: String::new(),
+                    reason: join_error.to_string(),
+                })
+            });
+            if let Err(error) = result {
+                first_error.get_or_insert(error);
+            }
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Add a pipeline, rejecting names that are already registered.
+    pub fn register(
+        &self,
+        pipeline: ManagedPipeline,
+    ) -> Result<Arc<ManagedPipeline>, PipelineError> {
+        let mut pipelines = self
+            .pipelines
+            .write()
+            .expect("pipeline registry lock poisoned");
+        if pipelines.contains_key(pipeline.name()) {
+            return Err(PipelineError::DuplicateName {
+                name: pipeline.name().to_string(),
+            });
+        }
+
+        let pipeline = Arc::new(pipeline);
+        pipelines.insert(pipeline.name().to_string(), pipeline.clone());
+        Ok(pipeline)
+    }
+
+    /// Remove a pipeline, returning it if it was registered.
+    pub fn remove(&self, name: &str) -> Option<Arc<ManagedPipeline>> {
+        self.pipelines
+            .write()
+            .expect("pipeline registry lock poisoned")
+            .remove(name)
+    }
+
+    /// Remove every pipeline, releasing each version once its last execution finishes.
+    ///
+    /// Used at shutdown; returns the number of pipelines released.
+    pub fn release_all(&self) -> usize {
+        let released: Vec<Arc<ManagedPipeline>> = self
+            .pipelines
+            .write()
+            .expect("pipeline registry lock poisoned")
+            .drain()
+            .map(|(_, pipeline)| pipeline)
+            .collect();
+        for pipeline in &released {
+            pipeline.drain();
+        }
+        released.len()
+    }
+
+    /// Look up a pipeline by name.
+    pub fn get(&self, name: &str) -> Option<Arc<ManagedPipeline>> {
+        self.pipelines
+            .read()
+            .expect("pipeline registry lock poisoned")
+            .get(name)
+            .cloned()
+    }
+
+    /// Whether a pipeline with the given name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.pipelines
+            .read()
+            .expect("pipeline registry lock poisoned")
+            .contains_key(name)
+    }
+
+    /// Names of all registered pipelines, sorted.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .pipelines
+            .read()
+            .expect("pipeline registry lock poisoned")
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Lifecycle state of every registered pipeline, sorted by name.
+    pub fn states(&self) -> Vec<(String, PipelineState)> {
+        let mut states: Vec<(String, PipelineState)> = self
+            .pipelines
+            .read()
+            .expect("pipeline registry lock poisoned")
+            .iter()
+            .map(|(name, pipeline)| (name.clone(), pipeline.state()))
+            .collect();
+        states.sort_by(|a, b| a.0.cmp(&b.0));
+        states
+    }
+
+    /// Number of registered pipelines.
+    pub fn len(&self) -> usize {
+        self.pipelines
+            .read()
+            .expect("pipeline registry lock poisoned")
+            .len()
+    }
+
+    /// Whether no pipelines are registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Apply a new multi-pipeline configuration with drain-and-switch.
+    ///
+    /// New and changed pipelines that need a running version are built in the background
+    /// first; if validation or any build fails, the error is returned and nothing changes.
+    /// Otherwise new requests switch to the new versions at once, removed pipelines stop
+    /// accepting requests, and the versions still referenced by in-flight executions are
+    /// returned as [`Drain`]s.
+    pub async fn reload(&self, cfg: PipelinesConfig) -> Result<ReloadReport, PipelineError> {
+        self.apply(cfg, None).await
+    }
+
+    /// Like [`PipelineRegistry::reload`], but rebuilds `name` even if its configuration did
+    /// not change.
+    ///
+    /// Fails with [`PipelineError::NotFound`] if `name` is neither registered nor configured.
+    pub async fn reload_pipeline(
+        &self,
+        cfg: PipelinesConfig,
+        name: &str,
+    ) -> Result<ReloadReport, PipelineError> {
+        self.apply(cfg, Some(name)).await
+    }
+
+    async fn apply(
+        &self,
+        cfg: PipelinesConfig,
+        force: Option<&str>,
+    ) -> Result<ReloadReport, PipelineError> {
+        let _reload = self.reload_lock.lock().await;
+        validate_pipelines(&cfg).map_err(|errors| PipelineError::InvalidConfig { errors })?;
+        if let Some(name) = force {
+            if !self.contains(name) && !cfg.pipelines.iter().any(|p| p.name == name) {
+                return Err(PipelineError::NotFound {
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        // Classify every configured pipeline against the running registry
+        let mut report = ReloadReport::default();
+        let mut changes = Vec::new();
+        let mut configured = Vec::new();
+        for pipeline_config in cfg.pipelines {
+            let config = Arc::new(pipeline_config);
+            configured.push(config.name.clone());
+            match self.get(&config.name) {
+                None => changes.push((config, None)),
+                Some(current)
+                    if force == Some(config.name.as_str()) || *current.config() != *config =>
+                {
+                    changes.push((config, Some(current)))
+                }
+                Some(_) => report.unchanged.push(config.name.clone()),
+            }
+        }
+
+        // Build the versions that need to be running before anything is switched
+        let mut builds = JoinSet::new();
+        for (index, (config, current)) in changes.iter().enumerate() {
+            let needs_version = match current.as_ref().map(|current| current.state()) {
+                Some(state) if state.is_idle() => false,
+                Some(PipelineState::Uninitialized) | None => config.startup == StartupMode::Auto,
+                Some(_) => true,
+            };
+            if needs_version {
+                let config = config.clone();
+                builds.spawn(async move { (index, build(config).await) });
+            }
+        }
+        let mut versions: Vec<Option<Pipeline>> = changes.iter().map(|_| None).collect();
+        while let Some(result) = builds.join_next().await {
+            let (index, version) =
+                result.map_err(|join_error| PipelineError::InitializationFailed {
+                    name: String::new(),
+                    reason: join_error.to_string(),
+                })?;
+            versions[index] = Some(version?);
+        }
+
+        // Switch: every version is built, so the reload can no longer fail
+        for ((config, current), version) in changes.into_iter().zip(versions) {
+            match current {
+                Some(current) => {
+                    let drain_timeout = current.config().hot_reload.drain_timeout();
+                    if let Some(previous) = current.replace(config.clone(), version) {
+                        report.drains.push(Drain::new(previous, drain_timeout));
+                    }
+                    report.modified.push(config.name.clone());
+                }
+                None => {
+                    let pipeline = Arc::new(ManagedPipeline::with_shared_config(config.clone()));
+                    if version.is_some() {
+                        pipeline.replace(config.clone(), version);
+                    }
+                    self.pipelines
+                        .write()
+                        .expect("pipeline registry lock poisoned")
+                        .insert(config.name.clone(), pipeline);
+                    report.added.push(config.name.clone());
+                }
+            }
+        }
+        for name in self.names() {
+            if configured.contains(&name) {
+                continue;
+            }
+            if let Some(removed) = self.remove(&name) {
+                let drain_timeout = removed.config().hot_reload.drain_timeout();
+                if let Some(previous) = removed.drain() {
+                    report.drains.push(Drain::new(previous, drain_timeout));
+                }
+                report.removed.push(name);
+            }
+        }
+
+        report.added.sort();
+        report.modified.sort();
+        report.unchanged.sort();
+        Ok(report)
+    }
+
+    fn pipelines_with_startup(&self, startup: StartupMode) -> Vec<Arc<ManagedPipeline>> {
+        self.pipelines
+            .read()
+            .expect("pipeline registry lock poisoned")
+            .values()
+            .filter(|pipeline| pipeline.startup() == startup)
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::consts::DEFAULT_PIPELINE_NAME;
+
+    fn multi_pipeline_config() -> PipelinesConfig {
+        PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: reverse
+    startup: on-demand
+    strategy: level
+    failure_strategy: continue_on_error
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_start_initializes_auto_pipelines_only() {
+        let registry = PipelineRegistry::from_config(multi_pipeline_config()).unwrap();
+
+        assert_eq!(registry.len(), 2);
+        assert_eq!(registry.names(), vec!["reverse", "upper"]);
+        assert!(registry
+            .states()
+            .iter()
+            .all(|(_, state)| *state == PipelineState::Uninitialized));
+
+        registry.start().await.unwrap();
+        assert_eq!(
+            registry.states(),
+            vec![
+                ("reverse".to_string(), PipelineState::Uninitialized),
+                ("upper".to_string(), PipelineState::Ready),
+            ]
+        );
+
+        let upper = registry.get("upper").unwrap().ready_pipeline().unwrap();
+        assert_eq!(upper.strategy(), Strategy::WorkQueue);
+
+        let reverse = registry.get("reverse").unwrap().pipeline().await.unwrap();
+        assert_eq!(reverse.strategy(), Strategy::Level);
+        assert_eq!(reverse.failure_strategy(), FailureStrategy::ContinueOnError);
+    }
+
+    #[test]
+    fn test_from_config_wraps_legacy_config_as_default() {
+        let config = PipelinesConfig::from_yaml(
+            r#"
+strategy: reactive
+processors:
+  - id: upper
+    type: local
+    processor: change_text_case_upper
+"#,
+        )
+        .unwrap();
+
+        let registry = PipelineRegistry::from_config(config).unwrap();
+        assert_eq!(registry.names(), vec![DEFAULT_PIPELINE_NAME]);
+        assert_eq!(
+            registry.get(DEFAULT_PIPELINE_NAME).unwrap().startup(),
+            StartupMode::Auto
+        );
+    }
+
+    #[test]
+    fn test_from_config_rejects_duplicate_names() {
+        let mut config = multi_pipeline_config();
+        config.pipelines[1].name = "upper".to_string();
+
+        let error = PipelineRegistry::from_config(config).unwrap_err();
+        assert!(matches!(error, PipelineError::InvalidConfig { .. }));
+        assert!(error
+            .to_string()
+            .contains("Duplicate pipeline name: 'upper'"));
+    }
+
+    #[tokio::test]
+    async fn test_start_reports_permanently_failed_auto_pipeline() {
+        let config = PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: broken
+    initialization:
+      max_retries: 1
+      retry_delay_ms: 1
+    strategy: work_queue
+    processors:
+      - id: missing
+        type: local
+        processor: does_not_exist
+"#,
+        )
+        .unwrap();
+
+        let registry = PipelineRegistry::from_config(config).unwrap();
+        let error = registry.start().await.unwrap_err();
+        assert!(matches!(
+            error,
+            PipelineError::PermanentlyFailed { ref name, attempts: 2, .. } if name == "broken"
+        ));
+        assert_eq!(
+            registry.get("broken").unwrap().state(),
+            PipelineState::PermanentlyFailed
+        );
+    }
+
+    #[test]
+    fn test_register_and_remove() {
+        let mut config = multi_pipeline_config();
+        let registry = PipelineRegistry::new();
+        assert!(registry.is_empty());
+
+        let upper = config.pipelines.remove(0);
+        let duplicate = PipelineConfig {
+            name: upper.name.clone(),
+            startup: upper.startup,
+            initialization: upper.initialization,
+            hot_reload: upper.hot_reload,
+            webhook: None,
+            quota: None,
+            config: config.pipelines.remove(0).config,
+        };
+        registry.register(ManagedPipeline::new(upper)).unwrap();
+        assert!(matches!(
+            registry.register(ManagedPipeline::new(duplicate)),
+            Err(PipelineError::DuplicateName { .. })
+        ));
+
+        let removed = registry.remove("upper").unwrap();
+        assert_eq!(removed.name(), "upper");
+        assert!(!registry.contains("upper"));
+        assert!(registry.remove("upper").is_none());
+    }
+
+    /// `multi_pipeline_config` with `upper` switched to reactive, `reverse` removed and a
+    /// new `lower` pipeline
+    fn reloaded_config() -> PipelinesConfig {
+        PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: upper
+    strategy: reactive
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: lower
+    strategy: work_queue
+    processors:
+      - id: lower
+        type: local
+        processor: change_text_case_lower
+"#,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reload_adds_modifies_and_removes() {
+        let registry = PipelineRegistry::from_config(multi_pipeline_config()).unwrap();
+        registry.start().await.unwrap();
+        let upper = registry.get("upper").unwrap();
+
+        let report = registry.reload(reloaded_config()).await.unwrap();
+        assert_eq!(report.added, vec!["lower"]);
+        assert_eq!(report.removed, vec!["reverse"]);
+        assert_eq!(report.modified, vec!["upper"]);
+        assert!(report.unchanged.is_empty());
+        // Nothing held the replaced version, so it is gone already
+        assert!(report.drains.iter().all(|drain| drain.is_drained()));
+
+        // Managed pipelines are kept across reloads; new versions are built up front
+        assert!(Arc::ptr_eq(&upper, &registry.get("upper").unwrap()));
+        assert_eq!(
+            upper.ready_pipeline().unwrap().strategy(),
+            Strategy::Reactive
+        );
+        assert_eq!(registry.get("lower").unwrap().state(), PipelineState::Ready);
+        assert!(!registry.contains("reverse"));
+
+        let report = registry.reload(reloaded_config()).await.unwrap();
+        assert!(!report.has_changes());
+        assert_eq!(report.unchanged, vec!["lower", "upper"]);
+    }
+
+    #[tokio::test]
+    async fn test_reload_rejects_invalid_config_without_changes() {
+        let registry = PipelineRegistry::from_config(multi_pipeline_config()).unwrap();
+        registry.start().await.unwrap();
+        let running = registry.get("upper").unwrap().ready_pipeline().unwrap();
+
+        let mut duplicate = multi_pipeline_config();
+        duplicate.pipelines[1].name = "upper".to_string();
+        assert!(matches!(
+            registry.reload(duplicate).await,
+            Err(PipelineError::InvalidConfig { .. })
+        ));
+
+        let mut unbuildable = multi_pipeline_config();
+        unbuildable.pipelines[0].config.processors[0].processor =
+            Some("does_not_exist".to_string());
+        unbuildable.pipelines[1].name = "added".to_string();
+        assert!(matches!(
+            registry.reload(unbuildable).await,
+            Err(PipelineError::InitializationFailed { .. })
+        ));
+
+        assert_eq!(registry.names(), vec!["reverse", "upper"]);
+        let current = registry.get("upper").unwrap().ready_pipeline().unwrap();
+        assert!(Arc::ptr_eq(&running, &current));
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_in_flight_versions_until_released() {
+        let registry = PipelineRegistry::from_config(multi_pipeline_config()).unwrap();
+        registry.start().await.unwrap();
+        let in_flight = registry.get("upper").unwrap().ready_pipeline().unwrap();
+        let removed = registry.get("reverse").unwrap().pipeline().await.unwrap();
+
+        let mut config = multi_pipeline_config();
+        config.pipelines.remove(1);
+        config.pipelines[0].config.processors[0].processor =
+            Some("change_text_case_lower".to_string());
+        let report = registry.reload(config).await.unwrap();
+        assert_eq!(report.drains.len(), 2);
+        assert!(report.drains.iter().all(|drain| !drain.is_drained()));
+
+        let request = || ProcessorRequest {
+            payload: b"MiXed".to_vec(),
+        };
+        let old = in_flight.execute(request(), PipelineMetadata::new()).await;
+        assert_eq!(old.unwrap().final_output, b"MIXED");
+        let new = registry.get("upper").unwrap().pipeline().await.unwrap();
+        let new = new.execute(request(), PipelineMetadata::new()).await;
+        assert_eq!(new.unwrap().final_output, b"mixed");
+        let removed_run = removed.execute(request(), PipelineMetadata::new()).await;
+        assert_eq!(removed_run.unwrap().final_output, b"deXiM");
+
+        drop(in_flight);
+        drop(removed);
+        assert!(report.drains.iter().all(|drain| drain.is_drained()));
+    }
+
+    #[tokio::test]
+    async fn test_reload_leaves_unused_on_demand_pipelines_unbuilt() {
+        let registry = PipelineRegistry::from_config(multi_pipeline_config()).unwrap();
+
+        let mut config = multi_pipeline_config();
+        config.pipelines[1].config.failure_strategy = FailureStrategy::FailFast;
+        let report = registry.reload(config).await.unwrap();
+        assert_eq!(report.modified, vec!["reverse"]);
+        assert_eq!(
+            registry.get("reverse").unwrap().state(),
+            PipelineState::Uninitialized
+        );
+
+        let reverse = registry.get("reverse").unwrap().pipeline().await.unwrap();
+        assert_eq!(reverse.failure_strategy(), FailureStrategy::FailFast);
+    }
+}
@@ -0,0 +1,353 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::engine::tasks::cancelled;
+use crate::engine::{ExecutionReport, NoopObserver};
+use crate::errors::PipelineError;
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+use crate::server::{
+    Access, AuthClient, Authenticator, Credential, HotReloader, InFlightRuns, JobManager, Pipeline,
+    PipelineRegistry, Quota, QuotaPermit, QuotaScope, QuotaUsage, Quotas,
+};
+use crate::traits::ExecutionObserver;
+
+/// Routes requests to the pipelines of a [`PipelineRegistry`] by name.
+///
+/// The router is protocol-agnostic: HTTP, gRPC and Unix socket receivers extract a pipeline
+/// name from the incoming request and hand it to the router together with the payload.
+/// Routing is a plain name lookup; complex routing such as traffic splitting belongs in
+/// front of DAGwood (see ADR 24).
+///
+/// Executions through the router are tracked as in-flight runs (see [`InFlightRuns`]), so
+/// a shutting-down server can drain them and reject new ones.
+///
+/// Requests dispatched by the receivers are admitted against the quotas of their pipeline
+/// and client first (see [`Quotas`]); [`execute`](Self::execute) and
+/// [`execute_observed`](Self::execute_observed) bypass them for in-process callers.
+#[derive(Debug, Clone)]
+pub struct PipelineRouter {
+    registry: Arc<PipelineRegistry>,
+    reloader: Option<Arc<HotReloader>>,
+    jobs: Option<Arc<JobManager>>,
+    auth: Option<Arc<Authenticator>>,
+    runs: Arc<InFlightRuns>,
+    quotas: Arc<Quotas>,
+}
+
+impl PipelineRouter {
+    /// Create a router over a shared registry.
+    pub fn new(registry: Arc<PipelineRegistry>) -> Self {
+        Self {
+            registry,
+            reloader: None,
+            jobs: None,
+            auth: None,
+            runs: Arc::new(InFlightRuns::new()),
+            quotas: Arc::new(Quotas::new()),
+        }
+    }
+
+    /// Expose hot reload to the receivers (e.g. the HTTP admin reload endpoints).
+    pub fn with_reloader(mut self, reloader: Arc<HotReloader>) -> Self {
+        self.reloader = Some(reloader);
+        self
+    }
+
+    /// Expose asynchronous jobs to the receivers (e.g. the HTTP job endpoints).
+    pub fn with_jobs(mut self, jobs: Arc<JobManager>) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    /// Require every request of every receiver to be authorized by `auth`.
+    pub fn with_auth(mut self, auth: Arc<Authenticator>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// The registry this router dispatches to.
+    pub fn registry(&self) -> &Arc<PipelineRegistry> {
+        &self.registry
+    }
+
+    /// Runs in flight through this router.
+    pub fn runs(&self) -> &Arc<InFlightRuns> {
+        &self.runs
+    }
+
+    /// Usage of the client and pipeline quotas.
+    pub fn quotas(&self) -> &Arc<Quotas> {
+        &self.quotas
+    }
+
+    /// The hot reloader, if the server supports reloading its configuration.
+    pub fn reloader(&self) -> Option<&Arc<HotReloader>> {
+        self.reloader.as_ref()
+    }
+
+    /// The job manager, if the server runs asynchronous jobs.
+    pub fn jobs(&self) -> Option<&Arc<JobManager>> {
+        self.jobs.as_ref()
+    }
+
+    /// The authenticator, if the server requires authentication.
+    pub fn auth(&self) -> Option<&Arc<Authenticator>> {
+        self.auth.as_ref()
+    }
+
+    /// Check that `credential`, presented over `protocol`, allows `access`, returning the
+    /// authenticated client.
+    ///
+    /// Every access is allowed, without a client, when the server does not require
+    /// authentication.
+    pub fn authorize(
+        &self,
+        protocol: &str,
+        credential: Option<&Credential>,
+        access: Access<'_>,
+    ) -> Result<Option<&AuthClient>, PipelineError> {
+        match &self.auth {
+            Some(auth) => auth
+                .authorize(protocol, credential, access)
+                .map(Some)
+                .map_err(PipelineError::from),
+            None => Ok(None),
+        }
+    }
+
+    /// Admit a request of `payload_bytes` to the named pipeline against the quota of the
+    /// pipeline and of `client`, if they have one.
+    ///
+    /// The pipeline's quota is read from its current configuration, so reloaded quotas
+    /// apply at once.
+    pub fn admit(
+        &self,
+        client: Option<&AuthClient>,
+        pipeline: &str,
+        payload_bytes: usize,
+    ) -> Result<QuotaPermit, PipelineError> {
+        let pipeline_quota = self
+            .registry
+            .get(pipeline)
+            .and_then(|managed| managed.config().quota);
+        let quotas: Vec<Quota<'_>> = pipeline_quota
+            .map(|config| Quota {
+                scope: QuotaScope::Pipeline,
+                name: pipeline,
+                config,
+            })
+            .into_iter()
+            .chain(client.and_then(|client| {
+                client.quota().map(|&config| Quota {
+                    scope: QuotaScope::Client,
+                    name: client.name(),
+                    config,
+                })
+            }))
+            .collect();
+        if quotas.is_empty() {
+            return Ok(QuotaPermit::unlimited());
+        }
+        self.quotas
+            .admit(pipeline, &quotas, payload_bytes)
+            .map_err(PipelineError::from)
+    }
+
+    /// Usage of every configured quota: the clients' first, then the pipelines', by name.
+    pub fn quota_usage(&self) -> Vec<QuotaUsage> {
+        let clients = self.auth.iter().flat_map(|auth| auth.clients());
+        let mut usage: Vec<QuotaUsage> = clients
+            .filter_map(|client| {
+                let config = client.quota()?;
+                Some(self.quotas.usage(QuotaScope::Client, client.name(), config))
+            })
+            .collect();
+        usage.sort_by(|a, b| a.name.cmp(&b.name));
+        for name in self.registry.names() {
+            let Some(config) = self.registry.get(&name).and_then(|p| p.config().quota) else {
+                continue;
+            };
+            usage.push(self.quotas.usage(QuotaScope::Pipeline, &name, &config));
+        }
+        usage
+    }
+
+    /// Resolve a pipeline name to the registered, ready pipeline.
+    ///
+    /// A pipeline that is not ready yet is initialized first; concurrent requests queue
+    /// behind its single initialization.
+    pub async fn route(&self, name: &str) -> Result<Arc<Pipeline>, PipelineError> {
+        let managed = self
+            .registry
+            .get(name)
+            .ok_or_else(|| PipelineError::NotFound {
+                name: name.to_string(),
+            })?;
+        managed.pipeline().await
+    }
+
+    /// Resolve a pipeline name to the ready pipeline, waiting at most `wait` for it.
+    ///
+    /// Fails with `PipelineError::NotReady` if the pipeline is still initializing (or
+    /// waiting to retry) after `wait`; the initialization itself carries on.
+    pub async fn route_within(
+        &self,
+        name: &str,
+        wait: Duration,
+    ) -> Result<Arc<Pipeline>, PipelineError> {
+        let managed = self
+            .registry
+            .get(name)
+            .ok_or_else(|| PipelineError::NotFound {
+                name: name.to_string(),
+            })?;
+        managed.pipeline_within(wait).await
+    }
+
+    /// Execute the named pipeline once with the given input.
+    ///
+    /// Processor failures are reported in the returned `ExecutionReport`; an `Err` means the
+    /// server is shutting down, the pipeline does not exist or could not be initialized, or
+    /// its executor could not carry out the execution.
+    pub async fn execute(
+        &self,
+        name: &str,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+    ) -> Result<ExecutionReport, PipelineError> {
+        self.execute_observed(name, input, pipeline_metadata, Arc::new(NoopObserver))
+            .await
+    }
+
+    /// Execute the named pipeline once, reporting live progress to `observer`.
+    pub async fn execute_observed(
+        &self,
+        name: &str,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        observer: Arc<dyn ExecutionObserver>,
+    ) -> Result<ExecutionReport, PipelineError> {
+        self.execute_cancellable(
+            name,
+            input,
+            pipeline_metadata,
+            observer,
+            CancellationToken::new(),
+        )
+        .await
+    }
+
+    /// Execute the named pipeline once like [`execute_observed`](Self::execute_observed),
+    /// until `cancellation` is cancelled.
+    ///
+    /// A cancelled execution returns once its processors stopped, failing with
+    /// `ExecutionError::Cancelled`.
+    pub async fn execute_cancellable(
+        &self,
+        name: &str,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        observer: Arc<dyn ExecutionObserver>,
+        cancellation: CancellationToken,
+    ) -> Result<ExecutionReport, PipelineError> {
+        let run = self.runs.begin()?;
+        let pipeline = tokio::select! {
+            pipeline = self.route(name) => pipeline?,
+            () = cancellation.cancelled() => {
+                return Err(PipelineError::ExecutionFailed {
+                    name: name.to_string(),
+                    error: cancelled(),
+                });
+            }
+        };
+        let cancellation = cancellation.child_token();
+        run.run(
+            &cancellation,
+            pipeline.execute_cancellable(input, pipeline_metadata, observer, cancellation.clone()),
+        )
+        .await
+        .map_err(|error| PipelineError::ExecutionFailed {
+            name: name.to_string(),
+            error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PipelinesConfig;
+    use crate::proto::processor_v1::processor_response::Outcome;
+
+    fn router() -> PipelineRouter {
+        let config = PipelinesConfig::from_yaml(
+            r#"
+pipelines:
+  - name: upper
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+  - name: reverse
+    startup: on-demand
+    strategy: reactive
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+"#,
+        )
+        .unwrap();
+        PipelineRouter::new(Arc::new(PipelineRegistry::from_config(config).unwrap()))
+    }
+
+    fn final_payload(report: &ExecutionReport, processor_id: &str) -> Vec<u8> {
+        match &report.results()[processor_id].outcome {
+            Some(Outcome::NextPayload(payload)) => payload.clone(),
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_routes_requests_by_name() {
+        let router = router();
+        let input = || ProcessorRequest {
+            payload: b"hello".to_vec(),
+        };
+
+        let upper = router
+            .execute("upper", input(), PipelineMetadata::new())
+            .await
+            .unwrap();
+        assert!(upper.is_success());
+        assert_eq!(final_payload(&upper, "upper"), b"HELLO");
+
+        let reverse = router
+            .execute("reverse", input(), PipelineMetadata::new())
+            .await
+            .unwrap();
+        assert_eq!(final_payload(&reverse, "reverse"), b"olleh");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_pipeline_is_not_found() {
+        let router = router();
+
+        let error = router
+            .execute(
+                "missing",
+                ProcessorRequest { payload: vec![] },
+                PipelineMetadata::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(error, PipelineError::NotFound { ref name } if name == "missing"));
+        assert_eq!(error.to_string(), "Pipeline 'missing' not found");
+    }
+}
@@ -0,0 +1,611 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Per-client and per-pipeline quotas, keeping a shared server fair.
+//!
+//! Every request is admitted by [`Quotas::admit`] before anything executes: against the
+//! `quota` of its pipeline ([`PipelineConfig`](crate::config::PipelineConfig)) and, when
+//! authentication is enabled, the `quota` of its client
+//! ([`AuthClientConfig`](crate::config::AuthClientConfig)). A request must fit every limit
+//! of both; a rejected request uses up nothing.
+//!
+//! | Limit                              | Rejection reason    | Retry hint                        |
+//! |------------------------------------|---------------------|-----------------------------------|
+//! | `max_payload_bytes`                | `payload_too_large` | none, retrying does not help      |
+//! | `max_concurrent`                   | `concurrency`       | [`CONCURRENCY_RETRY_AFTER`]       |
+//! | `fuel_per_window`                  | `fuel`              | until the fuel window ends        |
+//! | `requests_per_second` and `burst`  | `rate`              | until the bucket holds a token    |
+//!
+//! Request rates are token buckets holding up to `burst` tokens, refilled continuously at
+//! `requests_per_second`. Admitted requests hold a [`QuotaPermit`] until they finish, so
+//! queued jobs count against `max_concurrent` too.
+//!
+//! WASM fuel is charged once an execution finished, from the fuel its WASM processors
+//! report in their response metadata (see [`FUEL_CONSUMED_KEY`]), whether they succeeded or
+//! failed; retried processors are charged for their last attempt. A window is only closed
+//! to new requests once it is used up, so its last execution may overdraw it; the next
+//! window starts afresh.
+//!
+//! Limits are read from the configuration on every admission, so reloaded quotas apply to
+//! the next request. Rejections are logged as `QuotaExceeded` events and counted in
+//! `dagwood_quota_rejections_total`; executions in flight and fuel charged are exposed as
+//! `dagwood_quota_active_runs` and `dagwood_quota_fuel_consumed_total`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::backends::wasm::processor::FUEL_CONSUMED_KEY;
+use crate::config::QuotaConfig;
+use crate::engine::ExecutionReport;
+use crate::errors::QuotaError;
+use crate::observability::messages::server::QuotaExceeded;
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+
+/// Delay suggested to clients rejected for too many executions in flight
+pub const CONCURRENCY_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Owner of a quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuotaScope {
+    /// An authenticated client, across every pipeline it executes
+    Client,
+    /// A pipeline, across every client executing it
+    Pipeline,
+}
+
+impl QuotaScope {
+    /// Lowercase name for logs, metric labels and the admin API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QuotaScope::Client => "client",
+            QuotaScope::Pipeline => "pipeline",
+        }
+    }
+}
+
+/// A quota a request is admitted against: its owner and the owner's limits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quota<'a> {
+    pub scope: QuotaScope,
+    /// Name of the client or pipeline
+    pub name: &'a str,
+    pub config: QuotaConfig,
+}
+
+/// Current usage of a quota, as reported by the admin API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaUsage {
+    pub scope: QuotaScope,
+    pub name: String,
+    pub config: QuotaConfig,
+    /// Executions in flight
+    pub active: usize,
+    /// Requests the token bucket would admit right now, if requests are rate limited
+    pub tokens: Option<f64>,
+    /// Fuel charged in the current window
+    pub fuel_used: u64,
+    /// Time until the current fuel window ends, if fuel is limited
+    pub fuel_window_remaining: Option<Duration>,
+}
+
+/// Usage tracked for one quota
+#[derive(Debug)]
+struct QuotaState {
+    active: usize,
+    tokens: f64,
+    refilled_at: Instant,
+    fuel_used: u64,
+    window_started: Instant,
+}
+
+impl QuotaState {
+    fn new(config: &QuotaConfig, now: Instant) -> Self {
+        Self {
+            active: 0,
+            tokens: config.burst().map_or(0.0, f64::from),
+            refilled_at: now,
+            fuel_used: 0,
+            window_started: now,
+        }
+    }
+
+    /// Refill the token bucket and start a new fuel window if the current one ended
+    fn advance(&mut self, config: &QuotaConfig, now: Instant) {
+        if let (Some(rate), Some(burst)) = (config.requests_per_second, config.burst()) {
+            let elapsed = now.saturating_duration_since(self.refilled_at);
+            self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(f64::from(burst));
+        }
+        self.refilled_at = now;
+
+        let window = config.fuel_window();
+        if now.saturating_duration_since(self.window_started) >= window {
+            self.fuel_used = 0;
+            self.window_started = now;
+        }
+    }
+
+    /// Why the quota cannot admit another request of `payload_bytes` right now, if it cannot
+    fn check(
+        &self,
+        quota: &Quota<'_>,
+        payload_bytes: usize,
+        now: Instant,
+    ) -> Result<(), QuotaError> {
+        let config = &quota.config;
+        let scope = quota.scope.as_str();
+        let name = || quota.name.to_string();
+
+        if let Some(limit) = config
+            .max_payload_bytes
+            .filter(|&limit| payload_bytes > limit)
+        {
+            return Err(QuotaError::PayloadTooLarge {
+                scope,
+                name: name(),
+                size: payload_bytes,
+                limit,
+            });
+        }
+        if let Some(limit) = config.max_concurrent.filter(|&limit| self.active >= limit) {
+            return Err(QuotaError::ConcurrencyExceeded {
+                scope,
+                name: name(),
+                limit,
+                retry_after: CONCURRENCY_RETRY_AFTER,
+            });
+        }
+        if config
+            .fuel_per_window
+            .is_some_and(|limit| self.fuel_used >= limit)
+        {
+            let elapsed = now.saturating_duration_since(self.window_started);
+            return Err(QuotaError::FuelExhausted {
+                scope,
+                name: name(),
+                retry_after: config.fuel_window().saturating_sub(elapsed),
+            });
+        }
+        if let Some(rate) = config.requests_per_second.filter(|_| self.tokens < 1.0) {
+            return Err(QuotaError::RateLimited {
+                scope,
+                name: name(),
+                retry_after: Duration::from_secs_f64((1.0 - self.tokens) / rate),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the usage of every client and pipeline quota of a server.
+#[derive(Debug, Default)]
+pub struct Quotas {
+    states: Mutex<HashMap<(QuotaScope, String), QuotaState>>,
+}
+
+impl Quotas {
+    /// Create a tracker with no usage recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit a request of `payload_bytes` to `pipeline` against every one of `quotas`.
+    ///
+    /// The request is only counted once every quota admitted it; the returned permit holds
+    /// its place in their concurrency limits until dropped. Rejections are logged and
+    /// counted in the metrics.
+    pub fn admit(
+        self: &Arc<Self>,
+        pipeline: &str,
+        quotas: &[Quota<'_>],
+        payload_bytes: usize,
+    ) -> Result<QuotaPermit, QuotaError> {
+        self.admit_at(pipeline, quotas, payload_bytes, Instant::now())
+    }
+
+    fn admit_at(
+        self: &Arc<Self>,
+        pipeline: &str,
+        quotas: &[Quota<'_>],
+        payload_bytes: usize,
+        now: Instant,
+    ) -> Result<QuotaPermit, QuotaError> {
+        let mut states = self.states.lock().unwrap();
+        for quota in quotas {
+            let state = states
+                .entry((quota.scope, quota.name.to_string()))
+                .or_insert_with(|| QuotaState::new(&quota.config, now));
+            state.advance(&quota.config, now);
+            if let Err(error) = state.check(quota, payload_bytes, now) {
+                QuotaExceeded {
+                    pipeline,
+                    scope: error.scope(),
+                    name: error.name(),
+                    reason: error.reason(),
+                }
+                .log();
+                metrics().quota_rejected(error.scope(), error.name(), error.reason());
+                return Err(error);
+            }
+        }
+
+        let mut keys = Vec::with_capacity(quotas.len());
+        for quota in quotas {
+            let key = (quota.scope, quota.name.to_string());
+            let state = states.get_mut(&key).expect("state created while checking");
+            state.active += 1;
+            if quota.config.requests_per_second.is_some() {
+                state.tokens -= 1.0;
+            }
+            metrics().quota_active_runs(quota.scope.as_str(), quota.name, state.active);
+            keys.push(key);
+        }
+        Ok(QuotaPermit {
+            quotas: Some(Arc::clone(self)),
+            keys,
+        })
+    }
+
+    /// Current usage of the quota of `scope` `name`, limited by `config`.
+    pub fn usage(&self, scope: QuotaScope, name: &str, config: &QuotaConfig) -> QuotaUsage {
+        let now = Instant::now();
+        let mut states = self.states.lock().unwrap();
+        let state = states
+            .entry((scope, name.to_string()))
+            .or_insert_with(|| QuotaState::new(config, now));
+        state.advance(config, now);
+
+        QuotaUsage {
+            scope,
+            name: name.to_string(),
+            config: *config,
+            active: state.active,
+            tokens: config.burst().map(|_| state.tokens),
+            fuel_used: state.fuel_used,
+            fuel_window_remaining: config.fuel_per_window.map(|_| {
+                config
+                    .fuel_window()
+                    .saturating_sub(now.saturating_duration_since(state.window_started))
+            }),
+        }
+    }
+
+    fn charge_fuel(&self, keys: &[(QuotaScope, String)], fuel: u64) {
+        let mut states = self.states.lock().unwrap();
+        for key in keys {
+            if let Some(state) = states.get_mut(key) {
+                state.fuel_used = state.fuel_used.saturating_add(fuel);
+                metrics().quota_fuel_consumed(key.0.as_str(), &key.1, fuel);
+            }
+        }
+    }
+
+    fn release(&self, keys: &[(QuotaScope, String)]) {
+        let mut states = self.states.lock().unwrap();
+        for key in keys {
+            if let Some(state) = states.get_mut(key) {
+                state.active = state.active.saturating_sub(1);
+                metrics().quota_active_runs(key.0.as_str(), &key.1, state.active);
+            }
+        }
+    }
+}
+
+/// An admitted request's place in the quotas it was admitted against, released when
+/// dropped.
+#[derive(Debug)]
+pub struct QuotaPermit {
+    quotas: Option<Arc<Quotas>>,
+    keys: Vec<(QuotaScope, String)>,
+}
+
+impl QuotaPermit {
+    /// A permit of a request no quota applies to.
+    pub fn unlimited() -> Self {
+        Self {
+            quotas: None,
+            keys: Vec::new(),
+        }
+    }
+
+    /// Charge `fuel` to the fuel windows of every quota the request was admitted against.
+    pub fn charge_fuel(&self, fuel: u64) {
+        if let Some(quotas) = &self.quotas {
+            if fuel > 0 {
+                quotas.charge_fuel(&self.keys, fuel);
+            }
+        }
+    }
+
+    /// Charge the fuel the WASM processors of a finished execution reported, including the
+    /// processors that failed.
+    pub fn charge_report(&self, report: &ExecutionReport) {
+        let fuel = report
+            .processors
+            .values()
+            .filter_map(|processor| processor.response.as_ref()?.metadata.as_ref())
+            .flat_map(|metadata| metadata.metadata.values())
+            .filter_map(|metadata| metadata.metadata.get(FUEL_CONSUMED_KEY))
+            .filter_map(|fuel| fuel.parse::<u64>().ok())
+            .fold(0u64, u64::saturating_add);
+        self.charge_fuel(fuel);
+    }
+}
+
+impl Drop for QuotaPermit {
+    fn drop(&mut self) {
+        if let Some(quotas) = &self.quotas {
+            quotas.release(&self.keys);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PipelinesConfig;
+    use crate::protocols::{ErrorKind, PipelineRequest};
+    use crate::server::{PipelineRegistry, PipelineRouter};
+
+    fn quota(scope: QuotaScope, name: &str, config: QuotaConfig) -> Quota<'_> {
+        Quota {
+            scope,
+            name,
+            config,
+        }
+    }
+
+    #[test]
+    fn test_concurrency_released_when_permit_dropped() {
+        let quotas = Arc::new(Quotas::new());
+        let limits = [quota(
+            QuotaScope::Pipeline,
+            "upper",
+            QuotaConfig {
+                max_concurrent: Some(1),
+                ..QuotaConfig::default()
+            },
+        )];
+
+        let permit = quotas.admit("upper", &limits, 5).unwrap();
+        let error = quotas.admit("upper", &limits, 5).unwrap_err();
+        assert_eq!(error.reason(), "concurrency");
+        assert_eq!(error.retry_after(), Some(CONCURRENCY_RETRY_AFTER));
+        assert_eq!(
+            quotas
+                .usage(QuotaScope::Pipeline, "upper", &limits[0].config)
+                .active,
+            1
+        );
+
+        drop(permit);
+        assert!(quotas.admit("upper", &limits, 5).is_ok());
+    }
+
+    #[test]
+    fn test_token_bucket_refills_at_rate() {
+        let quotas = Arc::new(Quotas::new());
+        let limits = [quota(
+            QuotaScope::Client,
+            "dashboard",
+            QuotaConfig {
+                requests_per_second: Some(2.0),
+                burst: Some(2),
+                ..QuotaConfig::default()
+            },
+        )];
+        let start = Instant::now();
+
+        quotas.admit_at("upper", &limits, 0, start).unwrap();
+        quotas.admit_at("upper", &limits, 0, start).unwrap();
+        let error = quotas.admit_at("upper", &limits, 0, start).unwrap_err();
+        assert_eq!(
+            error,
+            QuotaError::RateLimited {
+                scope: "client",
+                name: "dashboard".to_string(),
+                retry_after: Duration::from_millis(500),
+            }
+        );
+
+        let later = start + Duration::from_millis(500);
+        quotas.admit_at("upper", &limits, 0, later).unwrap();
+        assert!(quotas.admit_at("upper", &limits, 0, later).is_err());
+    }
+
+    #[test]
+    fn test_payload_limit_is_not_retryable() {
+        let quotas = Arc::new(Quotas::new());
+        let limits = [quota(
+            QuotaScope::Pipeline,
+            "upper",
+            QuotaConfig {
+                max_payload_bytes: Some(4),
+                ..QuotaConfig::default()
+            },
+        )];
+
+        let error = quotas.admit("upper", &limits, 5).unwrap_err();
+        assert_eq!(error.reason(), "payload_too_large");
+        assert_eq!(error.retry_after(), None);
+        assert_eq!(
+            error.to_string(),
+            "Payload of 5 bytes exceeds the 4 bytes allowed for pipeline 'upper'"
+        );
+        assert!(quotas.admit("upper", &limits, 4).is_ok());
+    }
+
+    #[test]
+    fn test_fuel_window_closes_until_it_ends() {
+        let quotas = Arc::new(Quotas::new());
+        let config = QuotaConfig {
+            fuel_per_window: Some(1_000),
+            fuel_window_seconds: Some(10),
+            ..QuotaConfig::default()
+        };
+        let limits = [quota(QuotaScope::Pipeline, "analysis", config)];
+        let start = Instant::now();
+
+        let permit = quotas.admit_at("analysis", &limits, 0, start).unwrap();
+        permit.charge_fuel(1_200);
+        drop(permit);
+        assert_eq!(
+            quotas
+                .usage(QuotaScope::Pipeline, "analysis", &config)
+                .fuel_used,
+            1_200
+        );
+
+        let later = start + Duration::from_secs(4);
+        let error = quotas.admit_at("analysis", &limits, 0, later).unwrap_err();
+        assert_eq!(error.reason(), "fuel");
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(6)));
+
+        let next_window = start + Duration::from_secs(10);
+        assert!(quotas.admit_at("analysis", &limits, 0, next_window).is_ok());
+    }
+
+    #[test]
+    fn test_rejection_by_one_quota_counts_against_none() {
+        let quotas = Arc::new(Quotas::new());
+        let pipeline = QuotaConfig {
+            max_concurrent: Some(2),
+            ..QuotaConfig::default()
+        };
+        let client = QuotaConfig {
+            max_payload_bytes: Some(4),
+            ..QuotaConfig::default()
+        };
+        let limits = [
+            quota(QuotaScope::Pipeline, "upper", pipeline),
+            quota(QuotaScope::Client, "dashboard", client),
+        ];
+
+        let error = quotas.admit("upper", &limits, 10).unwrap_err();
+        assert_eq!(error.scope(), "client");
+        assert_eq!(
+            quotas
+                .usage(QuotaScope::Pipeline, "upper", &pipeline)
+                .active,
+            0
+        );
+
+        let _permit = quotas.admit("upper", &limits, 4).unwrap();
+        assert_eq!(
+            quotas
+                .usage(QuotaScope::Client, "dashboard", &client)
+                .active,
+            1
+        );
+    }
+
+    fn echo_module() -> tempfile::NamedTempFile {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (global $next (mut i32) (i32.const 1024))
+              (func (export "allocate") (param $size i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $size)))
+                (local.get $ptr))
+              (func (export "deallocate") (param i32 i32))
+              (func (export "process") (param $ptr i32) (param $len i32) (param $out i32) (result i32)
+                (i32.store (local.get $out) (local.get $len))
+                (local.get $ptr)))
+            "#,
+        )
+        .unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, &wasm).unwrap();
+        file
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_charges_wasm_fuel() {
+        let module = echo_module();
+        let config = PipelinesConfig::from_yaml(&format!(
+            r#"
+pipelines:
+  - name: echo
+    strategy: work_queue
+    quota:
+      fuel_per_window: 1
+    processors:
+      - {{id: echo, type: wasm, module: "{path}"}}
+"#,
+            path = module.path().display()
+        ))
+        .unwrap();
+        let router = PipelineRouter::new(Arc::new(PipelineRegistry::from_config(config).unwrap()));
+        let wait = Duration::from_secs(5);
+
+        PipelineRequest::new("echo", b"hello".to_vec())
+            .dispatch(&router, wait)
+            .await
+            .unwrap();
+        let usage = router.quota_usage();
+        assert_eq!(usage.len(), 1);
+        assert!(usage[0].fuel_used > 0);
+        assert_eq!(usage[0].active, 0);
+
+        let error = PipelineRequest::new("echo", b"hello".to_vec())
+            .dispatch(&router, wait)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::RateLimited);
+        assert!(error.retry_after.unwrap() <= Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_charges_fuel_of_failed_runs() {
+        // `process` never returns, so every execution runs out of fuel
+        let wasm = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "allocate") (param i32) (result i32) (i32.const 1024))
+              (func (export "deallocate") (param i32 i32))
+              (func (export "process") (param i32 i32 i32) (result i32)
+                (loop $spin (br $spin))
+                (i32.const 0)))
+            "#,
+        )
+        .unwrap();
+        let mut module = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut module, &wasm).unwrap();
+        let config = PipelinesConfig::from_yaml(&format!(
+            r#"
+pipelines:
+  - name: spin
+    strategy: work_queue
+    quota:
+      fuel_per_window: 1000000
+    processors:
+      - id: spin
+        type: wasm
+        module: "{path}"
+        options: {{fuel_level: 1000000}}
+"#,
+            path = module.path().display()
+        ))
+        .unwrap();
+        let router = PipelineRouter::new(Arc::new(PipelineRegistry::from_config(config).unwrap()));
+        let wait = Duration::from_secs(5);
+
+        let error = PipelineRequest::new("spin", b"hello".to_vec())
+            .dispatch(&router, wait)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::ProcessorFailed);
+        assert_eq!(router.quota_usage()[0].fuel_used, 1_000_000);
+
+        let error = PipelineRequest::new("spin", b"hello".to_vec())
+            .dispatch(&router, wait)
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::RateLimited);
+    }
+}
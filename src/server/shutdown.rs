@@ -0,0 +1,307 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Graceful shutdown (ADR 27): in-flight run tracking and shutdown signals.
+//!
+//! [`DagwoodServer::shutdown`](crate::server::DagwoodServer::shutdown) runs the phases:
+//!
+//! ```text
+//! 1. Stop accepting   receivers stop taking connections, new runs are rejected (503)
+//! 2. Drain            in-flight runs finish, up to the drain timeout
+//! 3. Cancel           runs still going are cancelled, their processors stopped, and fail
+//!                     with ExecutionError::Cancelled
+//! 4. Release          receivers close, pipelines (executors, processors, WASM engines) drop
+//! ```
+//!
+//! Every run dispatched through the [`PipelineRouter`](crate::server::PipelineRouter) holds a
+//! [`RunGuard`] from the router's [`InFlightRuns`], which is what the drain waits on.
+
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::signal::unix::{signal, Signal, SignalKind};
+use tokio::sync::{mpsc, watch};
+use tokio_util::sync::CancellationToken;
+
+use crate::engine::ExecutionReport;
+use crate::errors::{ExecutionError, PipelineError};
+
+/// Number of in-flight runs, and whether new runs are accepted
+#[derive(Debug)]
+struct Runs {
+    active: usize,
+    accepting: bool,
+}
+
+/// Tracks the pipeline runs in flight so shutdown can drain and, if needed, cancel them.
+#[derive(Debug)]
+pub struct InFlightRuns {
+    runs: watch::Sender<Runs>,
+    cancellation: CancellationToken,
+}
+
+impl Default for InFlightRuns {
+    fn default() -> Self {
+        Self {
+            runs: watch::Sender::new(Runs {
+                active: 0,
+                accepting: true,
+            }),
+            cancellation: CancellationToken::new(),
+        }
+    }
+}
+
+impl InFlightRuns {
+    /// Create a tracker accepting runs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a run, or fail with `PipelineError::ShuttingDown` once shutdown has begun.
+    ///
+    /// The run counts as in flight until the returned guard is dropped.
+    pub fn begin(self: &Arc<Self>) -> Result<RunGuard, PipelineError> {
+        let accepted = self.runs.send_if_modified(|runs| {
+            if runs.accepting {
+                runs.active += 1;
+            }
+            runs.accepting
+        });
+        if !accepted {
+            return Err(PipelineError::ShuttingDown);
+        }
+        Ok(RunGuard { runs: self.clone() })
+    }
+
+    /// Number of runs in flight.
+    pub fn active(&self) -> usize {
+        self.runs.borrow().active
+    }
+
+    /// Whether new runs are accepted.
+    pub fn is_accepting(&self) -> bool {
+        self.runs.borrow().accepting
+    }
+
+    /// Reject new runs; runs already in flight carry on.
+    pub fn stop_accepting(&self) {
+        self.runs.send_modify(|runs| runs.accepting = false);
+    }
+
+    /// Wait until no run is in flight, for at most `timeout`.
+    ///
+    /// Returns `true` if every run finished in time.
+    pub async fn wait_idle(&self, timeout: Duration) -> bool {
+        tokio::time::timeout(timeout, self.idle()).await.is_ok()
+    }
+
+    /// Wait until no run is in flight.
+    pub async fn idle(&self) {
+        let mut runs = self.runs.subscribe();
+        // The sender lives in `self`, so the channel cannot close while waiting
+        let _ = runs.wait_for(|runs| runs.active == 0).await;
+    }
+
+    /// Cancel every run in flight, and any run started later.
+    pub fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether runs have been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation.is_cancelled()
+    }
+}
+
+/// An in-flight run; dropping it marks the run finished.
+#[derive(Debug)]
+pub struct RunGuard {
+    runs: Arc<InFlightRuns>,
+}
+
+impl RunGuard {
+    /// Drive `execution` until it finishes, cancelling it if the server cancels its in-flight
+    /// runs first.
+    ///
+    /// `execution` must stop once `cancellation` is cancelled (see
+    /// [`DagExecutor::execute_plan_cancellable`](crate::traits::DagExecutor::execute_plan_cancellable));
+    /// the run stays in flight until it did, then fails with `ExecutionError::Cancelled`.
+    pub async fn run(
+        &self,
+        cancellation: &CancellationToken,
+        execution: impl Future<Output = Result<ExecutionReport, ExecutionError>>,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        cancel_when(
+            execution,
+            cancellation,
+            self.runs.cancellation.cancelled(),
+            || "server is shutting down".to_string(),
+        )
+        .await
+    }
+}
+
+impl Drop for RunGuard {
+    fn drop(&mut self) {
+        self.runs.runs.send_modify(|runs| runs.active -= 1);
+    }
+}
+
+/// Drive `execution`, which stops once `cancellation` is cancelled, and cancel it when
+/// `trigger` completes first.
+///
+/// A cancelled execution is still awaited, so its processors have stopped when this returns;
+/// its `ExecutionError::Cancelled` then carries `reason`.
+pub(crate) async fn cancel_when(
+    execution: impl Future<Output = Result<ExecutionReport, ExecutionError>>,
+    cancellation: &CancellationToken,
+    trigger: impl Future<Output = ()>,
+    reason: impl FnOnce() -> String,
+) -> Result<ExecutionReport, ExecutionError> {
+    tokio::pin!(execution);
+    tokio::select! {
+        result = &mut execution => return result,
+        _ = trigger => cancellation.cancel(),
+    }
+    match execution.await {
+        Err(ExecutionError::Cancelled { .. }) => {
+            Err(ExecutionError::Cancelled { reason: reason() })
+        }
+        result => result,
+    }
+}
+
+/// SIGTERM (orchestrators) and SIGINT (Ctrl+C), both asking for a graceful shutdown.
+///
+/// The handlers are installed by [`ShutdownSignal::listen`], so signals arriving between
+/// then and [`ShutdownSignal::recv`] are not lost. [`ShutdownSignal::channel`] delivers the
+/// signals through a channel instead, e.g. to shut down an embedded server.
+#[derive(Debug)]
+pub struct ShutdownSignal {
+    source: SignalSource,
+}
+
+/// Where shutdown signals come from
+#[derive(Debug)]
+enum SignalSource {
+    Os {
+        terminate: Signal,
+        interrupt: Signal,
+    },
+    Channel(mpsc::UnboundedReceiver<&'static str>),
+}
+
+impl ShutdownSignal {
+    /// Install the SIGTERM and SIGINT handlers.
+    pub fn listen() -> io::Result<Self> {
+        Ok(Self {
+            source: SignalSource::Os {
+                terminate: signal(SignalKind::terminate())?,
+                interrupt: signal(SignalKind::interrupt())?,
+            },
+        })
+    }
+
+    /// Create a signal raised by sending its name through the returned sender.
+    ///
+    /// Once every sender is dropped without sending, the signal never arrives.
+    pub fn channel() -> (mpsc::UnboundedSender<&'static str>, Self) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let signal = Self {
+            source: SignalSource::Channel(receiver),
+        };
+        (sender, signal)
+    }
+
+    /// Wait for the first shutdown signal, returning its name.
+    pub async fn recv(self) -> &'static str {
+        match self.source {
+            SignalSource::Os {
+                mut terminate,
+                mut interrupt,
+            } => {
+                tokio::select! {
+                    _ = terminate.recv() => "SIGTERM",
+                    _ = interrupt.recv() => "SIGINT",
+                }
+            }
+            SignalSource::Channel(mut receiver) => match receiver.recv().await {
+                Some(name) => name,
+                None => std::future::pending().await,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_tracks_runs_and_rejects_after_stop() {
+        let runs = Arc::new(InFlightRuns::new());
+        let first = runs.begin().unwrap();
+        let second = runs.begin().unwrap();
+        assert_eq!(runs.active(), 2);
+
+        runs.stop_accepting();
+        assert!(!runs.is_accepting());
+        assert!(matches!(runs.begin(), Err(PipelineError::ShuttingDown)));
+        assert!(!runs.wait_idle(Duration::from_millis(10)).await);
+
+        drop(first);
+        let waiting = tokio::spawn({
+            let runs = runs.clone();
+            async move { runs.wait_idle(Duration::from_secs(5)).await }
+        });
+        drop(second);
+        assert!(waiting.await.unwrap());
+        assert_eq!(runs.active(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_aborts_runs() {
+        let runs = Arc::new(InFlightRuns::new());
+        let guard = runs.begin().unwrap();
+        let cancellation = CancellationToken::new();
+        let execution = {
+            let cancellation = cancellation.clone();
+            async move {
+                cancellation.cancelled().await;
+                Err(ExecutionError::Cancelled {
+                    reason: "execution was cancelled".to_string(),
+                })
+            }
+        };
+
+        let cancel = {
+            let runs = runs.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                runs.cancel();
+            }
+        };
+        let (result, ()) = tokio::join!(guard.run(&cancellation, execution), cancel);
+        assert!(matches!(
+            result,
+            Err(ExecutionError::Cancelled { reason }) if reason == "server is shutting down"
+        ));
+        assert!(cancellation.is_cancelled());
+        assert!(runs.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn test_channel_signal() {
+        let (sender, signal) = ShutdownSignal::channel();
+        sender.send("SIGTERM").unwrap();
+        assert_eq!(signal.recv().await, "SIGTERM");
+
+        let (sender, signal) = ShutdownSignal::channel();
+        drop(sender);
+        let received = tokio::time::timeout(Duration::from_millis(20), signal.recv()).await;
+        assert!(received.is_err());
+    }
+}
@@ -0,0 +1,785 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+//! Webhook notifications of finished pipeline executions.
+//!
+//! A pipeline configured with a [`WebhookConfig`] gets a [`Webhook`] that POSTs an
+//! [`ExecutionSummary`] (status, outputs, metadata and timings of the execution report) once
+//! an execution finishes:
+//!
+//! * to the configured `url`, for every execution of the pipeline
+//! * to the callback URL named by a request, when the webhook allows request callbacks
+//!
+//! `https://` URLs are delivered over TLS, trusting the Mozilla root certificates bundled
+//! with the server.
+//!
+//! Deliveries run in the background and never delay or fail the execution. Each attempt is
+//! bounded by the webhook's timeout; connection errors, timeouts and `429` / `5xx` responses
+//! are retried with backoff, other responses end the delivery.
+//!
+//! # Headers
+//! * `Content-Type: application/json`
+//! * `X-Dagwood-Event` - `execution.succeeded` or `execution.failed`
+//! * `X-Dagwood-Delivery` - ID of the notification, the same for every attempt
+//! * `X-Dagwood-Signature` - `sha256=` and the hex HMAC-SHA256 of the body keyed with the
+//!   webhook's `secret`, when one is configured
+//!
+//! Receivers verify a notification by computing [`signature`] over the raw body with the
+//! shared secret and comparing it to the header in constant time.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use hyper::client::connect::{Connected, Connection};
+use hyper::client::HttpConnector;
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::service::Service;
+use hyper::{Body, Client, Method, Request, StatusCode, Uri};
+use rustls_pki_types::ServerName;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use crate::config::{validate_webhook_url, WebhookConfig};
+use crate::engine::{ExecutionReport, PipelineOutput, ProcessorStatus};
+use crate::errors::PipelineError;
+use crate::observability::messages::server::{WebhookDelivered, WebhookDeliveryFailed};
+use crate::observability::messages::StructuredLog;
+use crate::observability::metrics::metrics;
+
+/// Header naming the kind of notification
+pub const EVENT_HEADER: &str = "x-dagwood-event";
+/// Header carrying the ID of a notification, unchanged across retries
+pub const DELIVERY_HEADER: &str = "x-dagwood-delivery";
+/// Header carrying the HMAC-SHA256 signature of the body
+pub const SIGNATURE_HEADER: &str = "x-dagwood-signature";
+
+/// Signature of a notification body, as sent in the `X-Dagwood-Signature` header.
+///
+/// # Example
+/// ```
+/// use the_dagwood::server::webhooks::signature;
+///
+/// let header = signature("change-me", br#"{"pipeline":"upper"}"#);
+/// assert!(header.starts_with("sha256="));
+/// assert_eq!(header.len(), "sha256=".len() + 64);
+/// ```
+pub fn signature(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    let digest: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", digest)
+}
+
+/// Outcome of a finished execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    /// Every processor succeeded
+    Succeeded,
+    /// A processor failed, or the execution could not be carried out
+    Failed,
+}
+
+/// Outcome of one processor within an [`ExecutionSummary`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessorSummary {
+    pub processor_id: String,
+    pub status: ProcessorStatus,
+    pub attempts: u32,
+    /// Offset from the start of the execution to the first attempt
+    pub started_at_ms: Option<f64>,
+    pub duration_ms: f64,
+    pub error: Option<String>,
+}
+
+/// Body of a webhook notification: a summary of the execution report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSummary {
+    pub pipeline: String,
+    pub status: ExecutionStatus,
+    /// Correlation ID of the execution; absent when the execution could not start
+    pub run_id: Option<String>,
+    /// Executor strategy that ran the pipeline
+    pub strategy: Option<String>,
+    pub duration_ms: f64,
+    /// Canonical payload at the end of a succeeded execution, base64 encoded
+    #[serde(with = "crate::engine::execution_report::base64_payload::optional")]
+    pub payload: Option<Vec<u8>>,
+    /// Designated pipeline outputs, in configuration order
+    pub outputs: Vec<PipelineOutput>,
+    /// Accumulated pipeline metadata, by processor
+    pub metadata: HashMap<String, HashMap<String, String>>,
+    /// Every processor of the plan, in execution order
+    pub processors: Vec<ProcessorSummary>,
+    /// Why the execution failed
+    pub error: Option<String>,
+}
+
+impl ExecutionSummary {
+    /// Summarize the outcome of an execution of `pipeline`.
+    pub fn new<E: Display>(pipeline: &str, result: &Result<ExecutionReport, E>) -> Self {
+        let report = match result {
+            Ok(report) => report,
+            Err(error) => {
+                return Self {
+                    pipeline: pipeline.to_string(),
+                    status: ExecutionStatus::Failed,
+                    run_id: None,
+                    strategy: None,
+                    duration_ms: 0.0,
+                    payload: None,
+                    outputs: Vec::new(),
+                    metadata: HashMap::new(),
+                    processors: Vec::new(),
+                    error: Some(error.to_string()),
+                }
+            }
+        };
+
+        let succeeded = report.errors.is_empty();
+        let errors: Vec<String> = report.errors.iter().map(|e| e.to_string()).collect();
+        Self {
+            pipeline: pipeline.to_string(),
+            status: if succeeded {
+                ExecutionStatus::Succeeded
+            } else {
+                ExecutionStatus::Failed
+            },
+            run_id: Some(report.run_id.clone()),
+            strategy: Some(report.strategy.to_string()),
+            duration_ms: millis(report.duration),
+            payload: succeeded.then(|| report.final_output.clone()),
+            outputs: report.outputs.clone(),
+            metadata: report
+                .pipeline_metadata
+                .metadata
+                .iter()
+                .map(|(key, metadata)| (key.clone(), metadata.metadata.clone()))
+                .collect(),
+            processors: report
+                .processors_in_order()
+                .into_iter()
+                .map(|(processor_id, processor)| ProcessorSummary {
+                    processor_id: processor_id.to_string(),
+                    status: processor.status,
+                    attempts: processor.attempts,
+                    started_at_ms: processor.started_at.map(millis),
+                    duration_ms: millis(processor.duration),
+                    error: processor.error.as_ref().map(|e| e.to_string()),
+                })
+                .collect(),
+            error: (!succeeded).then(|| errors.join("; ")),
+        }
+    }
+
+    /// Value of the `X-Dagwood-Event` header
+    pub fn event(&self) -> &'static str {
+        match self.status {
+            ExecutionStatus::Succeeded => "execution.succeeded",
+            ExecutionStatus::Failed => "execution.failed",
+        }
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Webhook of a pipeline, delivering notifications of its finished executions.
+#[derive(Clone)]
+pub struct Webhook {
+    pipeline: String,
+    config: WebhookConfig,
+    client: Client<WebhookConnector>,
+}
+
+impl Webhook {
+    /// Create the webhook of `pipeline`.
+    pub fn new(pipeline: impl Into<String>, config: WebhookConfig) -> Self {
+        Self {
+            pipeline: pipeline.into(),
+            config,
+            client: Client::builder().build(WebhookConnector::new(default_tls_config())),
+        }
+    }
+
+    /// Trust `roots` instead of the bundled root certificates for `https://` deliveries
+    #[cfg(test)]
+    fn with_roots(mut self, roots: RootCertStore) -> Self {
+        let config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        self.client = Client::builder().build(WebhookConnector::new(Arc::new(config)));
+        self
+    }
+
+    /// Settings the webhook was created with.
+    pub fn config(&self) -> &WebhookConfig {
+        &self.config
+    }
+
+    /// Check that a request to `pipeline` may name `url` as its callback.
+    ///
+    /// Only pipelines whose webhook allows request callbacks accept them, and only for
+    /// absolute `http://` and `https://` URLs.
+    pub fn check_callback(
+        pipeline: &str,
+        config: Option<&WebhookConfig>,
+        url: &str,
+    ) -> Result<(), PipelineError> {
+        let result = match config {
+            Some(config) if config.allow_request_callbacks => validate_webhook_url(url),
+            _ => Err("the pipeline does not accept request callbacks".to_string()),
+        };
+        result.map_err(|reason| PipelineError::CallbackRejected {
+            name: pipeline.to_string(),
+            reason,
+        })
+    }
+
+    /// Notify the configured URL, if any, that an execution finished.
+    pub fn notify<E: Display>(&self, result: &Result<ExecutionReport, E>) {
+        if let Some(url) = &self.config.url {
+            self.spawn_delivery(url, ExecutionSummary::new(&self.pipeline, result));
+        }
+    }
+
+    /// Notify the callback URL of a request that its execution finished.
+    ///
+    /// The URL must have been accepted by [`Webhook::check_callback`].
+    pub fn notify_callback<E: Display>(&self, url: &str, result: &Result<ExecutionReport, E>) {
+        self.spawn_delivery(url, ExecutionSummary::new(&self.pipeline, result));
+    }
+
+    fn spawn_delivery(&self, url: &str, summary: ExecutionSummary) {
+        let body = match serde_json::to_vec(&summary) {
+            Ok(body) => body,
+            Err(error) => {
+                self.delivery_failed(url, &summary, 0, &error.to_string());
+                return;
+            }
+        };
+        let webhook = self.clone();
+        let url = url.to_string();
+        tokio::spawn(async move {
+            let (attempts, result) = webhook.deliver(&url, &summary, body).await;
+            match result {
+                Ok(status) => {
+                    metrics().webhook_notified(&webhook.pipeline, true, attempts);
+                    WebhookDelivered {
+                        pipeline: &webhook.pipeline,
+                        url: &url,
+                        run_id: summary.run_id.as_deref(),
+                        status: status.as_u16(),
+                        attempts,
+                    }
+                    .log();
+                }
+                Err(reason) => webhook.delivery_failed(&url, &summary, attempts, &reason),
+            }
+        });
+    }
+
+    fn delivery_failed(&self, url: &str, summary: &ExecutionSummary, attempts: u32, reason: &str) {
+        metrics().webhook_notified(&self.pipeline, false, attempts);
+        WebhookDeliveryFailed {
+            pipeline: &self.pipeline,
+            url,
+            run_id: summary.run_id.as_deref(),
+            attempts,
+            reason,
+        }
+        .log();
+    }
+
+    /// POST the notification until it is accepted, rejected or out of attempts; returns the
+    /// number of attempts made and the accepting status or why the last attempt failed
+    async fn deliver(
+        &self,
+        url: &str,
+        summary: &ExecutionSummary,
+        body: Vec<u8>,
+    ) -> (u32, Result<StatusCode, String>) {
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+        let signature = self
+            .config
+            .secret
+            .as_deref()
+            .map(|secret| signature(secret, &body));
+        let max_attempts = self.config.max_attempts();
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let mut request = Request::builder()
+                .method(Method::POST)
+                .uri(url)
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .header(EVENT_HEADER, summary.event())
+                .header(DELIVERY_HEADER, &delivery_id);
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            let request = match request.body(Body::from(body.clone())) {
+                Ok(request) => request,
+                Err(error) => return (attempt, Err(format!("invalid request: {}", error))),
+            };
+
+            let reason =
+                match tokio::time::timeout(self.config.timeout(), self.client.request(request))
+                    .await
+                {
+                    Ok(Ok(response)) if response.status().is_success() => {
+                        return (attempt, Ok(response.status()));
+                    }
+                    Ok(Ok(response)) => {
+                        let status = response.status();
+                        if !(status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS) {
+                            return (attempt, Err(format!("rejected with status {}", status)));
+                        }
+                        format!("failed with status {}", status)
+                    }
+                    Ok(Err(error)) => error.to_string(),
+                    Err(_) => format!("timed out after {:?}", self.config.timeout()),
+                };
+
+            if attempt >= max_attempts {
+                return (attempt, Err(reason));
+            }
+            tokio::time::sleep(self.config.retry_delay(attempt)).await;
+        }
+    }
+}
+
+impl std::fmt::Debug for Webhook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Webhook")
+            .field("pipeline", &self.pipeline)
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+/// TLS configuration trusting the bundled Mozilla root certificates, shared by all webhooks
+fn default_tls_config() -> Arc<ClientConfig> {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    CONFIG
+        .get_or_init(|| {
+            let mut roots = RootCertStore::empty();
+            roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+            let config = ClientConfig::builder()
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+            Arc::new(config)
+        })
+        .clone()
+}
+
+/// Connector of the webhook client: plain TCP for `http://` URLs, TLS for `https://` URLs
+#[derive(Clone)]
+struct WebhookConnector {
+    http: HttpConnector,
+    tls: TlsConnector,
+}
+
+impl WebhookConnector {
+    fn new(config: Arc<ClientConfig>) -> Self {
+        let mut http = HttpConnector::new();
+        http.enforce_http(false);
+        Self {
+            http,
+            tls: TlsConnector::from(config),
+        }
+    }
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+impl Service<Uri> for WebhookConnector {
+    type Response = WebhookStream;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<WebhookStream, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        let tls = self.tls.clone();
+        Box::pin(async move {
+            let server_name = match uri.scheme_str() {
+                Some("https") => {
+                    let host = uri.host().unwrap_or_default();
+                    let host = host.trim_start_matches('[').trim_end_matches(']');
+                    Some(ServerName::try_from(host.to_string())?)
+                }
+                _ => None,
+            };
+            let stream = http.call(uri).await?;
+            match server_name {
+                Some(server_name) => {
+                    let stream = tls.connect(server_name, stream).await?;
+                    Ok(WebhookStream::Tls(Box::new(stream)))
+                }
+                None => Ok(WebhookStream::Plain(stream)),
+            }
+        })
+    }
+}
+
+/// Connection to a webhook receiver
+enum WebhookStream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl Connection for WebhookStream {
+    fn connected(&self) -> Connected {
+        match self {
+            WebhookStream::Plain(stream) => stream.connected(),
+            WebhookStream::Tls(stream) => stream.get_ref().0.connected(),
+        }
+    }
+}
+
+impl AsyncRead for WebhookStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            WebhookStream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            WebhookStream::Tls(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for WebhookStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            WebhookStream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            WebhookStream::Tls(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            WebhookStream::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            WebhookStream::Tls(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            WebhookStream::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            WebhookStream::Tls(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{PipelinesConfig, ServerConfig};
+    use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
+    use crate::protocols::PipelineRequest;
+    use crate::server::{JobManager, PipelineRegistry, PipelineRouter};
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{HeaderMap, Response, Server};
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    /// A local stand-in for a webhook receiver, answering with `statuses` in turn (then 200)
+    /// and forwarding every request it receives
+    fn stand_in(statuses: Vec<u16>) -> (String, mpsc::UnboundedReceiver<(HeaderMap, Vec<u8>)>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let statuses = Arc::new(std::sync::Mutex::new(statuses.into_iter()));
+        let make_service = make_service_fn(move |_| {
+            let sender = sender.clone();
+            let statuses = statuses.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let sender = sender.clone();
+                    let status = statuses.lock().unwrap().next().unwrap_or(200);
+                    async move {
+                        let headers = request.headers().clone();
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        sender.send((headers, body.to_vec())).unwrap();
+                        let mut response = Response::new(Body::empty());
+                        *response.status_mut() = StatusCode::from_u16(status).unwrap();
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let url = format!("http://{}/hooks", server.local_addr());
+        tokio::spawn(server);
+        (url, receiver)
+    }
+
+    fn webhook(yaml: &str) -> WebhookConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn summary() -> ExecutionSummary {
+        ExecutionSummary::new::<String>("upper", &Err("boom".to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_webhook_posts_signed_summary() {
+        let (url, mut requests) = stand_in(vec![]);
+        let config = PipelinesConfig::from_yaml(&format!(
+            r#"
+pipelines:
+  - name: analysis
+    webhook:
+      url: "{}"
+      secret: change-me
+    strategy: reactive
+    processors:
+      - id: reverse
+        type: local
+        processor: reverse_text
+      - id: tokens
+        type: local
+        processor: token_counter
+    outputs:
+      - processor: tokens
+"#,
+            url
+        ))
+        .unwrap();
+        let router = PipelineRouter::new(Arc::new(PipelineRegistry::from_config(config).unwrap()));
+
+        let report = router
+            .execute(
+                "analysis",
+                ProcessorRequest {
+                    payload: b"hello world".to_vec(),
+                },
+                PipelineMetadata::new(),
+            )
+            .await
+            .unwrap();
+
+        let (headers, body) = requests.recv().await.unwrap();
+        assert_eq!(headers[CONTENT_TYPE], "application/json");
+        assert_eq!(headers[EVENT_HEADER], "execution.succeeded");
+        assert_eq!(headers[SIGNATURE_HEADER], signature("change-me", &body));
+        assert_ne!(headers[SIGNATURE_HEADER], signature("wrong", &body));
+
+        let summary: ExecutionSummary = serde_json::from_slice(&body).unwrap();
+        assert_eq!(summary.run_id, Some(report.run_id));
+        assert_eq!(summary.status, ExecutionStatus::Succeeded);
+        assert_eq!(summary.payload, Some(report.final_output));
+        assert_eq!(summary.outputs[0].name, "tokens");
+        assert_eq!(summary.processors.len(), 2);
+        assert!(summary.processors[0].duration_ms > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_request_callbacks() {
+        let (url, mut requests) = stand_in(vec![]);
+        let config = ServerConfig::from_yaml(
+            r#"
+jobs:
+  enabled: true
+pipelines:
+  - name: upper
+    webhook:
+      allow_request_callbacks: true
+    strategy: work_queue
+    processors:
+      - id: upper
+        type: local
+        processor: change_text_case_upper
+"#,
+        )
+        .unwrap();
+        let router = PipelineRouter::new(Arc::new(
+            PipelineRegistry::from_config(config.pipelines).unwrap(),
+        ));
+
+        // Synchronous requests
+        PipelineRequest::new("upper", b"hello".to_vec())
+            .with_callback(&url)
+            .dispatch(&router, Duration::from_secs(5))
+            .await
+            .unwrap();
+        let (headers, body) = requests.recv().await.unwrap();
+        assert!(headers.get(SIGNATURE_HEADER).is_none());
+        let summary: ExecutionSummary = serde_json::from_slice(&body).unwrap();
+        assert_eq!(summary.payload.as_deref(), Some(&b"HELLO"[..]));
+
+        // Jobs, including failed ones
+        let jobs = Arc::new(JobManager::from_config(&config.jobs).unwrap());
+        let request = PipelineRequest::new("upper", vec![0xff]).with_callback(&url);
+        let job = jobs.submit(&router, request).await.unwrap();
+        let (headers, body) = requests.recv().await.unwrap();
+        assert_eq!(headers[EVENT_HEADER], "execution.failed");
+        let summary: ExecutionSummary = serde_json::from_slice(&body).unwrap();
+        assert_eq!(summary.status, ExecutionStatus::Failed);
+        assert_eq!(summary.processors[0].status, ProcessorStatus::Failed);
+        assert!(summary.error.unwrap().contains("upper"));
+        assert!(jobs.get(&job.id).await.is_ok());
+
+        // Without a configured URL, plain executions notify nobody
+        PipelineRequest::new("upper", b"hello".to_vec())
+            .dispatch(&router, Duration::from_secs(5))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_retries_failures() {
+        let (url, mut requests) = stand_in(vec![503, 500]);
+        let hook = Webhook::new("upper", webhook("{retry_delay_ms: 1}"));
+
+        let (attempts, result) = hook.deliver(&url, &summary(), b"{}".to_vec()).await;
+        assert_eq!((attempts, result), (3, Ok(StatusCode::OK)));
+        let ids: Vec<HeaderValue> = [(); 3]
+            .iter()
+            .map(|_| requests.try_recv().unwrap().0[DELIVERY_HEADER].clone())
+            .collect();
+        assert!(ids.iter().all(|id| id == ids[0]));
+        assert!(requests.try_recv().is_err());
+
+        // Retries are bounded, and client errors are not retried
+        let (url, _requests) = stand_in(vec![500; 10]);
+        let hook = Webhook::new("upper", webhook("{max_retries: 2, retry_delay_ms: 1}"));
+        let (attempts, result) = hook.deliver(&url, &summary(), b"{}".to_vec()).await;
+        assert_eq!(attempts, 3);
+        assert_eq!(
+            result.unwrap_err(),
+            "failed with status 500 Internal Server Error"
+        );
+
+        let (url, _requests) = stand_in(vec![404]);
+        let (attempts, result) = hook.deliver(&url, &summary(), b"{}".to_vec()).await;
+        assert_eq!(attempts, 1);
+        assert_eq!(result.unwrap_err(), "rejected with status 404 Not Found");
+    }
+
+    #[tokio::test]
+    async fn test_delivery_times_out() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        let hook = Webhook::new(
+            "upper",
+            webhook("{timeout_ms: 20, max_retries: 1, retry_delay_ms: 1}"),
+        );
+
+        let (attempts, result) = hook.deliver(&url, &summary(), b"{}".to_vec()).await;
+        assert_eq!(attempts, 2);
+        assert_eq!(result.unwrap_err(), "timed out after 20ms");
+        drop(listener);
+    }
+
+    /// Certificate files of `tests/fixtures/tls`
+    fn fixture(name: &str) -> std::path::PathBuf {
+        std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    #[tokio::test]
+    async fn test_delivery_over_https() {
+        use rustls_pki_types::pem::PemObject;
+        use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+        use tokio_rustls::rustls::ServerConfig;
+
+        // An HTTPS stand-in serving the fixture certificate for `localhost`
+        let chain = vec![CertificateDer::from_pem_file(fixture("server.pem")).unwrap()];
+        let key = PrivateKeyDer::from_pem_file(fixture("server.key")).unwrap();
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(chain, key)
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "https://localhost:{}/hooks",
+            listener.local_addr().unwrap().port()
+        );
+        let (sender, mut requests) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let Ok(stream) = acceptor.accept(stream).await else {
+                    continue;
+                };
+                let sender = sender.clone();
+                let service = service_fn(move |request: Request<Body>| {
+                    let _ = sender.send(request.headers().clone());
+                    async { Ok::<_, Infallible>(Response::new(Body::empty())) }
+                });
+                tokio::spawn(hyper::server::conn::Http::new().serve_connection(stream, service));
+            }
+        });
+
+        // The bundled roots do not trust the test CA
+        let hook = Webhook::new("upper", webhook("{max_retries: 0}"));
+        let (_, result) = hook.deliver(&url, &summary(), b"{}".to_vec()).await;
+        assert!(result.unwrap_err().contains("certificate"));
+        assert!(requests.try_recv().is_err());
+
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_file(fixture("ca.pem")).unwrap())
+            .unwrap();
+        let hook = Webhook::new("upper", webhook("{}")).with_roots(roots);
+        let (attempts, result) = hook.deliver(&url, &summary(), b"{}".to_vec()).await;
+        assert_eq!((attempts, result), (1, Ok(StatusCode::OK)));
+        let headers = requests.recv().await.unwrap();
+        assert_eq!(headers[EVENT_HEADER], "execution.failed");
+    }
+
+    #[test]
+    fn test_check_callback() {
+        let allowing = webhook("{allow_request_callbacks: true}");
+        assert!(
+            Webhook::check_callback("upper", Some(&allowing), "http://localhost:9000/cb").is_ok()
+        );
+        assert!(matches!(
+            Webhook::check_callback("upper", Some(&allowing), "file:///etc/passwd"),
+            Err(PipelineError::CallbackRejected { .. })
+        ));
+        assert!(
+            Webhook::check_callback("upper", Some(&allowing), "https://localhost:9000/cb").is_ok()
+        );
+        let error = Webhook::check_callback("upper", None, "http://localhost:9000/cb").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Pipeline 'upper' rejected the callback: the pipeline does not accept request callbacks"
+        );
+    }
+}
@@ -2,16 +2,123 @@
 // SPDX-License-Identifier: MIT
 
 use crate::errors::{ExecutionError, FailureStrategy};
-use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest, ProcessorResponse};
+use crate::proto::processor_v1::{PipelineMetadata, ProcessorRequest};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
+use crate::engine::{ExecutionPlan, ExecutionReport, NoopObserver};
+use crate::observability::trace_context::TraceContext;
+use crate::traits::ExecutionObserver;
 
 #[async_trait]
 pub trait DagExecutor: Send + Sync {
+    /// Execute a pipeline from a pre-compiled execution plan.
+    ///
+    /// The plan is shared immutably, so a single `Arc<ExecutionPlan>` can be reused across
+    /// many concurrent executions without recomputing ranks, levels or dependency maps.
+    ///
+    /// - `plan`: compiled processors, graph and derived scheduling structures
+    /// - `input`: initial request payload
+    /// - `pipeline_metadata`: metadata accumulator for the entire pipeline
+    /// - `failure_strategy`: how to handle processor failures
+    ///
+    /// Returns a Result containing either:
+    /// - Ok(ExecutionReport): Per-processor outcomes, final output, metadata and collected failures.
+    ///   Processor failures are reported here rather than as `Err`, so partial results are kept.
+    /// - Err(ExecutionError): The execution could not be carried out (e.g. internal executor errors)
+    async fn execute_plan(
+        &self,
+        plan: Arc<ExecutionPlan>,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        failure_strategy: FailureStrategy,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        self.execute_plan_observed(
+            plan,
+            input,
+            pipeline_metadata,
+            failure_strategy,
+            Arc::new(NoopObserver),
+        )
+        .await
+    }
+
+    /// Execute a pipeline from a pre-compiled execution plan, reporting live progress.
+    ///
+    /// Behaves exactly like `execute_plan`, and additionally delivers an `ExecutionEvent` to
+    /// `observer` for every scheduled, started, retried, completed, failed and blocked
+    /// processor, every canonical payload update, and the start and end of the execution.
+    async fn execute_plan_observed(
+        &self,
+        plan: Arc<ExecutionPlan>,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        failure_strategy: FailureStrategy,
+        observer: Arc<dyn ExecutionObserver>,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        self.execute_plan_cancellable(
+            plan,
+            input,
+            pipeline_metadata,
+            failure_strategy,
+            observer,
+            CancellationToken::new(),
+        )
+        .await
+    }
+
+    /// Execute a pipeline from a pre-compiled execution plan until `cancellation` is
+    /// cancelled.
+    ///
+    /// Behaves exactly like `execute_plan_observed`. Once `cancellation` is cancelled, no
+    /// further processor is started and the tasks of the running processors are aborted; the
+    /// execution waits until they stopped and fails with `ExecutionError::Cancelled`, so none
+    /// of its processors is still running when it returns. Dropping the execution also aborts
+    /// its processor tasks, without waiting for them.
+    async fn execute_plan_cancellable(
+        &self,
+        plan: Arc<ExecutionPlan>,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        failure_strategy: FailureStrategy,
+        observer: Arc<dyn ExecutionObserver>,
+        cancellation: CancellationToken,
+    ) -> Result<ExecutionReport, ExecutionError>;
+
+    /// Execute a pipeline from a pre-compiled execution plan as part of an incoming trace.
+    ///
+    /// Behaves exactly like `execute_plan`. When `trace_context` is present (typically parsed
+    /// from a W3C `traceparent` header), the execution runs inside a `dag_request` span that
+    /// continues the caller's trace, so the DAG and processor spans are exported as children
+    /// of the remote span. Without a trace context the execution starts a new trace.
+    ///
+    /// To combine tracing with an observer, instrument `execute_plan_observed` with
+    /// `TraceContext::execution_span` directly.
+    async fn execute_plan_traced(
+        &self,
+        plan: Arc<ExecutionPlan>,
+        input: ProcessorRequest,
+        pipeline_metadata: PipelineMetadata,
+        failure_strategy: FailureStrategy,
+        trace_context: Option<TraceContext>,
+    ) -> Result<ExecutionReport, ExecutionError> {
+        let span = trace_context
+            .as_ref()
+            .map_or_else(tracing::Span::current, TraceContext::execution_span);
+
+        self.execute_plan(plan, input, pipeline_metadata, failure_strategy)
+            .instrument(span)
+            .await
+    }
+
     /// Execute a pipeline given processors and their dependency graph.
     ///
+    /// Compiles an `ExecutionPlan` for this single execution and delegates to `execute_plan`.
+    /// Callers executing the same pipeline repeatedly should compile the plan once instead.
+    ///
     /// - `processors`: registry mapping id -> processor instance
     /// - `graph`: adjacency list (id -> list of dependents)
     /// - `entrypoints`: processors with no dependencies
@@ -20,17 +127,21 @@ pub trait DagExecutor: Send + Sync {
     /// - `failure_strategy`: how to handle processor failures
     ///
     /// Returns a Result containing either:
-    /// - Ok((HashMap, PipelineMetadata)): Successful execution results and accumulated metadata
-    /// - Err(ExecutionError): Details about what went wrong during execution
+    /// - Ok(ExecutionReport): Per-processor outcomes, final output, metadata and collected failures
+    /// - Err(ExecutionError): The plan could not be compiled or executed
     async fn execute_with_strategy(
         &self,
         processors: ProcessorMap,
         graph: DependencyGraph,
         entrypoints: EntryPoints,
         input: ProcessorRequest,
-        mut pipeline_metadata: PipelineMetadata,
+        pipeline_metadata: PipelineMetadata,
         failure_strategy: FailureStrategy,
-    ) -> Result<(HashMap<String, ProcessorResponse>, PipelineMetadata), ExecutionError>;
+    ) -> Result<ExecutionReport, ExecutionError> {
+        let plan = ExecutionPlan::compile(processors, graph, entrypoints)?;
+        self.execute_plan(Arc::new(plan), input, pipeline_metadata, failure_strategy)
+            .await
+    }
 
     /// Test convenience method that uses the default failure strategy (FailFast).
     /// Production code should use `execute_with_strategy` to explicitly specify failure handling.
@@ -41,7 +152,10 @@ pub trait DagExecutor: Send + Sync {
         graph: DependencyGraph,
         entrypoints: EntryPoints,
         input: ProcessorRequest,
-    ) -> Result<HashMap<String, ProcessorResponse>, ExecutionError> {
+    ) -> Result<
+        std::collections::HashMap<String, crate::proto::processor_v1::ProcessorResponse>,
+        ExecutionError,
+    > {
         let pipeline_metadata = PipelineMetadata::new();
         let (results, _metadata) = self
             .execute_with_strategy(
@@ -52,7 +166,8 @@ pub trait DagExecutor: Send + Sync {
                 pipeline_metadata,
                 FailureStrategy::default(),
             )
-            .await?;
+            .await?
+            .into_result()?;
         Ok(results)
     }
 }
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: MIT
 
 pub mod executor;
+pub mod observer;
 pub mod processor;
 
 pub use crate::config::{DependencyGraph, EntryPoints, ProcessorMap};
 pub use executor::DagExecutor;
+pub use observer::ExecutionObserver;
 pub use processor::Processor;
@@ -0,0 +1,17 @@
+// Copyright (c) 2025 Steve Wagner (ciroque@live.com)
+// SPDX-License-Identifier: MIT
+
+use crate::engine::events::ExecutionEvent;
+
+/// Receives live progress events from a DAG execution.
+///
+/// All executors emit the same [`ExecutionEvent`]s (scheduled, started, retried, completed,
+/// failed, blocked, canonical payload updated), so observers can drive progress UIs,
+/// server-sent events or custom metrics without parsing logs.
+///
+/// `on_event` is called synchronously from executor tasks and must not block; observers that
+/// need to do slow work should hand events off (see `BroadcastObserver`).
+pub trait ExecutionObserver: Send + Sync {
+    /// Called for every event emitted during an execution, in emission order per processor
+    fn on_event(&self, event: &ExecutionEvent);
+}
@@ -32,4 +32,19 @@ pub trait Processor: Send + Sync {
     fn declared_intent(&self) -> ProcessorIntent {
         ProcessorIntent::Transform
     }
+
+    /// Estimated bytes held by the processor between requests, such as compiled WASM code
+    ///
+    /// Used by the server's memory budget (ADR 28). Default implementation returns 0.
+    fn memory_footprint(&self) -> usize {
+        0
+    }
+
+    /// Release memory that can be rebuilt on demand, returning the bytes released
+    ///
+    /// The processor must keep working: released resources are rebuilt by the next request.
+    /// Default implementation releases nothing.
+    fn release_memory(&self) -> usize {
+        0
+    }
 }